@@ -14,7 +14,10 @@ use tokio::time::timeout;
 
 use crate::backend::events::{AppServerEvent, EventSink};
 use crate::codex::args::parse_codex_args;
+use crate::shared::agent_audit_core::{self, AgentAuditTracker};
+use crate::shared::auto_approval_core::{self, AutoApprovalTracker};
 use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::shared::webhooks_core;
 use crate::types::WorkspaceEntry;
 
 #[cfg(target_os = "windows")]
@@ -416,7 +419,7 @@ pub(crate) struct RequestContext {
     method: String,
 }
 
-fn build_initialize_params(client_version: &str) -> Value {
+pub(crate) fn build_initialize_params(client_version: &str) -> Value {
     json!({
         "clientInfo": {
             "name": "codex_monitor",
@@ -442,9 +445,16 @@ pub(crate) struct WorkspaceSession {
     pub(crate) next_id: AtomicU64,
     /// Callbacks for background threads - events for these threadIds are sent through the channel
     pub(crate) background_thread_callbacks: Mutex<HashMap<String, mpsc::UnboundedSender<Value>>>,
+    /// Observers for visible threads - events are copied to the channel and still emitted
+    pub(crate) thread_observers: Mutex<HashMap<String, mpsc::UnboundedSender<Value>>>,
     pub(crate) owner_workspace_id: String,
     pub(crate) workspace_ids: Mutex<HashSet<String>>,
     pub(crate) workspace_roots: Mutex<HashMap<String, String>>,
+    /// App data dir holding backend-side state such as the auto-approval
+    /// policy; `None` leaves every approval to the UI.
+    pub(crate) data_dir: Option<PathBuf>,
+    pub(crate) approval_tracker: AutoApprovalTracker,
+    pub(crate) audit_tracker: AgentAuditTracker,
 }
 
 impl WorkspaceSession {
@@ -752,6 +762,7 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
     codex_args: Option<String>,
     codex_home: Option<PathBuf>,
     client_version: String,
+    data_dir: Option<PathBuf>,
     event_sink: E,
 ) -> Result<Arc<WorkspaceSession>, String> {
     let codex_bin = default_codex_bin;
@@ -785,12 +796,16 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         hidden_thread_ids: Mutex::new(HashSet::new()),
         next_id: AtomicU64::new(1),
         background_thread_callbacks: Mutex::new(HashMap::new()),
+        thread_observers: Mutex::new(HashMap::new()),
         owner_workspace_id: entry.id.clone(),
         workspace_ids: Mutex::new(HashSet::from([entry.id.clone()])),
         workspace_roots: Mutex::new(HashMap::from([(
             entry.id.clone(),
             normalize_root_path(&entry.path),
         )])),
+        data_dir,
+        approval_tracker: AutoApprovalTracker::default(),
+        audit_tracker: AgentAuditTracker::default(),
     });
 
     let session_clone = Arc::clone(&session);
@@ -946,6 +961,40 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                 }
             }
 
+            if has_method {
+                if let Some(ref tid) = thread_id {
+                    let observers = session_clone.thread_observers.lock().await;
+                    if let Some(tx) = observers.get(tid) {
+                        let _ = tx.send(value.clone());
+                    }
+                }
+            }
+
+            if has_method {
+                if let Some(data_dir) = session_clone.data_dir.as_deref() {
+                    session_clone.approval_tracker.observe(method_name, &value);
+                    agent_audit_core::record_app_server_message(
+                        &session_clone.audit_tracker,
+                        data_dir,
+                        &routed_workspace_id,
+                        thread_id.as_deref(),
+                        &value,
+                    );
+                    // Approval prompts are published once the policy has declined to
+                    // answer them, below.
+                    let is_approval_request = maybe_id.is_some()
+                        && method_name.is_some_and(|method| method.ends_with("requestApproval"));
+                    if !is_approval_request {
+                        webhooks_core::dispatch_app_server_event(
+                            data_dir,
+                            &routed_workspace_id,
+                            thread_id.as_deref(),
+                            &value,
+                        );
+                    }
+                }
+            }
+
             if method_name == Some("thread/archived") {
                 if let Some(ref tid) = thread_id {
                     session_clone.thread_workspace.lock().await.remove(tid);
@@ -959,6 +1008,46 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
                         let _ = tx.send(value);
                     }
                 } else if has_method {
+                    if let Some(data_dir) = session_clone.data_dir.as_deref() {
+                        if method_name.is_some_and(|method| method.ends_with("requestApproval")) {
+                            if let Some(event) = auto_approval_core::handle_approval_request(
+                                &session_clone,
+                                data_dir,
+                                &routed_workspace_id,
+                                thread_id.as_deref(),
+                                &value,
+                            )
+                            .await
+                            {
+                                if let Some(decision) = event.evaluation.outcome.decision() {
+                                    agent_audit_core::record_approval_decision(
+                                        &session_clone.audit_tracker,
+                                        data_dir,
+                                        &routed_workspace_id,
+                                        &event.request_id,
+                                        decision,
+                                        "policy",
+                                        event.evaluation.rule_name.clone(),
+                                    );
+                                }
+                                let payload = AppServerEvent {
+                                    workspace_id: routed_workspace_id.clone(),
+                                    message: json!({
+                                        "method": "codex/autoApproval",
+                                        "params": { "entry": event },
+                                    }),
+                                };
+                                event_sink_clone.emit_app_server_event(payload);
+                                continue;
+                            }
+                            webhooks_core::dispatch_app_server_event(
+                                data_dir,
+                                &routed_workspace_id,
+                                thread_id.as_deref(),
+                                &value,
+                            );
+                        }
+                    }
                     // Check for background thread callback
                     let mut sent_to_background = false;
                     if let Some(ref tid) = thread_id {
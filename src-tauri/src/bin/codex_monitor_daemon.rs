@@ -79,16 +79,28 @@ use backend::app_server::{spawn_workspace_session, WorkspaceSession};
 use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
 use shared::codex_core::CodexLoginCancelState;
 use shared::process_core::kill_child_process_tree;
-use shared::prompts_core::{self, CustomPromptEntry};
+use shared::prompts_core::{
+    self, CustomPromptEntry, PromptRenderResult, PromptRevision, PromptRevisionDiff,
+};
 use shared::{
-    agents_config_core, codex_aux_core, codex_core, files_core, git_core, git_ui_core,
-    local_usage_core, settings_core, workspaces_core, worktree_core,
+    agent_audit_core, agents_config_core, auto_approval_core, batch_runs_core, codex_aux_core,
+    codex_core, config_toml_core, files_core, git_core, git_ui_core, local_usage_core,
+    schedules_core, settings_core, thread_export_core, thread_search_core, turn_context_core,
+    webhooks_core, workspace_bundle_core, workspaces_core, worktree_core,
 };
 use storage::{read_settings, read_workspaces};
 use types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, LocalUsageSnapshot,
-    WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    AgentAuditEntry, AgentAuditExport, AgentAuditQuery, AppSettings, ApprovalRuleSpec,
+    ApprovalRuleTestResult, ApprovalRulesFile, AutoApprovalEvaluation, AutoApprovalPolicy,
+    AutoApprovalRequest, BatchRun, BatchRunDiffSummary, BatchRunInput, ConfigDryRunResult,
+    GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
+    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, GitStatusPage,
+    LocalUsageBreakdown, LocalUsageBreakdownQuery, LocalUsageExport, LocalUsageExportQuery,
+    LocalUsageSnapshot, PromptLibraryStatus, ScheduledRun, ScheduledRunInput, ScheduledRunRecord,
+    ThreadExport, ThreadExportFormat, ThreadSearchQuery, ThreadSearchResponse,
+    TurnContextAttachment, UsageExportFormat, WebhookDelivery, WebhookInfo, WebhookInput,
+    WorkspaceDoctorReport, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupLog,
+    WorktreeSetupRun, WorktreeSetupStatus,
 };
 use workspace_settings::apply_workspace_settings_update;
 
@@ -99,6 +111,7 @@ const DAEMON_NAME: &str = "codex-monitor-daemon";
 fn spawn_with_client(
     event_sink: DaemonEventSink,
     client_version: String,
+    data_dir: PathBuf,
     entry: WorkspaceEntry,
     default_bin: Option<String>,
     codex_args: Option<String>,
@@ -110,6 +123,7 @@ fn spawn_with_client(
         codex_args,
         codex_home,
         client_version,
+        Some(data_dir),
         event_sink,
     )
 }
@@ -158,6 +172,8 @@ struct DaemonState {
     event_sink: DaemonEventSink,
     codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
     daemon_binary_path: Option<String>,
+    worktree_setup_jobs: workspaces_core::WorktreeSetupJobs,
+    schedule_runner: schedules_core::ScheduleRunner,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -185,6 +201,8 @@ impl DaemonState {
             event_sink,
             codex_login_cancels: Mutex::new(HashMap::new()),
             daemon_binary_path,
+            worktree_setup_jobs: workspaces_core::WorktreeSetupJobs::default(),
+            schedule_runner: schedules_core::ScheduleRunner::default(),
         }
     }
 
@@ -263,6 +281,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
@@ -293,6 +312,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
@@ -341,6 +361,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
@@ -351,12 +372,64 @@ impl DaemonState {
         .await
     }
 
+    async fn batch_runs_start(
+        &self,
+        input: BatchRunInput,
+        client_version: String,
+    ) -> Result<BatchRun, String> {
+        let copy_agents_md = input.copy_agents_md.unwrap_or(true);
+        batch_runs_core::batch_runs_start_core(
+            &self.data_dir,
+            &self.sessions,
+            &self.workspaces,
+            input,
+            |parent_id, branch, name| {
+                self.add_worktree(
+                    parent_id,
+                    branch,
+                    name,
+                    copy_agents_md,
+                    client_version.clone(),
+                )
+            },
+            |workspace_id, thread_id| {
+                emit_background_thread_hide(&self.event_sink, workspace_id, thread_id);
+            },
+        )
+        .await
+    }
+
+    fn batch_runs_list(&self) -> Result<Vec<BatchRun>, String> {
+        batch_runs_core::batch_runs_list_core(&self.data_dir)
+    }
+
+    async fn batch_runs_status(&self, batch_id: String) -> Result<BatchRun, String> {
+        batch_runs_core::batch_runs_status_core(&self.data_dir, &self.sessions, batch_id).await
+    }
+
+    async fn batch_runs_diff_summary(
+        &self,
+        batch_id: String,
+    ) -> Result<BatchRunDiffSummary, String> {
+        batch_runs_core::batch_runs_diff_summary_core(&self.data_dir, &self.workspaces, batch_id)
+            .await
+    }
+
+    fn batch_runs_delete(&self, batch_id: String) -> Result<(), String> {
+        batch_runs_core::batch_runs_delete_core(&self.data_dir, batch_id)
+    }
+
     async fn worktree_setup_status(
         &self,
         workspace_id: String,
     ) -> Result<WorktreeSetupStatus, String> {
-        workspaces_core::worktree_setup_status_core(&self.workspaces, &workspace_id, &self.data_dir)
-            .await
+        workspaces_core::worktree_setup_status_core(
+            &self.workspaces,
+            &self.worktree_setup_jobs,
+            &workspace_id,
+            &self.data_dir,
+        )
+        .await
     }
 
     async fn worktree_setup_mark_ran(&self, workspace_id: String) -> Result<(), String> {
@@ -368,6 +441,83 @@ impl DaemonState {
         .await
     }
 
+    async fn worktree_setup_run(&self, workspace_id: String) -> Result<WorktreeSetupRun, String> {
+        workspaces_core::worktree_setup_run_core(
+            &self.workspaces,
+            &self.worktree_setup_jobs,
+            &workspace_id,
+            &self.data_dir,
+            self.event_sink.clone(),
+        )
+        .await
+    }
+
+    async fn worktree_setup_retry(&self, workspace_id: String) -> Result<WorktreeSetupRun, String> {
+        workspaces_core::worktree_setup_retry_core(
+            &self.workspaces,
+            &self.worktree_setup_jobs,
+            &workspace_id,
+            &self.data_dir,
+            self.event_sink.clone(),
+        )
+        .await
+    }
+
+    async fn worktree_setup_cancel(&self, workspace_id: String) -> Result<(), String> {
+        workspaces_core::worktree_setup_cancel_core(&self.worktree_setup_jobs, &workspace_id).await
+    }
+
+    async fn worktree_setup_log(&self, workspace_id: String) -> Result<WorktreeSetupLog, String> {
+        workspaces_core::worktree_setup_log_core(&self.workspaces, &workspace_id, &self.data_dir)
+            .await
+    }
+
+    async fn workspace_doctor(
+        &self,
+        workspace_id: Option<String>,
+        apply_fixes: bool,
+    ) -> Result<Vec<WorkspaceDoctorReport>, String> {
+        workspaces_core::workspace_doctor_core(
+            &self.workspaces,
+            &self.sessions,
+            &self.worktree_setup_jobs,
+            &self.storage_path,
+            &self.data_dir,
+            workspace_id,
+            apply_fixes,
+        )
+        .await
+    }
+
+    async fn export_workspace_bundle(
+        &self,
+    ) -> Result<workspace_bundle_core::WorkspaceBundle, String> {
+        self.sync_workspaces_from_storage().await;
+        workspace_bundle_core::export_workspace_bundle_core(
+            &self.workspaces,
+            &self.app_settings,
+            &self.settings_path,
+        )
+        .await
+    }
+
+    async fn import_workspace_bundle(
+        &self,
+        bundle: workspace_bundle_core::WorkspaceBundle,
+        options: workspace_bundle_core::WorkspaceBundleImportOptions,
+    ) -> Result<workspace_bundle_core::WorkspaceBundleImportSummary, String> {
+        self.sync_workspaces_from_storage().await;
+        workspace_bundle_core::import_workspace_bundle_core(
+            bundle,
+            options,
+            &self.workspaces,
+            &self.app_settings,
+            &self.storage_path,
+            &self.settings_path,
+        )
+        .await
+    }
+
     async fn remove_workspace(&self, id: String) -> Result<(), String> {
         workspaces_core::remove_workspace_core(
             id,
@@ -442,6 +592,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
@@ -515,6 +666,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
@@ -543,6 +695,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
@@ -569,6 +722,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     next_args,
@@ -640,6 +794,33 @@ impl DaemonState {
         agents_config_core::write_agent_config_toml_core(agent_name.as_str(), content.as_str())
     }
 
+    async fn export_agent(
+        &self,
+        input: agents_config_core::ExportAgentInput,
+    ) -> Result<agents_config_core::AgentExportDto, String> {
+        agents_config_core::export_agent_core(input)
+    }
+
+    async fn import_agent(
+        &self,
+        input: agents_config_core::ImportAgentInput,
+    ) -> Result<agents_config_core::AgentImportResultDto, String> {
+        agents_config_core::import_agent_core(input)
+    }
+
+    async fn list_agent_templates(
+        &self,
+    ) -> Result<Vec<agents_config_core::AgentTemplateDto>, String> {
+        agents_config_core::list_agent_templates_core()
+    }
+
+    async fn install_agent_templates(
+        &self,
+        input: agents_config_core::InstallAgentTemplatesInput,
+    ) -> Result<agents_config_core::InstallAgentTemplatesResultDto, String> {
+        agents_config_core::install_agent_templates_core(input)
+    }
+
     async fn list_workspace_files(&self, workspace_id: String) -> Result<Vec<String>, String> {
         workspaces_core::list_workspace_files_core(&self.workspaces, &workspace_id, |root| {
             list_workspace_files_inner(root, 20000)
@@ -700,6 +881,34 @@ impl DaemonState {
         codex_core::read_thread_core(&self.sessions, workspace_id, thread_id).await
     }
 
+    async fn export_thread(
+        &self,
+        workspace_id: String,
+        thread_id: String,
+        format: ThreadExportFormat,
+    ) -> Result<ThreadExport, String> {
+        thread_export_core::export_thread_core(
+            &self.sessions,
+            &self.workspaces,
+            workspace_id,
+            thread_id,
+            format,
+        )
+        .await
+    }
+
+    async fn search_threads(
+        &self,
+        query: ThreadSearchQuery,
+    ) -> Result<ThreadSearchResponse, String> {
+        thread_search_core::search_threads_core(
+            &self.workspaces,
+            Some(self.data_dir.clone()),
+            query,
+        )
+        .await
+    }
+
     async fn thread_live_subscribe(
         &self,
         workspace_id: String,
@@ -803,6 +1012,26 @@ impl DaemonState {
         codex_core::set_thread_name_core(&self.sessions, workspace_id, thread_id, name).await
     }
 
+    /// Renders the message's context attachments into turn input items.
+    async fn resolve_context_items(
+        &self,
+        workspace_id: &str,
+        attachments: Option<Vec<TurnContextAttachment>>,
+    ) -> Result<Vec<Value>, String> {
+        let Some(attachments) = attachments.filter(|attachments| !attachments.is_empty()) else {
+            return Ok(Vec::new());
+        };
+        turn_context_core::resolve_turn_attachments_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+            attachments,
+            |root, path| read_workspace_file_inner(root, path).map(|file| file.content),
+            |_| Err("Terminal attachments are not available on the daemon.".to_string()),
+        )
+        .await
+    }
+
     async fn send_user_message(
         &self,
         workspace_id: String,
@@ -814,8 +1043,12 @@ impl DaemonState {
         access_mode: Option<String>,
         images: Option<Vec<String>>,
         app_mentions: Option<Vec<Value>>,
+        attachments: Option<Vec<TurnContextAttachment>>,
         collaboration_mode: Option<Value>,
     ) -> Result<Value, String> {
+        let context_items = self
+            .resolve_context_items(&workspace_id, attachments)
+            .await?;
         codex_core::send_user_message_core(
             &self.sessions,
             &self.workspaces,
@@ -828,6 +1061,7 @@ impl DaemonState {
             access_mode,
             images,
             app_mentions,
+            context_items,
             collaboration_mode,
         )
         .await
@@ -839,17 +1073,25 @@ impl DaemonState {
         thread_id: String,
         turn_id: String,
         text: String,
+        access_mode: Option<String>,
         images: Option<Vec<String>>,
         app_mentions: Option<Vec<Value>>,
+        attachments: Option<Vec<TurnContextAttachment>>,
     ) -> Result<Value, String> {
+        let context_items = self
+            .resolve_context_items(&workspace_id, attachments)
+            .await?;
         codex_core::turn_steer_core(
             &self.sessions,
+            &self.workspaces,
             workspace_id,
             thread_id,
             turn_id,
             text,
+            access_mode,
             images,
             app_mentions,
+            context_items,
         )
         .await
     }
@@ -869,9 +1111,18 @@ impl DaemonState {
         thread_id: String,
         target: Value,
         delivery: Option<String>,
+        access_mode: Option<String>,
     ) -> Result<Value, String> {
-        codex_core::start_review_core(&self.sessions, workspace_id, thread_id, target, delivery)
-            .await
+        codex_core::start_review_core(
+            &self.sessions,
+            &self.workspaces,
+            workspace_id,
+            thread_id,
+            target,
+            delivery,
+            access_mode,
+        )
+        .await
     }
 
     async fn model_list(&self, workspace_id: String) -> Result<Value, String> {
@@ -947,6 +1198,91 @@ impl DaemonState {
         codex_core::remember_approval_rule_core(&self.workspaces, workspace_id, command).await
     }
 
+    async fn approval_rules_list(
+        &self,
+        workspace_id: String,
+    ) -> Result<Vec<ApprovalRulesFile>, String> {
+        codex_core::approval_rules_list_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn approval_rule_add(
+        &self,
+        workspace_id: String,
+        file: Option<String>,
+        rule: ApprovalRuleSpec,
+        index: Option<usize>,
+    ) -> Result<ApprovalRulesFile, String> {
+        codex_core::approval_rule_add_core(&self.workspaces, workspace_id, file, rule, index).await
+    }
+
+    async fn approval_rule_update(
+        &self,
+        workspace_id: String,
+        file: Option<String>,
+        index: usize,
+        rule: ApprovalRuleSpec,
+    ) -> Result<ApprovalRulesFile, String> {
+        codex_core::approval_rule_update_core(&self.workspaces, workspace_id, file, index, rule)
+            .await
+    }
+
+    async fn approval_rule_delete(
+        &self,
+        workspace_id: String,
+        file: Option<String>,
+        index: usize,
+    ) -> Result<ApprovalRulesFile, String> {
+        codex_core::approval_rule_delete_core(&self.workspaces, workspace_id, file, index).await
+    }
+
+    async fn approval_rule_move(
+        &self,
+        workspace_id: String,
+        file: Option<String>,
+        from: usize,
+        to: usize,
+    ) -> Result<ApprovalRulesFile, String> {
+        codex_core::approval_rule_move_core(&self.workspaces, workspace_id, file, from, to).await
+    }
+
+    async fn approval_rules_test(
+        &self,
+        workspace_id: String,
+        command: Vec<String>,
+    ) -> Result<ApprovalRuleTestResult, String> {
+        codex_core::approval_rules_test_core(&self.workspaces, workspace_id, command).await
+    }
+
+    fn auto_approval_policy_get(&self) -> Result<AutoApprovalPolicy, String> {
+        auto_approval_core::auto_approval_policy_get_core(&self.data_dir)
+    }
+
+    fn auto_approval_policy_set(
+        &self,
+        policy: AutoApprovalPolicy,
+    ) -> Result<AutoApprovalPolicy, String> {
+        auto_approval_core::auto_approval_policy_set_core(&self.data_dir, policy)
+    }
+
+    async fn auto_approval_test(
+        &self,
+        request: AutoApprovalRequest,
+    ) -> Result<AutoApprovalEvaluation, String> {
+        auto_approval_core::auto_approval_test_core(&self.data_dir, &self.workspaces, request).await
+    }
+
+    fn agent_audit_query(&self, query: AgentAuditQuery) -> Result<Vec<AgentAuditEntry>, String> {
+        agent_audit_core::agent_audit_query_core(&self.data_dir, query)
+    }
+
+    fn agent_audit_export(
+        &self,
+        query: AgentAuditQuery,
+        format: UsageExportFormat,
+    ) -> Result<AgentAuditExport, String> {
+        agent_audit_core::agent_audit_export_core(&self.data_dir, query, format)
+    }
+
     async fn get_config_model(&self, workspace_id: String) -> Result<Value, String> {
         codex_core::get_config_model_core(&self.workspaces, workspace_id).await
     }
@@ -970,6 +1306,7 @@ impl DaemonState {
                 spawn_with_client(
                     self.event_sink.clone(),
                     client_version.clone(),
+                    self.data_dir.clone(),
                     entry,
                     default_bin,
                     codex_args,
@@ -1011,8 +1348,20 @@ impl DaemonState {
         }
     }
 
-    async fn get_git_status(&self, workspace_id: String) -> Result<Value, String> {
-        git_ui_core::get_git_status_core(&self.workspaces, workspace_id).await
+    async fn get_git_status(
+        &self,
+        workspace_id: String,
+        root: Option<String>,
+    ) -> Result<Value, String> {
+        git_ui_core::get_git_status_core(&self.workspaces, workspace_id, root).await
+    }
+
+    async fn get_git_status_aggregate(
+        &self,
+        workspace_id: String,
+        depth: Option<usize>,
+    ) -> Result<Value, String> {
+        git_ui_core::get_git_status_aggregate_core(&self.workspaces, workspace_id, depth).await
     }
 
     async fn init_git_repo(
@@ -1049,16 +1398,59 @@ impl DaemonState {
         git_ui_core::list_git_roots_core(&self.workspaces, workspace_id, depth).await
     }
 
-    async fn get_git_diffs(&self, workspace_id: String) -> Result<Vec<GitFileDiff>, String> {
-        git_ui_core::get_git_diffs_core(&self.workspaces, &self.app_settings, workspace_id).await
+    async fn get_git_diffs(
+        &self,
+        workspace_id: String,
+        root: Option<String>,
+    ) -> Result<Vec<GitFileDiff>, String> {
+        git_ui_core::get_git_diffs_core(&self.workspaces, &self.app_settings, workspace_id, root)
+            .await
+    }
+
+    async fn get_git_status_page(
+        &self,
+        workspace_id: String,
+        root: Option<String>,
+        offset: Option<usize>,
+        limit: Option<usize>,
+        include_stats: bool,
+    ) -> Result<GitStatusPage, String> {
+        git_ui_core::get_git_status_page_core(
+            &self.workspaces,
+            workspace_id,
+            root,
+            offset,
+            limit,
+            include_stats,
+        )
+        .await
+    }
+
+    async fn get_git_file_diff(
+        &self,
+        workspace_id: String,
+        path: String,
+        root: Option<String>,
+        max_lines: Option<usize>,
+    ) -> Result<GitFileDiff, String> {
+        git_ui_core::get_git_file_diff_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+            path,
+            root,
+            max_lines,
+        )
+        .await
     }
 
     async fn get_git_log(
         &self,
         workspace_id: String,
         limit: Option<usize>,
+        root: Option<String>,
     ) -> Result<GitLogResponse, String> {
-        git_ui_core::get_git_log_core(&self.workspaces, workspace_id, limit).await
+        git_ui_core::get_git_log_core(&self.workspaces, workspace_id, limit, root).await
     }
 
     async fn get_git_commit_diff(
@@ -1079,28 +1471,56 @@ impl DaemonState {
         git_ui_core::get_git_remote_core(&self.workspaces, workspace_id).await
     }
 
-    async fn stage_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
-        git_ui_core::stage_git_file_core(&self.workspaces, workspace_id, path).await
+    async fn stage_git_file(
+        &self,
+        workspace_id: String,
+        path: String,
+        root: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::stage_git_file_core(&self.workspaces, workspace_id, path, root).await
     }
 
-    async fn stage_git_all(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::stage_git_all_core(&self.workspaces, workspace_id).await
+    async fn stage_git_all(
+        &self,
+        workspace_id: String,
+        root: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::stage_git_all_core(&self.workspaces, workspace_id, root).await
     }
 
-    async fn unstage_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
-        git_ui_core::unstage_git_file_core(&self.workspaces, workspace_id, path).await
+    async fn unstage_git_file(
+        &self,
+        workspace_id: String,
+        path: String,
+        root: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::unstage_git_file_core(&self.workspaces, workspace_id, path, root).await
     }
 
-    async fn revert_git_file(&self, workspace_id: String, path: String) -> Result<(), String> {
-        git_ui_core::revert_git_file_core(&self.workspaces, workspace_id, path).await
+    async fn revert_git_file(
+        &self,
+        workspace_id: String,
+        path: String,
+        root: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::revert_git_file_core(&self.workspaces, workspace_id, path, root).await
     }
 
-    async fn revert_git_all(&self, workspace_id: String) -> Result<(), String> {
-        git_ui_core::revert_git_all_core(&self.workspaces, workspace_id).await
+    async fn revert_git_all(
+        &self,
+        workspace_id: String,
+        root: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::revert_git_all_core(&self.workspaces, workspace_id, root).await
     }
 
-    async fn commit_git(&self, workspace_id: String, message: String) -> Result<(), String> {
-        git_ui_core::commit_git_core(&self.workspaces, workspace_id, message).await
+    async fn commit_git(
+        &self,
+        workspace_id: String,
+        message: String,
+        root: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::commit_git_core(&self.workspaces, workspace_id, message, root).await
     }
 
     async fn push_git(&self, workspace_id: String) -> Result<(), String> {
@@ -1258,6 +1678,88 @@ impl DaemonState {
         .await
     }
 
+    async fn prompts_render(
+        &self,
+        workspace_id: String,
+        path: String,
+        args: HashMap<String, Value>,
+        selection: Option<String>,
+    ) -> Result<PromptRenderResult, String> {
+        prompts_core::prompts_render_core(
+            &self.workspaces,
+            &self.settings_path,
+            workspace_id,
+            path,
+            args,
+            selection,
+        )
+        .await
+    }
+
+    async fn prompts_upstream_override(
+        &self,
+        library_id: String,
+        name: String,
+    ) -> Result<CustomPromptEntry, String> {
+        prompts_core::prompts_upstream_override_core(&self.settings_path, library_id, name).await
+    }
+
+    async fn prompts_history_list(
+        &self,
+        workspace_id: String,
+        path: String,
+    ) -> Result<Vec<PromptRevision>, String> {
+        prompts_core::prompts_history_list_core(
+            &self.workspaces,
+            &self.settings_path,
+            workspace_id,
+            path,
+        )
+        .await
+    }
+
+    async fn prompts_history_diff(
+        &self,
+        workspace_id: String,
+        path: String,
+        from_revision: String,
+        to_revision: Option<String>,
+    ) -> Result<PromptRevisionDiff, String> {
+        prompts_core::prompts_history_diff_core(
+            &self.workspaces,
+            &self.settings_path,
+            workspace_id,
+            path,
+            from_revision,
+            to_revision,
+        )
+        .await
+    }
+
+    async fn prompts_history_restore(
+        &self,
+        workspace_id: String,
+        path: String,
+        revision_id: String,
+    ) -> Result<CustomPromptEntry, String> {
+        prompts_core::prompts_history_restore_core(
+            &self.workspaces,
+            &self.settings_path,
+            workspace_id,
+            path,
+            revision_id,
+        )
+        .await
+    }
+
+    async fn prompt_libraries_sync(&self) -> Result<Vec<PromptLibraryStatus>, String> {
+        prompts_core::prompt_libraries_sync_core(&self.app_settings, &self.settings_path).await
+    }
+
+    fn prompt_libraries_status(&self) -> Result<Vec<PromptLibraryStatus>, String> {
+        prompts_core::prompt_libraries_status_core(&self.settings_path)
+    }
+
     async fn codex_doctor(
         &self,
         codex_bin: Option<String>,
@@ -1266,6 +1768,13 @@ impl DaemonState {
         codex_aux_core::codex_doctor_core(&self.app_settings, codex_bin, codex_args).await
     }
 
+    async fn config_toml_dry_run(
+        &self,
+        content: Option<String>,
+    ) -> Result<ConfigDryRunResult, String> {
+        codex_aux_core::config_toml_dry_run_core(&self.app_settings, content).await
+    }
+
     async fn generate_commit_message(
         &self,
         workspace_id: String,
@@ -1334,7 +1843,120 @@ impl DaemonState {
         days: Option<u32>,
         workspace_path: Option<String>,
     ) -> Result<LocalUsageSnapshot, String> {
-        local_usage_core::local_usage_snapshot_core(&self.workspaces, days, workspace_path).await
+        let snapshot = local_usage_core::local_usage_snapshot_core(
+            &self.workspaces,
+            &self.app_settings,
+            Some(self.data_dir.clone()),
+            days,
+            workspace_path,
+        )
+        .await?;
+        local_usage_core::notify_usage_budgets_core(&snapshot, &self.data_dir, &self.event_sink)
+            .await;
+        Ok(snapshot)
+    }
+
+    async fn local_usage_export(
+        &self,
+        query: LocalUsageExportQuery,
+    ) -> Result<LocalUsageExport, String> {
+        local_usage_core::local_usage_export_core(
+            &self.workspaces,
+            &self.app_settings,
+            Some(self.data_dir.clone()),
+            query,
+        )
+        .await
+    }
+
+    async fn local_usage_breakdown(
+        &self,
+        query: LocalUsageBreakdownQuery,
+    ) -> Result<LocalUsageBreakdown, String> {
+        local_usage_core::local_usage_breakdown_core(
+            &self.workspaces,
+            &self.app_settings,
+            Some(self.data_dir.clone()),
+            query,
+        )
+        .await
+    }
+
+    fn schedules_list(&self) -> Result<Vec<ScheduledRun>, String> {
+        schedules_core::schedules_list_core(&self.data_dir)
+    }
+
+    async fn schedules_create(&self, input: ScheduledRunInput) -> Result<ScheduledRun, String> {
+        schedules_core::schedules_create_core(&self.data_dir, &self.workspaces, input).await
+    }
+
+    async fn schedules_update(
+        &self,
+        id: String,
+        input: ScheduledRunInput,
+    ) -> Result<ScheduledRun, String> {
+        schedules_core::schedules_update_core(&self.data_dir, &self.workspaces, id, input).await
+    }
+
+    fn schedules_delete(&self, id: String) -> Result<(), String> {
+        schedules_core::schedules_delete_core(&self.data_dir, id)
+    }
+
+    fn schedules_history(
+        &self,
+        schedule_id: Option<String>,
+        limit: Option<usize>,
+    ) -> Result<Vec<ScheduledRunRecord>, String> {
+        schedules_core::schedules_history_core(&self.data_dir, schedule_id, limit)
+    }
+
+    fn schedules_run_now(&self, id: String) -> Result<ScheduledRunRecord, String> {
+        schedules_core::schedules_run_now_core(&self.data_dir, &self.schedule_runner, id)
+    }
+
+    async fn execute_scheduled_run(&self, job: schedules_core::ScheduledRunJob) {
+        let workspace_id = job.schedule.workspace_id.clone();
+        let run_id = job.run_id.clone();
+        let result = schedules_core::execute_scheduled_run_core(
+            &self.data_dir,
+            &self.sessions,
+            &self.workspaces,
+            &self.event_sink,
+            job,
+            self.connect_workspace(workspace_id, env!("CARGO_PKG_VERSION").to_string()),
+        )
+        .await;
+        if let Err(err) = result {
+            eprintln!("daemon: scheduled run {run_id} failed to record: {err}");
+        }
+    }
+
+    fn webhooks_list(&self) -> Result<Vec<WebhookInfo>, String> {
+        webhooks_core::webhooks_list_core(&self.data_dir)
+    }
+
+    fn webhooks_create(&self, input: WebhookInput) -> Result<WebhookInfo, String> {
+        webhooks_core::webhooks_create_core(&self.data_dir, input)
+    }
+
+    fn webhooks_update(&self, id: String, input: WebhookInput) -> Result<WebhookInfo, String> {
+        webhooks_core::webhooks_update_core(&self.data_dir, id, input)
+    }
+
+    fn webhooks_delete(&self, id: String) -> Result<(), String> {
+        webhooks_core::webhooks_delete_core(&self.data_dir, id)
+    }
+
+    fn webhook_deliveries(
+        &self,
+        webhook_id: Option<String>,
+        limit: Option<usize>,
+    ) -> Result<Vec<WebhookDelivery>, String> {
+        webhooks_core::webhook_deliveries_core(&self.data_dir, webhook_id, limit)
+    }
+
+    async fn webhooks_test(&self, id: String) -> Result<WebhookDelivery, String> {
+        webhooks_core::webhooks_test_core(&self.data_dir, id).await
     }
 
     async fn menu_set_accelerators(&self, _updates: Vec<Value>) -> Result<(), String> {
@@ -1615,6 +2237,8 @@ mod tests {
             event_sink: DaemonEventSink { tx },
             codex_login_cancels: Mutex::new(HashMap::new()),
             daemon_binary_path: Some("/tmp/codex-monitor-daemon".to_string()),
+            worktree_setup_jobs: workspaces_core::WorktreeSetupJobs::default(),
+            schedule_runner: schedules_core::ScheduleRunner::default(),
         }
     }
 
@@ -1678,9 +2302,13 @@ mod tests {
             hidden_thread_ids: Mutex::new(HashSet::new()),
             next_id: AtomicU64::new(0),
             background_thread_callbacks: Mutex::new(HashMap::new()),
+            thread_observers: Mutex::new(HashMap::new()),
             workspace_ids: Mutex::new(HashSet::from([owner_workspace_id.clone()])),
             workspace_roots: Mutex::new(HashMap::new()),
             owner_workspace_id,
+            data_dir: None,
+            approval_tracker: Default::default(),
+            audit_tracker: Default::default(),
         })
     }
 
@@ -1932,6 +2560,48 @@ fn main() {
         let state = Arc::new(DaemonState::load(&config, event_sink));
         let config = Arc::new(config);
 
+        let sync_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            prompts_core::run_prompt_library_sync_loop(
+                &sync_state.app_settings,
+                &sync_state.settings_path,
+            )
+            .await;
+        });
+
+        let clock_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            schedules_core::run_schedule_clock(&clock_state.data_dir, &clock_state.schedule_runner)
+                .await;
+        });
+
+        let budget_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(local_usage_core::USAGE_BUDGET_CHECK_INTERVAL).await;
+                local_usage_core::check_usage_budgets_core(
+                    &budget_state.workspaces,
+                    &budget_state.app_settings,
+                    &budget_state.data_dir,
+                    &budget_state.event_sink,
+                )
+                .await;
+            }
+        });
+
+        let worker_state = Arc::clone(&state);
+        tokio::spawn(async move {
+            let Some(mut jobs) = worker_state.schedule_runner.take_jobs().await else {
+                return;
+            };
+            while let Some(job) = jobs.recv().await {
+                let state = Arc::clone(&worker_state);
+                tokio::spawn(async move {
+                    state.execute_scheduled_run(job).await;
+                });
+            }
+        });
+
         let listener = match TcpListener::bind(config.listen).await {
             Ok(listener) => listener,
             Err(err) => {
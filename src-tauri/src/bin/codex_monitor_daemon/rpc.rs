@@ -10,6 +10,10 @@ mod dispatcher;
 mod git;
 #[path = "rpc/prompts.rs"]
 mod prompts;
+#[path = "rpc/schedules.rs"]
+mod schedules;
+#[path = "rpc/webhooks.rs"]
+mod webhooks;
 #[path = "rpc/workspace.rs"]
 mod workspace;
 
@@ -10,6 +10,17 @@ fn parse_input<T: DeserializeOwned>(params: &Value) -> Result<T, String> {
     serde_json::from_value(input_value).map_err(|err| err.to_string())
 }
 
+fn parse_field<T: DeserializeOwned>(params: &Value, key: &str) -> Result<T, String> {
+    let value = parse_optional_value(params, key).ok_or_else(|| format!("missing `{key}`"))?;
+    serde_json::from_value(value).map_err(|err| err.to_string())
+}
+
+fn parse_optional_index(params: &Value, key: &str) -> Option<usize> {
+    parse_optional_value(params, key)
+        .and_then(|value| value.as_u64())
+        .map(|value| value as usize)
+}
+
 pub(super) async fn try_handle(
     state: &DaemonState,
     method: &str,
@@ -59,6 +70,41 @@ pub(super) async fn try_handle(
             };
             Some(state.read_thread(workspace_id, thread_id).await)
         }
+        "export_thread" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let thread_id = match parse_string(params, "threadId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let format = match parse_optional_value(params, "format")
+                .map(serde_json::from_value::<ThreadExportFormat>)
+                .transpose()
+            {
+                Ok(value) => value.unwrap_or_default(),
+                Err(err) => return Some(Err(format!("invalid `format`: {err}"))),
+            };
+            Some(
+                state
+                    .export_thread(workspace_id, thread_id, format)
+                    .await
+                    .and_then(|export| serde_json::to_value(export).map_err(|err| err.to_string())),
+            )
+        }
+        "search_threads" => {
+            let query = match parse_field::<ThreadSearchQuery>(params, "query") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .search_threads(query)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
         "thread_live_subscribe" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -176,6 +222,14 @@ pub(super) async fn try_handle(
             let images = parse_optional_string_array(params, "images");
             let app_mentions = parse_optional_value(params, "appMentions")
                 .and_then(|value| value.as_array().cloned());
+            let attachments = match parse_optional_value(params, "attachments")
+                .filter(|value| !value.is_null())
+                .map(serde_json::from_value::<Vec<TurnContextAttachment>>)
+                .transpose()
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err.to_string())),
+            };
             let collaboration_mode = parse_optional_value(params, "collaborationMode");
             Some(
                 state
@@ -189,6 +243,7 @@ pub(super) async fn try_handle(
                         access_mode,
                         images,
                         app_mentions,
+                        attachments,
                         collaboration_mode,
                     )
                     .await,
@@ -226,12 +281,30 @@ pub(super) async fn try_handle(
                 Ok(value) => value,
                 Err(err) => return Some(Err(err)),
             };
+            let access_mode = parse_optional_string(params, "accessMode");
             let images = parse_optional_string_array(params, "images");
             let app_mentions = parse_optional_value(params, "appMentions")
                 .and_then(|value| value.as_array().cloned());
+            let attachments = match parse_optional_value(params, "attachments")
+                .filter(|value| !value.is_null())
+                .map(serde_json::from_value::<Vec<TurnContextAttachment>>)
+                .transpose()
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err.to_string())),
+            };
             Some(
                 state
-                    .turn_steer(workspace_id, thread_id, turn_id, text, images, app_mentions)
+                    .turn_steer(
+                        workspace_id,
+                        thread_id,
+                        turn_id,
+                        text,
+                        access_mode,
+                        images,
+                        app_mentions,
+                        attachments,
+                    )
                     .await,
             )
         }
@@ -254,9 +327,10 @@ pub(super) async fn try_handle(
                 Err(err) => return Some(Err(err.to_string())),
             };
             let delivery = parse_optional_string(params, "delivery");
+            let access_mode = parse_optional_string(params, "accessMode");
             Some(
                 state
-                    .start_review(workspace_id, thread_id, target, delivery)
+                    .start_review(workspace_id, thread_id, target, delivery, access_mode)
                     .await,
             )
         }
@@ -385,6 +459,49 @@ pub(super) async fn try_handle(
                     .map(|_| json!({ "ok": true })),
             )
         }
+        "export_agent" => {
+            let input = match parse_input::<agents_config_core::ExportAgentInput>(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .export_agent(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "import_agent" => {
+            let input = match parse_input::<agents_config_core::ImportAgentInput>(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .import_agent(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "list_agent_templates" => Some(
+            state
+                .list_agent_templates()
+                .await
+                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+        ),
+        "install_agent_templates" => {
+            let input = match parse_input::<agents_config_core::InstallAgentTemplatesInput>(params)
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .install_agent_templates(input)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
         "account_rate_limits" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -473,11 +590,197 @@ pub(super) async fn try_handle(
             };
             Some(state.remember_approval_rule(workspace_id, command).await)
         }
+        "approval_rules_list" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let files = match state.approval_rules_list(workspace_id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(files).map_err(|err| err.to_string()))
+        }
+        "approval_rule_validate" => {
+            let rule = match parse_field(params, "rule") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let problems = codex_core::approval_rule_validate_core(rule);
+            Some(serde_json::to_value(problems).map_err(|err| err.to_string()))
+        }
+        "approval_rule_add" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let file = parse_optional_string(params, "file");
+            let rule = match parse_field(params, "rule") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let index = parse_optional_index(params, "index");
+            let updated = match state
+                .approval_rule_add(workspace_id, file, rule, index)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(updated).map_err(|err| err.to_string()))
+        }
+        "approval_rule_update" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let file = parse_optional_string(params, "file");
+            let index = match parse_field(params, "index") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let rule = match parse_field(params, "rule") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let updated = match state
+                .approval_rule_update(workspace_id, file, index, rule)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(updated).map_err(|err| err.to_string()))
+        }
+        "approval_rule_delete" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let file = parse_optional_string(params, "file");
+            let index = match parse_field(params, "index") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let updated = match state.approval_rule_delete(workspace_id, file, index).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(updated).map_err(|err| err.to_string()))
+        }
+        "approval_rule_move" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let file = parse_optional_string(params, "file");
+            let from = match parse_field(params, "from") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let to = match parse_field(params, "to") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let updated = match state.approval_rule_move(workspace_id, file, from, to).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(updated).map_err(|err| err.to_string()))
+        }
+        "approval_rules_test" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let command = match parse_string_array(params, "command") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let result = match state.approval_rules_test(workspace_id, command).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(result).map_err(|err| err.to_string()))
+        }
+        "auto_approval_policy_get" => Some(
+            state
+                .auto_approval_policy_get()
+                .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+        ),
+        "auto_approval_policy_set" => {
+            let policy = match parse_field::<AutoApprovalPolicy>(params, "policy") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .auto_approval_policy_set(policy)
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "auto_approval_test" => {
+            let request = match parse_field::<AutoApprovalRequest>(params, "request") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .auto_approval_test(request)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "agent_audit_query" => {
+            let query = match parse_field::<AgentAuditQuery>(params, "query") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(
+                state
+                    .agent_audit_query(query)
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "agent_audit_export" => {
+            let query = match parse_field::<AgentAuditQuery>(params, "query") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let format = match parse_optional_value(params, "format")
+                .map(serde_json::from_value::<UsageExportFormat>)
+                .transpose()
+            {
+                Ok(value) => value.unwrap_or_default(),
+                Err(err) => return Some(Err(format!("invalid `format`: {err}"))),
+            };
+            Some(
+                state
+                    .agent_audit_export(query, format)
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
         "codex_doctor" => {
             let codex_bin = parse_optional_string(params, "codexBin");
             let codex_args = parse_optional_string(params, "codexArgs");
             Some(state.codex_doctor(codex_bin, codex_args).await)
         }
+        "config_toml_validate" => {
+            let content = parse_optional_string(params, "content");
+            Some(
+                config_toml_core::validate_global_config_core(content)
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
+        "config_toml_dry_run" => {
+            let content = parse_optional_string(params, "content");
+            Some(
+                state
+                    .config_toml_dry_run(content)
+                    .await
+                    .and_then(|value| serde_json::to_value(value).map_err(|err| err.to_string())),
+            )
+        }
         "generate_run_metadata" => {
             let workspace_id = match parse_string(params, "workspaceId") {
                 Ok(value) => value,
@@ -26,5 +26,13 @@ pub(super) async fn dispatch_rpc_request(
         return result;
     }
 
+    if let Some(result) = schedules::try_handle(state, method, params).await {
+        return result;
+    }
+
+    if let Some(result) = webhooks::try_handle(state, method, params).await {
+        return result;
+    }
+
     Err(format!("unknown method: {method}"))
 }
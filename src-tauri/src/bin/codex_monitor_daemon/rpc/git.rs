@@ -43,8 +43,21 @@ pub(super) async fn try_handle(
 ) -> Option<Result<Value, String>> {
     match method {
         git_rpc::METHOD_GET_GIT_STATUS => {
+            let request = parse_request_or_err!(params, git_rpc::WorkspaceRootRequest);
+            Some(
+                state
+                    .get_git_status(request.workspace_id, request.root)
+                    .await,
+            )
+        }
+        git_rpc::METHOD_GET_GIT_STATUS_AGGREGATE => {
             let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
-            Some(state.get_git_status(request.workspace_id).await)
+            let depth = parse_optional_u32(params, "depth").map(|value| value as usize);
+            Some(
+                state
+                    .get_git_status_aggregate(request.workspace_id, depth)
+                    .await,
+            )
         }
         git_rpc::METHOD_INIT_GIT_REPO => {
             let request = parse_request_or_err!(params, git_rpc::InitGitRepoRequiredRequest);
@@ -75,13 +88,41 @@ pub(super) async fn try_handle(
             Some(serialize_result(state.list_git_roots(request.workspace_id, depth)).await)
         }
         git_rpc::METHOD_GET_GIT_DIFFS => {
-            let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
-            Some(serialize_result(state.get_git_diffs(request.workspace_id)).await)
+            let request = parse_request_or_err!(params, git_rpc::WorkspaceRootRequest);
+            Some(serialize_result(state.get_git_diffs(request.workspace_id, request.root)).await)
+        }
+        git_rpc::METHOD_GET_GIT_STATUS_PAGE => {
+            let request = parse_request_or_err!(params, git_rpc::GetGitStatusPageRequest);
+            Some(
+                serialize_result(state.get_git_status_page(
+                    request.workspace_id,
+                    request.root,
+                    request.offset.map(|value| value as usize),
+                    request.limit.map(|value| value as usize),
+                    request.include_stats,
+                ))
+                .await,
+            )
+        }
+        git_rpc::METHOD_GET_GIT_FILE_DIFF => {
+            let request = parse_request_or_err!(params, git_rpc::GetGitFileDiffRequest);
+            Some(
+                serialize_result(state.get_git_file_diff(
+                    request.workspace_id,
+                    request.path,
+                    request.root,
+                    request.max_lines.map(|value| value as usize),
+                ))
+                .await,
+            )
         }
         git_rpc::METHOD_GET_GIT_LOG => {
-            let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
+            let request = parse_request_or_err!(params, git_rpc::WorkspaceRootRequest);
             let limit = parse_optional_u32(params, "limit").map(|value| value as usize);
-            Some(serialize_result(state.get_git_log(request.workspace_id, limit)).await)
+            Some(
+                serialize_result(state.get_git_log(request.workspace_id, limit, request.root))
+                    .await,
+            )
         }
         git_rpc::METHOD_GET_GIT_COMMIT_DIFF => {
             let request = parse_request_or_err!(params, git_rpc::WorkspaceShaRequest);
@@ -96,27 +137,51 @@ pub(super) async fn try_handle(
         }
         git_rpc::METHOD_STAGE_GIT_FILE => {
             let request = parse_request_or_err!(params, git_rpc::WorkspacePathRequest);
-            Some(serialize_ok(state.stage_git_file(request.workspace_id, request.path)).await)
+            Some(
+                serialize_ok(state.stage_git_file(
+                    request.workspace_id,
+                    request.path,
+                    request.root,
+                ))
+                .await,
+            )
         }
         git_rpc::METHOD_STAGE_GIT_ALL => {
-            let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
-            Some(serialize_ok(state.stage_git_all(request.workspace_id)).await)
+            let request = parse_request_or_err!(params, git_rpc::WorkspaceRootRequest);
+            Some(serialize_ok(state.stage_git_all(request.workspace_id, request.root)).await)
         }
         git_rpc::METHOD_UNSTAGE_GIT_FILE => {
             let request = parse_request_or_err!(params, git_rpc::WorkspacePathRequest);
-            Some(serialize_ok(state.unstage_git_file(request.workspace_id, request.path)).await)
+            Some(
+                serialize_ok(state.unstage_git_file(
+                    request.workspace_id,
+                    request.path,
+                    request.root,
+                ))
+                .await,
+            )
         }
         git_rpc::METHOD_REVERT_GIT_FILE => {
             let request = parse_request_or_err!(params, git_rpc::WorkspacePathRequest);
-            Some(serialize_ok(state.revert_git_file(request.workspace_id, request.path)).await)
+            Some(
+                serialize_ok(state.revert_git_file(
+                    request.workspace_id,
+                    request.path,
+                    request.root,
+                ))
+                .await,
+            )
         }
         git_rpc::METHOD_REVERT_GIT_ALL => {
-            let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
-            Some(serialize_ok(state.revert_git_all(request.workspace_id)).await)
+            let request = parse_request_or_err!(params, git_rpc::WorkspaceRootRequest);
+            Some(serialize_ok(state.revert_git_all(request.workspace_id, request.root)).await)
         }
         git_rpc::METHOD_COMMIT_GIT => {
             let request = parse_request_or_err!(params, git_rpc::WorkspaceMessageRequest);
-            Some(serialize_ok(state.commit_git(request.workspace_id, request.message)).await)
+            Some(
+                serialize_ok(state.commit_git(request.workspace_id, request.message, request.root))
+                    .await,
+            )
         }
         git_rpc::METHOD_PUSH_GIT => {
             let request = parse_request_or_err!(params, git_rpc::WorkspaceIdRequest);
@@ -144,6 +144,118 @@ pub(super) async fn try_handle(
             };
             Some(serde_json::to_value(prompt).map_err(|err| err.to_string()))
         }
+        "prompts_render" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let args = match parse_optional_value(params, "args") {
+                Some(Value::Object(map)) => map.into_iter().collect(),
+                _ => HashMap::new(),
+            };
+            let selection = parse_optional_string(params, "selection");
+            let rendered = match state
+                .prompts_render(workspace_id, path, args, selection)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(rendered).map_err(|err| err.to_string()))
+        }
+        "prompts_upstream_override" => {
+            let library_id = match parse_string(params, "libraryId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let name = match parse_string(params, "name") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let prompt = match state.prompts_upstream_override(library_id, name).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(prompt).map_err(|err| err.to_string()))
+        }
+        "prompts_history_list" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let revisions = match state.prompts_history_list(workspace_id, path).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(revisions).map_err(|err| err.to_string()))
+        }
+        "prompts_history_diff" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let from_revision = match parse_string(params, "fromRevision") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let to_revision = parse_optional_string(params, "toRevision");
+            let diff = match state
+                .prompts_history_diff(workspace_id, path, from_revision, to_revision)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(diff).map_err(|err| err.to_string()))
+        }
+        "prompts_history_restore" => {
+            let workspace_id = match parse_string(params, "workspaceId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let path = match parse_string(params, "path") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let revision_id = match parse_string(params, "revisionId") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let prompt = match state
+                .prompts_history_restore(workspace_id, path, revision_id)
+                .await
+            {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(prompt).map_err(|err| err.to_string()))
+        }
+        "prompt_libraries_sync" => {
+            let statuses = match state.prompt_libraries_sync().await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(statuses).map_err(|err| err.to_string()))
+        }
+        "prompt_libraries_status" => {
+            let statuses = match state.prompt_libraries_status() {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(statuses).map_err(|err| err.to_string()))
+        }
         _ => None,
     }
 }
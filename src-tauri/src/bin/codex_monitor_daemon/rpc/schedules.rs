@@ -0,0 +1,76 @@
+use super::*;
+
+fn parse_schedule_input(params: &Value) -> Result<ScheduledRunInput, String> {
+    let input = parse_optional_value(params, "input").ok_or("missing `input`".to_string())?;
+    serde_json::from_value(input).map_err(|err| err.to_string())
+}
+
+pub(super) async fn try_handle(
+    state: &DaemonState,
+    method: &str,
+    params: &Value,
+) -> Option<Result<Value, String>> {
+    match method {
+        "schedules_list" => {
+            let schedules = match state.schedules_list() {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(schedules).map_err(|err| err.to_string()))
+        }
+        "schedules_create" => {
+            let input = match parse_schedule_input(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let schedule = match state.schedules_create(input).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(schedule).map_err(|err| err.to_string()))
+        }
+        "schedules_update" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let input = match parse_schedule_input(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let schedule = match state.schedules_update(id, input).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(schedule).map_err(|err| err.to_string()))
+        }
+        "schedules_delete" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(state.schedules_delete(id).map(|_| json!({ "ok": true })))
+        }
+        "schedules_history" => {
+            let schedule_id = parse_optional_string(params, "scheduleId");
+            let limit = parse_optional_u32(params, "limit").map(|value| value as usize);
+            let runs = match state.schedules_history(schedule_id, limit) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(runs).map_err(|err| err.to_string()))
+        }
+        "schedules_run_now" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let run = match state.schedules_run_now(id) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(run).map_err(|err| err.to_string()))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,76 @@
+use super::*;
+
+fn parse_webhook_input(params: &Value) -> Result<WebhookInput, String> {
+    let input = parse_optional_value(params, "input").ok_or("missing `input`".to_string())?;
+    serde_json::from_value(input).map_err(|err| err.to_string())
+}
+
+pub(super) async fn try_handle(
+    state: &DaemonState,
+    method: &str,
+    params: &Value,
+) -> Option<Result<Value, String>> {
+    match method {
+        "webhooks_list" => {
+            let webhooks = match state.webhooks_list() {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(webhooks).map_err(|err| err.to_string()))
+        }
+        "webhooks_create" => {
+            let input = match parse_webhook_input(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let webhook = match state.webhooks_create(input) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(webhook).map_err(|err| err.to_string()))
+        }
+        "webhooks_update" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let input = match parse_webhook_input(params) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let webhook = match state.webhooks_update(id, input) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(webhook).map_err(|err| err.to_string()))
+        }
+        "webhooks_delete" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(state.webhooks_delete(id).map(|_| json!({ "ok": true })))
+        }
+        "webhook_deliveries" => {
+            let webhook_id = parse_optional_string(params, "webhookId");
+            let limit = parse_optional_u32(params, "limit").map(|value| value as usize);
+            let deliveries = match state.webhook_deliveries(webhook_id, limit) {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(deliveries).map_err(|err| err.to_string()))
+        }
+        "webhooks_test" => {
+            let id = match parse_string(params, "id") {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            let delivery = match state.webhooks_test(id).await {
+                Ok(value) => value,
+                Err(err) => return Some(Err(err)),
+            };
+            Some(serde_json::to_value(delivery).map_err(|err| err.to_string()))
+        }
+        _ => None,
+    }
+}
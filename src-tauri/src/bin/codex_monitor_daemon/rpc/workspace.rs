@@ -108,6 +108,30 @@ pub(super) async fn try_handle(
                 .await,
             )
         }
+        "batch_runs_start" => {
+            let request = parse_request_or_err!(params, workspace_rpc::BatchRunStartRequest);
+            Some(
+                serialize_result(state.batch_runs_start(request.input, client_version.to_string()))
+                    .await,
+            )
+        }
+        "batch_runs_list" => Some(state.batch_runs_list().and_then(serialize_value)),
+        "batch_runs_status" => {
+            let request = parse_request_or_err!(params, workspace_rpc::BatchRunIdRequest);
+            Some(serialize_result(state.batch_runs_status(request.batch_id)).await)
+        }
+        "batch_runs_diff_summary" => {
+            let request = parse_request_or_err!(params, workspace_rpc::BatchRunIdRequest);
+            Some(serialize_result(state.batch_runs_diff_summary(request.batch_id)).await)
+        }
+        "batch_runs_delete" => {
+            let request = parse_request_or_err!(params, workspace_rpc::BatchRunIdRequest);
+            Some(
+                state
+                    .batch_runs_delete(request.batch_id)
+                    .map(|_| json!({ "ok": true })),
+            )
+        }
         "worktree_setup_status" => {
             let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
             Some(serialize_result(state.worktree_setup_status(request.workspace_id)).await)
@@ -116,6 +140,38 @@ pub(super) async fn try_handle(
             let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
             Some(serialize_ok(state.worktree_setup_mark_ran(request.workspace_id)).await)
         }
+        "worktree_setup_run" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
+            Some(serialize_result(state.worktree_setup_run(request.workspace_id)).await)
+        }
+        "worktree_setup_retry" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
+            Some(serialize_result(state.worktree_setup_retry(request.workspace_id)).await)
+        }
+        "worktree_setup_cancel" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
+            Some(serialize_ok(state.worktree_setup_cancel(request.workspace_id)).await)
+        }
+        "worktree_setup_log" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceIdRequest);
+            Some(serialize_result(state.worktree_setup_log(request.workspace_id)).await)
+        }
+        "workspace_doctor" => {
+            let request = parse_request_or_err!(params, workspace_rpc::WorkspaceDoctorRequest);
+            Some(
+                serialize_result(state.workspace_doctor(request.workspace_id, request.apply_fixes))
+                    .await,
+            )
+        }
+        "export_workspace_bundle" => Some(serialize_result(state.export_workspace_bundle()).await),
+        "import_workspace_bundle" => {
+            let request =
+                parse_request_or_err!(params, workspace_rpc::ImportWorkspaceBundleRequest);
+            Some(
+                serialize_result(state.import_workspace_bundle(request.bundle, request.options))
+                    .await,
+            )
+        }
         "connect_workspace" => {
             let request = parse_request_or_err!(params, workspace_rpc::IdRequest);
             Some(
@@ -268,6 +324,14 @@ pub(super) async fn try_handle(
             let workspace_path = parse_optional_string(params, "workspacePath");
             Some(serialize_result(state.local_usage_snapshot(days, workspace_path)).await)
         }
+        "local_usage_breakdown" => {
+            let query = parse_request_or_err!(params, LocalUsageBreakdownQuery);
+            Some(serialize_result(state.local_usage_breakdown(query)).await)
+        }
+        "local_usage_export" => {
+            let query = parse_request_or_err!(params, LocalUsageExportQuery);
+            Some(serialize_result(state.local_usage_export(query)).await)
+        }
         _ => None,
     }
 }
@@ -1,3 +1,5 @@
+#[path = "codex_monitor_daemonctl/client.rs"]
+mod client;
 #[path = "../daemon_binary.rs"]
 mod daemon_binary;
 #[allow(dead_code)]
@@ -7,6 +9,7 @@ mod storage;
 #[path = "../types.rs"]
 mod types;
 
+use client::{ApprovalPolicy, DaemonSession, EventFilter, SendOptions};
 use daemon_binary::resolve_daemon_binary_path;
 use serde_json::{json, Value};
 use std::env;
@@ -29,6 +32,7 @@ const DEFAULT_LISTEN_ADDR: &str = "0.0.0.0:4732";
 const REMOTE_TOKEN_PLACEHOLDER: &str = "<remote-backend-token>";
 const APP_IDENTIFIER: &str = "com.dimillian.codexmonitor";
 const DAEMON_RPC_TIMEOUT: Duration = Duration::from_millis(700);
+const DAEMON_BUNDLE_RPC_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[derive(Debug, Clone)]
 struct CliArgs {
@@ -39,6 +43,22 @@ struct CliArgs {
     daemon_path: Option<PathBuf>,
     json: bool,
     insecure_no_auth: bool,
+    host: Option<String>,
+    bundle_path: Option<PathBuf>,
+    path_remaps: Vec<(String, String)>,
+    overwrite: bool,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    usage_format: Option<String>,
+    workspace_path: Option<String>,
+    workspace_id: Option<String>,
+    thread_id: Option<String>,
+    model: Option<String>,
+    effort: Option<String>,
+    access_mode: Option<String>,
+    approvals: Option<ApprovalPolicy>,
+    limit: Option<u32>,
+    message: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -47,6 +67,29 @@ enum CliCommand {
     Stop,
     Status,
     CommandPreview,
+    ExportBundle,
+    ImportBundle,
+    UsageExport,
+    WorkspacesList,
+    ThreadsList,
+    ThreadStart,
+    Send,
+    Events,
+    GitStatus,
+}
+
+impl CliCommand {
+    fn is_client(self) -> bool {
+        matches!(
+            self,
+            CliCommand::WorkspacesList
+                | CliCommand::ThreadsList
+                | CliCommand::ThreadStart
+                | CliCommand::Send
+                | CliCommand::Events
+                | CliCommand::GitStatus
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -87,7 +130,7 @@ fn main() {
 
 async fn run() -> Result<(), String> {
     let args = parse_args()?;
-    let data_dir = resolve_data_dir(args.data_dir);
+    let data_dir = resolve_data_dir(args.data_dir.clone());
     let settings = load_settings(&data_dir);
 
     let listen_addr = resolve_listen_addr(args.listen.as_deref(), settings.as_ref())?;
@@ -97,6 +140,12 @@ async fn run() -> Result<(), String> {
         resolve_token(args.token.as_deref(), settings.as_ref())
     };
 
+    if args.command.is_client() {
+        let connect_addr = resolve_bundle_connect_addr(args.host.as_deref(), &listen_addr)?;
+        let mut session = DaemonSession::connect(&connect_addr, token.as_deref()).await?;
+        return run_client_command(&mut session, args).await;
+    }
+
     match args.command {
         CliCommand::CommandPreview => {
             let daemon_path = resolve_daemon_path(args.daemon_path.as_deref())?;
@@ -141,11 +190,149 @@ async fn run() -> Result<(), String> {
             .await?;
             print_status(&status, args.json)?;
         }
+        CliCommand::ExportBundle => {
+            let connect_addr = resolve_bundle_connect_addr(args.host.as_deref(), &listen_addr)?;
+            let bundle = daemon_call(
+                &connect_addr,
+                token.as_deref(),
+                "export_workspace_bundle",
+                json!({}),
+            )
+            .await?;
+            let rendered = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+            match args.bundle_path.as_deref() {
+                Some(path) => {
+                    std::fs::write(path, rendered).map_err(|err| {
+                        format!("Failed to write bundle {}: {err}", path.display())
+                    })?;
+                    println!("Wrote workspace bundle to {}", path.display());
+                }
+                None => println!("{rendered}"),
+            }
+        }
+        CliCommand::ImportBundle => {
+            let path = args
+                .bundle_path
+                .as_deref()
+                .ok_or_else(|| format!("import-bundle requires a bundle path\n\n{}", usage()))?;
+            let raw = std::fs::read_to_string(path)
+                .map_err(|err| format!("Failed to read bundle {}: {err}", path.display()))?;
+            let bundle: Value = serde_json::from_str(&raw)
+                .map_err(|err| format!("Invalid bundle {}: {err}", path.display()))?;
+            let path_remaps: Vec<Value> = args
+                .path_remaps
+                .iter()
+                .map(|(from, to)| json!({ "from": from, "to": to }))
+                .collect();
+            let connect_addr = resolve_bundle_connect_addr(args.host.as_deref(), &listen_addr)?;
+            let summary = daemon_call(
+                &connect_addr,
+                token.as_deref(),
+                "import_workspace_bundle",
+                json!({
+                    "bundle": bundle,
+                    "options": {
+                        "pathRemaps": path_remaps,
+                        "overwriteExisting": args.overwrite,
+                    },
+                }),
+            )
+            .await?;
+            print_import_summary(&summary, args.json)?;
+        }
+        CliCommand::UsageExport => {
+            let connect_addr = resolve_bundle_connect_addr(args.host.as_deref(), &listen_addr)?;
+            let export = daemon_call(
+                &connect_addr,
+                token.as_deref(),
+                "local_usage_export",
+                json!({
+                    "startDate": args.start_date,
+                    "endDate": args.end_date,
+                    "workspacePath": args.workspace_path,
+                    "format": args.usage_format,
+                }),
+            )
+            .await?;
+            let content = export
+                .get("content")
+                .and_then(Value::as_str)
+                .ok_or("Daemon returned an invalid usage export")?;
+            let row_count = export.get("rowCount").and_then(Value::as_u64).unwrap_or(0);
+            match args.bundle_path.as_deref() {
+                Some(path) => {
+                    std::fs::write(path, content).map_err(|err| {
+                        format!("Failed to write usage export {}: {err}", path.display())
+                    })?;
+                    println!("Wrote {row_count} usage rows to {}", path.display());
+                }
+                None => print!("{content}"),
+            }
+        }
+        _ => unreachable!("client commands are handled above"),
     }
 
     Ok(())
 }
 
+fn require_workspace(args: &CliArgs) -> Result<String, String> {
+    args.workspace_id
+        .clone()
+        .ok_or_else(|| format!("this command requires --workspace <id>\n\n{}", usage()))
+}
+
+fn read_message_from_stdin() -> Result<String, String> {
+    let mut message = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut message)
+        .map_err(|err| format!("Failed to read message from stdin: {err}"))?;
+    trim_non_empty(Some(&message)).ok_or_else(|| "send requires a non-empty message".to_string())
+}
+
+async fn run_client_command(session: &mut DaemonSession, args: CliArgs) -> Result<(), String> {
+    match args.command {
+        CliCommand::WorkspacesList => client::list_workspaces(session, args.json).await,
+        CliCommand::ThreadsList => {
+            let workspace_id = require_workspace(&args)?;
+            client::list_threads(session, &workspace_id, args.limit, args.json).await
+        }
+        CliCommand::ThreadStart => {
+            let workspace_id = require_workspace(&args)?;
+            client::start_thread(session, &workspace_id, args.json).await
+        }
+        CliCommand::Send => {
+            let workspace_id = require_workspace(&args)?;
+            let message = match args.message.as_deref() {
+                Some("-") | None => read_message_from_stdin()?,
+                Some(message) => message.to_string(),
+            };
+            let options = SendOptions {
+                workspace_id,
+                thread_id: args.thread_id,
+                message,
+                model: args.model,
+                effort: args.effort,
+                access_mode: args.access_mode,
+                approvals: args
+                    .approvals
+                    .unwrap_or_else(client::default_approval_policy),
+            };
+            client::send_message(session, options, args.json).await
+        }
+        CliCommand::Events => {
+            let filter = EventFilter {
+                workspace_id: args.workspace_id,
+                thread_id: args.thread_id,
+            };
+            client::watch_events(session, filter, args.approvals, args.json).await
+        }
+        CliCommand::GitStatus => {
+            let workspace_id = require_workspace(&args)?;
+            client::git_status(session, &workspace_id, args.json).await
+        }
+        _ => unreachable!("daemon management commands are handled in run"),
+    }
+}
+
 fn parse_args() -> Result<CliArgs, String> {
     let mut args = env::args().skip(1);
 
@@ -163,6 +350,36 @@ fn parse_args() -> Result<CliArgs, String> {
         "stop" => CliCommand::Stop,
         "status" => CliCommand::Status,
         "command-preview" => CliCommand::CommandPreview,
+        "export-bundle" => CliCommand::ExportBundle,
+        "import-bundle" => CliCommand::ImportBundle,
+        "usage" => match args.next().as_deref() {
+            Some("export") => CliCommand::UsageExport,
+            Some(other) => return Err(format!("Unknown usage command: {other}\n\n{}", usage())),
+            None => return Err(format!("usage requires a subcommand\n\n{}", usage())),
+        },
+        "workspaces" => match args.next().as_deref() {
+            Some("list") => CliCommand::WorkspacesList,
+            Some(other) => {
+                return Err(format!(
+                    "Unknown workspaces command: {other}\n\n{}",
+                    usage()
+                ))
+            }
+            None => return Err(format!("workspaces requires a subcommand\n\n{}", usage())),
+        },
+        "threads" => match args.next().as_deref() {
+            Some("list") => CliCommand::ThreadsList,
+            Some("start") => CliCommand::ThreadStart,
+            Some(other) => return Err(format!("Unknown threads command: {other}\n\n{}", usage())),
+            None => return Err(format!("threads requires a subcommand\n\n{}", usage())),
+        },
+        "send" => CliCommand::Send,
+        "events" => CliCommand::Events,
+        "git" => match args.next().as_deref() {
+            Some("status") => CliCommand::GitStatus,
+            Some(other) => return Err(format!("Unknown git command: {other}\n\n{}", usage())),
+            None => return Err(format!("git requires a subcommand\n\n{}", usage())),
+        },
         _ => return Err(format!("Unknown command: {first}\n\n{}", usage())),
     };
 
@@ -172,6 +389,22 @@ fn parse_args() -> Result<CliArgs, String> {
     let mut daemon_path: Option<PathBuf> = None;
     let mut json = false;
     let mut insecure_no_auth = false;
+    let mut host: Option<String> = None;
+    let mut bundle_path: Option<PathBuf> = None;
+    let mut path_remaps: Vec<(String, String)> = Vec::new();
+    let mut overwrite = false;
+    let mut start_date: Option<String> = None;
+    let mut end_date: Option<String> = None;
+    let mut usage_format: Option<String> = None;
+    let mut workspace_path: Option<String> = None;
+    let mut workspace_id: Option<String> = None;
+    let mut thread_id: Option<String> = None;
+    let mut model: Option<String> = None;
+    let mut effort: Option<String> = None;
+    let mut access_mode: Option<String> = None;
+    let mut approvals: Option<ApprovalPolicy> = None;
+    let mut limit: Option<u32> = None;
+    let mut message: Option<String> = None;
 
     while let Some(arg) = args.next() {
         match arg.as_str() {
@@ -207,6 +440,78 @@ fn parse_args() -> Result<CliArgs, String> {
                 }
                 daemon_path = Some(PathBuf::from(trimmed));
             }
+            "--host" => {
+                let value = args.next().ok_or("--host requires a value")?;
+                let trimmed = value.trim();
+                if trimmed.is_empty() {
+                    return Err("--host requires a non-empty value".to_string());
+                }
+                host = Some(trimmed.to_string());
+            }
+            "--output" => {
+                let value = args.next().ok_or("--output requires a value")?;
+                let trimmed = value.trim();
+                if trimmed.is_empty() {
+                    return Err("--output requires a non-empty value".to_string());
+                }
+                bundle_path = Some(PathBuf::from(trimmed));
+            }
+            "--remap" => {
+                let value = args.next().ok_or("--remap requires a value")?;
+                path_remaps.push(parse_path_remap(&value)?);
+            }
+            "--overwrite" => {
+                overwrite = true;
+            }
+            "--from" => {
+                let value = args.next().ok_or("--from requires a value")?;
+                start_date = trim_non_empty(Some(&value));
+            }
+            "--to" => {
+                let value = args.next().ok_or("--to requires a value")?;
+                end_date = trim_non_empty(Some(&value));
+            }
+            "--format" => {
+                let value = args.next().ok_or("--format requires a value")?;
+                usage_format = Some(parse_usage_format(&value)?);
+            }
+            "--workspace-path" => {
+                let value = args.next().ok_or("--workspace-path requires a value")?;
+                workspace_path = trim_non_empty(Some(&value));
+            }
+            "--workspace" => {
+                let value = args.next().ok_or("--workspace requires a value")?;
+                workspace_id = trim_non_empty(Some(&value));
+            }
+            "--thread" => {
+                let value = args.next().ok_or("--thread requires a value")?;
+                thread_id = trim_non_empty(Some(&value));
+            }
+            "--model" => {
+                let value = args.next().ok_or("--model requires a value")?;
+                model = trim_non_empty(Some(&value));
+            }
+            "--effort" => {
+                let value = args.next().ok_or("--effort requires a value")?;
+                effort = trim_non_empty(Some(&value));
+            }
+            "--access-mode" => {
+                let value = args.next().ok_or("--access-mode requires a value")?;
+                access_mode = trim_non_empty(Some(&value));
+            }
+            "--approvals" => {
+                let value = args.next().ok_or("--approvals requires a value")?;
+                approvals = Some(client::parse_approval_policy(&value)?);
+            }
+            "--limit" => {
+                let value = args.next().ok_or("--limit requires a value")?;
+                limit = Some(
+                    value
+                        .trim()
+                        .parse::<u32>()
+                        .map_err(|_| format!("--limit expects a number, got `{value}`"))?,
+                );
+            }
             "--json" => {
                 json = true;
             }
@@ -218,6 +523,15 @@ fn parse_args() -> Result<CliArgs, String> {
                 print!("{}", usage());
                 std::process::exit(0);
             }
+            _ if command == CliCommand::ImportBundle
+                && bundle_path.is_none()
+                && !arg.starts_with('-') =>
+            {
+                bundle_path = Some(PathBuf::from(&arg));
+            }
+            _ if command == CliCommand::Send && message.is_none() && !arg.starts_with("--") => {
+                message = Some(arg);
+            }
             _ => return Err(format!("Unknown argument: {arg}\n\n{}", usage())),
         }
     }
@@ -230,15 +544,51 @@ fn parse_args() -> Result<CliArgs, String> {
         daemon_path,
         json,
         insecure_no_auth,
+        host,
+        bundle_path,
+        path_remaps,
+        overwrite,
+        start_date,
+        end_date,
+        usage_format,
+        workspace_path,
+        workspace_id,
+        thread_id,
+        model,
+        effort,
+        access_mode,
+        approvals,
+        limit,
+        message,
     })
 }
 
+fn parse_usage_format(value: &str) -> Result<String, String> {
+    let format = value.trim().to_ascii_lowercase();
+    match format.as_str() {
+        "csv" | "json" => Ok(format),
+        _ => Err(format!("--format expects csv or json, got `{value}`")),
+    }
+}
+
+fn parse_path_remap(value: &str) -> Result<(String, String), String> {
+    let (from, to) = value
+        .split_once('=')
+        .ok_or_else(|| format!("--remap expects <from>=<to>, got `{value}`"))?;
+    let from = from.trim();
+    let to = to.trim();
+    if from.is_empty() || to.is_empty() {
+        return Err(format!("--remap expects <from>=<to>, got `{value}`"));
+    }
+    Ok((from.to_string(), to.to_string()))
+}
+
 fn usage() -> String {
     format!(
         "\
 USAGE:\n  codex-monitor-daemonctl <command> [options]\n\n\
-COMMANDS:\n  start              Start daemon (auto-restarts mismatched daemon if safe)\n  stop               Stop daemon\n  status             Show daemon status\n  command-preview    Print equivalent daemon start command\n\n\
-OPTIONS:\n  --listen <addr>        Bind/listen address (default derived from settings, fallback: {DEFAULT_LISTEN_ADDR})\n  --token <token>        Remote backend token override\n  --data-dir <path>      App data dir (contains settings.json/workspaces.json)\n  --daemon-path <path>   Explicit path to codex-monitor-daemon binary\n  --insecure-no-auth     Start/probe daemon without auth token (dev only)\n  --json                 Print JSON output\n  -h, --help             Show this help\n\n\
+COMMANDS:\n  start              Start daemon (auto-restarts mismatched daemon if safe)\n  stop               Stop daemon\n  status             Show daemon status\n  command-preview    Print equivalent daemon start command\n  export-bundle      Export workspaces, settings, prompts and agents from the daemon\n  import-bundle <file>  Seed the daemon from a workspace bundle\n  usage export       Export per-day, per-model and per-workspace token usage\n  workspaces list    List workspaces known to the daemon\n  threads list       List threads in --workspace\n  threads start      Start a thread in --workspace and print its id\n  send <message>     Send a message (or `-`/stdin) and stream the turn until it completes\n  events             Stream app-server events (filter with --workspace/--thread)\n  git status         Show git status for --workspace\n\n\
+OPTIONS:\n  --listen <addr>        Bind/listen address (default derived from settings, fallback: {DEFAULT_LISTEN_ADDR})\n  --token <token>        Remote backend token override\n  --data-dir <path>      App data dir (contains settings.json/workspaces.json)\n  --daemon-path <path>   Explicit path to codex-monitor-daemon binary\n  --insecure-no-auth     Start/probe daemon without auth token (dev only)\n  --host <host:port>     Daemon to export from/import into or drive with client commands (default: local listen address)\n  --output <path>        Write export-bundle/usage export output to a file instead of stdout\n  --remap <from>=<to>    Rewrite path prefixes while importing (repeatable)\n  --overwrite            Replace existing scripts, prompts and agents on import\n  --from <YYYY-MM-DD>    First day to export (default: 30 days before --to)\n  --to <YYYY-MM-DD>      Last day to export (default: today)\n  --format <csv|json>    Usage export format (default: csv)\n  --workspace-path <path>  Only export sessions that ran under this path\n  --workspace <id>       Workspace for client commands\n  --thread <id>          Thread to send to or watch (send starts a new thread when omitted)\n  --model <model>        Model override for send\n  --effort <effort>      Reasoning effort override for send\n  --access-mode <mode>   Access mode or `profile:<name>` for send\n  --approvals <policy>   Answer approvals with ask, accept or decline (send default: ask on a TTY, else decline)\n  --limit <n>            Maximum threads to list\n  --json                 Print JSON output (events stream as JSON lines)\n  -h, --help             Show this help\n\n\
 NOTES:\n  - Defaults read token/host from <data-dir>/settings.json\n  - If no --data-dir is provided, default app data dir is used for this platform\n"
     )
 }
@@ -512,8 +862,12 @@ async fn send_rpc_request(
         .map_err(|err| err.to_string())
 }
 
-async fn read_rpc_response(lines: &mut DaemonLines, expected_id: u64) -> Result<Value, String> {
-    let deadline = Instant::now() + DAEMON_RPC_TIMEOUT;
+async fn read_rpc_response(
+    lines: &mut DaemonLines,
+    expected_id: u64,
+    rpc_timeout: Duration,
+) -> Result<Value, String> {
+    let deadline = Instant::now() + rpc_timeout;
     loop {
         let now = Instant::now();
         if now >= deadline {
@@ -545,9 +899,20 @@ async fn send_and_expect_result(
     id: u64,
     method: &str,
     params: Value,
+) -> Result<Value, String> {
+    send_and_expect_result_with_timeout(writer, lines, id, method, params, DAEMON_RPC_TIMEOUT).await
+}
+
+async fn send_and_expect_result_with_timeout(
+    writer: &mut OwnedWriteHalf,
+    lines: &mut DaemonLines,
+    id: u64,
+    method: &str,
+    params: Value,
+    rpc_timeout: Duration,
 ) -> Result<Value, String> {
     send_rpc_request(writer, id, method, params).await?;
-    let response = read_rpc_response(lines, id).await?;
+    let response = read_rpc_response(lines, id, rpc_timeout).await?;
     if let Some(message) = parse_daemon_error_message(&response) {
         return Err(message);
     }
@@ -649,12 +1014,11 @@ async fn probe_daemon(listen_addr: &str, token: Option<&str>) -> DaemonProbe {
     }
 }
 
-async fn request_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> Result<(), String> {
-    let Some(connect_addr) = daemon_connect_addr(listen_addr) else {
-        return Err("invalid daemon listen address".to_string());
-    };
-
-    let stream = timeout(DAEMON_RPC_TIMEOUT, TcpStream::connect(&connect_addr))
+async fn connect_authenticated(
+    connect_addr: &str,
+    token: Option<&str>,
+) -> Result<(OwnedWriteHalf, DaemonLines), String> {
+    let stream = timeout(DAEMON_RPC_TIMEOUT, TcpStream::connect(connect_addr))
         .await
         .map_err(|_| format!("Timed out connecting to daemon at {connect_addr}"))?
         .map_err(|err| format!("Failed to connect to daemon at {connect_addr}: {err}"))?;
@@ -686,12 +1050,47 @@ async fn request_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> Resu
         }
     }
 
+    Ok((writer, lines))
+}
+
+async fn request_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> Result<(), String> {
+    let Some(connect_addr) = daemon_connect_addr(listen_addr) else {
+        return Err("invalid daemon listen address".to_string());
+    };
+    let (mut writer, mut lines) = connect_authenticated(&connect_addr, token).await?;
+
     send_and_expect_result(&mut writer, &mut lines, 3, "daemon_shutdown", json!({}))
         .await
         .map(|_| ())
         .map_err(|err| format!("Daemon shutdown request failed: {err}"))
 }
 
+fn resolve_bundle_connect_addr(host: Option<&str>, listen_addr: &str) -> Result<String, String> {
+    if let Some(host) = trim_non_empty(host) {
+        return Ok(host);
+    }
+    daemon_connect_addr(listen_addr).ok_or_else(|| "invalid daemon listen address".to_string())
+}
+
+async fn daemon_call(
+    connect_addr: &str,
+    token: Option<&str>,
+    method: &str,
+    params: Value,
+) -> Result<Value, String> {
+    let (mut writer, mut lines) = connect_authenticated(connect_addr, token).await?;
+    send_and_expect_result_with_timeout(
+        &mut writer,
+        &mut lines,
+        3,
+        method,
+        params,
+        DAEMON_BUNDLE_RPC_TIMEOUT,
+    )
+    .await
+    .map_err(|err| format!("Daemon {method} request failed: {err}"))
+}
+
 async fn wait_for_daemon_shutdown(listen_addr: &str, token: Option<&str>) -> bool {
     for _ in 0..20 {
         if matches!(
@@ -1275,12 +1674,38 @@ fn print_status(status: &TcpDaemonStatus, as_json: bool) -> Result<(), String> {
     Ok(())
 }
 
+fn print_import_summary(summary: &Value, as_json: bool) -> Result<(), String> {
+    if as_json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(summary).map_err(|e| e.to_string())?
+        );
+        return Ok(());
+    }
+    let count = |key: &str| summary.get(key).and_then(Value::as_u64).unwrap_or(0);
+    println!(
+        "workspaces: {} added, {} updated",
+        count("workspacesAdded"),
+        count("workspacesUpdated")
+    );
+    println!("groups: {}", count("groupsImported"));
+    println!("open app targets: {}", count("openAppTargetsImported"));
+    println!("prompts: {}", count("promptsImported"));
+    println!("agents: {}", count("agentsImported"));
+    if let Some(warnings) = summary.get("warnings").and_then(Value::as_array) {
+        for warning in warnings.iter().filter_map(Value::as_str) {
+            println!("warning: {warning}");
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
         daemon_connect_addr, daemon_listen_addr, local_listener_port, parse_netstat_listener_pid,
-        parse_port_from_remote_host, parse_ss_listener_pid, resolve_listen_addr, safe_force_stop_pid,
-        shell_quote,
+        parse_path_remap, parse_port_from_remote_host, parse_ss_listener_pid, parse_usage_format,
+        resolve_listen_addr, safe_force_stop_pid, shell_quote,
     };
 
     #[test]
@@ -1411,4 +1836,21 @@ tcp        0      0 0.0.0.0:47320           0.0.0.0:*               LISTEN
 "#;
         assert_eq!(parse_netstat_listener_pid(output, 4732), None);
     }
+
+    #[test]
+    fn parses_path_remap_pairs() {
+        assert_eq!(
+            parse_path_remap("/Users/alex=/home/alex"),
+            Ok(("/Users/alex".to_string(), "/home/alex".to_string()))
+        );
+        assert!(parse_path_remap("/Users/alex").is_err());
+        assert!(parse_path_remap("=/home/alex").is_err());
+    }
+
+    #[test]
+    fn parses_usage_export_format() {
+        assert_eq!(parse_usage_format(" CSV "), Ok("csv".to_string()));
+        assert_eq!(parse_usage_format("json"), Ok("json".to_string()));
+        assert!(parse_usage_format("xlsx").is_err());
+    }
 }
@@ -0,0 +1,638 @@
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::io::{IsTerminal, Write};
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::time::{timeout, Instant};
+
+use super::{
+    connect_authenticated, parse_daemon_error_message, send_rpc_request, DaemonLines,
+    DAEMON_BUNDLE_RPC_TIMEOUT,
+};
+
+const PREVIEW_MAX_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ApprovalPolicy {
+    Ask,
+    Accept,
+    Decline,
+}
+
+pub(super) fn parse_approval_policy(value: &str) -> Result<ApprovalPolicy, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "ask" => Ok(ApprovalPolicy::Ask),
+        "accept" => Ok(ApprovalPolicy::Accept),
+        "decline" => Ok(ApprovalPolicy::Decline),
+        _ => Err(format!(
+            "--approvals expects ask, accept or decline, got `{value}`"
+        )),
+    }
+}
+
+/// Prompts when a human is attached; otherwise declines so unattended jobs
+/// never hang waiting on stdin.
+pub(super) fn default_approval_policy() -> ApprovalPolicy {
+    if std::io::stdin().is_terminal() {
+        ApprovalPolicy::Ask
+    } else {
+        ApprovalPolicy::Decline
+    }
+}
+
+/// A single authenticated daemon connection. Notifications that arrive while
+/// waiting for a response are queued so event streams never drop messages.
+pub(super) struct DaemonSession {
+    writer: OwnedWriteHalf,
+    lines: DaemonLines,
+    next_id: u64,
+    pending: VecDeque<Value>,
+}
+
+impl DaemonSession {
+    pub(super) async fn connect(connect_addr: &str, token: Option<&str>) -> Result<Self, String> {
+        let (writer, lines) = connect_authenticated(connect_addr, token).await?;
+        Ok(Self {
+            writer,
+            lines,
+            next_id: 100,
+            pending: VecDeque::new(),
+        })
+    }
+
+    pub(super) async fn call(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        self.next_id += 1;
+        let id = self.next_id;
+        send_rpc_request(&mut self.writer, id, method, params).await?;
+        let deadline = Instant::now() + DAEMON_BUNDLE_RPC_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let message = timeout(remaining, self.read_message())
+                .await
+                .map_err(|_| format!("Daemon {method} request timed out"))??;
+            if message.get("id").and_then(Value::as_u64) == Some(id) {
+                if let Some(error) = parse_daemon_error_message(&message) {
+                    return Err(format!("Daemon {method} request failed: {error}"));
+                }
+                return message
+                    .get("result")
+                    .cloned()
+                    .ok_or_else(|| format!("Daemon {method} response missing result"));
+            }
+            if is_notification(&message) {
+                self.pending.push_back(message);
+            }
+        }
+    }
+
+    async fn next_notification(&mut self) -> Result<Value, String> {
+        if let Some(message) = self.pending.pop_front() {
+            return Ok(message);
+        }
+        loop {
+            let message = self.read_message().await?;
+            if is_notification(&message) {
+                return Ok(message);
+            }
+        }
+    }
+
+    async fn read_message(&mut self) -> Result<Value, String> {
+        loop {
+            let line = self
+                .lines
+                .next_line()
+                .await
+                .map_err(|err| err.to_string())?
+                .ok_or("Daemon closed the connection")?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Ok(message) = serde_json::from_str(&line) {
+                return Ok(message);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct EventFilter {
+    pub(super) workspace_id: Option<String>,
+    pub(super) thread_id: Option<String>,
+}
+
+impl EventFilter {
+    fn matches(&self, workspace_id: &str, message: &Value) -> bool {
+        if self
+            .workspace_id
+            .as_deref()
+            .is_some_and(|expected| expected != workspace_id)
+        {
+            return false;
+        }
+        match self.thread_id.as_deref() {
+            Some(expected) => event_thread_id(message) == Some(expected),
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct SendOptions {
+    pub(super) workspace_id: String,
+    pub(super) thread_id: Option<String>,
+    pub(super) message: String,
+    pub(super) model: Option<String>,
+    pub(super) effort: Option<String>,
+    pub(super) access_mode: Option<String>,
+    pub(super) approvals: ApprovalPolicy,
+}
+
+fn is_notification(message: &Value) -> bool {
+    message.get("id").is_none() && message.get("method").is_some()
+}
+
+/// Splits a daemon `app-server-event` notification into its workspace id and
+/// the raw app-server message.
+fn app_server_event(notification: &Value) -> Option<(&str, &Value)> {
+    if notification.get("method").and_then(Value::as_str) != Some("app-server-event") {
+        return None;
+    }
+    let params = notification.get("params")?;
+    let workspace_id = params.get("workspace_id").and_then(Value::as_str)?;
+    Some((workspace_id, params.get("message")?))
+}
+
+fn event_thread_id(message: &Value) -> Option<&str> {
+    let params = message.get("params")?;
+    params
+        .get("threadId")
+        .or_else(|| params.get("thread_id"))
+        .or_else(|| params.get("turn").and_then(|turn| turn.get("threadId")))
+        .or_else(|| params.get("thread").and_then(|thread| thread.get("id")))
+        .and_then(Value::as_str)
+}
+
+fn app_server_result(response: &Value) -> Result<&Value, String> {
+    if let Some(error) = response.get("error") {
+        return Err(error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("app-server returned an error")
+            .to_string());
+    }
+    Ok(response.get("result").unwrap_or(response))
+}
+
+fn command_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(command)) => command.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => String::new(),
+    }
+}
+
+fn single_line_preview(value: &str) -> String {
+    let flattened = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flattened.chars().count() <= PREVIEW_MAX_CHARS {
+        return flattened;
+    }
+    let truncated: String = flattened.chars().take(PREVIEW_MAX_CHARS - 1).collect();
+    format!("{truncated}…")
+}
+
+/// Renders the subset of app-server events worth showing in a terminal.
+fn render_event_text(message: &Value) -> Option<String> {
+    let method = message.get("method").and_then(Value::as_str)?;
+    let params = message.get("params").unwrap_or(&Value::Null);
+    let item = params.get("item").unwrap_or(&Value::Null);
+    let item_type = item.get("type").and_then(Value::as_str).unwrap_or("");
+    match method {
+        "item/agentMessage/delta" => params
+            .get("delta")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        "item/started" if item_type == "commandExecution" => {
+            Some(format!("\n$ {}\n", command_text(item.get("command"))))
+        }
+        "item/completed" => match item_type {
+            "agentMessage" => Some("\n".to_string()),
+            "commandExecution" => item
+                .get("exitCode")
+                .and_then(Value::as_i64)
+                .map(|code| format!("[exit {code}]\n")),
+            "fileChange" => {
+                let paths: Vec<&str> = item
+                    .get("changes")
+                    .and_then(Value::as_array)
+                    .map(|changes| {
+                        changes
+                            .iter()
+                            .filter_map(|change| change.get("path").and_then(Value::as_str))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (!paths.is_empty()).then(|| format!("edited: {}\n", paths.join(", ")))
+            }
+            _ => None,
+        },
+        "turn/completed" => {
+            let status = params
+                .get("turn")
+                .and_then(|turn| turn.get("status"))
+                .and_then(Value::as_str)
+                .unwrap_or("completed");
+            Some(format!("\n[turn {status}]\n"))
+        }
+        "error" => {
+            let text = params
+                .get("error")
+                .and_then(|error| error.get("message"))
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            Some(format!("\n[error] {text}\n"))
+        }
+        _ => None,
+    }
+}
+
+fn approval_summary(message: &Value) -> String {
+    let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+    let params = message.get("params").unwrap_or(&Value::Null);
+    let command = command_text(params.get("command"));
+    let reason = params
+        .get("reason")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let mut summary = if method.contains("fileChange") {
+        "File change approval requested".to_string()
+    } else if command.is_empty() {
+        format!("Approval requested ({method})")
+    } else {
+        format!("Command approval requested: {command}")
+    };
+    if !reason.is_empty() {
+        summary.push_str(&format!("\n  reason: {reason}"));
+    }
+    summary
+}
+
+async fn decide_approval(policy: ApprovalPolicy, summary: String) -> Result<&'static str, String> {
+    match policy {
+        ApprovalPolicy::Accept => Ok("accept"),
+        ApprovalPolicy::Decline => Ok("decline"),
+        ApprovalPolicy::Ask => {
+            let answer = tokio::task::spawn_blocking(move || {
+                eprint!("{summary}\nApprove? [y/N] ");
+                let _ = std::io::stderr().flush();
+                let mut line = String::new();
+                std::io::stdin().read_line(&mut line).map(|_| line)
+            })
+            .await
+            .map_err(|err| err.to_string())?
+            .map_err(|err| format!("Failed to read approval answer: {err}"))?;
+            let answer = answer.trim().to_ascii_lowercase();
+            Ok(if matches!(answer.as_str(), "y" | "yes") {
+                "accept"
+            } else {
+                "decline"
+            })
+        }
+    }
+}
+
+fn print_json_line(value: &Value) -> Result<(), String> {
+    println!(
+        "{}",
+        serde_json::to_string(value).map_err(|err| err.to_string())?
+    );
+    Ok(())
+}
+
+/// Streams matching events, returning the turn once a `turn/completed` for
+/// `until_turn` arrives (`Some(None)` accepts any turn). Streams forever when
+/// `until_turn` is `None`. Approval requests are answered only when a policy
+/// is set.
+async fn stream_events(
+    session: &mut DaemonSession,
+    filter: &EventFilter,
+    approvals: Option<ApprovalPolicy>,
+    as_json: bool,
+    until_turn: Option<Option<&str>>,
+) -> Result<Option<Value>, String> {
+    loop {
+        let notification = session.next_notification().await?;
+        let Some((workspace_id, message)) = app_server_event(&notification) else {
+            continue;
+        };
+        if !filter.matches(workspace_id, message) {
+            continue;
+        }
+        let workspace_id = workspace_id.to_string();
+        let message = message.clone();
+        if as_json {
+            print_json_line(&json!({ "workspaceId": workspace_id, "message": message }))?;
+        } else if let Some(text) = render_event_text(&message) {
+            print!("{text}");
+            let _ = std::io::stdout().flush();
+        }
+
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        if method.ends_with("requestApproval") {
+            if let (Some(policy), Some(request_id)) = (approvals, message.get("id").cloned()) {
+                let decision = decide_approval(policy, approval_summary(&message)).await?;
+                session
+                    .call(
+                        "respond_to_server_request",
+                        json!({
+                            "workspaceId": workspace_id,
+                            "requestId": request_id,
+                            "result": { "decision": decision },
+                        }),
+                    )
+                    .await?;
+                if !as_json {
+                    let outcome = if decision == "accept" {
+                        "accepted"
+                    } else {
+                        "declined"
+                    };
+                    eprintln!("[approval {outcome}]");
+                }
+            }
+            continue;
+        }
+
+        if method == "turn/completed" {
+            if let Some(expected) = until_turn {
+                let turn = message.get("params").and_then(|params| params.get("turn"));
+                let completed = turn.and_then(|turn| turn.get("id")).and_then(Value::as_str);
+                if expected.is_none() || completed.is_none() || completed == expected {
+                    return Ok(turn.cloned());
+                }
+            }
+        }
+    }
+}
+
+async fn connect_workspace(session: &mut DaemonSession, workspace_id: &str) -> Result<(), String> {
+    session
+        .call("connect_workspace", json!({ "id": workspace_id }))
+        .await
+        .map(|_| ())
+}
+
+async fn start_thread_id(
+    session: &mut DaemonSession,
+    workspace_id: &str,
+) -> Result<String, String> {
+    let response = session
+        .call("start_thread", json!({ "workspaceId": workspace_id }))
+        .await?;
+    app_server_result(&response)?
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| "start_thread response missing thread id".to_string())
+}
+
+pub(super) async fn list_workspaces(
+    session: &mut DaemonSession,
+    as_json: bool,
+) -> Result<(), String> {
+    let workspaces = session.call("list_workspaces", json!({})).await?;
+    if as_json {
+        return print_json_line(&workspaces);
+    }
+    for workspace in workspaces.as_array().into_iter().flatten() {
+        let field = |key: &str| workspace.get(key).and_then(Value::as_str).unwrap_or("");
+        let connected = workspace
+            .get("connected")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        println!(
+            "{}\t{}\t{}{}",
+            field("id"),
+            field("name"),
+            field("path"),
+            if connected { "\t(connected)" } else { "" }
+        );
+    }
+    Ok(())
+}
+
+pub(super) async fn list_threads(
+    session: &mut DaemonSession,
+    workspace_id: &str,
+    limit: Option<u32>,
+    as_json: bool,
+) -> Result<(), String> {
+    connect_workspace(session, workspace_id).await?;
+    let response = session
+        .call(
+            "list_threads",
+            json!({ "workspaceId": workspace_id, "limit": limit }),
+        )
+        .await?;
+    let result = app_server_result(&response)?;
+    if as_json {
+        return print_json_line(result);
+    }
+    let threads = result.get("data").and_then(Value::as_array);
+    for thread in threads.into_iter().flatten() {
+        let id = thread.get("id").and_then(Value::as_str).unwrap_or("");
+        let title = thread
+            .get("name")
+            .and_then(Value::as_str)
+            .filter(|name| !name.trim().is_empty())
+            .or_else(|| thread.get("preview").and_then(Value::as_str))
+            .unwrap_or("");
+        println!("{id}\t{}", single_line_preview(title));
+    }
+    Ok(())
+}
+
+pub(super) async fn start_thread(
+    session: &mut DaemonSession,
+    workspace_id: &str,
+    as_json: bool,
+) -> Result<(), String> {
+    connect_workspace(session, workspace_id).await?;
+    let thread_id = start_thread_id(session, workspace_id).await?;
+    if as_json {
+        return print_json_line(&json!({ "workspaceId": workspace_id, "threadId": thread_id }));
+    }
+    println!("{thread_id}");
+    Ok(())
+}
+
+/// Sends a message (starting a thread when none is given) and streams the
+/// resulting turn until it completes. Fails when the turn does not succeed.
+pub(super) async fn send_message(
+    session: &mut DaemonSession,
+    options: SendOptions,
+    as_json: bool,
+) -> Result<(), String> {
+    connect_workspace(session, &options.workspace_id).await?;
+    let thread_id = match options.thread_id {
+        Some(thread_id) => thread_id,
+        None => {
+            let thread_id = start_thread_id(session, &options.workspace_id).await?;
+            if !as_json {
+                eprintln!("thread: {thread_id}");
+            }
+            thread_id
+        }
+    };
+    let response = session
+        .call(
+            "send_user_message",
+            json!({
+                "workspaceId": options.workspace_id,
+                "threadId": thread_id,
+                "text": options.message,
+                "model": options.model,
+                "effort": options.effort,
+                "accessMode": options.access_mode,
+            }),
+        )
+        .await?;
+    let turn_id = app_server_result(&response)?
+        .get("turn")
+        .and_then(|turn| turn.get("id"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let filter = EventFilter {
+        workspace_id: Some(options.workspace_id),
+        thread_id: Some(thread_id),
+    };
+    let turn = stream_events(
+        session,
+        &filter,
+        Some(options.approvals),
+        as_json,
+        Some(turn_id.as_deref()),
+    )
+    .await?;
+    let status = turn
+        .as_ref()
+        .and_then(|turn| turn.get("status"))
+        .and_then(Value::as_str)
+        .unwrap_or("completed");
+    match status {
+        "failed" => Err(turn
+            .as_ref()
+            .and_then(|turn| turn.get("error"))
+            .and_then(|error| error.get("message"))
+            .and_then(Value::as_str)
+            .map(|message| format!("Turn failed: {message}"))
+            .unwrap_or_else(|| "Turn failed.".to_string())),
+        "interrupted" => Err("Turn was interrupted.".to_string()),
+        _ => Ok(()),
+    }
+}
+
+pub(super) async fn watch_events(
+    session: &mut DaemonSession,
+    filter: EventFilter,
+    approvals: Option<ApprovalPolicy>,
+    as_json: bool,
+) -> Result<(), String> {
+    if let Some(workspace_id) = filter.workspace_id.as_deref() {
+        connect_workspace(session, workspace_id).await?;
+    }
+    stream_events(session, &filter, approvals, as_json, None)
+        .await
+        .map(|_| ())
+}
+
+pub(super) async fn git_status(
+    session: &mut DaemonSession,
+    workspace_id: &str,
+    as_json: bool,
+) -> Result<(), String> {
+    let status = session
+        .call("get_git_status", json!({ "workspaceId": workspace_id }))
+        .await?;
+    if as_json {
+        return print_json_line(&status);
+    }
+    let branch = status
+        .get("branchName")
+        .and_then(Value::as_str)
+        .unwrap_or("(detached)");
+    println!("branch: {branch}");
+    let files = status
+        .get("files")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if files.is_empty() {
+        println!("working tree clean");
+        return Ok(());
+    }
+    for file in &files {
+        let field = |key: &str| file.get(key).and_then(Value::as_str).unwrap_or("");
+        let count = |key: &str| file.get(key).and_then(Value::as_i64).unwrap_or(0);
+        println!(
+            "{}\t{}\t+{} -{}",
+            field("status"),
+            field("path"),
+            count("additions"),
+            count("deletions")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{event_thread_id, parse_approval_policy, render_event_text, ApprovalPolicy};
+    use serde_json::json;
+
+    #[test]
+    fn parses_approval_policies() {
+        assert_eq!(
+            parse_approval_policy(" Accept "),
+            Ok(ApprovalPolicy::Accept)
+        );
+        assert_eq!(parse_approval_policy("ask"), Ok(ApprovalPolicy::Ask));
+        assert!(parse_approval_policy("always").is_err());
+    }
+
+    #[test]
+    fn renders_turn_events_as_text() {
+        let delta = json!({
+            "method": "item/agentMessage/delta",
+            "params": { "threadId": "t1", "delta": "Hello" }
+        });
+        assert_eq!(render_event_text(&delta).as_deref(), Some("Hello"));
+        assert_eq!(event_thread_id(&delta), Some("t1"));
+
+        let command = json!({
+            "method": "item/started",
+            "params": { "item": { "type": "commandExecution", "command": ["cargo", "test"] } }
+        });
+        assert_eq!(
+            render_event_text(&command).as_deref(),
+            Some("\n$ cargo test\n")
+        );
+
+        let completed = json!({
+            "method": "turn/completed",
+            "params": { "turn": { "id": "turn-1", "threadId": "t2", "status": "failed" } }
+        });
+        assert_eq!(
+            render_event_text(&completed).as_deref(),
+            Some("\n[turn failed]\n")
+        );
+        assert_eq!(event_thread_id(&completed), Some("t2"));
+
+        let ignored = json!({ "method": "thread/tokenUsage/updated", "params": {} });
+        assert_eq!(render_event_text(&ignored), None);
+    }
+}
@@ -19,6 +19,11 @@ pub(crate) fn resolve_default_codex_home() -> Option<PathBuf> {
     resolve_home_dir().map(|home| home.join(".codex"))
 }
 
+/// Expands `~`, `$VAR` and `%VAR%` prefixes the same way CODEX_HOME is read.
+pub(crate) fn expand_user_path(value: &str) -> Option<PathBuf> {
+    normalize_codex_home(value)
+}
+
 fn normalize_codex_home(value: &str) -> Option<PathBuf> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
@@ -2,7 +2,7 @@ use serde_json::{json, Map, Value};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use tauri::{AppHandle, Emitter, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 pub(crate) mod args;
 pub(crate) mod config;
@@ -13,10 +13,23 @@ pub(crate) use crate::backend::app_server::WorkspaceSession;
 use crate::backend::events::AppServerEvent;
 use crate::event_sink::TauriEventSink;
 use crate::remote_backend;
+use crate::shared::agent_audit_core;
 use crate::shared::agents_config_core;
+use crate::shared::auto_approval_core;
 use crate::shared::codex_core::{self, insert_optional_nullable_string};
+use crate::shared::config_toml_core;
+use crate::shared::thread_export_core;
+use crate::shared::thread_search_core;
+use crate::shared::turn_context_core;
 use crate::state::AppState;
-use crate::types::WorkspaceEntry;
+use crate::types::{
+    AgentAuditEntry, AgentAuditExport, AgentAuditQuery, ApprovalRuleSpec, ApprovalRuleTestResult,
+    ApprovalRulesFile, AutoApprovalEvaluation, AutoApprovalPolicy, AutoApprovalRequest,
+    ConfigDryRunResult, ConfigValidationReport, ThreadExport, ThreadExportFormat,
+    ThreadSearchQuery, ThreadSearchResponse, TurnContextAttachment, UsageExportFormat,
+    WorkspaceEntry,
+};
+use crate::workspaces::read_workspace_file_inner;
 
 fn emit_thread_live_event(app: &AppHandle, workspace_id: &str, method: &str, params: Value) {
     let _ = app.emit(
@@ -39,6 +52,7 @@ pub(crate) async fn spawn_workspace_session(
     codex_home: Option<PathBuf>,
 ) -> Result<Arc<WorkspaceSession>, String> {
     let client_version = app_handle.package_info().version.to_string();
+    let data_dir = app_handle.path().app_data_dir().ok();
     let event_sink = TauriEventSink::new(app_handle);
     spawn_workspace_session_inner(
         entry,
@@ -46,6 +60,7 @@ pub(crate) async fn spawn_workspace_session(
         codex_args,
         codex_home,
         client_version,
+        data_dir,
         event_sink,
     )
     .await
@@ -61,6 +76,46 @@ pub(crate) async fn codex_doctor(
         .await
 }
 
+#[tauri::command]
+pub(crate) async fn config_toml_validate(
+    content: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ConfigValidationReport, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "config_toml_validate",
+            json!({ "content": content }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    config_toml_core::validate_global_config_core(content)
+}
+
+#[tauri::command]
+pub(crate) async fn config_toml_dry_run(
+    content: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ConfigDryRunResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "config_toml_dry_run",
+            json!({ "content": content }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    crate::shared::codex_aux_core::config_toml_dry_run_core(&state.app_settings, content).await
+}
+
 #[tauri::command]
 pub(crate) async fn codex_update(
     codex_bin: Option<String>,
@@ -130,6 +185,53 @@ pub(crate) async fn read_thread(
     codex_core::read_thread_core(&state.sessions, workspace_id, thread_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn export_thread(
+    workspace_id: String,
+    thread_id: String,
+    format: Option<ThreadExportFormat>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadExport, String> {
+    let format = format.unwrap_or_default();
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "export_thread",
+            json!({ "workspaceId": workspace_id, "threadId": thread_id, "format": format }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    thread_export_core::export_thread_core(
+        &state.sessions,
+        &state.workspaces,
+        workspace_id,
+        thread_id,
+        format,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn search_threads(
+    query: ThreadSearchQuery,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ThreadSearchResponse, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "search_threads", json!({ "query": query }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let index_dir = app.path().app_data_dir().ok();
+    thread_search_core::search_threads_core(&state.workspaces, index_dir, query).await
+}
+
 #[tauri::command]
 pub(crate) async fn thread_live_subscribe(
     workspace_id: String,
@@ -335,6 +437,32 @@ pub(crate) async fn set_thread_name(
     codex_core::set_thread_name_core(&state.sessions, workspace_id, thread_id, name).await
 }
 
+/// Renders the message's context attachments into turn input items.
+async fn resolve_context_items(
+    state: &AppState,
+    workspace_id: &str,
+    attachments: Option<Vec<TurnContextAttachment>>,
+) -> Result<Vec<Value>, String> {
+    let Some(attachments) = attachments.filter(|attachments| !attachments.is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let terminal_outputs = crate::terminal::terminal_output_snapshot(state, workspace_id).await;
+    turn_context_core::resolve_turn_attachments_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+        attachments,
+        |root, path| read_workspace_file_inner(root, path).map(|file| file.content),
+        |terminal_id| {
+            terminal_outputs
+                .get(terminal_id)
+                .cloned()
+                .ok_or_else(|| "Terminal session not found".to_string())
+        },
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn send_user_message(
     workspace_id: String,
@@ -346,6 +474,7 @@ pub(crate) async fn send_user_message(
     access_mode: Option<String>,
     images: Option<Vec<String>>,
     app_mentions: Option<Vec<Value>>,
+    attachments: Option<Vec<TurnContextAttachment>>,
     collaboration_mode: Option<Value>,
     state: State<'_, AppState>,
     app: AppHandle,
@@ -367,6 +496,7 @@ pub(crate) async fn send_user_message(
         payload.insert("accessMode".to_string(), json!(access_mode));
         payload.insert("images".to_string(), json!(images));
         payload.insert("appMentions".to_string(), json!(app_mentions));
+        payload.insert("attachments".to_string(), json!(attachments));
         if let Some(mode) = collaboration_mode {
             if !mode.is_null() {
                 payload.insert("collaborationMode".to_string(), mode);
@@ -381,6 +511,8 @@ pub(crate) async fn send_user_message(
         .await;
     }
 
+    let context_items = resolve_context_items(&state, &workspace_id, attachments).await?;
+
     codex_core::send_user_message_core(
         &state.sessions,
         &state.workspaces,
@@ -393,6 +525,7 @@ pub(crate) async fn send_user_message(
         access_mode,
         images,
         app_mentions,
+        context_items,
         collaboration_mode,
     )
     .await
@@ -404,8 +537,10 @@ pub(crate) async fn turn_steer(
     thread_id: String,
     turn_id: String,
     text: String,
+    access_mode: Option<String>,
     images: Option<Vec<String>>,
     app_mentions: Option<Vec<Value>>,
+    attachments: Option<Vec<TurnContextAttachment>>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -425,21 +560,27 @@ pub(crate) async fn turn_steer(
                 "threadId": thread_id,
                 "turnId": turn_id,
                 "text": text,
+                "accessMode": access_mode,
                 "images": images,
                 "appMentions": app_mentions,
+                "attachments": attachments,
             }),
         )
         .await;
     }
 
+    let context_items = resolve_context_items(&state, &workspace_id, attachments).await?;
     codex_core::turn_steer_core(
         &state.sessions,
+        &state.workspaces,
         workspace_id,
         thread_id,
         turn_id,
         text,
+        access_mode,
         images,
         app_mentions,
+        context_items,
     )
     .await
 }
@@ -490,6 +631,7 @@ pub(crate) async fn start_review(
     thread_id: String,
     target: Value,
     delivery: Option<String>,
+    access_mode: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
@@ -503,6 +645,7 @@ pub(crate) async fn start_review(
                 "threadId": thread_id,
                 "target": target,
                 "delivery": delivery,
+                "accessMode": access_mode,
             }),
         )
         .await;
@@ -510,10 +653,12 @@ pub(crate) async fn start_review(
 
     codex_core::start_review_core(
         &state.sessions,
+        &state.workspaces,
         workspace_id,
         thread_id,
         target,
         delivery,
+        access_mode,
     )
     .await
 }
@@ -712,6 +857,72 @@ pub(crate) async fn write_agent_config_toml(
     agents_config_core::write_agent_config_toml_core(agent_name.as_str(), content.as_str())
 }
 
+#[tauri::command]
+pub(crate) async fn export_agent(
+    input: agents_config_core::ExportAgentInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<agents_config_core::AgentExportDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "export_agent", json!({ "input": input }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agents_config_core::export_agent_core(input)
+}
+
+#[tauri::command]
+pub(crate) async fn import_agent(
+    input: agents_config_core::ImportAgentInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<agents_config_core::AgentImportResultDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "import_agent", json!({ "input": input }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agents_config_core::import_agent_core(input)
+}
+
+#[tauri::command]
+pub(crate) async fn list_agent_templates(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<agents_config_core::AgentTemplateDto>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "list_agent_templates", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agents_config_core::list_agent_templates_core()
+}
+
+#[tauri::command]
+pub(crate) async fn install_agent_templates(
+    input: agents_config_core::InstallAgentTemplatesInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<agents_config_core::InstallAgentTemplatesResultDto, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "install_agent_templates",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agents_config_core::install_agent_templates_core(input)
+}
+
 #[tauri::command]
 pub(crate) async fn account_rate_limits(
     workspace_id: String,
@@ -867,6 +1078,262 @@ pub(crate) async fn remember_approval_rule(
     codex_core::remember_approval_rule_core(&state.workspaces, workspace_id, command).await
 }
 
+#[tauri::command]
+pub(crate) async fn approval_rules_list(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ApprovalRulesFile>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "approval_rules_list",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::approval_rules_list_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn approval_rule_validate(
+    rule: ApprovalRuleSpec,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<String>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "approval_rule_validate",
+            json!({ "rule": rule }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(codex_core::approval_rule_validate_core(rule))
+}
+
+#[tauri::command]
+pub(crate) async fn approval_rule_add(
+    workspace_id: String,
+    file: Option<String>,
+    rule: ApprovalRuleSpec,
+    index: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ApprovalRulesFile, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "approval_rule_add",
+            json!({ "workspaceId": workspace_id, "file": file, "rule": rule, "index": index }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::approval_rule_add_core(&state.workspaces, workspace_id, file, rule, index).await
+}
+
+#[tauri::command]
+pub(crate) async fn approval_rule_update(
+    workspace_id: String,
+    file: Option<String>,
+    index: usize,
+    rule: ApprovalRuleSpec,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ApprovalRulesFile, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "approval_rule_update",
+            json!({ "workspaceId": workspace_id, "file": file, "index": index, "rule": rule }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::approval_rule_update_core(&state.workspaces, workspace_id, file, index, rule).await
+}
+
+#[tauri::command]
+pub(crate) async fn approval_rule_delete(
+    workspace_id: String,
+    file: Option<String>,
+    index: usize,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ApprovalRulesFile, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "approval_rule_delete",
+            json!({ "workspaceId": workspace_id, "file": file, "index": index }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::approval_rule_delete_core(&state.workspaces, workspace_id, file, index).await
+}
+
+#[tauri::command]
+pub(crate) async fn approval_rule_move(
+    workspace_id: String,
+    file: Option<String>,
+    from: usize,
+    to: usize,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ApprovalRulesFile, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "approval_rule_move",
+            json!({ "workspaceId": workspace_id, "file": file, "from": from, "to": to }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::approval_rule_move_core(&state.workspaces, workspace_id, file, from, to).await
+}
+
+#[tauri::command]
+pub(crate) async fn approval_rules_test(
+    workspace_id: String,
+    command: Vec<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ApprovalRuleTestResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "approval_rules_test",
+            json!({ "workspaceId": workspace_id, "command": command }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    codex_core::approval_rules_test_core(&state.workspaces, workspace_id, command).await
+}
+
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))
+}
+
+#[tauri::command]
+pub(crate) async fn auto_approval_policy_get(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AutoApprovalPolicy, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "auto_approval_policy_get", json!({}))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    auto_approval_core::auto_approval_policy_get_core(&app_data_dir(&app)?)
+}
+
+#[tauri::command]
+pub(crate) async fn auto_approval_policy_set(
+    policy: AutoApprovalPolicy,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AutoApprovalPolicy, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "auto_approval_policy_set",
+            json!({ "policy": policy }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    auto_approval_core::auto_approval_policy_set_core(&app_data_dir(&app)?, policy)
+}
+
+#[tauri::command]
+pub(crate) async fn auto_approval_test(
+    request: AutoApprovalRequest,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AutoApprovalEvaluation, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "auto_approval_test",
+            json!({ "request": request }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    auto_approval_core::auto_approval_test_core(&app_data_dir(&app)?, &state.workspaces, request)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn agent_audit_query(
+    query: AgentAuditQuery,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<AgentAuditEntry>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "agent_audit_query",
+            json!({ "query": query }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agent_audit_core::agent_audit_query_core(&app_data_dir(&app)?, query)
+}
+
+#[tauri::command]
+pub(crate) async fn agent_audit_export(
+    query: AgentAuditQuery,
+    format: Option<UsageExportFormat>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<AgentAuditExport, String> {
+    let format = format.unwrap_or_default();
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "agent_audit_export",
+            json!({ "query": query, "format": format }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    agent_audit_core::agent_audit_export_core(&app_data_dir(&app)?, query, format)
+}
+
 #[tauri::command]
 pub(crate) async fn get_config_model(
     workspace_id: String,
@@ -8,7 +8,7 @@ use crate::shared::{git_rpc, git_ui_core};
 use crate::state::AppState;
 use crate::types::{
     GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse,
+    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, GitStatusPage,
 };
 
 fn git_remote_params<T: Serialize>(request: &T) -> Result<Value, String> {
@@ -81,11 +81,13 @@ macro_rules! try_remote_unit {
 #[tauri::command]
 pub(crate) async fn get_git_status(
     workspace_id: String,
+    root: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Value, String> {
-    let request = git_rpc::WorkspaceIdRequest {
+    let request = git_rpc::WorkspaceRootRequest {
         workspace_id: workspace_id.clone(),
+        root: root.clone(),
     };
     try_remote_value!(
         state,
@@ -93,7 +95,27 @@ pub(crate) async fn get_git_status(
         git_rpc::METHOD_GET_GIT_STATUS,
         git_remote_params(&request)?
     );
-    git_ui_core::get_git_status_core(&state.workspaces, workspace_id).await
+    git_ui_core::get_git_status_core(&state.workspaces, workspace_id, root).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_status_aggregate(
+    workspace_id: String,
+    depth: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    let request = git_rpc::ListGitRootsRequest {
+        workspace_id: workspace_id.clone(),
+        depth: optional_usize_to_u32(depth),
+    };
+    try_remote_value!(
+        state,
+        app,
+        git_rpc::METHOD_GET_GIT_STATUS_AGGREGATE,
+        git_remote_params(&request)?
+    );
+    git_ui_core::get_git_status_aggregate_core(&state.workspaces, workspace_id, depth).await
 }
 
 #[tauri::command]
@@ -153,12 +175,14 @@ pub(crate) async fn create_github_repo(
 pub(crate) async fn stage_git_file(
     workspace_id: String,
     path: String,
+    root: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
     let request = git_rpc::WorkspacePathRequest {
         workspace_id: workspace_id.clone(),
         path: path.clone(),
+        root: root.clone(),
     };
     try_remote_unit!(
         state,
@@ -166,17 +190,19 @@ pub(crate) async fn stage_git_file(
         git_rpc::METHOD_STAGE_GIT_FILE,
         git_remote_params(&request)?
     );
-    git_ui_core::stage_git_file_core(&state.workspaces, workspace_id, path).await
+    git_ui_core::stage_git_file_core(&state.workspaces, workspace_id, path, root).await
 }
 
 #[tauri::command]
 pub(crate) async fn stage_git_all(
     workspace_id: String,
+    root: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
-    let request = git_rpc::WorkspaceIdRequest {
+    let request = git_rpc::WorkspaceRootRequest {
         workspace_id: workspace_id.clone(),
+        root: root.clone(),
     };
     try_remote_unit!(
         state,
@@ -184,19 +210,21 @@ pub(crate) async fn stage_git_all(
         git_rpc::METHOD_STAGE_GIT_ALL,
         git_remote_params(&request)?
     );
-    git_ui_core::stage_git_all_core(&state.workspaces, workspace_id).await
+    git_ui_core::stage_git_all_core(&state.workspaces, workspace_id, root).await
 }
 
 #[tauri::command]
 pub(crate) async fn unstage_git_file(
     workspace_id: String,
     path: String,
+    root: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
     let request = git_rpc::WorkspacePathRequest {
         workspace_id: workspace_id.clone(),
         path: path.clone(),
+        root: root.clone(),
     };
     try_remote_unit!(
         state,
@@ -204,19 +232,21 @@ pub(crate) async fn unstage_git_file(
         git_rpc::METHOD_UNSTAGE_GIT_FILE,
         git_remote_params(&request)?
     );
-    git_ui_core::unstage_git_file_core(&state.workspaces, workspace_id, path).await
+    git_ui_core::unstage_git_file_core(&state.workspaces, workspace_id, path, root).await
 }
 
 #[tauri::command]
 pub(crate) async fn revert_git_file(
     workspace_id: String,
     path: String,
+    root: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
     let request = git_rpc::WorkspacePathRequest {
         workspace_id: workspace_id.clone(),
         path: path.clone(),
+        root: root.clone(),
     };
     try_remote_unit!(
         state,
@@ -224,17 +254,19 @@ pub(crate) async fn revert_git_file(
         git_rpc::METHOD_REVERT_GIT_FILE,
         git_remote_params(&request)?
     );
-    git_ui_core::revert_git_file_core(&state.workspaces, workspace_id, path).await
+    git_ui_core::revert_git_file_core(&state.workspaces, workspace_id, path, root).await
 }
 
 #[tauri::command]
 pub(crate) async fn revert_git_all(
     workspace_id: String,
+    root: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
-    let request = git_rpc::WorkspaceIdRequest {
+    let request = git_rpc::WorkspaceRootRequest {
         workspace_id: workspace_id.clone(),
+        root: root.clone(),
     };
     try_remote_unit!(
         state,
@@ -242,19 +274,21 @@ pub(crate) async fn revert_git_all(
         git_rpc::METHOD_REVERT_GIT_ALL,
         git_remote_params(&request)?
     );
-    git_ui_core::revert_git_all_core(&state.workspaces, workspace_id).await
+    git_ui_core::revert_git_all_core(&state.workspaces, workspace_id, root).await
 }
 
 #[tauri::command]
 pub(crate) async fn commit_git(
     workspace_id: String,
     message: String,
+    root: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<(), String> {
     let request = git_rpc::WorkspaceMessageRequest {
         workspace_id: workspace_id.clone(),
         message: message.clone(),
+        root: root.clone(),
     };
     try_remote_unit!(
         state,
@@ -262,7 +296,7 @@ pub(crate) async fn commit_git(
         git_rpc::METHOD_COMMIT_GIT,
         git_remote_params(&request)?
     );
-    git_ui_core::commit_git_core(&state.workspaces, workspace_id, message).await
+    git_ui_core::commit_git_core(&state.workspaces, workspace_id, message, root).await
 }
 
 #[tauri::command]
@@ -374,11 +408,13 @@ pub(crate) async fn get_workspace_diff(
 #[tauri::command]
 pub(crate) async fn get_git_diffs(
     workspace_id: String,
+    root: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<Vec<GitFileDiff>, String> {
-    let request = git_rpc::WorkspaceIdRequest {
+    let request = git_rpc::WorkspaceRootRequest {
         workspace_id: workspace_id.clone(),
+        root: root.clone(),
     };
     try_remote_typed!(
         state,
@@ -387,19 +423,91 @@ pub(crate) async fn get_git_diffs(
         git_remote_params(&request)?,
         Vec<GitFileDiff>
     );
-    git_ui_core::get_git_diffs_core(&state.workspaces, &state.app_settings, workspace_id).await
+    git_ui_core::get_git_diffs_core(&state.workspaces, &state.app_settings, workspace_id, root)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_status_page(
+    workspace_id: String,
+    root: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    include_stats: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitStatusPage, String> {
+    let include_stats = include_stats.unwrap_or(false);
+    let request = git_rpc::GetGitStatusPageRequest {
+        workspace_id: workspace_id.clone(),
+        root: root.clone(),
+        offset: optional_usize_to_u32(offset),
+        limit: optional_usize_to_u32(limit),
+        include_stats,
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_GET_GIT_STATUS_PAGE,
+        git_remote_params(&request)?,
+        GitStatusPage
+    );
+    git_ui_core::get_git_status_page_core(
+        &state.workspaces,
+        workspace_id,
+        root,
+        offset,
+        limit,
+        include_stats,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_file_diff(
+    workspace_id: String,
+    path: String,
+    root: Option<String>,
+    max_lines: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitFileDiff, String> {
+    let request = git_rpc::GetGitFileDiffRequest {
+        workspace_id: workspace_id.clone(),
+        path: path.clone(),
+        root: root.clone(),
+        max_lines: optional_usize_to_u32(max_lines),
+    };
+    try_remote_typed!(
+        state,
+        app,
+        git_rpc::METHOD_GET_GIT_FILE_DIFF,
+        git_remote_params(&request)?,
+        GitFileDiff
+    );
+    git_ui_core::get_git_file_diff_core(
+        &state.workspaces,
+        &state.app_settings,
+        workspace_id,
+        path,
+        root,
+        max_lines,
+    )
+    .await
 }
 
 #[tauri::command]
 pub(crate) async fn get_git_log(
     workspace_id: String,
     limit: Option<usize>,
+    root: Option<String>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<GitLogResponse, String> {
     let request = git_rpc::GetGitLogRequest {
         workspace_id: workspace_id.clone(),
         limit: optional_usize_to_u32(limit),
+        root: root.clone(),
     };
     try_remote_typed!(
         state,
@@ -408,7 +516,7 @@ pub(crate) async fn get_git_log(
         git_remote_params(&request)?,
         GitLogResponse
     );
-    git_ui_core::get_git_log_core(&state.workspaces, workspace_id, limit).await
+    git_ui_core::get_git_log_core(&state.workspaces, workspace_id, limit, root).await
 }
 
 #[tauri::command]
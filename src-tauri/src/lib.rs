@@ -24,6 +24,7 @@ mod notifications;
 mod prompts;
 mod remote_backend;
 mod rules;
+mod schedules;
 mod settings;
 mod shared;
 mod state;
@@ -37,6 +38,7 @@ mod terminal;
 mod tray;
 mod types;
 mod utils;
+mod webhooks;
 mod window;
 mod workspaces;
 
@@ -116,6 +118,19 @@ pub fn run() {
         .setup(|app| {
             let state = state::AppState::load(&app.handle());
             app.manage(state);
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let state = app_handle.state::<state::AppState>();
+                    shared::prompts_core::run_prompt_library_sync_loop(
+                        &state.app_settings,
+                        &state.settings_path,
+                    )
+                    .await;
+                });
+            }
+            tauri::async_runtime::spawn(schedules::run_scheduler(app.handle().clone()));
+            tauri::async_runtime::spawn(local_usage::run_budget_monitor(app.handle().clone()));
             #[cfg(target_os = "macos")]
             {
                 let tray_state = app.state::<tray::TrayState>();
@@ -192,6 +207,8 @@ pub fn run() {
             tray::set_tray_recent_threads,
             tray::set_tray_session_usage,
             codex::codex_doctor,
+            codex::config_toml_validate,
+            codex::config_toml_dry_run,
             codex::codex_update,
             workspaces::list_workspaces,
             workspaces::is_workspace_path_dir,
@@ -201,6 +218,18 @@ pub fn run() {
             workspaces::add_worktree,
             workspaces::worktree_setup_status,
             workspaces::worktree_setup_mark_ran,
+            workspaces::worktree_setup_run,
+            workspaces::worktree_setup_retry,
+            workspaces::worktree_setup_cancel,
+            workspaces::worktree_setup_log,
+            workspaces::workspace_doctor,
+            workspaces::batch_runs_start,
+            workspaces::batch_runs_list,
+            workspaces::batch_runs_status,
+            workspaces::batch_runs_diff_summary,
+            workspaces::batch_runs_delete,
+            workspaces::export_workspace_bundle,
+            workspaces::import_workspace_bundle,
             workspaces::remove_workspace,
             workspaces::remove_worktree,
             workspaces::rename_worktree,
@@ -215,11 +244,25 @@ pub fn run() {
             codex::start_review,
             codex::respond_to_server_request,
             codex::remember_approval_rule,
+            codex::approval_rules_list,
+            codex::approval_rule_validate,
+            codex::approval_rule_add,
+            codex::approval_rule_update,
+            codex::approval_rule_delete,
+            codex::approval_rule_move,
+            codex::approval_rules_test,
+            codex::auto_approval_policy_get,
+            codex::auto_approval_policy_set,
+            codex::auto_approval_test,
+            codex::agent_audit_query,
+            codex::agent_audit_export,
             codex::generate_commit_message,
             codex::generate_run_metadata,
             codex::generate_agent_description,
             codex::resume_thread,
             codex::read_thread,
+            codex::export_thread,
+            codex::search_threads,
             codex::thread_live_subscribe,
             codex::thread_live_unsubscribe,
             codex::fork_thread,
@@ -231,10 +274,13 @@ pub fn run() {
             codex::collaboration_mode_list,
             workspaces::connect_workspace,
             git::get_git_status,
+            git::get_git_status_aggregate,
             git::init_git_repo,
             git::create_github_repo,
             git::list_git_roots,
             git::get_git_diffs,
+            git::get_git_status_page,
+            git::get_git_file_diff,
             git::get_git_log,
             git::get_git_commit_diff,
             git::get_git_remote,
@@ -270,6 +316,10 @@ pub fn run() {
             codex::delete_agent,
             codex::read_agent_config_toml,
             codex::write_agent_config_toml,
+            codex::export_agent,
+            codex::import_agent,
+            codex::list_agent_templates,
+            codex::install_agent_templates,
             codex::account_rate_limits,
             codex::account_read,
             codex::codex_login,
@@ -283,6 +333,13 @@ pub fn run() {
             prompts::prompts_move,
             prompts::prompts_workspace_dir,
             prompts::prompts_global_dir,
+            prompts::prompts_render,
+            prompts::prompts_upstream_override,
+            prompts::prompts_history_list,
+            prompts::prompts_history_diff,
+            prompts::prompts_history_restore,
+            prompts::prompt_libraries_sync,
+            prompts::prompt_libraries_status,
             terminal::terminal_open,
             terminal::terminal_write,
             terminal::terminal_resize,
@@ -296,6 +353,20 @@ pub fn run() {
             dictation::dictation_stop,
             dictation::dictation_cancel,
             local_usage::local_usage_snapshot,
+            local_usage::local_usage_breakdown,
+            local_usage::local_usage_export,
+            schedules::schedules_list,
+            schedules::schedules_create,
+            schedules::schedules_update,
+            schedules::schedules_delete,
+            schedules::schedules_history,
+            schedules::schedules_run_now,
+            webhooks::webhooks_list,
+            webhooks::webhooks_create,
+            webhooks::webhooks_update,
+            webhooks::webhooks_delete,
+            webhooks::webhook_deliveries,
+            webhooks::webhooks_test,
             notifications::is_macos_debug_build,
             notifications::app_build_type,
             notifications::send_notification_fallback,
@@ -1,10 +1,14 @@
 use serde_json::json;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 
+use crate::event_sink::TauriEventSink;
 use crate::remote_backend;
 use crate::shared::local_usage_core;
 use crate::state::AppState;
-use crate::types::LocalUsageSnapshot;
+use crate::types::{
+    LocalUsageBreakdown, LocalUsageBreakdownQuery, LocalUsageExport, LocalUsageExportQuery,
+    LocalUsageSnapshot, UsageBreakdownGroup, UsageExportFormat,
+};
 
 #[tauri::command]
 pub(crate) async fn local_usage_snapshot(
@@ -24,5 +28,108 @@ pub(crate) async fn local_usage_snapshot(
         return serde_json::from_value(response).map_err(|err| err.to_string());
     }
 
-    local_usage_core::local_usage_snapshot_core(&state.workspaces, days, workspace_path).await
+    let data_dir = app.path().app_data_dir().ok();
+    let snapshot = local_usage_core::local_usage_snapshot_core(
+        &state.workspaces,
+        &state.app_settings,
+        data_dir.clone(),
+        days,
+        workspace_path,
+    )
+    .await?;
+    if let Some(data_dir) = data_dir {
+        local_usage_core::notify_usage_budgets_core(
+            &snapshot,
+            &data_dir,
+            &TauriEventSink::new(app.clone()),
+        )
+        .await;
+    }
+    Ok(snapshot)
+}
+
+/// Re-evaluates usage budgets on a fixed interval so alerts fire even when no
+/// usage view is open. Remote backends run their own check.
+pub(crate) async fn run_budget_monitor(app: AppHandle) {
+    loop {
+        tokio::time::sleep(local_usage_core::USAGE_BUDGET_CHECK_INTERVAL).await;
+        let state = app.state::<AppState>();
+        if remote_backend::is_remote_mode(&*state).await {
+            continue;
+        }
+        let Ok(data_dir) = app.path().app_data_dir() else {
+            continue;
+        };
+        local_usage_core::check_usage_budgets_core(
+            &state.workspaces,
+            &state.app_settings,
+            &data_dir,
+            &TauriEventSink::new(app.clone()),
+        )
+        .await;
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn local_usage_breakdown(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    group_by: Option<UsageBreakdownGroup>,
+    workspace_path: Option<String>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<LocalUsageBreakdown, String> {
+    let query = LocalUsageBreakdownQuery {
+        start_date,
+        end_date,
+        group_by,
+        workspace_path,
+        limit,
+    };
+    if remote_backend::is_remote_mode(&*state).await {
+        let params = serde_json::to_value(&query).map_err(|err| err.to_string())?;
+        let response =
+            remote_backend::call_remote(&*state, app, "local_usage_breakdown", params).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    local_usage_core::local_usage_breakdown_core(
+        &state.workspaces,
+        &state.app_settings,
+        app.path().app_data_dir().ok(),
+        query,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn local_usage_export(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    workspace_path: Option<String>,
+    format: Option<UsageExportFormat>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<LocalUsageExport, String> {
+    let query = LocalUsageExportQuery {
+        start_date,
+        end_date,
+        workspace_path,
+        format,
+    };
+    if remote_backend::is_remote_mode(&*state).await {
+        let params = serde_json::to_value(&query).map_err(|err| err.to_string())?;
+        let response =
+            remote_backend::call_remote(&*state, app, "local_usage_export", params).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    local_usage_core::local_usage_export_core(
+        &state.workspaces,
+        &state.app_settings,
+        app.path().app_data_dir().ok(),
+        query,
+    )
+    .await
 }
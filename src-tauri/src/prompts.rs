@@ -1,7 +1,12 @@
+use serde_json::Value;
+use std::collections::HashMap;
 use tauri::State;
 
-use crate::shared::prompts_core::{self, CustomPromptEntry};
+use crate::shared::prompts_core::{
+    self, CustomPromptEntry, PromptRenderResult, PromptRevision, PromptRevisionDiff,
+};
 use crate::state::AppState;
+use crate::types::PromptLibraryStatus;
 
 #[tauri::command]
 pub(crate) async fn prompts_list(
@@ -100,3 +105,96 @@ pub(crate) async fn prompts_move(
     )
     .await
 }
+
+#[tauri::command]
+pub(crate) async fn prompts_render(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    path: String,
+    args: Option<HashMap<String, Value>>,
+    selection: Option<String>,
+) -> Result<PromptRenderResult, String> {
+    prompts_core::prompts_render_core(
+        &state.workspaces,
+        &state.settings_path,
+        workspace_id,
+        path,
+        args.unwrap_or_default(),
+        selection,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_history_list(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    path: String,
+) -> Result<Vec<PromptRevision>, String> {
+    prompts_core::prompts_history_list_core(
+        &state.workspaces,
+        &state.settings_path,
+        workspace_id,
+        path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_history_diff(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    path: String,
+    from_revision: String,
+    to_revision: Option<String>,
+) -> Result<PromptRevisionDiff, String> {
+    prompts_core::prompts_history_diff_core(
+        &state.workspaces,
+        &state.settings_path,
+        workspace_id,
+        path,
+        from_revision,
+        to_revision,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_history_restore(
+    state: State<'_, AppState>,
+    workspace_id: String,
+    path: String,
+    revision_id: String,
+) -> Result<CustomPromptEntry, String> {
+    prompts_core::prompts_history_restore_core(
+        &state.workspaces,
+        &state.settings_path,
+        workspace_id,
+        path,
+        revision_id,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn prompt_libraries_sync(
+    state: State<'_, AppState>,
+) -> Result<Vec<PromptLibraryStatus>, String> {
+    prompts_core::prompt_libraries_sync_core(&state.app_settings, &state.settings_path).await
+}
+
+#[tauri::command]
+pub(crate) async fn prompt_libraries_status(
+    state: State<'_, AppState>,
+) -> Result<Vec<PromptLibraryStatus>, String> {
+    prompts_core::prompt_libraries_status_core(&state.settings_path)
+}
+
+#[tauri::command]
+pub(crate) async fn prompts_upstream_override(
+    state: State<'_, AppState>,
+    library_id: String,
+    name: String,
+) -> Result<CustomPromptEntry, String> {
+    prompts_core::prompts_upstream_override_core(&state.settings_path, library_id, name).await
+}
@@ -147,28 +147,48 @@ fn can_retry_after_disconnect(method: &str) -> bool {
         method,
         "account_rate_limits"
             | "account_read"
+            | "agent_audit_export"
+            | "agent_audit_query"
             | "apps_list"
+            | "approval_rule_validate"
+            | "approval_rules_list"
+            | "approval_rules_test"
+            | "auto_approval_policy_get"
+            | "auto_approval_test"
+            | "batch_runs_diff_summary"
+            | "batch_runs_list"
+            | "batch_runs_status"
             | "collaboration_mode_list"
+            | "config_toml_validate"
             | "connect_workspace"
             | "experimental_feature_list"
+            | "export_agent"
+            | "export_thread"
             | "set_workspace_runtime_codex_args"
             | "file_read"
+            | "export_workspace_bundle"
             | "get_agents_settings"
             | "get_config_model"
             | "get_git_commit_diff"
             | "get_git_diffs"
+            | "get_git_file_diff"
             | "get_git_log"
             | "get_git_remote"
             | "get_git_status"
+            | "get_git_status_aggregate"
+            | "get_git_status_page"
             | "get_github_issues"
             | "get_github_pull_request_comments"
             | "get_github_pull_request_diff"
             | "get_github_pull_requests"
             | "is_workspace_path_dir"
+            | "list_agent_templates"
             | "list_git_branches"
             | "list_git_roots"
             | "list_mcp_server_status"
             | "list_threads"
+            | "local_usage_breakdown"
+            | "local_usage_export"
             | "local_usage_snapshot"
             | "list_workspace_files"
             | "list_workspaces"
@@ -177,9 +197,15 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "read_agent_config_toml"
             | "read_workspace_file"
             | "resume_thread"
+            | "schedules_history"
+            | "schedules_list"
+            | "search_threads"
             | "thread_live_subscribe"
             | "thread_live_unsubscribe"
             | "skills_list"
+            | "webhook_deliveries"
+            | "webhooks_list"
+            | "worktree_setup_log"
             | "worktree_setup_status"
     )
 }
@@ -4,6 +4,11 @@ use std::path::{Path, PathBuf};
 use std::thread;
 use std::time::{Duration, Instant, SystemTime};
 
+use crate::types::{
+    ApprovalRuleEntry, ApprovalRuleMatch, ApprovalRuleSpec, ApprovalRuleTestResult,
+    ApprovalRulesFile, RuleDecision, RuleExample, RulePatternToken,
+};
+
 const RULES_DIR: &str = "rules";
 const DEFAULT_RULES_FILE: &str = "default.rules";
 
@@ -160,3 +165,815 @@ fn escape_string(value: &str) -> String {
         .replace('\r', "\\r")
         .replace('\t', "\\t")
 }
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Str(String),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Equals,
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    start: usize,
+    end: usize,
+    line: usize,
+    col: usize,
+}
+
+#[derive(Debug, Clone)]
+enum RuleValue {
+    Str(String),
+    List(Vec<RuleValue>),
+    Ident(String),
+}
+
+struct ParsedRule {
+    start: usize,
+    end: usize,
+    line: usize,
+    spec: ApprovalRuleSpec,
+}
+
+fn parse_error(line: usize, col: usize, message: &str) -> String {
+    format!("line {line}, column {col}: {message}")
+}
+
+fn tokenize(contents: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = contents.char_indices().peekable();
+    let mut line = 1;
+    let mut line_start = 0;
+
+    while let Some((start, ch)) = chars.next() {
+        let col = contents[line_start..start].chars().count() + 1;
+        let single = match ch {
+            '\n' => {
+                line += 1;
+                line_start = start + 1;
+                continue;
+            }
+            ch if ch.is_whitespace() => continue,
+            '#' => {
+                while let Some((_, next)) = chars.peek() {
+                    if *next == '\n' {
+                        break;
+                    }
+                    chars.next();
+                }
+                continue;
+            }
+            '(' => Some(TokenKind::LParen),
+            ')' => Some(TokenKind::RParen),
+            '[' => Some(TokenKind::LBracket),
+            ']' => Some(TokenKind::RBracket),
+            '=' => Some(TokenKind::Equals),
+            ',' => Some(TokenKind::Comma),
+            _ => None,
+        };
+        if let Some(kind) = single {
+            tokens.push(Token {
+                kind,
+                start,
+                end: start + 1,
+                line,
+                col,
+            });
+            continue;
+        }
+        if ch == '"' || ch == '\'' {
+            let mut value = String::new();
+            let mut end = None;
+            while let Some((index, next)) = chars.next() {
+                match next {
+                    '\\' => {
+                        let Some((_, escaped)) = chars.next() else {
+                            break;
+                        };
+                        value.push(match escaped {
+                            'n' => '\n',
+                            'r' => '\r',
+                            't' => '\t',
+                            other => other,
+                        });
+                    }
+                    '\n' => break,
+                    next if next == ch => {
+                        end = Some(index + 1);
+                        break;
+                    }
+                    next => value.push(next),
+                }
+            }
+            let Some(end) = end else {
+                return Err(parse_error(line, col, "unterminated string"));
+            };
+            tokens.push(Token {
+                kind: TokenKind::Str(value),
+                start,
+                end,
+                line,
+                col,
+            });
+            continue;
+        }
+        if ch.is_ascii_alphabetic() || ch == '_' {
+            let mut end = start + ch.len_utf8();
+            while let Some((index, next)) = chars.peek() {
+                if !(next.is_ascii_alphanumeric() || *next == '_') {
+                    break;
+                }
+                end = index + next.len_utf8();
+                chars.next();
+            }
+            tokens.push(Token {
+                kind: TokenKind::Ident(contents[start..end].to_string()),
+                start,
+                end,
+                line,
+                col,
+            });
+            continue;
+        }
+        return Err(parse_error(
+            line,
+            col,
+            &format!("unexpected character `{ch}`"),
+        ));
+    }
+    Ok(tokens)
+}
+
+struct RuleParser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl RuleParser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn error_at_end(&self, message: &str) -> String {
+        match self.tokens.last() {
+            Some(token) => parse_error(token.line, token.col, message),
+            None => parse_error(1, 1, message),
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind, what: &str) -> Result<Token, String> {
+        match self.advance().cloned() {
+            Some(token) if token.kind == kind => Ok(token),
+            Some(token) => Err(parse_error(
+                token.line,
+                token.col,
+                &format!("expected {what}"),
+            )),
+            None => Err(self.error_at_end(&format!("expected {what}"))),
+        }
+    }
+
+    fn value(&mut self) -> Result<RuleValue, String> {
+        let Some(token) = self.advance().cloned() else {
+            return Err(self.error_at_end("expected a value"));
+        };
+        match token.kind {
+            TokenKind::Str(value) => Ok(RuleValue::Str(value)),
+            TokenKind::Ident(value) => Ok(RuleValue::Ident(value)),
+            TokenKind::LBracket => {
+                let mut items = Vec::new();
+                loop {
+                    if matches!(
+                        self.peek().map(|token| &token.kind),
+                        Some(TokenKind::RBracket)
+                    ) {
+                        self.advance();
+                        break;
+                    }
+                    items.push(self.value()?);
+                    match self.advance().cloned() {
+                        Some(Token {
+                            kind: TokenKind::Comma,
+                            ..
+                        }) => continue,
+                        Some(Token {
+                            kind: TokenKind::RBracket,
+                            ..
+                        }) => break,
+                        Some(other) => {
+                            return Err(parse_error(other.line, other.col, "expected `,` or `]`"))
+                        }
+                        None => return Err(self.error_at_end("unterminated list")),
+                    }
+                }
+                Ok(RuleValue::List(items))
+            }
+            _ => Err(parse_error(token.line, token.col, "expected a value")),
+        }
+    }
+
+    /// Parses `name(key = value, ...)` after the name token; returns the
+    /// keyword arguments and the closing paren.
+    fn call_arguments(&mut self) -> Result<(Vec<(Token, RuleValue)>, Token), String> {
+        self.expect(TokenKind::LParen, "`(`")?;
+        let mut arguments = Vec::new();
+        loop {
+            if let Some(token) = self.peek().cloned() {
+                if token.kind == TokenKind::RParen {
+                    self.advance();
+                    return Ok((arguments, token));
+                }
+            }
+            let Some(key) = self.advance().cloned() else {
+                return Err(self.error_at_end("unterminated call"));
+            };
+            if !matches!(key.kind, TokenKind::Ident(_)) {
+                return Err(parse_error(
+                    key.line,
+                    key.col,
+                    "expected a keyword argument",
+                ));
+            }
+            self.expect(TokenKind::Equals, "`=`")?;
+            arguments.push((key, self.value()?));
+            match self.advance().cloned() {
+                Some(Token {
+                    kind: TokenKind::Comma,
+                    ..
+                }) => continue,
+                Some(token) if token.kind == TokenKind::RParen => {
+                    return Ok((arguments, token));
+                }
+                Some(other) => {
+                    return Err(parse_error(other.line, other.col, "expected `,` or `)`"))
+                }
+                None => return Err(self.error_at_end("unterminated call")),
+            }
+        }
+    }
+}
+
+fn value_strings(value: &RuleValue) -> Option<Vec<String>> {
+    match value {
+        RuleValue::List(items) => items
+            .iter()
+            .map(|item| match item {
+                RuleValue::Str(value) => Some(value.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+fn rule_spec_from_arguments(
+    arguments: Vec<(Token, RuleValue)>,
+    call: &Token,
+) -> Result<ApprovalRuleSpec, String> {
+    let mut pattern = None;
+    let mut decision = RuleDecision::Allow;
+    let mut justification = None;
+    let mut match_examples = Vec::new();
+    let mut not_match_examples = Vec::new();
+
+    for (key, value) in arguments {
+        let TokenKind::Ident(name) = &key.kind else {
+            continue;
+        };
+        let invalid = |what: &str| parse_error(key.line, key.col, &format!("`{name}` {what}"));
+        match name.as_str() {
+            "pattern" => {
+                let RuleValue::List(items) = value else {
+                    return Err(invalid("must be a list"));
+                };
+                let tokens = items
+                    .iter()
+                    .map(|item| match item {
+                        RuleValue::Str(value) => Ok(RulePatternToken::Literal(value.clone())),
+                        RuleValue::List(_) => value_strings(item)
+                            .map(RulePatternToken::Alternatives)
+                            .ok_or_else(|| invalid("must contain strings or lists of strings")),
+                        RuleValue::Ident(ident) => Err(invalid(&format!(
+                            "must contain strings or lists of strings, got identifier `{ident}`"
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                pattern = Some(tokens);
+            }
+            "decision" => {
+                decision = match value {
+                    RuleValue::Str(value) => match value.as_str() {
+                        "allow" => RuleDecision::Allow,
+                        "prompt" => RuleDecision::Prompt,
+                        "forbidden" => RuleDecision::Forbidden,
+                        _ => return Err(invalid("must be \"allow\", \"prompt\" or \"forbidden\"")),
+                    },
+                    RuleValue::Ident(ident) => {
+                        return Err(invalid(&format!(
+                            "must be a string, got identifier `{ident}`"
+                        )))
+                    }
+                    RuleValue::List(_) => return Err(invalid("must be a string")),
+                };
+            }
+            "justification" => match value {
+                RuleValue::Str(value) => justification = Some(value),
+                RuleValue::Ident(ident) => {
+                    return Err(invalid(&format!(
+                        "must be a string, got identifier `{ident}`"
+                    )))
+                }
+                RuleValue::List(_) => return Err(invalid("must be a string")),
+            },
+            "match" | "not_match" => {
+                let RuleValue::List(items) = value else {
+                    return Err(invalid("must be a list"));
+                };
+                let examples = items
+                    .iter()
+                    .map(|item| match item {
+                        RuleValue::Str(value) => Ok(RuleExample::Command(value.clone())),
+                        RuleValue::List(_) => value_strings(item)
+                            .map(RuleExample::Argv)
+                            .ok_or_else(|| invalid("must contain strings or lists of strings")),
+                        RuleValue::Ident(ident) => Err(invalid(&format!(
+                            "must contain strings or lists of strings, got identifier `{ident}`"
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                if name == "match" {
+                    match_examples = examples;
+                } else {
+                    not_match_examples = examples;
+                }
+            }
+            _ => return Err(invalid("is not a known prefix_rule argument")),
+        }
+    }
+
+    let Some(pattern) = pattern else {
+        return Err(parse_error(
+            call.line,
+            call.col,
+            "prefix_rule is missing `pattern`",
+        ));
+    };
+    Ok(ApprovalRuleSpec {
+        pattern,
+        decision,
+        justification,
+        match_examples,
+        not_match_examples,
+    })
+}
+
+/// Parses the `prefix_rule(...)` calls in a rules file. Other top-level calls
+/// are skipped but left in place when the file is rewritten.
+fn parse_rules(contents: &str) -> Result<Vec<ParsedRule>, String> {
+    let tokens = tokenize(contents)?;
+    let mut parser = RuleParser {
+        tokens: &tokens,
+        pos: 0,
+    };
+    let mut rules = Vec::new();
+    while let Some(token) = parser.advance().cloned() {
+        let TokenKind::Ident(name) = &token.kind else {
+            return Err(parse_error(token.line, token.col, "expected a rule"));
+        };
+        let (arguments, close) = parser.call_arguments()?;
+        if name != "prefix_rule" {
+            continue;
+        }
+        let spec = rule_spec_from_arguments(arguments, &token)?;
+        rules.push(ParsedRule {
+            start: token.start,
+            end: close.end,
+            line: token.line,
+            spec,
+        });
+    }
+    Ok(rules)
+}
+
+fn example_argv(example: &RuleExample) -> Result<Vec<String>, String> {
+    match example {
+        RuleExample::Command(command) => shell_words::split(command)
+            .map_err(|err| format!("Example `{command}` is not a valid command: {err}")),
+        RuleExample::Argv(argv) => Ok(argv.clone()),
+    }
+}
+
+fn pattern_matches(pattern: &[RulePatternToken], command: &[String]) -> bool {
+    command.len() >= pattern.len()
+        && pattern.iter().zip(command).all(|(token, arg)| match token {
+            RulePatternToken::Literal(value) => value == arg,
+            RulePatternToken::Alternatives(values) => values.contains(arg),
+        })
+}
+
+pub(crate) fn validate_rule(spec: &ApprovalRuleSpec) -> Vec<String> {
+    let mut problems = Vec::new();
+    if spec.pattern.is_empty() {
+        problems.push("Pattern must not be empty.".to_string());
+    }
+    for (index, token) in spec.pattern.iter().enumerate() {
+        let position = index + 1;
+        match token {
+            RulePatternToken::Literal(value) if value.trim().is_empty() => {
+                problems.push(format!("Pattern token {position} must not be empty."));
+            }
+            RulePatternToken::Alternatives(values) if values.is_empty() => {
+                problems.push(format!(
+                    "Pattern token {position} must list at least one alternative."
+                ));
+            }
+            RulePatternToken::Alternatives(values)
+                if values.iter().any(|value| value.trim().is_empty()) =>
+            {
+                problems.push(format!(
+                    "Pattern token {position} has an empty alternative."
+                ));
+            }
+            _ => {}
+        }
+    }
+    let examples = spec
+        .match_examples
+        .iter()
+        .map(|example| (example, true))
+        .chain(
+            spec.not_match_examples
+                .iter()
+                .map(|example| (example, false)),
+        );
+    for (example, should_match) in examples {
+        match example_argv(example) {
+            Ok(argv) if pattern_matches(&spec.pattern, &argv) != should_match => {
+                let expectation = if should_match {
+                    "does not match"
+                } else {
+                    "unexpectedly matches"
+                };
+                problems.push(format!(
+                    "Example `{}` {expectation} the pattern.",
+                    argv.join(" ")
+                ));
+            }
+            Ok(_) => {}
+            Err(err) => problems.push(err),
+        }
+    }
+    problems
+}
+
+fn format_pattern_token(token: &RulePatternToken) -> String {
+    match token {
+        RulePatternToken::Literal(value) => format!("\"{}\"", escape_string(value)),
+        RulePatternToken::Alternatives(values) => format!("[{}]", format_pattern_list(values)),
+    }
+}
+
+fn format_example(example: &RuleExample) -> String {
+    match example {
+        RuleExample::Command(command) => format!("\"{}\"", escape_string(command)),
+        RuleExample::Argv(argv) => format!("[{}]", format_pattern_list(argv)),
+    }
+}
+
+fn format_rule(spec: &ApprovalRuleSpec) -> String {
+    let pattern = spec
+        .pattern
+        .iter()
+        .map(format_pattern_token)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let decision = match spec.decision {
+        RuleDecision::Allow => "allow",
+        RuleDecision::Prompt => "prompt",
+        RuleDecision::Forbidden => "forbidden",
+    };
+    let mut rule =
+        format!("prefix_rule(\n    pattern = [{pattern}],\n    decision = \"{decision}\",\n");
+    if let Some(justification) = spec
+        .justification
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        rule.push_str(&format!(
+            "    justification = \"{}\",\n",
+            escape_string(justification)
+        ));
+    }
+    for (key, examples) in [
+        ("match", &spec.match_examples),
+        ("not_match", &spec.not_match_examples),
+    ] {
+        if !examples.is_empty() {
+            let items = examples
+                .iter()
+                .map(format_example)
+                .collect::<Vec<_>>()
+                .join(", ");
+            rule.push_str(&format!("    {key} = [{items}],\n"));
+        }
+    }
+    rule.push(')');
+    rule
+}
+
+pub(crate) fn rules_dir(codex_home: &Path) -> PathBuf {
+    codex_home.join(RULES_DIR)
+}
+
+/// Resolves a rules file name inside `CODEX_HOME/rules`, defaulting to
+/// `default.rules`.
+pub(crate) fn resolve_rules_file(codex_home: &Path, name: Option<&str>) -> Result<PathBuf, String> {
+    let name = name
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_RULES_FILE);
+    if name.contains('/') || name.contains('\\') || name.starts_with('.') {
+        return Err("Invalid rules file name.".to_string());
+    }
+    let file_name = if name.ends_with(".rules") {
+        name.to_string()
+    } else {
+        format!("{name}.rules")
+    };
+    Ok(rules_dir(codex_home).join(file_name))
+}
+
+fn rules_file_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or(DEFAULT_RULES_FILE)
+        .to_string()
+}
+
+pub(crate) fn read_rules_file(path: &Path) -> ApprovalRulesFile {
+    let contents = fs::read_to_string(path).unwrap_or_default();
+    let (rules, error) = match parse_rules(&contents) {
+        Ok(rules) => (rules, None),
+        Err(err) => (Vec::new(), Some(err)),
+    };
+    ApprovalRulesFile {
+        name: rules_file_name(path),
+        path: path.to_string_lossy().to_string(),
+        rules: rules
+            .into_iter()
+            .enumerate()
+            .map(|(index, rule)| ApprovalRuleEntry {
+                index,
+                line: rule.line,
+                spec: rule.spec,
+            })
+            .collect(),
+        error,
+    }
+}
+
+pub(crate) fn list_rules_files(codex_home: &Path) -> Vec<ApprovalRulesFile> {
+    let mut paths = fs::read_dir(rules_dir(codex_home))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.is_file()
+                        && path.extension().and_then(|value| value.to_str()) == Some("rules")
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    paths.sort();
+    paths.iter().map(|path| read_rules_file(path)).collect()
+}
+
+/// Applies an edit to a rules file under the rules lock. The closure gets the
+/// current contents and parsed rules and returns the new contents.
+fn edit_rules_file<F>(path: &Path, edit: F) -> Result<ApprovalRulesFile, String>
+where
+    F: FnOnce(&str, &[ParsedRule]) -> Result<String, String>,
+{
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    {
+        let _lock = acquire_rules_lock(path)?;
+        let existing = fs::read_to_string(path).unwrap_or_default();
+        let rules = parse_rules(&existing)
+            .map_err(|err| format!("Cannot edit {}: {err}", rules_file_name(path)))?;
+        let mut updated = edit(&existing, &rules)?;
+        if !updated.is_empty() && !updated.ends_with('\n') {
+            updated.push('\n');
+        }
+        fs::write(path, updated).map_err(|err| err.to_string())?;
+    }
+    Ok(read_rules_file(path))
+}
+
+fn rule_at(rules: &[ParsedRule], index: usize) -> Result<&ParsedRule, String> {
+    rules
+        .get(index)
+        .ok_or_else(|| format!("Rule {index} not found."))
+}
+
+fn ensure_valid_rule(spec: &ApprovalRuleSpec) -> Result<(), String> {
+    let problems = validate_rule(spec);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join(" "))
+    }
+}
+
+pub(crate) fn insert_rule(
+    path: &Path,
+    spec: &ApprovalRuleSpec,
+    index: Option<usize>,
+) -> Result<ApprovalRulesFile, String> {
+    ensure_valid_rule(spec)?;
+    edit_rules_file(path, |existing, rules| {
+        let rule = format_rule(spec);
+        match index.and_then(|index| rules.get(index)) {
+            Some(before) => Ok(format!(
+                "{}{rule}\n\n{}",
+                &existing[..before.start],
+                &existing[before.start..]
+            )),
+            None => {
+                let mut updated = existing.to_string();
+                if !updated.is_empty() && !updated.ends_with('\n') {
+                    updated.push('\n');
+                }
+                if !updated.is_empty() {
+                    updated.push('\n');
+                }
+                updated.push_str(&rule);
+                Ok(updated)
+            }
+        }
+    })
+}
+
+pub(crate) fn replace_rule(
+    path: &Path,
+    index: usize,
+    spec: &ApprovalRuleSpec,
+) -> Result<ApprovalRulesFile, String> {
+    ensure_valid_rule(spec)?;
+    edit_rules_file(path, |existing, rules| {
+        let target = rule_at(rules, index)?;
+        Ok(format!(
+            "{}{}{}",
+            &existing[..target.start],
+            format_rule(spec),
+            &existing[target.end..]
+        ))
+    })
+}
+
+pub(crate) fn remove_rule(path: &Path, index: usize) -> Result<ApprovalRulesFile, String> {
+    edit_rules_file(path, |existing, rules| {
+        let target = rule_at(rules, index)?;
+        let mut before = &existing[..target.start];
+        let mut after = &existing[target.end..];
+        after = after.strip_prefix('\n').unwrap_or(after);
+        // Drop the blank separator line that surrounded the removed rule.
+        if before.is_empty() || before.ends_with('\n') {
+            after = after.strip_prefix('\n').unwrap_or(after);
+        }
+        if after.is_empty() && before.ends_with("\n\n") {
+            before = &before[..before.len() - 1];
+        }
+        Ok(format!("{before}{after}"))
+    })
+}
+
+/// Moves a rule to a new position; rule text is swapped between the existing
+/// rule slots so comments and spacing stay where they were.
+pub(crate) fn move_rule(path: &Path, from: usize, to: usize) -> Result<ApprovalRulesFile, String> {
+    edit_rules_file(path, |existing, rules| {
+        rule_at(rules, from)?;
+        rule_at(rules, to)?;
+        let mut texts = rules
+            .iter()
+            .map(|rule| &existing[rule.start..rule.end])
+            .collect::<Vec<_>>();
+        let moved = texts.remove(from);
+        texts.insert(to, moved);
+        let mut updated = String::with_capacity(existing.len());
+        let mut cursor = 0;
+        for (rule, text) in rules.iter().zip(texts) {
+            updated.push_str(&existing[cursor..rule.start]);
+            updated.push_str(text);
+            cursor = rule.end;
+        }
+        updated.push_str(&existing[cursor..]);
+        Ok(updated)
+    })
+}
+
+/// Reports every rule whose prefix matches `command` and the resulting
+/// decision, which is the most restrictive of the matches.
+pub(crate) fn test_command(codex_home: &Path, command: Vec<String>) -> ApprovalRuleTestResult {
+    let mut matches = Vec::new();
+    for file in list_rules_files(codex_home) {
+        for rule in file.rules {
+            if pattern_matches(&rule.spec.pattern, &command) {
+                matches.push(ApprovalRuleMatch {
+                    file: file.name.clone(),
+                    index: rule.index,
+                    line: rule.line,
+                    decision: rule.spec.decision,
+                    pattern: rule.spec.pattern,
+                });
+            }
+        }
+    }
+    ApprovalRuleTestResult {
+        decision: matches.iter().map(|rule| rule.decision).max(),
+        command,
+        matches,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_codex_home() -> PathBuf {
+        std::env::temp_dir().join(format!("codex-rules-{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn parse_rules_reads_alternatives_examples_and_positions() {
+        let contents = "# team rules\nprefix_rule(pattern = [\"git\", [\"status\", \"diff\"]], decision = \"allow\", match = [\"git status -s\"])\n\nprefix_rule(\n    pattern = [\"rm\"],\n    decision = \"forbidden\",\n    justification = \"no deletes\",\n)\n";
+        let rules = parse_rules(contents).expect("parse");
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].line, 2);
+        assert_eq!(
+            rules[0].spec.pattern[1],
+            RulePatternToken::Alternatives(vec!["status".to_string(), "diff".to_string()])
+        );
+        assert!(validate_rule(&rules[0].spec).is_empty());
+        assert_eq!(rules[1].spec.decision, RuleDecision::Forbidden);
+
+        let err = parse_rules("prefix_rule(pattern = [\"ls\"], decision = \"maybe\")")
+            .err()
+            .expect("invalid decision");
+        assert!(err.starts_with("line 1, column 31"), "{err}");
+
+        let err = parse_rules("prefix_rule(pattern = [\"ls\"], decision = allow)")
+            .err()
+            .expect("identifier decision");
+        assert!(err.contains("got identifier `allow`"), "{err}");
+    }
+
+    #[test]
+    fn rule_edits_round_trip_and_test_command_picks_strictest() {
+        let codex_home = temp_codex_home();
+        let path = resolve_rules_file(&codex_home, None).expect("rules path");
+        append_prefix_rule(&path, &["git".to_string()]).expect("append");
+        let push = ApprovalRuleSpec {
+            pattern: vec![
+                RulePatternToken::Literal("git".to_string()),
+                RulePatternToken::Literal("push".to_string()),
+            ],
+            decision: RuleDecision::Prompt,
+            justification: None,
+            match_examples: vec![RuleExample::Command("git push origin".to_string())],
+            not_match_examples: Vec::new(),
+        };
+        insert_rule(&path, &push, None).expect("insert");
+        let file = move_rule(&path, 1, 0).expect("move");
+        assert_eq!(file.rules[0].spec, push);
+
+        let result = test_command(
+            &codex_home,
+            vec!["git".to_string(), "push".to_string(), "origin".to_string()],
+        );
+        assert_eq!(result.matches.len(), 2);
+        assert_eq!(result.decision, Some(RuleDecision::Prompt));
+
+        let file = remove_rule(&path, 0).expect("remove");
+        assert_eq!(file.rules.len(), 1);
+        let contents = fs::read_to_string(&path).expect("read");
+        let _ = fs::remove_dir_all(&codex_home);
+        assert!(rule_already_present(&contents, &["git".to_string()]));
+        assert!(!contents.starts_with('\n'));
+    }
+}
@@ -0,0 +1,188 @@
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+
+use crate::event_sink::TauriEventSink;
+use crate::remote_backend;
+use crate::shared::{schedules_core, workspaces_core};
+use crate::state::AppState;
+use crate::types::{ScheduledRun, ScheduledRunInput, ScheduledRunRecord};
+
+fn app_data_dir(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))
+}
+
+#[tauri::command]
+pub(crate) async fn schedules_list(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ScheduledRun>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "schedules_list", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    schedules_core::schedules_list_core(&app_data_dir(&app)?)
+}
+
+#[tauri::command]
+pub(crate) async fn schedules_create(
+    input: ScheduledRunInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ScheduledRun, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "schedules_create",
+            json!({ "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    schedules_core::schedules_create_core(&app_data_dir(&app)?, &state.workspaces, input).await
+}
+
+#[tauri::command]
+pub(crate) async fn schedules_update(
+    id: String,
+    input: ScheduledRunInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ScheduledRun, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "schedules_update",
+            json!({ "id": id, "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    schedules_core::schedules_update_core(&app_data_dir(&app)?, &state.workspaces, id, input).await
+}
+
+#[tauri::command]
+pub(crate) async fn schedules_delete(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(&*state, app, "schedules_delete", json!({ "id": id })).await?;
+        return Ok(());
+    }
+
+    schedules_core::schedules_delete_core(&app_data_dir(&app)?, id)
+}
+
+#[tauri::command]
+pub(crate) async fn schedules_history(
+    schedule_id: Option<String>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<ScheduledRunRecord>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "schedules_history",
+            json!({ "scheduleId": schedule_id, "limit": limit }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    schedules_core::schedules_history_core(
+        &app_data_dir(&app)?,
+        schedule_id,
+        limit.map(|value| value as usize),
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn schedules_run_now(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ScheduledRunRecord, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "schedules_run_now", json!({ "id": id }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    schedules_core::schedules_run_now_core(&app_data_dir(&app)?, &state.schedule_runner, id)
+}
+
+async fn connect_for_schedule(app: &AppHandle, workspace_id: String) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Local schedules do not run while the app uses a remote backend.".to_string());
+    }
+    if state.sessions.lock().await.contains_key(&workspace_id) {
+        return Ok(());
+    }
+    workspaces_core::connect_workspace_core(
+        workspace_id,
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        |entry, default_bin, codex_args, codex_home| {
+            crate::codex::spawn_workspace_session(
+                entry,
+                default_bin,
+                codex_args,
+                app.clone(),
+                codex_home,
+            )
+        },
+    )
+    .await
+}
+
+/// Drives the local schedule clock and runs queued jobs until the app exits.
+pub(crate) async fn run_scheduler(app: AppHandle) {
+    let Ok(data_dir) = app_data_dir(&app) else {
+        return;
+    };
+    let clock_app = app.clone();
+    let clock_data_dir = data_dir.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = clock_app.state::<AppState>();
+        schedules_core::run_schedule_clock(&clock_data_dir, &state.schedule_runner).await;
+    });
+
+    let Some(mut jobs) = app.state::<AppState>().schedule_runner.take_jobs().await else {
+        return;
+    };
+    while let Some(job) = jobs.recv().await {
+        let app = app.clone();
+        let data_dir = data_dir.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<AppState>();
+            let run_id = job.run_id.clone();
+            let connect = connect_for_schedule(&app, job.schedule.workspace_id.clone());
+            if let Err(err) = schedules_core::execute_scheduled_run_core(
+                &data_dir,
+                &state.sessions,
+                &state.workspaces,
+                &TauriEventSink::new(app.clone()),
+                job,
+                connect,
+            )
+            .await
+            {
+                eprintln!("scheduled run {run_id} failed to record: {err}");
+            }
+        });
+    }
+}
@@ -0,0 +1,639 @@
+use chrono::{Local, TimeZone};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Mutex as StdMutex, OnceLock};
+
+use uuid::Uuid;
+
+use crate::shared::local_usage_core::csv_field;
+use crate::shared::store_helpers::{now_ms, StoreLock};
+use crate::types::{
+    AgentAuditEntry, AgentAuditExport, AgentAuditFileChange, AgentAuditKind, AgentAuditQuery,
+    UsageExportFormat,
+};
+
+const AUDIT_DIR: &str = "agent-audit";
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept per workspace (`<id>.1.jsonl` is the newest).
+const MAX_ROTATED_FILES: usize = 5;
+const DEFAULT_QUERY_LIMIT: usize = 200;
+const MAX_QUERY_LIMIT: usize = 5_000;
+
+/// Serializes appends and rotation; every connected session writes here.
+static STORE_LOCK: StoreLock = StoreLock::new();
+
+/// Entries are recorded from the app-server reader loops, which must not wait
+/// on the disk; a single writer thread does the appends in order.
+static AUDIT_WRITER: OnceLock<mpsc::Sender<AuditWrite>> = OnceLock::new();
+
+enum AuditWrite {
+    Append(PathBuf, AgentAuditEntry),
+    Flush(mpsc::Sender<()>),
+}
+
+/// Approval requests waiting for an answer, so the decision entry can carry
+/// the thread and turn of the request it answers.
+#[derive(Default)]
+pub(crate) struct AgentAuditTracker {
+    pending_approvals: StdMutex<HashMap<String, PendingApproval>>,
+}
+
+struct PendingApproval {
+    thread_id: Option<String>,
+    turn_id: Option<String>,
+    item_id: Option<String>,
+    method: String,
+}
+
+impl AgentAuditTracker {
+    fn remember(&self, request_id: &Value, pending: PendingApproval) {
+        self.pending_approvals
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(request_id.to_string(), pending);
+    }
+
+    fn take(&self, request_id: &Value) -> Option<PendingApproval> {
+        self.pending_approvals
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&request_id.to_string())
+    }
+}
+
+fn file_stem(workspace_id: &str) -> String {
+    workspace_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn log_path(data_dir: &Path, workspace_id: &str, generation: usize) -> PathBuf {
+    let stem = file_stem(workspace_id);
+    let name = if generation == 0 {
+        format!("{stem}.jsonl")
+    } else {
+        format!("{stem}.{generation}.jsonl")
+    };
+    data_dir.join(AUDIT_DIR).join(name)
+}
+
+fn rotate(data_dir: &Path, workspace_id: &str) -> Result<(), String> {
+    let oldest = log_path(data_dir, workspace_id, MAX_ROTATED_FILES);
+    if oldest.exists() {
+        std::fs::remove_file(&oldest).map_err(|err| err.to_string())?;
+    }
+    for generation in (0..MAX_ROTATED_FILES).rev() {
+        let from = log_path(data_dir, workspace_id, generation);
+        if from.exists() {
+            std::fs::rename(&from, log_path(data_dir, workspace_id, generation + 1))
+                .map_err(|err| format!("Failed to rotate {}: {err}", from.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn append_entry(data_dir: &Path, entry: &AgentAuditEntry) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock();
+    let path = log_path(data_dir, &entry.workspace_id, 0);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    if std::fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0) > MAX_LOG_BYTES {
+        rotate(data_dir, &entry.workspace_id)?;
+    }
+    let mut line = serde_json::to_string(entry).map_err(|err| err.to_string())?;
+    line.push('\n');
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+    file.write_all(line.as_bytes())
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+fn audit_writer() -> &'static mpsc::Sender<AuditWrite> {
+    AUDIT_WRITER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for write in rx {
+                match write {
+                    AuditWrite::Append(data_dir, entry) => {
+                        if let Err(err) = append_entry(&data_dir, &entry) {
+                            eprintln!("agent audit: {err}");
+                        }
+                    }
+                    AuditWrite::Flush(done) => {
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+        tx
+    })
+}
+
+fn queue_entry(data_dir: &Path, entry: AgentAuditEntry) {
+    if audit_writer()
+        .send(AuditWrite::Append(data_dir.to_path_buf(), entry))
+        .is_err()
+    {
+        eprintln!("agent audit: writer stopped; entry dropped");
+    }
+}
+
+/// Waits until everything queued so far is on disk, so reads see it.
+fn flush_queued_entries() {
+    let (done_tx, done_rx) = mpsc::channel();
+    if audit_writer().send(AuditWrite::Flush(done_tx)).is_ok() {
+        let _ = done_rx.recv();
+    }
+}
+
+fn new_entry(workspace_id: &str, kind: AgentAuditKind) -> AgentAuditEntry {
+    AgentAuditEntry {
+        id: Uuid::new_v4().to_string(),
+        timestamp_ms: now_ms(),
+        workspace_id: workspace_id.to_string(),
+        kind,
+        thread_id: None,
+        turn_id: None,
+        item_id: None,
+        status: None,
+        command: None,
+        cwd: None,
+        exit_code: None,
+        duration_ms: None,
+        files: Vec::new(),
+        method: None,
+        request_id: None,
+        decision: None,
+        decided_by: None,
+        rule_name: None,
+        error: None,
+    }
+}
+
+fn string_field(value: Option<&Value>, key: &str) -> Option<String> {
+    value
+        .and_then(|value| value.get(key))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn command_text(value: Option<&Value>) -> Option<String> {
+    match value {
+        Some(Value::Array(parts)) => Some(
+            parts
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        Some(Value::String(command)) => Some(command.clone()),
+        _ => None,
+    }
+}
+
+fn file_changes(item: &Value) -> Vec<AgentAuditFileChange> {
+    item.get("changes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|change| {
+            let path = change.get("path").and_then(Value::as_str)?;
+            let kind = match change.get("kind") {
+                Some(Value::String(kind)) => kind.as_str(),
+                Some(kind) => kind.get("type").and_then(Value::as_str).unwrap_or(""),
+                None => "",
+            };
+            Some(AgentAuditFileChange {
+                path: path.to_string(),
+                kind: match kind.to_ascii_lowercase().as_str() {
+                    "add" => "add",
+                    "delete" => "delete",
+                    _ => "update",
+                }
+                .to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Maps one app-server message to an audit entry, if it is one we keep.
+fn entry_for_message(
+    tracker: &AgentAuditTracker,
+    workspace_id: &str,
+    thread_id: Option<&str>,
+    message: &Value,
+) -> Option<AgentAuditEntry> {
+    let method = message.get("method").and_then(Value::as_str)?;
+    let params = message.get("params");
+    let turn_id = string_field(params, "turnId")
+        .or_else(|| string_field(params.and_then(|params| params.get("turn")), "id"));
+    let mut entry = match method {
+        "turn/started" => new_entry(workspace_id, AgentAuditKind::TurnStarted),
+        "turn/completed" => {
+            let turn = params.and_then(|params| params.get("turn"));
+            let mut entry = new_entry(workspace_id, AgentAuditKind::TurnCompleted);
+            entry.status = string_field(turn, "status");
+            entry.error = string_field(turn.and_then(|turn| turn.get("error")), "message");
+            entry
+        }
+        "item/completed" => {
+            let item = params.and_then(|params| params.get("item"))?;
+            let mut entry = match item.get("type").and_then(Value::as_str)? {
+                "commandExecution" => {
+                    let mut entry = new_entry(workspace_id, AgentAuditKind::CommandExecuted);
+                    entry.command = command_text(item.get("command"));
+                    entry.cwd = string_field(Some(item), "cwd");
+                    entry.exit_code = item.get("exitCode").and_then(Value::as_i64);
+                    entry.duration_ms = item.get("durationMs").and_then(Value::as_i64);
+                    entry
+                }
+                "fileChange" => {
+                    let mut entry = new_entry(workspace_id, AgentAuditKind::FileChanged);
+                    entry.files = file_changes(item);
+                    entry
+                }
+                _ => return None,
+            };
+            entry.item_id = string_field(Some(item), "id");
+            entry.status = string_field(Some(item), "status");
+            entry
+        }
+        _ if method.ends_with("requestApproval") => {
+            let request_id = message.get("id")?;
+            let mut entry = new_entry(workspace_id, AgentAuditKind::ApprovalRequested);
+            entry.item_id = string_field(params, "itemId");
+            entry.command = command_text(params.and_then(|params| params.get("command")));
+            entry.cwd = string_field(params, "cwd");
+            entry.request_id = Some(request_id.clone());
+            tracker.remember(
+                request_id,
+                PendingApproval {
+                    thread_id: thread_id.map(str::to_string),
+                    turn_id: turn_id.clone(),
+                    item_id: entry.item_id.clone(),
+                    method: method.to_string(),
+                },
+            );
+            entry
+        }
+        _ => return None,
+    };
+    entry.thread_id = thread_id.map(str::to_string);
+    entry.turn_id = turn_id;
+    entry.method = Some(method.to_string());
+    Some(entry)
+}
+
+/// Queues the audit entry for an app-server message routed to
+/// `workspace_id`; other messages are ignored.
+pub(crate) fn record_app_server_message(
+    tracker: &AgentAuditTracker,
+    data_dir: &Path,
+    workspace_id: &str,
+    thread_id: Option<&str>,
+    message: &Value,
+) {
+    let Some(entry) = entry_for_message(tracker, workspace_id, thread_id, message) else {
+        return;
+    };
+    queue_entry(data_dir, entry);
+}
+
+/// Records the answer to an approval request, from the UI (`user`) or the
+/// auto-approval policy (`policy`).
+pub(crate) fn record_approval_decision(
+    tracker: &AgentAuditTracker,
+    data_dir: &Path,
+    workspace_id: &str,
+    request_id: &Value,
+    decision: &str,
+    decided_by: &str,
+    rule_name: Option<String>,
+) {
+    let pending = tracker.take(request_id);
+    let mut entry = new_entry(workspace_id, AgentAuditKind::ApprovalDecided);
+    if let Some(pending) = pending {
+        entry.thread_id = pending.thread_id;
+        entry.turn_id = pending.turn_id;
+        entry.item_id = pending.item_id;
+        entry.method = Some(pending.method);
+    }
+    entry.request_id = Some(request_id.clone());
+    entry.decision = Some(decision.to_string());
+    entry.decided_by = Some(decided_by.to_string());
+    entry.rule_name = rule_name;
+    queue_entry(data_dir, entry);
+}
+
+fn matches_query(entry: &AgentAuditEntry, query: &AgentAuditQuery, text: Option<&str>) -> bool {
+    if query
+        .thread_id
+        .as_deref()
+        .is_some_and(|thread_id| entry.thread_id.as_deref() != Some(thread_id))
+    {
+        return false;
+    }
+    if !query.kinds.is_empty() && !query.kinds.contains(&entry.kind) {
+        return false;
+    }
+    if query
+        .start_ms
+        .is_some_and(|start| entry.timestamp_ms < start)
+        || query.end_ms.is_some_and(|end| entry.timestamp_ms > end)
+    {
+        return false;
+    }
+    let Some(text) = text else {
+        return true;
+    };
+    [
+        entry.command.as_deref(),
+        entry.cwd.as_deref(),
+        entry.method.as_deref(),
+        entry.error.as_deref(),
+        entry.decision.as_deref(),
+        entry.rule_name.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .chain(entry.files.iter().map(|file| file.path.as_str()))
+    .any(|value| value.to_lowercase().contains(text))
+}
+
+/// Matching entries, oldest first, limited to the newest `limit`.
+fn read_entries(
+    data_dir: &Path,
+    query: &AgentAuditQuery,
+    limit: Option<usize>,
+) -> Vec<AgentAuditEntry> {
+    let text = query
+        .text
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_lowercase);
+    let mut entries = Vec::new();
+    flush_queued_entries();
+    let _guard = STORE_LOCK.lock();
+    for generation in (0..=MAX_ROTATED_FILES).rev() {
+        let Ok(file) = std::fs::File::open(log_path(data_dir, &query.workspace_id, generation))
+        else {
+            continue;
+        };
+        entries.extend(
+            BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(|line| serde_json::from_str::<AgentAuditEntry>(&line).ok())
+                .filter(|entry| matches_query(entry, query, text.as_deref())),
+        );
+    }
+    if let Some(limit) = limit {
+        let excess = entries.len().saturating_sub(limit);
+        entries.drain(..excess);
+    }
+    entries
+}
+
+/// Newest entries first.
+pub(crate) fn agent_audit_query_core(
+    data_dir: &Path,
+    query: AgentAuditQuery,
+) -> Result<Vec<AgentAuditEntry>, String> {
+    if query.workspace_id.trim().is_empty() {
+        return Err("workspaceId is required".to_string());
+    }
+    let limit = query
+        .limit
+        .map(|limit| limit as usize)
+        .unwrap_or(DEFAULT_QUERY_LIMIT)
+        .clamp(1, MAX_QUERY_LIMIT);
+    let mut entries = read_entries(data_dir, &query, Some(limit));
+    entries.reverse();
+    Ok(entries)
+}
+
+fn format_timestamp(timestamp_ms: i64) -> String {
+    Local
+        .timestamp_millis_opt(timestamp_ms)
+        .single()
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
+
+fn kind_label(kind: AgentAuditKind) -> String {
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn render_csv(entries: &[AgentAuditEntry]) -> String {
+    let mut csv = String::from(
+        "timestamp,kind,thread_id,turn_id,status,command,cwd,exit_code,duration_ms,files,decision,decided_by,rule_name,error\n",
+    );
+    for entry in entries {
+        let files = entry
+            .files
+            .iter()
+            .map(|file| format!("{}:{}", file.kind, file.path))
+            .collect::<Vec<_>>()
+            .join(";");
+        let fields = [
+            format_timestamp(entry.timestamp_ms),
+            kind_label(entry.kind),
+            csv_field(entry.thread_id.as_deref().unwrap_or("")),
+            csv_field(entry.turn_id.as_deref().unwrap_or("")),
+            csv_field(entry.status.as_deref().unwrap_or("")),
+            csv_field(entry.command.as_deref().unwrap_or("")),
+            csv_field(entry.cwd.as_deref().unwrap_or("")),
+            entry
+                .exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_default(),
+            entry
+                .duration_ms
+                .map(|duration| duration.to_string())
+                .unwrap_or_default(),
+            csv_field(&files),
+            csv_field(entry.decision.as_deref().unwrap_or("")),
+            csv_field(entry.decided_by.as_deref().unwrap_or("")),
+            csv_field(entry.rule_name.as_deref().unwrap_or("")),
+            csv_field(entry.error.as_deref().unwrap_or("")),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Exports every matching entry, oldest first; `query.limit` is ignored.
+pub(crate) fn agent_audit_export_core(
+    data_dir: &Path,
+    query: AgentAuditQuery,
+    format: UsageExportFormat,
+) -> Result<AgentAuditExport, String> {
+    if query.workspace_id.trim().is_empty() {
+        return Err("workspaceId is required".to_string());
+    }
+    let entries = read_entries(data_dir, &query, None);
+    let (content, extension) = match format {
+        UsageExportFormat::Csv => (render_csv(&entries), "csv"),
+        UsageExportFormat::Json => (
+            serde_json::to_string_pretty(&entries).map_err(|err| err.to_string())?,
+            "json",
+        ),
+    };
+    Ok(AgentAuditExport {
+        format,
+        file_name: format!(
+            "agent-audit-{}-{}.{extension}",
+            file_stem(&query.workspace_id),
+            Local::now().format("%Y%m%d")
+        ),
+        workspace_id: query.workspace_id,
+        entry_count: entries.len(),
+        content,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("codex-monitor-agent-audit-{}", Uuid::new_v4()))
+    }
+
+    #[test]
+    fn records_turns_commands_files_and_approvals() {
+        let data_dir = temp_dir();
+        let tracker = AgentAuditTracker::default();
+        let record = |message: Value| {
+            record_app_server_message(&tracker, &data_dir, "ws-1", Some("thread-1"), &message)
+        };
+        record(
+            json!({ "method": "turn/started", "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } } }),
+        );
+        record(
+            json!({ "method": "item/started", "params": { "item": { "type": "commandExecution" } } }),
+        );
+        record(json!({
+            "id": 7,
+            "method": "item/commandExecution/requestApproval",
+            "params": { "threadId": "thread-1", "turnId": "turn-1", "itemId": "cmd-1", "command": "cargo test" }
+        }));
+        record_approval_decision(
+            &tracker,
+            &data_dir,
+            "ws-1",
+            &json!(7),
+            "accept",
+            "user",
+            None,
+        );
+        record(
+            json!({ "method": "item/completed", "params": { "turnId": "turn-1", "item": {
+            "type": "commandExecution", "id": "cmd-1", "command": "cargo test",
+            "cwd": "/repo", "status": "completed", "exitCode": 101, "durationMs": 5400
+        } } }),
+        );
+        record(
+            json!({ "method": "item/completed", "params": { "turnId": "turn-1", "item": {
+            "type": "fileChange", "id": "patch-1", "status": "completed",
+            "changes": [{ "path": "src/lib.rs", "kind": { "type": "update" } }, { "path": "NOTES.md", "kind": "add" }]
+        } } }),
+        );
+        record(
+            json!({ "method": "turn/completed", "params": { "turn": { "id": "turn-1", "status": "failed", "error": { "message": "tests failed" } } } }),
+        );
+
+        let query = AgentAuditQuery {
+            workspace_id: "ws-1".to_string(),
+            ..AgentAuditQuery::default()
+        };
+        let entries = agent_audit_query_core(&data_dir, query.clone()).expect("query");
+        let kinds: Vec<AgentAuditKind> = entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AgentAuditKind::TurnCompleted,
+                AgentAuditKind::FileChanged,
+                AgentAuditKind::CommandExecuted,
+                AgentAuditKind::ApprovalDecided,
+                AgentAuditKind::ApprovalRequested,
+                AgentAuditKind::TurnStarted,
+            ]
+        );
+        assert_eq!(entries[0].error.as_deref(), Some("tests failed"));
+        assert_eq!(entries[1].files[1].kind, "add");
+        assert_eq!(entries[2].exit_code, Some(101));
+        assert_eq!(entries[3].turn_id.as_deref(), Some("turn-1"));
+        assert_eq!(entries[3].decided_by.as_deref(), Some("user"));
+
+        let commands = agent_audit_query_core(
+            &data_dir,
+            AgentAuditQuery {
+                text: Some("CARGO".to_string()),
+                kinds: vec![AgentAuditKind::CommandExecuted],
+                ..query.clone()
+            },
+        )
+        .expect("filtered query");
+        assert_eq!(commands.len(), 1);
+
+        let export =
+            agent_audit_export_core(&data_dir, query, UsageExportFormat::Csv).expect("export");
+        assert_eq!(export.entry_count, 6);
+        assert!(export
+            .content
+            .lines()
+            .any(|line| line.contains(",commandExecuted,") && line.contains(",101,5400,")));
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn rotates_full_logs_and_keeps_reading_them() {
+        let data_dir = temp_dir();
+        let path = log_path(&data_dir, "ws/1", 0);
+        std::fs::create_dir_all(path.parent().unwrap()).expect("create audit dir");
+        let mut old = new_entry("ws/1", AgentAuditKind::TurnStarted);
+        old.timestamp_ms = 1;
+        let mut content = serde_json::to_string(&old).unwrap();
+        content.push('\n');
+        content.push_str(&" ".repeat(MAX_LOG_BYTES as usize));
+        content.push('\n');
+        std::fs::write(&path, content).expect("seed log");
+
+        append_entry(&data_dir, &new_entry("ws/1", AgentAuditKind::TurnCompleted)).expect("append");
+        assert!(log_path(&data_dir, "ws/1", 1).exists());
+
+        let entries = agent_audit_query_core(
+            &data_dir,
+            AgentAuditQuery {
+                workspace_id: "ws/1".to_string(),
+                ..AgentAuditQuery::default()
+            },
+        )
+        .expect("query");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].timestamp_ms, 1);
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -9,14 +9,57 @@ use crate::shared::config_toml_core;
 
 pub(crate) const DEFAULT_AGENT_MAX_THREADS: u32 = 6;
 pub(crate) const DEFAULT_AGENT_MAX_DEPTH: u32 = 1;
-const MIN_AGENT_MAX_THREADS: u32 = 1;
-const MAX_AGENT_MAX_THREADS: u32 = 12;
-const MIN_AGENT_MAX_DEPTH: u32 = 1;
-const MAX_AGENT_MAX_DEPTH: u32 = 4;
+pub(crate) const MIN_AGENT_MAX_THREADS: u32 = 1;
+pub(crate) const MAX_AGENT_MAX_THREADS: u32 = 12;
+pub(crate) const MIN_AGENT_MAX_DEPTH: u32 = 1;
+pub(crate) const MAX_AGENT_MAX_DEPTH: u32 = 4;
 const MANAGED_AGENTS_DIR: &str = "agents";
 const TEMPLATE_BLANK: &str = "blank";
 const DEFAULT_AGENT_MODEL: &str = "gpt-5-codex";
 const DEFAULT_REASONING_EFFORT: &str = "medium";
+const BUNDLE_HEADER: &str = "# CodexMonitor agent role";
+const MAX_RENAME_ATTEMPTS: u32 = 99;
+
+struct AgentTemplate {
+    id: &'static str,
+    description: &'static str,
+    reasoning_effort: &'static str,
+    developer_instructions: &'static str,
+}
+
+const AGENT_TEMPLATES: &[AgentTemplate] = &[
+    AgentTemplate {
+        id: "reviewer",
+        description: "Reviews diffs for bugs, regressions and missing tests.",
+        reasoning_effort: "high",
+        developer_instructions: "Review the changes you are pointed at. Focus on correctness, \
+regressions, security issues and missing tests. Report findings ordered by severity with file \
+and line references. Do not rewrite code unless asked.",
+    },
+    AgentTemplate {
+        id: "explorer",
+        description: "Maps unfamiliar code and answers questions without editing files.",
+        reasoning_effort: "low",
+        developer_instructions: "Explore the codebase to answer the question you are given. \
+Read files and search, but do not modify anything. Summarize the relevant modules, entry points \
+and call paths with file references.",
+    },
+    AgentTemplate {
+        id: "test-writer",
+        description: "Adds focused tests that follow the project's existing test layout.",
+        reasoning_effort: "medium",
+        developer_instructions: "Write tests for the behavior you are pointed at. Match the \
+project's existing test layout, helpers and naming. Cover edge cases and failure paths, run the \
+tests, and report anything that fails.",
+    },
+    AgentTemplate {
+        id: "docs-writer",
+        description: "Keeps README and doc comments in sync with the code.",
+        reasoning_effort: "low",
+        developer_instructions: "Update documentation to match the current code. Keep the \
+existing tone and structure, prefer short concrete examples, and do not change code behavior.",
+    },
+];
 
 const fn default_agent_max_depth() -> u32 {
     DEFAULT_AGENT_MAX_DEPTH
@@ -81,6 +124,115 @@ pub(crate) struct DeleteAgentInput {
     pub delete_managed_file: Option<bool>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AgentBundleFormat {
+    Toml,
+    Markdown,
+}
+
+impl Default for AgentBundleFormat {
+    fn default() -> Self {
+        Self::Toml
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AgentImportConflict {
+    Fail,
+    Skip,
+    Rename,
+    Overwrite,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AgentImportAction {
+    Created,
+    Renamed,
+    Overwritten,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentRoleBundle {
+    pub name: String,
+    pub description: Option<String>,
+    pub developer_instructions: Option<String>,
+    pub model: Option<String>,
+    pub reasoning_effort: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ExportAgentInput {
+    pub name: String,
+    pub format: Option<AgentBundleFormat>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentExportDto {
+    pub name: String,
+    pub format: AgentBundleFormat,
+    pub file_name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImportAgentInput {
+    pub content: String,
+    /// Detected from the content (markdown bundles start with `---`) when unset.
+    pub format: Option<AgentBundleFormat>,
+    /// Overrides the name stored in the bundle.
+    pub name: Option<String>,
+    pub conflict: Option<AgentImportConflict>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentImportResultDto {
+    pub name: String,
+    pub action: AgentImportAction,
+    pub settings: AgentsSettingsDto,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentTemplateDto {
+    pub id: String,
+    pub bundle: AgentRoleBundle,
+    pub installed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InstallAgentTemplatesInput {
+    /// Installs the whole gallery when empty.
+    #[serde(default)]
+    pub ids: Vec<String>,
+    pub conflict: Option<AgentImportConflict>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentTemplateInstallDto {
+    pub id: String,
+    pub name: Option<String>,
+    pub action: Option<AgentImportAction>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InstallAgentTemplatesResultDto {
+    pub results: Vec<AgentTemplateInstallDto>,
+    pub settings: AgentsSettingsDto,
+}
+
 pub(crate) fn get_agents_settings_core() -> Result<AgentsSettingsDto, String> {
     let codex_home = resolve_codex_home()?;
     let config_path = codex_home.join("config.toml");
@@ -126,6 +278,11 @@ pub(crate) fn set_agents_core_settings_core(
 }
 
 pub(crate) fn create_agent_core(input: CreateAgentInput) -> Result<AgentsSettingsDto, String> {
+    create_agent_role(input)?;
+    get_agents_settings_core()
+}
+
+fn create_agent_role(input: CreateAgentInput) -> Result<(), String> {
     let name = normalize_agent_name(input.name.as_str())?;
     let description = normalize_optional_string(input.description.as_deref());
     let developer_instructions = normalize_optional_string(input.developer_instructions.as_deref());
@@ -172,7 +329,7 @@ pub(crate) fn create_agent_core(input: CreateAgentInput) -> Result<AgentsSetting
         return Err(err);
     }
 
-    get_agents_settings_core()
+    Ok(())
 }
 
 pub(crate) fn update_agent_core(input: UpdateAgentInput) -> Result<AgentsSettingsDto, String> {
@@ -374,11 +531,140 @@ pub(crate) fn read_agent_config_toml_core(agent_name: &str) -> Result<String, St
 }
 
 pub(crate) fn write_agent_config_toml_core(agent_name: &str, content: &str) -> Result<(), String> {
+    config_toml_core::ensure_valid_config_toml(content, "agent config")?;
     let (codex_home, relative_path) = resolve_managed_agent_config_relative_path(agent_name)?;
     let path = resolve_safe_managed_abs_path_for_write(&codex_home, &relative_path)?;
     std::fs::write(path, content).map_err(|err| format!("Failed to write agent config file: {err}"))
 }
 
+pub(crate) fn export_agent_core(input: ExportAgentInput) -> Result<AgentExportDto, String> {
+    let name = normalize_agent_lookup_name(input.name.as_str())?;
+    let format = input.format.unwrap_or_default();
+    let codex_home = resolve_codex_home()?;
+    let (_, document) = config_toml_core::load_global_config_document(&codex_home)?;
+    let role_item = document
+        .get("agents")
+        .and_then(Item::as_table_like)
+        .and_then(|agents| agents.get(name.as_str()))
+        .filter(|item| item.is_table_like() && !is_reserved_agents_key(name.as_str()))
+        .ok_or_else(|| format!("agent '{name}' not found"))?;
+
+    let overrides = read_role_config_file(role_item)
+        .and_then(|config_file| read_role_config_document(&codex_home, config_file.as_str()))
+        .unwrap_or_default();
+    let read_override = |key: &str| {
+        overrides
+            .get(key)
+            .and_then(Item::as_str)
+            .and_then(|value| normalize_optional_string(Some(value)))
+    };
+    let bundle = AgentRoleBundle {
+        name: name.clone(),
+        description: read_role_description(role_item),
+        developer_instructions: read_override("developer_instructions"),
+        model: read_override("model"),
+        reasoning_effort: read_override("model_reasoning_effort"),
+    };
+
+    let (file_name, content) = match format {
+        AgentBundleFormat::Toml => (format!("{name}.agent.toml"), render_toml_bundle(&bundle)),
+        AgentBundleFormat::Markdown => {
+            (format!("{name}.agent.md"), render_markdown_bundle(&bundle))
+        }
+    };
+    Ok(AgentExportDto {
+        name,
+        format,
+        file_name,
+        content,
+    })
+}
+
+pub(crate) fn import_agent_core(input: ImportAgentInput) -> Result<AgentImportResultDto, String> {
+    let format = input
+        .format
+        .unwrap_or_else(|| detect_bundle_format(input.content.as_str()));
+    let mut bundle = match format {
+        AgentBundleFormat::Toml => parse_toml_bundle(input.content.as_str())?,
+        AgentBundleFormat::Markdown => parse_markdown_bundle(input.content.as_str())?,
+    };
+    if let Some(name) = normalize_optional_string(input.name.as_deref()) {
+        bundle.name = name;
+    }
+    let (name, action) =
+        import_agent_role(&bundle, input.conflict.unwrap_or(AgentImportConflict::Fail))?;
+    Ok(AgentImportResultDto {
+        name,
+        action,
+        settings: get_agents_settings_core()?,
+    })
+}
+
+pub(crate) fn list_agent_templates_core() -> Result<Vec<AgentTemplateDto>, String> {
+    let codex_home = resolve_codex_home()?;
+    let (_, document) = config_toml_core::load_global_config_document(&codex_home)?;
+    let existing = document.get("agents").and_then(Item::as_table);
+    Ok(AGENT_TEMPLATES
+        .iter()
+        .map(|template| AgentTemplateDto {
+            id: template.id.to_string(),
+            bundle: template_bundle(template),
+            installed: existing
+                .map(|agents| has_agent_name_conflict(agents, template.id, None))
+                .unwrap_or(false),
+        })
+        .collect())
+}
+
+/// Installs gallery roles one by one; a failing template is reported in its
+/// result entry and does not stop the rest.
+pub(crate) fn install_agent_templates_core(
+    input: InstallAgentTemplatesInput,
+) -> Result<InstallAgentTemplatesResultDto, String> {
+    let conflict = input.conflict.unwrap_or(AgentImportConflict::Skip);
+    let mut selected = Vec::new();
+    if input.ids.is_empty() {
+        selected.extend(AGENT_TEMPLATES.iter());
+    } else {
+        for id in &input.ids {
+            let template = AGENT_TEMPLATES
+                .iter()
+                .find(|template| template.id == id.trim())
+                .ok_or_else(|| format!("unknown agent template '{}'", id.trim()))?;
+            if !selected
+                .iter()
+                .any(|entry: &&AgentTemplate| entry.id == template.id)
+            {
+                selected.push(template);
+            }
+        }
+    }
+
+    let results = selected
+        .into_iter()
+        .map(
+            |template| match import_agent_role(&template_bundle(template), conflict) {
+                Ok((name, action)) => AgentTemplateInstallDto {
+                    id: template.id.to_string(),
+                    name: Some(name),
+                    action: Some(action),
+                    error: None,
+                },
+                Err(err) => AgentTemplateInstallDto {
+                    id: template.id.to_string(),
+                    name: None,
+                    action: None,
+                    error: Some(err),
+                },
+            },
+        )
+        .collect();
+    Ok(InstallAgentTemplatesResultDto {
+        results,
+        settings: get_agents_settings_core()?,
+    })
+}
+
 fn resolve_codex_home() -> Result<PathBuf, String> {
     codex_home::resolve_default_codex_home()
         .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())
@@ -835,6 +1121,310 @@ fn build_template_content(
     }
 }
 
+fn template_bundle(template: &AgentTemplate) -> AgentRoleBundle {
+    AgentRoleBundle {
+        name: template.id.to_string(),
+        description: Some(template.description.to_string()),
+        developer_instructions: Some(template.developer_instructions.to_string()),
+        model: Some(DEFAULT_AGENT_MODEL.to_string()),
+        reasoning_effort: Some(template.reasoning_effort.to_string()),
+    }
+}
+
+fn read_role_config_document(codex_home: &Path, config_file: &str) -> Option<Document> {
+    let path = match managed_relative_path_from_config(config_file) {
+        Some(relative_path) => {
+            resolve_safe_managed_abs_path_for_read(codex_home, &relative_path).ok()?
+        }
+        None => resolve_config_file_path_for_display(codex_home, config_file)?,
+    };
+    if !path.is_file() {
+        return None;
+    }
+    let contents = std::fs::read_to_string(path).ok()?;
+    parse_agent_config_document(contents.as_str()).ok()
+}
+
+fn detect_bundle_format(content: &str) -> AgentBundleFormat {
+    if content
+        .trim_start_matches('\u{feff}')
+        .trim_start()
+        .starts_with("---")
+    {
+        AgentBundleFormat::Markdown
+    } else {
+        AgentBundleFormat::Toml
+    }
+}
+
+fn render_toml_bundle(bundle: &AgentRoleBundle) -> String {
+    let mut document = Document::new();
+    document["name"] = value(bundle.name.as_str());
+    let fields = [
+        ("description", &bundle.description),
+        ("model", &bundle.model),
+        ("model_reasoning_effort", &bundle.reasoning_effort),
+        ("developer_instructions", &bundle.developer_instructions),
+    ];
+    for (key, field) in fields {
+        if let Some(field) = field {
+            document[key] = value(field.as_str());
+        }
+    }
+    let mut rendered = format!("{BUNDLE_HEADER}\n");
+    rendered.push_str(document.to_string().as_str());
+    if !rendered.ends_with('\n') {
+        rendered.push('\n');
+    }
+    rendered
+}
+
+fn parse_toml_bundle(content: &str) -> Result<AgentRoleBundle, String> {
+    let document = content
+        .parse::<Document>()
+        .map_err(|err| format!("Failed to parse agent bundle: {err}"))?;
+    let read = |key: &str| -> Result<Option<String>, String> {
+        match document.get(key) {
+            None => Ok(None),
+            Some(item) => item
+                .as_str()
+                .map(|value| normalize_optional_string(Some(value)))
+                .ok_or_else(|| format!("agent bundle field `{key}` must be a string")),
+        }
+    };
+    Ok(AgentRoleBundle {
+        name: read("name")?.ok_or_else(|| "agent bundle is missing `name`".to_string())?,
+        description: read("description")?,
+        developer_instructions: read("developer_instructions")?,
+        model: read("model")?,
+        reasoning_effort: read("model_reasoning_effort")?,
+    })
+}
+
+/// Markdown bundles keep the scalar fields in frontmatter and use the body as
+/// the developer instructions, so they read naturally on GitHub or in a gist.
+fn render_markdown_bundle(bundle: &AgentRoleBundle) -> String {
+    let quote = |raw: &str| serde_json::to_string(raw).unwrap_or_else(|_| raw.to_string());
+    let mut rendered = String::from("---\n");
+    rendered.push_str(format!("name: {}\n", quote(bundle.name.as_str())).as_str());
+    let fields = [
+        ("description", &bundle.description),
+        ("model", &bundle.model),
+        ("model_reasoning_effort", &bundle.reasoning_effort),
+    ];
+    for (key, field) in fields {
+        if let Some(field) = field {
+            rendered.push_str(format!("{key}: {}\n", quote(field.as_str())).as_str());
+        }
+    }
+    rendered.push_str("---\n");
+    if let Some(instructions) = bundle.developer_instructions.as_deref() {
+        rendered.push('\n');
+        rendered.push_str(instructions);
+        rendered.push('\n');
+    }
+    rendered
+}
+
+fn parse_markdown_bundle(content: &str) -> Result<AgentRoleBundle, String> {
+    let content = content.trim_start_matches('\u{feff}').trim_start();
+    let Some(rest) = content.strip_prefix("---") else {
+        return Err("agent bundle is missing frontmatter".to_string());
+    };
+    let rest = rest.trim_start_matches([' ', '\t', '\r']);
+    let rest = rest.strip_prefix('\n').unwrap_or(rest);
+    let mut frontmatter = Vec::new();
+    let mut body = None;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim() == "---" {
+            body = Some(&rest[offset..]);
+            break;
+        }
+        frontmatter.push(line.trim_end());
+    }
+    let Some(body) = body else {
+        return Err("agent bundle frontmatter is not closed".to_string());
+    };
+
+    let mut bundle = AgentRoleBundle {
+        name: String::new(),
+        description: None,
+        developer_instructions: normalize_optional_string(Some(body)),
+        model: None,
+        reasoning_effort: None,
+    };
+    for line in frontmatter {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let Some((key, raw_value)) = line.split_once(':') else {
+            return Err(format!("invalid agent bundle frontmatter line: {line}"));
+        };
+        let raw_value = raw_value.trim();
+        let parsed = if raw_value.starts_with('"') {
+            serde_json::from_str::<String>(raw_value)
+                .map_err(|err| format!("invalid value for `{}`: {err}", key.trim()))?
+        } else {
+            raw_value.trim_matches('\'').to_string()
+        };
+        let parsed = normalize_optional_string(Some(parsed.as_str()));
+        match key.trim() {
+            "name" => bundle.name = parsed.unwrap_or_default(),
+            "description" => bundle.description = parsed,
+            "model" => bundle.model = parsed,
+            "model_reasoning_effort" | "reasoning_effort" => bundle.reasoning_effort = parsed,
+            _ => {}
+        }
+    }
+    if bundle.name.is_empty() {
+        return Err("agent bundle is missing `name`".to_string());
+    }
+    Ok(bundle)
+}
+
+fn next_available_agent_name(
+    codex_home: &Path,
+    agents: &Table,
+    base: &str,
+) -> Result<String, String> {
+    for attempt in 2..=MAX_RENAME_ATTEMPTS + 1 {
+        let suffix = format!("-{attempt}");
+        let stem: String = base.chars().take(32 - suffix.len()).collect();
+        let candidate = normalize_agent_name(format!("{stem}{suffix}").as_str())?;
+        let file_taken = codex_home
+            .join(managed_relative_config_for_name(&candidate))
+            .exists();
+        if !file_taken && !has_agent_name_conflict(agents, &candidate, None) {
+            return Ok(candidate);
+        }
+    }
+    Err(format!("no free name found for agent '{base}'"))
+}
+
+/// Creates or updates a role from a bundle. Names go through the same
+/// normalization and managed-path checks as `create_agent_core`.
+fn import_agent_role(
+    bundle: &AgentRoleBundle,
+    conflict: AgentImportConflict,
+) -> Result<(String, AgentImportAction), String> {
+    let mut name = normalize_agent_name(bundle.name.as_str())?;
+    let codex_home = resolve_codex_home()?;
+    let (_, mut document) = config_toml_core::load_global_config_document(&codex_home)?;
+    let agents = config_toml_core::ensure_table(&mut document, "agents")?;
+    let mut action = AgentImportAction::Created;
+    if has_agent_name_conflict(agents, &name, None) {
+        match conflict {
+            AgentImportConflict::Fail => return Err(format!("agent '{name}' already exists")),
+            AgentImportConflict::Skip => return Ok((name, AgentImportAction::Skipped)),
+            AgentImportConflict::Rename => {
+                name = next_available_agent_name(&codex_home, agents, &name)?;
+                action = AgentImportAction::Renamed;
+            }
+            AgentImportConflict::Overwrite => {
+                overwrite_agent_role(&codex_home, document, &name, bundle)?;
+                return Ok((name, AgentImportAction::Overwritten));
+            }
+        }
+    }
+
+    create_agent_role(CreateAgentInput {
+        name: name.clone(),
+        description: bundle.description.clone(),
+        developer_instructions: bundle.developer_instructions.clone(),
+        template: None,
+        model: bundle.model.clone(),
+        reasoning_effort: bundle.reasoning_effort.clone(),
+    })?;
+    Ok((name, action))
+}
+
+fn overwrite_agent_role(
+    codex_home: &Path,
+    mut document: Document,
+    name: &str,
+    bundle: &AgentRoleBundle,
+) -> Result<(), String> {
+    let agents = config_toml_core::ensure_table(&mut document, "agents")?;
+    let existing_name = agents
+        .iter()
+        .find(|(key, item)| {
+            !is_reserved_agents_key(key) && item.is_table_like() && key.eq_ignore_ascii_case(name)
+        })
+        .map(|(key, _)| key.to_string())
+        .ok_or_else(|| format!("agent '{name}' not found"))?;
+    let existing_item = &agents[existing_name.as_str()];
+    let relative_path = match read_role_config_file(existing_item) {
+        Some(config_file) => {
+            managed_relative_path_from_config(config_file.as_str()).ok_or_else(|| {
+                format!(
+                    "agent '{existing_name}' config_file is external; edit that file directly instead of overwriting it"
+                )
+            })?
+        }
+        None => managed_relative_config_for_name(&existing_name),
+    };
+    let mut role = clone_role_table(existing_item)?;
+    match bundle.description.as_deref() {
+        Some(description) => role["description"] = value(description),
+        None => {
+            let _ = role.remove("description");
+        }
+    }
+    role["config_file"] = value(pathbuf_to_string(&relative_path)?);
+    agents[existing_name.as_str()] = Item::Table(role);
+
+    let target = resolve_safe_managed_abs_path_for_write(codex_home, &relative_path)?;
+    let previous = upsert_bundle_overrides_in_agent_config_file(&target, bundle)?;
+    if let Err(err) = config_toml_core::persist_global_config_document(codex_home, &document) {
+        match previous {
+            Some(bytes) => {
+                let _ = std::fs::write(&target, bytes);
+            }
+            None => {
+                let _ = std::fs::remove_file(&target);
+            }
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
+fn upsert_bundle_overrides_in_agent_config_file(
+    path: &Path,
+    bundle: &AgentRoleBundle,
+) -> Result<Option<Vec<u8>>, String> {
+    let previous = upsert_developer_instructions_in_agent_config_file(
+        path,
+        bundle.developer_instructions.as_deref(),
+    )?;
+    if bundle.model.is_none() && bundle.reasoning_effort.is_none() {
+        return Ok(previous);
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read agent config file: {err}"))?;
+    let mut document = parse_agent_config_document(contents.as_str())?;
+    if let Some(model) = bundle.model.as_deref() {
+        document["model"] = value(model);
+    }
+    if let Some(reasoning_effort) = bundle.reasoning_effort.as_deref() {
+        document["model_reasoning_effort"] = value(reasoning_effort);
+    }
+    if let Err(err) = std::fs::write(path, document.to_string()) {
+        match previous.as_ref() {
+            Some(bytes) => {
+                let _ = std::fs::write(path, bytes);
+            }
+            None => {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+        return Err(format!("Failed to update agent config file: {err}"));
+    }
+    Ok(previous)
+}
+
 fn pathbuf_to_string(path: &Path) -> Result<String, String> {
     path.to_str()
         .map(str::to_string)
@@ -1023,4 +1613,54 @@ config_file = "agents/researcher.toml"
         );
         assert!(content.contains("developer_instructions"));
     }
+
+    #[test]
+    fn agent_bundles_round_trip_in_both_formats() {
+        let bundle = AgentRoleBundle {
+            name: "reviewer".to_string(),
+            description: Some("Reviews: diffs \"carefully\"".to_string()),
+            developer_instructions: Some("Check tests.\n\n---\nThen report.".to_string()),
+            model: Some("gpt-5.1".to_string()),
+            reasoning_effort: Some("high".to_string()),
+        };
+
+        let toml = render_toml_bundle(&bundle);
+        assert_eq!(detect_bundle_format(&toml), AgentBundleFormat::Toml);
+        assert_eq!(parse_toml_bundle(&toml).expect("toml bundle"), bundle);
+
+        let markdown = render_markdown_bundle(&bundle);
+        assert_eq!(detect_bundle_format(&markdown), AgentBundleFormat::Markdown);
+        assert_eq!(
+            parse_markdown_bundle(&markdown).expect("markdown bundle"),
+            bundle
+        );
+
+        let error = parse_markdown_bundle("---\ndescription: x\n---\nbody").expect_err("no name");
+        assert!(error.contains("name"));
+    }
+
+    #[test]
+    fn next_available_agent_name_skips_roles_and_managed_files() {
+        let codex_home = temp_dir("agent-rename");
+        std::fs::create_dir_all(codex_home.join(MANAGED_AGENTS_DIR)).expect("create agents dir");
+        std::fs::write(
+            codex_home.join(managed_relative_config_for_name("reviewer-3")),
+            "",
+        )
+        .expect("write managed file");
+        let document: Document = "[agents.reviewer]\n[agents.reviewer-2]\n"
+            .parse()
+            .expect("parse config");
+        let agents = document["agents"].as_table().expect("agents table");
+
+        let name = next_available_agent_name(&codex_home, agents, "reviewer").expect("name");
+        assert_eq!(name, "reviewer-4");
+
+        let long = "a".repeat(32);
+        let name = next_available_agent_name(&codex_home, agents, &long).expect("long name");
+        assert_eq!(name.len(), 32);
+        assert!(name.ends_with("-2"));
+
+        let _ = std::fs::remove_dir_all(codex_home);
+    }
 }
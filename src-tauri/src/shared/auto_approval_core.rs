@@ -0,0 +1,836 @@
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::shared::store_helpers::{now_ms, read_json, write_json, StoreLock};
+use crate::types::{
+    AutoApprovalDecision, AutoApprovalEvaluation, AutoApprovalEvent, AutoApprovalOutcome,
+    AutoApprovalPolicy, AutoApprovalRequest, AutoApprovalRequestKind, AutoApprovalRule,
+    WorkspaceEntry,
+};
+
+const POLICY_FILE: &str = "auto-approval-policy.json";
+const COMMAND_KEYS: [&str; 3] = ["command", "argv", "cmd"];
+const SHELL_WRAPPERS: [&str; 3] = ["sh", "bash", "zsh"];
+/// Redirections, parameter expansion and command or process substitution can
+/// turn an allowed command into something else entirely.
+const SHELL_EXPANSION_CHARS: [char; 4] = ['>', '<', '$', '`'];
+
+/// Serializes policy writes; the app and the daemon's handlers can save the
+/// policy at the same time.
+static STORE_LOCK: StoreLock = StoreLock::new();
+
+/// Parsed policy per data dir, so approval requests arriving on the reader
+/// loop do not read the disk.
+static POLICY_CACHE: OnceLock<StdMutex<HashMap<PathBuf, Arc<AutoApprovalPolicy>>>> =
+    OnceLock::new();
+
+/// Per-session state the policy needs beyond the request itself.
+#[derive(Default)]
+pub(crate) struct AutoApprovalTracker {
+    /// v2 file-change approvals only carry an item id; the touched paths come
+    /// from the preceding `item/started` notification.
+    file_changes: StdMutex<HashMap<String, Vec<String>>>,
+}
+
+impl AutoApprovalTracker {
+    pub(crate) fn observe(&self, method: Option<&str>, message: &Value) {
+        let Some(item) = message.get("params").and_then(|params| params.get("item")) else {
+            return;
+        };
+        if item.get("type").and_then(Value::as_str) != Some("fileChange") {
+            return;
+        }
+        let Some(item_id) = item.get("id").and_then(Value::as_str) else {
+            return;
+        };
+        let mut file_changes = self
+            .file_changes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match method {
+            Some("item/started") => {
+                let mut paths = Vec::new();
+                collect_change_paths(item.get("changes"), &mut paths);
+                file_changes.insert(item_id.to_string(), paths);
+            }
+            Some("item/completed") => {
+                file_changes.remove(item_id);
+            }
+            _ => {}
+        }
+    }
+
+    fn item_paths(&self, item_id: &str) -> Vec<String> {
+        self.file_changes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(item_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+fn read_policy(data_dir: &Path) -> Result<AutoApprovalPolicy, String> {
+    read_json(&data_dir.join(POLICY_FILE))
+}
+
+fn write_policy(data_dir: &Path, policy: &AutoApprovalPolicy) -> Result<(), String> {
+    write_json(&data_dir.join(POLICY_FILE), policy)?;
+    lock_policy_cache().insert(data_dir.to_path_buf(), Arc::new(policy.clone()));
+    Ok(())
+}
+
+fn lock_policy_cache() -> std::sync::MutexGuard<'static, HashMap<PathBuf, Arc<AutoApprovalPolicy>>>
+{
+    POLICY_CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+async fn cached_policy(data_dir: &Path) -> Result<Arc<AutoApprovalPolicy>, String> {
+    if let Some(policy) = lock_policy_cache().get(data_dir) {
+        return Ok(policy.clone());
+    }
+    let path = data_dir.to_path_buf();
+    let policy = tokio::task::spawn_blocking(move || read_policy(&path))
+        .await
+        .map_err(|err| err.to_string())??;
+    let policy = Arc::new(policy);
+    lock_policy_cache().insert(data_dir.to_path_buf(), policy.clone());
+    Ok(policy)
+}
+
+fn clean_list(values: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    values
+        .into_iter()
+        .map(|value| value.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|value| !value.is_empty() && seen.insert(value.clone()))
+        .collect()
+}
+
+fn normalize_policy(policy: AutoApprovalPolicy) -> Result<AutoApprovalPolicy, String> {
+    let mut ids = HashSet::new();
+    let mut rules = Vec::with_capacity(policy.rules.len());
+    for rule in policy.rules {
+        let name = rule.name.trim().to_string();
+        if name.is_empty() {
+            return Err("Auto-approval rules need a name.".to_string());
+        }
+        let id = match rule.id.trim() {
+            "" => Uuid::new_v4().to_string(),
+            id => id.to_string(),
+        };
+        if !ids.insert(id.clone()) {
+            return Err(format!("Duplicate auto-approval rule id `{id}`."));
+        }
+        rules.push(AutoApprovalRule {
+            id,
+            name,
+            enabled: rule.enabled,
+            decision: rule.decision,
+            kinds: rule.kinds,
+            workspace_ids: clean_list(rule.workspace_ids),
+            thread_ids: clean_list(rule.thread_ids),
+            commands: clean_list(rule.commands),
+            paths: clean_list(rule.paths)
+                .into_iter()
+                .map(|path| path.replace('\\', "/"))
+                .collect(),
+        });
+    }
+    Ok(AutoApprovalPolicy {
+        enabled: policy.enabled,
+        rules,
+    })
+}
+
+/// `*` and `?` do not cross `separator` when one is given; `**` crosses
+/// anything, and `**/` may also match nothing.
+fn glob_match(pattern: &[char], text: &[char], separator: Option<char>) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            if rest.first() == Some(&'/') && glob_match(&rest[1..], text, separator) {
+                return true;
+            }
+            (0..=text.len()).any(|skip| glob_match(rest, &text[skip..], separator))
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for skip in 0..=text.len() {
+                if glob_match(rest, &text[skip..], separator) {
+                    return true;
+                }
+                if skip < text.len() && Some(text[skip]) == separator {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => {
+            !text.is_empty()
+                && Some(text[0]) != separator
+                && glob_match(&pattern[1..], &text[1..], separator)
+        }
+        Some(ch) => text.first() == Some(ch) && glob_match(&pattern[1..], &text[1..], separator),
+    }
+}
+
+fn command_matches(pattern: &str, command: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let command: Vec<char> = command.chars().collect();
+    glob_match(&pattern, &command, None)
+}
+
+/// Patterns without a `/` also match the file name alone, like `.gitignore`.
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let chars: Vec<char> = pattern.chars().collect();
+    let full: Vec<char> = path.chars().collect();
+    if glob_match(&chars, &full, Some('/')) {
+        return true;
+    }
+    if pattern.contains('/') {
+        return false;
+    }
+    let name: Vec<char> = path.rsplit('/').next().unwrap_or(path).chars().collect();
+    glob_match(&chars, &name, Some('/'))
+}
+
+/// Splits a shell command on `;`, `&&`, `||`, `|` and newlines so an allow
+/// pattern for `git status*` cannot approve `git status && rm -rf ~`.
+fn command_segments(command: &str) -> Vec<String> {
+    command
+        .split(['\n', ';', '|', '&'])
+        .map(|segment| segment.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+/// The segment's argv, re-quoted canonically so patterns match what the shell
+/// would run rather than how the model happened to quote it.
+fn segment_argv(segment: &str) -> Option<String> {
+    shell_words::split(segment)
+        .ok()
+        .filter(|argv| !argv.is_empty())
+        .map(shell_words::join)
+}
+
+fn unwrap_shell(tokens: Vec<String>) -> String {
+    if let [shell, flag, script] = tokens.as_slice() {
+        let shell_name = shell.rsplit(['/', '\\']).next().unwrap_or(shell);
+        if SHELL_WRAPPERS.contains(&shell_name) && matches!(flag.as_str(), "-c" | "-lc" | "-ic") {
+            return script.clone();
+        }
+    }
+    shell_words::join(tokens)
+}
+
+fn extract_command(params: &Value) -> Option<String> {
+    for key in COMMAND_KEYS {
+        let command = match params.get(key) {
+            Some(Value::String(command)) => command.trim().to_string(),
+            Some(Value::Array(items)) => {
+                let tokens: Option<Vec<String>> = items
+                    .iter()
+                    .map(|item| item.as_str().map(str::to_string))
+                    .collect();
+                match tokens {
+                    Some(tokens) if !tokens.is_empty() => unwrap_shell(tokens),
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        if !command.is_empty() {
+            return Some(command);
+        }
+    }
+    None
+}
+
+fn collect_change_paths(changes: Option<&Value>, out: &mut Vec<String>) {
+    match changes {
+        Some(Value::Object(map)) => out.extend(map.keys().cloned()),
+        Some(Value::Array(items)) => out.extend(
+            items
+                .iter()
+                .filter_map(|item| item.get("path").and_then(Value::as_str))
+                .map(str::to_string),
+        ),
+        _ => {}
+    }
+}
+
+fn normalize_root(root: &str) -> String {
+    root.replace('\\', "/").trim_end_matches('/').to_string()
+}
+
+fn is_absolute_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with('/')
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+/// Resolves `.` and `..` without touching the filesystem. `None` when the path
+/// climbs above its first component.
+fn lexical_components(path: &str) -> Option<Vec<&str>> {
+    let mut components = Vec::new();
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            component => components.push(component),
+        }
+    }
+    Some(components)
+}
+
+/// A request path as rules see it: relative to the workspace root when it
+/// stays inside it, otherwise the normalized path with `inside_root` unset.
+struct ScopedPath {
+    display: String,
+    inside_root: bool,
+}
+
+fn scope_path(path: &str, root: Option<&str>) -> ScopedPath {
+    let path = path.replace('\\', "/");
+    let outside = |display: String| ScopedPath {
+        display,
+        inside_root: false,
+    };
+    let root = root.map(normalize_root).filter(|root| !root.is_empty());
+    let Some(root) = root else {
+        // Without a root only relative paths that stay put can be trusted.
+        return match lexical_components(&path) {
+            Some(components) if !is_absolute_path(&path) => ScopedPath {
+                display: components.join("/"),
+                inside_root: true,
+            },
+            _ => outside(path),
+        };
+    };
+    let full = if is_absolute_path(&path) {
+        path.clone()
+    } else {
+        format!("{root}/{path}")
+    };
+    let (Some(components), Some(root_components)) =
+        (lexical_components(&full), lexical_components(&root))
+    else {
+        return outside(path);
+    };
+    // Session roots for drive and UNC paths are stored lowercased, and those
+    // filesystems ignore case; everywhere else components must match exactly.
+    let fold_case = root.starts_with("//") || (is_absolute_path(&root) && !root.starts_with('/'));
+    let inside = components.len() >= root_components.len()
+        && components
+            .iter()
+            .zip(&root_components)
+            .all(|(component, root_component)| {
+                component == root_component
+                    || (fold_case && component.eq_ignore_ascii_case(root_component))
+            });
+    if !inside {
+        let prefix = if full.starts_with('/') { "/" } else { "" };
+        return outside(format!("{prefix}{}", components.join("/")));
+    }
+    let relative = components[root_components.len()..].join("/");
+    ScopedPath {
+        display: if relative.is_empty() {
+            ".".to_string()
+        } else {
+            relative
+        },
+        inside_root: true,
+    }
+}
+
+fn request_kind(method: &str) -> Option<AutoApprovalRequestKind> {
+    if method.contains("commandExecution") || method.contains("execCommand") {
+        Some(AutoApprovalRequestKind::Exec)
+    } else if method.contains("fileChange") || method.contains("applyPatch") {
+        Some(AutoApprovalRequestKind::ApplyPatch)
+    } else {
+        None
+    }
+}
+
+struct ApprovalContext<'a> {
+    workspace_id: &'a str,
+    thread_id: Option<&'a str>,
+    kind: Option<AutoApprovalRequestKind>,
+    command: Option<String>,
+    paths: Vec<String>,
+    /// Some path resolves outside the workspace root; allow rules never
+    /// match then.
+    escapes_root: bool,
+}
+
+impl<'a> ApprovalContext<'a> {
+    fn new(
+        workspace_id: &'a str,
+        thread_id: Option<&'a str>,
+        method: &str,
+        params: &Value,
+        workspace_root: Option<&str>,
+        tracker: Option<&AutoApprovalTracker>,
+    ) -> Self {
+        let kind = request_kind(method);
+        let mut raw_paths = Vec::new();
+        match kind {
+            Some(AutoApprovalRequestKind::Exec) => {
+                if let Some(cwd) = params.get("cwd").and_then(Value::as_str) {
+                    raw_paths.push(cwd.to_string());
+                }
+            }
+            Some(AutoApprovalRequestKind::ApplyPatch) => {
+                collect_change_paths(params.get("changes"), &mut raw_paths);
+                collect_change_paths(params.get("fileChanges"), &mut raw_paths);
+                if let (Some(tracker), Some(item_id)) =
+                    (tracker, params.get("itemId").and_then(Value::as_str))
+                {
+                    raw_paths.extend(tracker.item_paths(item_id));
+                }
+                if let Some(root) = params.get("grantRoot").and_then(Value::as_str) {
+                    raw_paths.push(root.to_string());
+                }
+            }
+            None => {}
+        }
+        let mut seen = HashSet::new();
+        let mut escapes_root = false;
+        let paths = raw_paths
+            .iter()
+            .map(|path| scope_path(path, workspace_root))
+            .inspect(|path| escapes_root |= !path.inside_root)
+            .map(|path| path.display)
+            .filter(|path| seen.insert(path.clone()))
+            .collect();
+        Self {
+            workspace_id,
+            thread_id,
+            kind,
+            command: match kind {
+                Some(AutoApprovalRequestKind::Exec) => extract_command(params),
+                _ => None,
+            },
+            paths,
+            escapes_root,
+        }
+    }
+
+    /// Allow rules must cover every command segment and every path, and
+    /// never apply outside the workspace root; deny rules fire when any of
+    /// them matches.
+    fn matches(&self, rule: &AutoApprovalRule) -> bool {
+        if !rule.enabled {
+            return false;
+        }
+        let deny = rule.decision == AutoApprovalDecision::Decline;
+        if !deny && self.escapes_root {
+            return false;
+        }
+        let Some(kind) = self.kind else {
+            return false;
+        };
+        if !rule.kinds.is_empty() && !rule.kinds.contains(&kind) {
+            return false;
+        }
+        if !rule.workspace_ids.is_empty()
+            && !rule.workspace_ids.iter().any(|id| id == self.workspace_id)
+        {
+            return false;
+        }
+        if !rule.thread_ids.is_empty() {
+            match self.thread_id {
+                Some(thread_id) if rule.thread_ids.iter().any(|id| id == thread_id) => {}
+                _ => return false,
+            }
+        }
+        if !rule.commands.is_empty() {
+            let Some(command) = self.command.as_deref() else {
+                return false;
+            };
+            let segments = command_segments(command);
+            let pattern_matches =
+                |text: &str| rule.commands.iter().any(|p| command_matches(p, text));
+            let matched = if deny {
+                segments.iter().any(|segment| {
+                    pattern_matches(segment)
+                        || segment_argv(segment).is_some_and(|argv| pattern_matches(&argv))
+                })
+            } else {
+                !command.contains(SHELL_EXPANSION_CHARS)
+                    && !segments.is_empty()
+                    && segments.iter().all(|segment| {
+                        segment_argv(segment).is_some_and(|argv| pattern_matches(&argv))
+                    })
+            };
+            if !matched {
+                return false;
+            }
+        }
+        if !rule.paths.is_empty() {
+            let path_matches_rule =
+                |path: &String| rule.paths.iter().any(|pattern| path_matches(pattern, path));
+            let matched = if deny {
+                self.paths.iter().any(path_matches_rule)
+            } else {
+                !self.paths.is_empty() && self.paths.iter().all(path_matches_rule)
+            };
+            if !matched {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Deny rules win over allow rules; with no match the request goes to the UI.
+/// Ignores `policy.enabled` so rules can be tried before switching them on.
+fn evaluate(policy: &AutoApprovalPolicy, context: &ApprovalContext<'_>) -> AutoApprovalEvaluation {
+    let matched = policy
+        .rules
+        .iter()
+        .filter(|rule| rule.decision == AutoApprovalDecision::Decline)
+        .chain(
+            policy
+                .rules
+                .iter()
+                .filter(|rule| rule.decision == AutoApprovalDecision::Accept),
+        )
+        .find(|rule| context.matches(rule));
+    AutoApprovalEvaluation {
+        outcome: match matched.map(|rule| rule.decision) {
+            Some(AutoApprovalDecision::Accept) => AutoApprovalOutcome::Accept,
+            Some(AutoApprovalDecision::Decline) => AutoApprovalOutcome::Decline,
+            None => AutoApprovalOutcome::Prompt,
+        },
+        kind: context.kind,
+        command: context.command.clone(),
+        paths: context.paths.clone(),
+        rule_id: matched.map(|rule| rule.id.clone()),
+        rule_name: matched.map(|rule| rule.name.clone()),
+    }
+}
+
+/// Answers an approval request from the policy when a rule matches. Returns
+/// the decision when the request was handled and must not reach the UI. The
+/// caller records it in the agent audit log.
+pub(crate) async fn handle_approval_request(
+    session: &WorkspaceSession,
+    data_dir: &Path,
+    workspace_id: &str,
+    thread_id: Option<&str>,
+    message: &Value,
+) -> Option<AutoApprovalEvent> {
+    let method = message.get("method").and_then(Value::as_str)?;
+    let request_id = message.get("id")?.clone();
+    let policy = match cached_policy(data_dir).await {
+        Ok(policy) if policy.enabled => policy,
+        Ok(_) => return None,
+        Err(err) => {
+            eprintln!("auto-approval: {err}");
+            return None;
+        }
+    };
+    let workspace_root = session
+        .workspace_roots
+        .lock()
+        .await
+        .get(workspace_id)
+        .cloned();
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let context = ApprovalContext::new(
+        workspace_id,
+        thread_id,
+        method,
+        &params,
+        workspace_root.as_deref(),
+        Some(&session.approval_tracker),
+    );
+    let evaluation = evaluate(&policy, &context);
+    let decision = evaluation.outcome.decision()?;
+    if let Err(err) = session
+        .send_response(request_id.clone(), json!({ "decision": decision }))
+        .await
+    {
+        eprintln!("auto-approval: failed to answer {method}: {err}");
+        return None;
+    }
+    Some(AutoApprovalEvent {
+        timestamp_ms: now_ms(),
+        workspace_id: workspace_id.to_string(),
+        thread_id: thread_id.map(str::to_string),
+        request_id,
+        method: method.to_string(),
+        evaluation,
+    })
+}
+
+pub(crate) fn auto_approval_policy_get_core(data_dir: &Path) -> Result<AutoApprovalPolicy, String> {
+    read_policy(data_dir)
+}
+
+pub(crate) fn auto_approval_policy_set_core(
+    data_dir: &Path,
+    policy: AutoApprovalPolicy,
+) -> Result<AutoApprovalPolicy, String> {
+    let policy = normalize_policy(policy)?;
+    let _guard = STORE_LOCK.lock();
+    write_policy(data_dir, &policy)?;
+    Ok(policy)
+}
+
+pub(crate) async fn auto_approval_test_core(
+    data_dir: &Path,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    request: AutoApprovalRequest,
+) -> Result<AutoApprovalEvaluation, String> {
+    let workspace_root = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(&request.workspace_id)
+            .ok_or("workspace not found")?;
+        normalize_root(&entry.path)
+    };
+    let policy = read_policy(data_dir)?;
+    let context = ApprovalContext::new(
+        &request.workspace_id,
+        request.thread_id.as_deref(),
+        &request.method,
+        &request.params,
+        Some(&workspace_root),
+        None,
+    );
+    Ok(evaluate(&policy, &context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str, decision: AutoApprovalDecision) -> AutoApprovalRule {
+        AutoApprovalRule {
+            id: name.to_string(),
+            name: name.to_string(),
+            enabled: true,
+            decision,
+            kinds: Vec::new(),
+            workspace_ids: Vec::new(),
+            thread_ids: Vec::new(),
+            commands: Vec::new(),
+            paths: Vec::new(),
+        }
+    }
+
+    fn exec_context(command: &str) -> ApprovalContext<'static> {
+        ApprovalContext::new(
+            "ws-1",
+            Some("thread-1"),
+            "item/commandExecution/requestApproval",
+            &json!({ "command": command, "cwd": "/repo/app" }),
+            Some("/repo"),
+            None,
+        )
+    }
+
+    #[test]
+    fn globs_respect_path_separators() {
+        assert!(path_matches("src/**/*.rs", "src/shared/core.rs"));
+        assert!(path_matches("src/**/*.rs", "src/lib.rs"));
+        assert!(!path_matches("src/*.rs", "src/shared/core.rs"));
+        assert!(path_matches(".env*", "config/.env.local"));
+        assert!(command_matches("git status*", "git status --short"));
+        assert!(!command_matches("git status", "git status --short"));
+    }
+
+    #[test]
+    fn deny_rules_win_and_allow_rules_cover_every_segment() {
+        let mut allow = rule("git-read", AutoApprovalDecision::Accept);
+        allow.commands = vec!["git status*".to_string(), "git diff*".to_string()];
+        allow.kinds = vec![AutoApprovalRequestKind::Exec];
+        let mut deny = rule("no-push", AutoApprovalDecision::Decline);
+        deny.commands = vec!["git push*".to_string()];
+        let policy = AutoApprovalPolicy {
+            enabled: true,
+            rules: vec![allow, deny],
+        };
+
+        let accepted = evaluate(&policy, &exec_context("git status && git diff HEAD"));
+        assert_eq!(accepted.outcome, AutoApprovalOutcome::Accept);
+        assert_eq!(accepted.rule_id.as_deref(), Some("git-read"));
+        assert_eq!(accepted.paths, vec!["app".to_string()]);
+
+        let declined = evaluate(&policy, &exec_context("git status; git push origin"));
+        assert_eq!(declined.outcome, AutoApprovalOutcome::Decline);
+        assert_eq!(declined.rule_id.as_deref(), Some("no-push"));
+
+        let prompted = evaluate(&policy, &exec_context("git status && rm -rf build"));
+        assert_eq!(prompted.outcome, AutoApprovalOutcome::Prompt);
+        let substituted = evaluate(&policy, &exec_context("git status $(rm -rf build)"));
+        assert_eq!(substituted.outcome, AutoApprovalOutcome::Prompt);
+        let quoted = evaluate(&policy, &exec_context("git 'status' --short"));
+        assert_eq!(quoted.outcome, AutoApprovalOutcome::Accept);
+        let quoted_push = evaluate(&policy, &exec_context("git \"push\" origin"));
+        assert_eq!(quoted_push.outcome, AutoApprovalOutcome::Decline);
+    }
+
+    #[test]
+    fn allow_rules_prompt_for_redirections_and_expansions() {
+        let mut allow = rule("git-read", AutoApprovalDecision::Accept);
+        allow.commands = vec![
+            "git status*".to_string(),
+            "git diff*".to_string(),
+            "git log*".to_string(),
+        ];
+        let policy = AutoApprovalPolicy {
+            enabled: true,
+            rules: vec![allow],
+        };
+        for command in [
+            "git status > ~/.bashrc",
+            "git diff <(cat ~/.ssh/id_rsa)",
+            "git log $HOME",
+            "git log ${HOME}",
+            "git diff `cat ~/.ssh/id_rsa`",
+            "git status 'unterminated",
+        ] {
+            assert_eq!(
+                evaluate(&policy, &exec_context(command)).outcome,
+                AutoApprovalOutcome::Prompt,
+                "{command}"
+            );
+        }
+        assert_eq!(
+            evaluate(&policy, &exec_context("git log --oneline")).outcome,
+            AutoApprovalOutcome::Accept
+        );
+    }
+
+    #[test]
+    fn paths_are_normalized_against_the_workspace_root() {
+        let scoped = |path: &str, root: Option<&str>| {
+            let scoped = scope_path(path, root);
+            (scoped.display, scoped.inside_root)
+        };
+        assert_eq!(
+            scoped("/repo/docs/../README.md", Some("/repo/")),
+            ("README.md".to_string(), true)
+        );
+        assert_eq!(
+            scoped("./docs/./guide.md", Some("/repo")),
+            ("docs/guide.md".to_string(), true)
+        );
+        assert_eq!(scoped("/repo", Some("/repo")), (".".to_string(), true));
+        assert_eq!(
+            scoped("/repo/../etc/passwd", Some("/repo")),
+            ("/etc/passwd".to_string(), false)
+        );
+        assert_eq!(
+            scoped("../secrets.md", Some("/repo")),
+            ("/secrets.md".to_string(), false)
+        );
+        assert!(!scoped("/repository/notes.md", Some("/repo")).1);
+        assert!(!scoped("/Repo/notes.md", Some("/repo")).1);
+        assert_eq!(
+            scoped("C:\\Repo\\notes.md", Some("c:/repo")),
+            ("notes.md".to_string(), true)
+        );
+        assert!(!scoped("docs/../../notes.md", None).1);
+        assert!(!scoped("/repo/notes.md", None).1);
+
+        let mut docs = rule("docs", AutoApprovalDecision::Accept);
+        docs.paths = vec!["**/*.md".to_string()];
+        let mut deny = rule("no-secrets", AutoApprovalDecision::Decline);
+        deny.paths = vec!["/etc/**".to_string()];
+        let policy = AutoApprovalPolicy {
+            enabled: true,
+            rules: vec![docs, deny],
+        };
+        let patch = |path: &str| {
+            ApprovalContext::new(
+                "ws-1",
+                None,
+                "item/fileChange/requestApproval",
+                &json!({ "changes": [{ "path": path }] }),
+                Some("/repo"),
+                None,
+            )
+        };
+        assert_eq!(
+            evaluate(&policy, &patch("/repo/docs/../guide.md")).outcome,
+            AutoApprovalOutcome::Accept
+        );
+        assert_eq!(
+            evaluate(&policy, &patch("/repo/../home/user/notes.md")).outcome,
+            AutoApprovalOutcome::Prompt
+        );
+        assert_eq!(
+            evaluate(&policy, &patch("/tmp/notes.md")).outcome,
+            AutoApprovalOutcome::Prompt
+        );
+        assert_eq!(
+            evaluate(&policy, &patch("/repo/../etc/hosts")).outcome,
+            AutoApprovalOutcome::Decline
+        );
+    }
+
+    #[test]
+    fn file_change_paths_come_from_tracked_items() {
+        let tracker = AutoApprovalTracker::default();
+        tracker.observe(
+            Some("item/started"),
+            &json!({ "params": { "item": {
+                "id": "item-1",
+                "type": "fileChange",
+                "changes": [{ "path": "/repo/docs/guide.md" }, { "path": "/repo/README.md" }]
+            } } }),
+        );
+        let mut docs = rule("docs", AutoApprovalDecision::Accept);
+        docs.paths = vec!["*.md".to_string()];
+        docs.workspace_ids = vec!["ws-1".to_string()];
+        let policy = AutoApprovalPolicy {
+            enabled: true,
+            rules: vec![docs],
+        };
+        let context = ApprovalContext::new(
+            "ws-1",
+            None,
+            "item/fileChange/requestApproval",
+            &json!({ "itemId": "item-1" }),
+            Some("/repo"),
+            Some(&tracker),
+        );
+        let evaluation = evaluate(&policy, &context);
+        assert_eq!(evaluation.outcome, AutoApprovalOutcome::Accept);
+        assert_eq!(
+            evaluation.paths,
+            vec!["docs/guide.md".to_string(), "README.md".to_string()]
+        );
+
+        let other_workspace = ApprovalContext::new(
+            "ws-2",
+            None,
+            "item/fileChange/requestApproval",
+            &json!({ "itemId": "item-1" }),
+            Some("/repo"),
+            Some(&tracker),
+        );
+        assert_eq!(
+            evaluate(&policy, &other_workspace).outcome,
+            AutoApprovalOutcome::Prompt
+        );
+    }
+}
@@ -0,0 +1,588 @@
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::shared::codex_aux_core::{generate_run_metadata_core, sanitize_run_worktree_name};
+use crate::shared::store_helpers::{now_ms, read_json, trim_optional, write_json, StoreLock};
+use crate::shared::{codex_core, git_ui_core};
+use crate::types::{
+    BatchRun, BatchRunDiffEntry, BatchRunDiffSummary, BatchRunEntry, BatchRunEntryStatus,
+    BatchRunFileComparison, BatchRunInput, BatchRunStatus, BatchRunVariant, GitFileStatus,
+    WorkspaceEntry, WorkspaceInfo,
+};
+
+const BATCH_RUNS_FILE: &str = "batch-runs.json";
+const MAX_BATCH_VARIANTS: usize = 8;
+const MAX_STORED_BATCHES: usize = 100;
+const FALLBACK_TITLE_CHARS: usize = 60;
+
+/// Serializes read-modify-write cycles on the batch file.
+static STORE_LOCK: StoreLock = StoreLock::new();
+
+fn batch_runs_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(BATCH_RUNS_FILE)
+}
+
+fn read_batches(data_dir: &Path) -> Result<Vec<BatchRun>, String> {
+    read_json(&batch_runs_path(data_dir))
+}
+
+fn write_batches(data_dir: &Path, batches: &mut Vec<BatchRun>) -> Result<(), String> {
+    if batches.len() > MAX_STORED_BATCHES {
+        let excess = batches.len() - MAX_STORED_BATCHES;
+        batches.drain(..excess);
+    }
+    write_json(&batch_runs_path(data_dir), &*batches)
+}
+
+/// Replaces the stored copy of `batch` (or appends it) after recomputing its
+/// aggregate status.
+fn save_batch(data_dir: &Path, batch: &mut BatchRun) -> Result<(), String> {
+    batch.status = aggregate_status(&batch.entries);
+    let _guard = STORE_LOCK.lock();
+    let mut batches = read_batches(data_dir)?;
+    match batches.iter_mut().find(|stored| stored.id == batch.id) {
+        Some(stored) => *stored = batch.clone(),
+        None => batches.push(batch.clone()),
+    }
+    write_batches(data_dir, &mut batches)
+}
+
+fn load_batch(data_dir: &Path, batch_id: &str) -> Result<BatchRun, String> {
+    let _guard = STORE_LOCK.lock();
+    read_batches(data_dir)?
+        .into_iter()
+        .find(|batch| batch.id == batch_id)
+        .ok_or_else(|| "Batch run not found.".to_string())
+}
+
+fn aggregate_status(entries: &[BatchRunEntry]) -> BatchRunStatus {
+    if entries.iter().any(|entry| {
+        matches!(
+            entry.status,
+            BatchRunEntryStatus::Pending | BatchRunEntryStatus::Running
+        )
+    }) {
+        return BatchRunStatus::Running;
+    }
+    if entries
+        .iter()
+        .any(|entry| entry.status == BatchRunEntryStatus::Unknown)
+    {
+        return BatchRunStatus::Unknown;
+    }
+    let completed = entries
+        .iter()
+        .filter(|entry| entry.status == BatchRunEntryStatus::Completed)
+        .count();
+    if completed == entries.len() {
+        BatchRunStatus::Completed
+    } else if completed == 0 {
+        BatchRunStatus::Failed
+    } else {
+        BatchRunStatus::Partial
+    }
+}
+
+fn fallback_title(prompt: &str) -> String {
+    let first_line = prompt.lines().next().unwrap_or("").trim();
+    let mut title: String = first_line.chars().take(FALLBACK_TITLE_CHARS).collect();
+    if first_line.chars().count() > FALLBACK_TITLE_CHARS {
+        title.push('…');
+    }
+    title
+}
+
+fn variant_prompt(prompt: &str, variant: &BatchRunVariant) -> String {
+    match variant.instructions.as_deref() {
+        Some(instructions) => format!("{prompt}\n\n{instructions}"),
+        None => prompt.to_string(),
+    }
+}
+
+/// Builds pending entries with unique labels and branch names derived from
+/// the batch's worktree name.
+fn plan_entries(worktree_name: &str, variants: Vec<BatchRunVariant>) -> Vec<BatchRunEntry> {
+    let mut used_branches: Vec<String> = Vec::new();
+    variants
+        .into_iter()
+        .enumerate()
+        .map(|(index, variant)| {
+            let variant = BatchRunVariant {
+                label: trim_optional(variant.label),
+                model: trim_optional(variant.model),
+                effort: trim_optional(variant.effort),
+                instructions: trim_optional(variant.instructions),
+                access_mode: trim_optional(variant.access_mode),
+            };
+            let label = variant
+                .label
+                .clone()
+                .or_else(|| variant.model.clone())
+                .unwrap_or_else(|| format!("variant {}", index + 1));
+            let base = sanitize_run_worktree_name(&format!("{worktree_name}-{label}"));
+            let mut branch = base.clone();
+            let mut suffix = 2;
+            while used_branches.contains(&branch) {
+                branch = format!("{base}-{suffix}");
+                suffix += 1;
+            }
+            used_branches.push(branch.clone());
+            BatchRunEntry {
+                index,
+                label,
+                variant,
+                branch,
+                workspace_id: None,
+                thread_id: None,
+                turn_id: None,
+                status: BatchRunEntryStatus::Pending,
+                error: None,
+                finished_at_ms: None,
+            }
+        })
+        .collect()
+}
+
+fn response_error(response: &Value) -> Option<String> {
+    response.get("error").map(|error| {
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("app-server returned an error")
+            .to_string()
+    })
+}
+
+fn response_id(response: &Value, object_key: &str) -> Option<String> {
+    let result = response.get("result").unwrap_or(response);
+    result
+        .get(object_key)
+        .and_then(|object| object.get("id"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+async fn launch_entry<FCreate, FutCreate>(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    parent_id: &str,
+    title: &str,
+    prompt: &str,
+    entry: &mut BatchRunEntry,
+    create_worktree: &FCreate,
+) -> Result<(), String>
+where
+    FCreate: Fn(String, String, Option<String>) -> FutCreate,
+    FutCreate: Future<Output = Result<WorkspaceInfo, String>>,
+{
+    let name = format!("{title} ({})", entry.label);
+    let worktree = create_worktree(parent_id.to_string(), entry.branch.clone(), Some(name)).await?;
+    if let Some(info) = worktree.worktree.as_ref() {
+        entry.branch = info.branch.clone();
+    }
+    entry.workspace_id = Some(worktree.id.clone());
+
+    let response = codex_core::start_thread_core(sessions, workspaces, worktree.id.clone()).await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    let thread_id = response_id(&response, "thread")
+        .ok_or_else(|| "thread/start did not return a thread id".to_string())?;
+    entry.thread_id = Some(thread_id.clone());
+    let _ = codex_core::set_thread_name_core(
+        sessions,
+        worktree.id.clone(),
+        thread_id.clone(),
+        format!("{title} ({})", entry.label),
+    )
+    .await;
+
+    let response = codex_core::send_user_message_core(
+        sessions,
+        workspaces,
+        worktree.id,
+        thread_id,
+        variant_prompt(prompt, &entry.variant),
+        entry.variant.model.clone(),
+        entry.variant.effort.clone(),
+        None,
+        entry.variant.access_mode.clone(),
+        None,
+        None,
+        Vec::new(),
+        None,
+    )
+    .await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    entry.turn_id = response_id(&response, "turn");
+    entry.status = BatchRunEntryStatus::Running;
+    Ok(())
+}
+
+/// Creates one worktree per variant off `input.workspace_id`, starts a thread
+/// in each with the shared prompt and tracks them as one batch. Variants that
+/// fail to launch are recorded as failed; the others keep going.
+/// `create_worktree(parent_id, branch, name)` is the host's `add_worktree`.
+pub(crate) async fn batch_runs_start_core<FCreate, FutCreate, FHide>(
+    data_dir: &Path,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    input: BatchRunInput,
+    create_worktree: FCreate,
+    on_hide_thread: FHide,
+) -> Result<BatchRun, String>
+where
+    FCreate: Fn(String, String, Option<String>) -> FutCreate,
+    FutCreate: Future<Output = Result<WorkspaceInfo, String>>,
+    FHide: Fn(&str, &str),
+{
+    let prompt = input.prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err("Prompt is required.".to_string());
+    }
+    if input.variants.is_empty() || input.variants.len() > MAX_BATCH_VARIANTS {
+        return Err(format!(
+            "A batch needs between 1 and {MAX_BATCH_VARIANTS} variants."
+        ));
+    }
+    {
+        let workspaces = workspaces.lock().await;
+        let parent = workspaces
+            .get(&input.workspace_id)
+            .ok_or_else(|| "workspace not found".to_string())?;
+        if parent.kind.is_worktree() {
+            return Err("Batch runs must start from a main workspace, not a worktree.".to_string());
+        }
+    }
+
+    // Variants branch from the parent's HEAD; diffs are measured against it
+    // so commits a variant makes still count as its changes.
+    let base_commit =
+        git_ui_core::resolve_repo_root_for_workspace_core(workspaces, input.workspace_id.clone())
+            .await
+            .and_then(|repo_root| git_ui_core::head_commit_sha_core(&repo_root))
+            .ok();
+
+    let parent_connected = sessions.lock().await.contains_key(&input.workspace_id);
+    let metadata = if parent_connected {
+        generate_run_metadata_core(
+            sessions,
+            workspaces,
+            input.workspace_id.clone(),
+            &prompt,
+            on_hide_thread,
+        )
+        .await
+        .ok()
+    } else {
+        None
+    };
+    let title = metadata
+        .as_ref()
+        .and_then(|value| value.get("title"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| fallback_title(&prompt));
+    let worktree_name = metadata
+        .as_ref()
+        .and_then(|value| value.get("worktreeName"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| sanitize_run_worktree_name(&title));
+    let worktree_name = if worktree_name.is_empty() || worktree_name.ends_with('/') {
+        "feat/batch".to_string()
+    } else {
+        worktree_name
+    };
+
+    let mut batch = BatchRun {
+        id: Uuid::new_v4().to_string(),
+        workspace_id: input.workspace_id.clone(),
+        title,
+        prompt,
+        created_at_ms: now_ms(),
+        base_commit,
+        status: BatchRunStatus::Running,
+        entries: plan_entries(&worktree_name, input.variants),
+    };
+    save_batch(data_dir, &mut batch)?;
+
+    for index in 0..batch.entries.len() {
+        let mut entry = batch.entries[index].clone();
+        if let Err(err) = launch_entry(
+            sessions,
+            workspaces,
+            &batch.workspace_id,
+            &batch.title,
+            &batch.prompt,
+            &mut entry,
+            &create_worktree,
+        )
+        .await
+        {
+            entry.status = BatchRunEntryStatus::Failed;
+            entry.error = Some(err);
+            entry.finished_at_ms = Some(now_ms());
+        }
+        batch.entries[index] = entry;
+        save_batch(data_dir, &mut batch)?;
+    }
+    Ok(batch)
+}
+
+pub(crate) fn batch_runs_list_core(data_dir: &Path) -> Result<Vec<BatchRun>, String> {
+    let _guard = STORE_LOCK.lock();
+    let mut batches = read_batches(data_dir)?;
+    batches.reverse();
+    Ok(batches)
+}
+
+/// Forgets the batch. Its worktrees and threads are left in place so the
+/// winning variant can be kept; remove the others as regular worktrees.
+pub(crate) fn batch_runs_delete_core(data_dir: &Path, batch_id: String) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock();
+    let mut batches = read_batches(data_dir)?;
+    let before = batches.len();
+    batches.retain(|batch| batch.id != batch_id);
+    if batches.len() == before {
+        return Err("Batch run not found.".to_string());
+    }
+    write_batches(data_dir, &mut batches)
+}
+
+fn turn_entry_status(turn: &Value) -> Option<(BatchRunEntryStatus, Option<String>)> {
+    let status = turn.get("status").and_then(Value::as_str)?;
+    let normalized = status.to_ascii_lowercase().replace(['_', '-'], "");
+    match normalized.as_str() {
+        "completed" => Some((BatchRunEntryStatus::Completed, None)),
+        "interrupted" | "cancelled" | "canceled" => Some((BatchRunEntryStatus::Interrupted, None)),
+        "failed" | "error" => Some((
+            BatchRunEntryStatus::Failed,
+            turn.get("error")
+                .and_then(|error| error.get("message"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        )),
+        _ => None,
+    }
+}
+
+/// Picks the entry's turn out of a `thread/read` response, falling back to
+/// the most recent turn when the turn id is unknown.
+fn entry_turn<'a>(response: &'a Value, turn_id: Option<&str>) -> Option<&'a Value> {
+    let result = response.get("result").unwrap_or(response);
+    let turns = result
+        .get("thread")
+        .and_then(|thread| thread.get("turns"))
+        .and_then(Value::as_array)?;
+    match turn_id {
+        Some(turn_id) => turns
+            .iter()
+            .find(|turn| turn.get("id").and_then(Value::as_str) == Some(turn_id)),
+        None => turns.last(),
+    }
+}
+
+/// Refreshes running entries from their threads and returns the batch with
+/// its aggregate status. Entries whose thread cannot be read are reported as
+/// `Unknown` with the reason, but stay `Running` in the store so a later call
+/// can pick them up once the worktree is connected again.
+pub(crate) async fn batch_runs_status_core(
+    data_dir: &Path,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    batch_id: String,
+) -> Result<BatchRun, String> {
+    let mut batch = load_batch(data_dir, &batch_id)?;
+    let mut changed = false;
+    let mut unknown = Vec::new();
+    for (slot, entry) in batch
+        .entries
+        .iter_mut()
+        .enumerate()
+        .filter(|(_, entry)| entry.status == BatchRunEntryStatus::Running)
+    {
+        let (Some(workspace_id), Some(thread_id)) =
+            (entry.workspace_id.clone(), entry.thread_id.clone())
+        else {
+            unknown.push((slot, "The variant has no thread to check.".to_string()));
+            continue;
+        };
+        let response = match codex_core::read_thread_with_turns_core(
+            sessions,
+            workspace_id,
+            thread_id,
+        )
+        .await
+        {
+            Ok(response) => response,
+            Err(err) => {
+                unknown.push((slot, format!("Could not read the variant's thread: {err}")));
+                continue;
+            }
+        };
+        let Some((status, error)) =
+            entry_turn(&response, entry.turn_id.as_deref()).and_then(turn_entry_status)
+        else {
+            continue;
+        };
+        entry.status = status;
+        entry.error = error;
+        entry.finished_at_ms = Some(now_ms());
+        changed = true;
+    }
+    if changed {
+        save_batch(data_dir, &mut batch)?;
+    }
+    for (slot, reason) in unknown {
+        batch.entries[slot].status = BatchRunEntryStatus::Unknown;
+        batch.entries[slot].error = Some(reason);
+    }
+    batch.status = aggregate_status(&batch.entries);
+    Ok(batch)
+}
+
+/// Compares what each variant changed since the batch's base commit, whether
+/// committed or still in its worktree: totals per variant plus a per-file
+/// matrix across variants. Batches recorded without a base commit fall back to
+/// each worktree's HEAD.
+pub(crate) async fn batch_runs_diff_summary_core(
+    data_dir: &Path,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    batch_id: String,
+) -> Result<BatchRunDiffSummary, String> {
+    let batch = load_batch(data_dir, &batch_id)?;
+    let entry_count = batch.entries.len();
+    let mut entries = Vec::with_capacity(entry_count);
+    let mut files: BTreeMap<String, Vec<Option<GitFileStatus>>> = BTreeMap::new();
+    for (slot, entry) in batch.entries.iter().enumerate() {
+        let mut summary = BatchRunDiffEntry {
+            index: entry.index,
+            label: entry.label.clone(),
+            branch: entry.branch.clone(),
+            workspace_id: entry.workspace_id.clone(),
+            status: entry.status,
+            files_changed: 0,
+            additions: 0,
+            deletions: 0,
+            error: None,
+        };
+        let changed = match entry.workspace_id.clone() {
+            Some(workspace_id) => {
+                git_ui_core::resolve_repo_root_for_workspace_core(workspaces, workspace_id)
+                    .await
+                    .and_then(|repo_root| {
+                        git_ui_core::changed_files_since_commit_core(
+                            &repo_root,
+                            batch.base_commit.as_deref(),
+                        )
+                    })
+            }
+            None => Err("Worktree was not created.".to_string()),
+        };
+        match changed {
+            Ok(changed) => {
+                summary.files_changed = changed.len();
+                for file in changed {
+                    summary.additions += file.additions;
+                    summary.deletions += file.deletions;
+                    let path = file.path.clone();
+                    files
+                        .entry(path)
+                        .or_insert_with(|| vec![None; entry_count])[slot] = Some(file);
+                }
+            }
+            Err(err) => summary.error = Some(err),
+        }
+        entries.push(summary);
+    }
+    Ok(BatchRunDiffSummary {
+        batch_id,
+        entries,
+        files: files
+            .into_iter()
+            .map(|(path, changes)| BatchRunFileComparison { path, changes })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn plan_entries_derives_unique_labels_and_branches() {
+        let entries = plan_entries(
+            "fix/login-loop",
+            vec![
+                BatchRunVariant {
+                    model: Some("gpt-5".to_string()),
+                    ..BatchRunVariant::default()
+                },
+                BatchRunVariant {
+                    label: Some(" gpt-5 ".to_string()),
+                    instructions: Some("  ".to_string()),
+                    ..BatchRunVariant::default()
+                },
+                BatchRunVariant::default(),
+            ],
+        );
+        let labels: Vec<&str> = entries.iter().map(|entry| entry.label.as_str()).collect();
+        assert_eq!(labels, vec!["gpt-5", "gpt-5", "variant 3"]);
+        let branches: Vec<&str> = entries.iter().map(|entry| entry.branch.as_str()).collect();
+        assert_eq!(
+            branches,
+            vec![
+                "fix/login-loop-gpt-5",
+                "fix/login-loop-gpt-5-2",
+                "fix/login-loop-variant-3"
+            ]
+        );
+        assert_eq!(entries[1].variant.instructions, None);
+        assert_eq!(aggregate_status(&entries), BatchRunStatus::Running);
+    }
+
+    #[test]
+    fn entry_turn_status_reads_matching_turn_and_aggregates() {
+        let response = json!({
+            "result": {
+                "thread": {
+                    "turns": [
+                        { "id": "turn-1", "status": "completed" },
+                        { "id": "turn-2", "status": "failed", "error": { "message": "boom" } },
+                        { "id": "turn-3", "status": "inProgress" }
+                    ]
+                }
+            }
+        });
+        let first = entry_turn(&response, Some("turn-1")).and_then(turn_entry_status);
+        assert_eq!(first, Some((BatchRunEntryStatus::Completed, None)));
+        let second = entry_turn(&response, Some("turn-2")).and_then(turn_entry_status);
+        assert_eq!(
+            second,
+            Some((BatchRunEntryStatus::Failed, Some("boom".to_string())))
+        );
+        assert_eq!(
+            entry_turn(&response, None).and_then(turn_entry_status),
+            None
+        );
+
+        let mut entries = plan_entries("batch", vec![BatchRunVariant::default(); 2]);
+        entries[0].status = BatchRunEntryStatus::Completed;
+        entries[1].status = BatchRunEntryStatus::Failed;
+        assert_eq!(aggregate_status(&entries), BatchRunStatus::Partial);
+        entries[1].status = BatchRunEntryStatus::Unknown;
+        assert_eq!(aggregate_status(&entries), BatchRunStatus::Unknown);
+        entries[1].status = BatchRunEntryStatus::Completed;
+        assert_eq!(aggregate_status(&entries), BatchRunStatus::Completed);
+    }
+}
@@ -2,16 +2,24 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::io::ErrorKind;
+use std::path::Path;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::sync::{mpsc, Mutex};
 use tokio::time::timeout;
+use uuid::Uuid;
 
 use crate::backend::app_server::{
-    build_codex_command_with_bin, build_codex_path_env, check_codex_installation, WorkspaceSession,
+    build_codex_command_with_bin, build_codex_path_env, build_initialize_params,
+    check_codex_installation, WorkspaceSession,
 };
-use crate::shared::process_core::tokio_command;
-use crate::types::{AppSettings, WorkspaceEntry};
+use crate::codex::home as codex_home;
+use crate::shared::config_toml_core;
+use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::types::{AppSettings, ConfigDryRunResult, WorkspaceEntry};
+
+const CONFIG_DRY_RUN_TIMEOUT: Duration = Duration::from_secs(15);
 
 const DEFAULT_COMMIT_MESSAGE_PROMPT: &str =
     "Generate a concise git commit message for the following changes. \
@@ -392,6 +400,156 @@ pub(crate) async fn codex_doctor_core(
     }))
 }
 
+/// Copies the candidate config plus managed agent role files into a scratch
+/// CODEX_HOME, so the dry run never touches the real one (or its auth).
+fn prepare_dry_run_home(
+    codex_home: &Path,
+    dry_run_home: &Path,
+    content: &str,
+) -> Result<(), String> {
+    std::fs::create_dir_all(dry_run_home).map_err(|err| err.to_string())?;
+    std::fs::write(dry_run_home.join("config.toml"), content).map_err(|err| err.to_string())?;
+    let Ok(entries) = std::fs::read_dir(codex_home.join("agents")) else {
+        return Ok(());
+    };
+    let agents_dir = dry_run_home.join("agents");
+    std::fs::create_dir_all(&agents_dir).map_err(|err| err.to_string())?;
+    for entry in entries.flatten() {
+        let is_file = entry
+            .file_type()
+            .map(|file_type| file_type.is_file())
+            .unwrap_or(false);
+        if is_file {
+            let _ = std::fs::copy(entry.path(), agents_dir.join(entry.file_name()));
+        }
+    }
+    Ok(())
+}
+
+async fn run_app_server_dry_run(
+    codex_bin: Option<String>,
+    codex_args: Option<String>,
+    dry_run_home: &Path,
+) -> (Result<(), String>, String) {
+    let mut command = match build_codex_command_with_bin(
+        codex_bin,
+        codex_args.as_deref(),
+        vec!["app-server".to_string()],
+    ) {
+        Ok(command) => command,
+        Err(err) => return (Err(err), String::new()),
+    };
+    command.current_dir(dry_run_home);
+    command.env("CODEX_HOME", dry_run_home);
+    command.stdin(std::process::Stdio::piped());
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(err) => return (Err(err.to_string()), String::new()),
+    };
+    let (Some(mut stdin), Some(stdout), Some(mut stderr)) =
+        (child.stdin.take(), child.stdout.take(), child.stderr.take())
+    else {
+        kill_child_process_tree(&mut child).await;
+        return (Err("missing app-server stdio".to_string()), String::new());
+    };
+    let stderr_task = tokio::spawn(async move {
+        let mut output = String::new();
+        let _ = stderr.read_to_string(&mut output).await;
+        output
+    });
+
+    let handshake = async {
+        let request = json!({
+            "id": 1,
+            "method": "initialize",
+            "params": build_initialize_params(env!("CARGO_PKG_VERSION")),
+        });
+        stdin
+            .write_all(format!("{request}\n").as_bytes())
+            .await
+            .map_err(|err| err.to_string())?;
+        let mut lines = BufReader::new(stdout).lines();
+        while let Some(line) = lines.next_line().await.map_err(|err| err.to_string())? {
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if message.get("id").and_then(Value::as_u64) != Some(1) {
+                continue;
+            }
+            return match message.get("error") {
+                Some(error) => Err(error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("initialize failed")
+                    .to_string()),
+                None => Ok(()),
+            };
+        }
+        Err("Codex app-server exited before responding to initialize.".to_string())
+    };
+    let outcome = match timeout(CONFIG_DRY_RUN_TIMEOUT, handshake).await {
+        Ok(outcome) => outcome,
+        Err(_) => Err("Codex app-server did not respond to initialize.".to_string()),
+    };
+    kill_child_process_tree(&mut child).await;
+    let stderr = match timeout(Duration::from_secs(2), stderr_task).await {
+        Ok(Ok(output)) => output,
+        _ => String::new(),
+    };
+    (outcome, stderr)
+}
+
+/// Validates the candidate config, then starts `codex app-server` against a
+/// scratch CODEX_HOME holding it to confirm codex itself accepts the file.
+pub(crate) async fn config_toml_dry_run_core(
+    app_settings: &Mutex<AppSettings>,
+    content: Option<String>,
+) -> Result<ConfigDryRunResult, String> {
+    let codex_home = codex_home::resolve_default_codex_home()
+        .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())?;
+    let content = match content {
+        Some(content) => content,
+        None => config_toml_core::read_global_config_text(&codex_home)?,
+    };
+    let validation = config_toml_core::validate_config_toml(&content);
+    if !validation.valid {
+        return Ok(ConfigDryRunResult {
+            ok: false,
+            validation,
+            error: Some("Fix the validation errors before running a dry run.".to_string()),
+            stderr: None,
+            duration_ms: 0,
+        });
+    }
+
+    let (codex_bin, codex_args) = {
+        let settings = app_settings.lock().await;
+        (settings.codex_bin.clone(), settings.codex_args.clone())
+    };
+    let dry_run_home =
+        std::env::temp_dir().join(format!("codex-monitor-config-dry-run-{}", Uuid::new_v4()));
+    let started = Instant::now();
+    let (outcome, stderr) = match prepare_dry_run_home(&codex_home, &dry_run_home, &content) {
+        Ok(()) => run_app_server_dry_run(codex_bin, codex_args, &dry_run_home).await,
+        Err(err) => (Err(err), String::new()),
+    };
+    let _ = std::fs::remove_dir_all(&dry_run_home);
+    let stderr = stderr.trim();
+    Ok(ConfigDryRunResult {
+        ok: outcome.is_ok(),
+        validation,
+        error: outcome.err(),
+        stderr: if stderr.is_empty() {
+            None
+        } else {
+            Some(stderr.to_string())
+        },
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
 pub(crate) async fn run_background_prompt_core<F>(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
@@ -16,7 +16,13 @@ use crate::codex::config as codex_config;
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
 use crate::rules;
 use crate::shared::account::{build_account_response, read_auth_account};
-use crate::types::WorkspaceEntry;
+use crate::shared::agent_audit_core;
+use crate::types::{ApprovalRuleSpec, ApprovalRuleTestResult, ApprovalRulesFile, WorkspaceEntry};
+
+#[path = "codex_core/sandbox.rs"]
+mod sandbox;
+
+pub(crate) use sandbox::normalize_sandbox_profiles;
 
 const LOGIN_START_TIMEOUT: Duration = Duration::from_secs(30);
 #[allow(dead_code)]
@@ -288,6 +294,20 @@ pub(crate) async fn read_thread_core(
         .await
 }
 
+/// Like `read_thread_core`, but asks the app-server to include the thread's
+/// turns and items.
+pub(crate) async fn read_thread_with_turns_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    thread_id: String,
+) -> Result<Value, String> {
+    let session = get_session_clone(sessions, &workspace_id).await?;
+    let params = json!({ "threadId": thread_id, "includeTurns": true });
+    session
+        .send_request_for_workspace(&workspace_id, "thread/read", params)
+        .await
+}
+
 pub(crate) async fn thread_live_subscribe_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
@@ -401,6 +421,7 @@ fn build_turn_input_items(
     text: String,
     images: Option<Vec<String>>,
     app_mentions: Option<Vec<Value>>,
+    context_items: Vec<Value>,
 ) -> Result<Vec<Value>, String> {
     let trimmed_text = text.trim();
     let mut input: Vec<Value> = Vec::new();
@@ -455,6 +476,7 @@ fn build_turn_input_items(
             input.push(json!({ "type": "mention", "name": name, "path": path }));
         }
     }
+    input.extend(context_items);
     if input.is_empty() {
         return Err("empty user message".to_string());
     }
@@ -483,35 +505,21 @@ pub(crate) async fn send_user_message_core(
     access_mode: Option<String>,
     images: Option<Vec<String>>,
     app_mentions: Option<Vec<Value>>,
+    context_items: Vec<Value>,
     collaboration_mode: Option<Value>,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let workspace_path = resolve_workspace_path_core(workspaces, &workspace_id).await?;
-    let access_mode = access_mode.unwrap_or_else(|| "current".to_string());
-    let sandbox_policy = match access_mode.as_str() {
-        "full-access" => json!({ "type": "dangerFullAccess" }),
-        "read-only" => json!({ "type": "readOnly" }),
-        _ => json!({
-            "type": "workspaceWrite",
-            "writableRoots": [workspace_path.clone()],
-            "networkAccess": true
-        }),
-    };
-
-    let approval_policy = if access_mode == "full-access" {
-        "never"
-    } else {
-        "on-request"
-    };
+    let policy =
+        sandbox::resolve_turn_policy(workspaces, &workspace_id, access_mode.as_deref()).await?;
 
-    let input = build_turn_input_items(text, images, app_mentions)?;
+    let input = build_turn_input_items(text, images, app_mentions, context_items)?;
 
     let mut params = Map::new();
     params.insert("threadId".to_string(), json!(thread_id));
     params.insert("input".to_string(), json!(input));
-    params.insert("cwd".to_string(), json!(workspace_path));
-    params.insert("approvalPolicy".to_string(), json!(approval_policy));
-    params.insert("sandboxPolicy".to_string(), json!(sandbox_policy));
+    params.insert("cwd".to_string(), json!(policy.workspace_path));
+    params.insert("approvalPolicy".to_string(), json!(policy.approval_policy));
+    params.insert("sandboxPolicy".to_string(), policy.sandbox_policy);
     params.insert("model".to_string(), json!(model));
     params.insert("effort".to_string(), json!(effort));
     insert_optional_nullable_string(&mut params, "serviceTier", service_tier);
@@ -527,23 +535,25 @@ pub(crate) async fn send_user_message_core(
 
 pub(crate) async fn turn_steer_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     thread_id: String,
     turn_id: String,
     text: String,
+    access_mode: Option<String>,
     images: Option<Vec<String>>,
     app_mentions: Option<Vec<Value>>,
+    context_items: Vec<Value>,
 ) -> Result<Value, String> {
     if turn_id.trim().is_empty() {
         return Err("missing active turn id".to_string());
     }
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let input = build_turn_input_items(text, images, app_mentions)?;
-    let params = json!({
-        "threadId": thread_id,
-        "expectedTurnId": turn_id,
-        "input": input
-    });
+    let policy =
+        sandbox::resolve_thread_policy_override(workspaces, &workspace_id, access_mode.as_deref())
+            .await?;
+    let input = build_turn_input_items(text, images, app_mentions, context_items)?;
+    let params = build_turn_steer_params(thread_id, turn_id, input, policy);
     session
         .send_request_for_workspace(&workspace_id, "turn/steer", params)
         .await
@@ -574,21 +584,58 @@ pub(crate) async fn turn_interrupt_core(
 
 pub(crate) async fn start_review_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     thread_id: String,
     target: Value,
     delivery: Option<String>,
+    access_mode: Option<String>,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
+    let policy =
+        sandbox::resolve_thread_policy_override(workspaces, &workspace_id, access_mode.as_deref())
+            .await?;
+    let params = build_review_start_params(thread_id, target, delivery, policy);
+    session
+        .send_request_for_workspace(&workspace_id, "review/start", params)
+        .await
+}
+
+fn insert_thread_policy(params: &mut Map<String, Value>, policy: Option<sandbox::TurnPolicy>) {
+    if let Some(policy) = policy {
+        params.insert("approvalPolicy".to_string(), json!(policy.approval_policy));
+        params.insert("sandboxPolicy".to_string(), policy.sandbox_policy);
+    }
+}
+
+fn build_turn_steer_params(
+    thread_id: String,
+    turn_id: String,
+    input: Vec<Value>,
+    policy: Option<sandbox::TurnPolicy>,
+) -> Value {
+    let mut params = Map::new();
+    params.insert("threadId".to_string(), json!(thread_id));
+    params.insert("expectedTurnId".to_string(), json!(turn_id));
+    params.insert("input".to_string(), json!(input));
+    insert_thread_policy(&mut params, policy);
+    Value::Object(params)
+}
+
+fn build_review_start_params(
+    thread_id: String,
+    target: Value,
+    delivery: Option<String>,
+    policy: Option<sandbox::TurnPolicy>,
+) -> Value {
     let mut params = Map::new();
     params.insert("threadId".to_string(), json!(thread_id));
     params.insert("target".to_string(), target);
+    insert_thread_policy(&mut params, policy);
     if let Some(delivery) = delivery {
         params.insert("delivery".to_string(), json!(delivery));
     }
-    session
-        .send_request_for_workspace(&workspace_id, "review/start", Value::Object(params))
-        .await
+    Value::Object(params)
 }
 
 pub(crate) async fn model_list_core(
@@ -852,7 +899,23 @@ pub(crate) async fn respond_to_server_request_core(
     result: Value,
 ) -> Result<(), String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    session.send_response(request_id, result).await
+    session
+        .send_response(request_id.clone(), result.clone())
+        .await?;
+    if let Some(data_dir) = session.data_dir.as_deref() {
+        if let Some(decision) = result.get("decision").and_then(Value::as_str) {
+            agent_audit_core::record_approval_decision(
+                &session.audit_tracker,
+                data_dir,
+                &workspace_id,
+                &request_id,
+                decision,
+                "user",
+                None,
+            );
+        }
+    }
+    Ok(())
 }
 
 pub(crate) async fn remember_approval_rule_core(
@@ -879,6 +942,88 @@ pub(crate) async fn remember_approval_rule_core(
     }))
 }
 
+pub(crate) async fn approval_rules_list_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<ApprovalRulesFile>, String> {
+    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+    let mut files = rules::list_rules_files(&codex_home);
+    if files.is_empty() {
+        files.push(rules::read_rules_file(&rules::default_rules_path(
+            &codex_home,
+        )));
+    }
+    Ok(files)
+}
+
+pub(crate) fn approval_rule_validate_core(rule: ApprovalRuleSpec) -> Vec<String> {
+    rules::validate_rule(&rule)
+}
+
+pub(crate) async fn approval_rule_add_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    file: Option<String>,
+    rule: ApprovalRuleSpec,
+    index: Option<usize>,
+) -> Result<ApprovalRulesFile, String> {
+    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+    let path = rules::resolve_rules_file(&codex_home, file.as_deref())?;
+    rules::insert_rule(&path, &rule, index)
+}
+
+pub(crate) async fn approval_rule_update_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    file: Option<String>,
+    index: usize,
+    rule: ApprovalRuleSpec,
+) -> Result<ApprovalRulesFile, String> {
+    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+    let path = rules::resolve_rules_file(&codex_home, file.as_deref())?;
+    rules::replace_rule(&path, index, &rule)
+}
+
+pub(crate) async fn approval_rule_delete_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    file: Option<String>,
+    index: usize,
+) -> Result<ApprovalRulesFile, String> {
+    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+    let path = rules::resolve_rules_file(&codex_home, file.as_deref())?;
+    rules::remove_rule(&path, index)
+}
+
+pub(crate) async fn approval_rule_move_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    file: Option<String>,
+    from: usize,
+    to: usize,
+) -> Result<ApprovalRulesFile, String> {
+    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+    let path = rules::resolve_rules_file(&codex_home, file.as_deref())?;
+    rules::move_rule(&path, from, to)
+}
+
+pub(crate) async fn approval_rules_test_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    command: Vec<String>,
+) -> Result<ApprovalRuleTestResult, String> {
+    let command = command
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect::<Vec<_>>();
+    if command.is_empty() {
+        return Err("empty command".to_string());
+    }
+    let codex_home = resolve_codex_home_for_workspace_core(workspaces, &workspace_id).await?;
+    Ok(rules::test_command(&codex_home, command))
+}
+
 pub(crate) async fn get_config_model_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -891,8 +1036,87 @@ pub(crate) async fn get_config_model_core(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{ApprovalPolicy, SandboxMode, SandboxProfile, WorkspaceSettings};
     use serde_json::Value;
 
+    #[test]
+    fn steer_and_review_params_keep_thread_policy_unless_overridden() {
+        let workspace = WorkspaceEntry {
+            id: "w1".to_string(),
+            name: "w1".to_string(),
+            path: "/repo".to_string(),
+            kind: Default::default(),
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        };
+        let workspaces = Mutex::new(HashMap::from([("w1".to_string(), workspace)]));
+        let runtime = tokio::runtime::Runtime::new().expect("create tokio runtime");
+        let policy = |access_mode: Option<&str>| {
+            runtime
+                .block_on(sandbox::resolve_thread_policy_override(
+                    &workspaces,
+                    "w1",
+                    access_mode,
+                ))
+                .expect("policy")
+        };
+
+        let input = vec![json!({ "type": "text", "text": "keep going" })];
+        let steer = build_turn_steer_params(
+            "thread-1".to_string(),
+            "turn-1".to_string(),
+            input.clone(),
+            policy(None),
+        );
+        assert_eq!(
+            steer,
+            json!({ "threadId": "thread-1", "expectedTurnId": "turn-1", "input": input })
+        );
+        let review = build_review_start_params(
+            "thread-1".to_string(),
+            json!({ "type": "uncommittedChanges" }),
+            None,
+            policy(Some(" ")),
+        );
+        assert!(review.get("approvalPolicy").is_none());
+        assert!(review.get("sandboxPolicy").is_none());
+
+        let steer = build_turn_steer_params(
+            "thread-1".to_string(),
+            "turn-1".to_string(),
+            Vec::new(),
+            policy(Some("full-access")),
+        );
+        assert_eq!(steer["approvalPolicy"], "never");
+        assert_eq!(
+            steer["sandboxPolicy"],
+            json!({ "type": "dangerFullAccess" })
+        );
+
+        {
+            let mut workspaces = workspaces.blocking_lock();
+            let settings = &mut workspaces.get_mut("w1").expect("workspace").settings;
+            settings.sandbox_profiles = vec![SandboxProfile {
+                name: "locked".to_string(),
+                sandbox_mode: SandboxMode::ReadOnly,
+                writable_roots: Vec::new(),
+                network_access: false,
+                approval_policy: ApprovalPolicy::Untrusted,
+            }];
+            settings.default_sandbox_profile = Some("locked".to_string());
+        }
+        let review = build_review_start_params(
+            "thread-1".to_string(),
+            json!({ "type": "uncommittedChanges" }),
+            Some("inline".to_string()),
+            policy(None),
+        );
+        assert_eq!(review["approvalPolicy"], "untrusted");
+        assert_eq!(review["sandboxPolicy"], json!({ "type": "readOnly" }));
+        assert_eq!(review["delivery"], "inline");
+    }
+
     #[test]
     fn normalize_strips_file_uri_prefix() {
         assert_eq!(
@@ -0,0 +1,294 @@
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use tokio::sync::Mutex;
+
+use crate::codex::home::expand_user_path;
+use crate::types::{SandboxMode, SandboxProfile, WorkspaceEntry, WorkspaceSettings};
+
+const PROFILE_ACCESS_PREFIX: &str = "profile:";
+
+pub(super) struct TurnPolicy {
+    pub(super) workspace_path: String,
+    pub(super) approval_policy: &'static str,
+    pub(super) sandbox_policy: Value,
+}
+
+fn access_mode_policy(access_mode: &str, workspace_path: &str) -> (&'static str, Value) {
+    match access_mode {
+        "full-access" => ("never", json!({ "type": "dangerFullAccess" })),
+        "read-only" => ("on-request", json!({ "type": "readOnly" })),
+        _ => (
+            "on-request",
+            json!({
+                "type": "workspaceWrite",
+                "writableRoots": [workspace_path],
+                "networkAccess": true
+            }),
+        ),
+    }
+}
+
+fn resolve_writable_root(raw: &str, workspace_path: &str) -> Option<String> {
+    let path = expand_user_path(raw)?;
+    let path = if path.is_absolute() {
+        path
+    } else {
+        Path::new(workspace_path).join(path)
+    };
+    Some(path.to_string_lossy().to_string())
+}
+
+fn profile_policy(profile: &SandboxProfile, workspace_path: &str) -> Value {
+    match profile.sandbox_mode {
+        SandboxMode::DangerFullAccess => json!({ "type": "dangerFullAccess" }),
+        SandboxMode::ReadOnly => json!({ "type": "readOnly" }),
+        SandboxMode::WorkspaceWrite => {
+            let mut roots = vec![workspace_path.to_string()];
+            for root in &profile.writable_roots {
+                if let Some(root) = resolve_writable_root(root, workspace_path) {
+                    if !roots.contains(&root) {
+                        roots.push(root);
+                    }
+                }
+            }
+            json!({
+                "type": "workspaceWrite",
+                "writableRoots": roots,
+                "networkAccess": profile.network_access
+            })
+        }
+    }
+}
+
+/// Maps a turn's access mode to the approval and sandbox policy sent to the
+/// app-server. `profile:<name>` picks a named profile; "current" uses the
+/// workspace default profile when one is set. Worktrees without profiles of
+/// their own use their parent's.
+pub(super) async fn resolve_turn_policy(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    access_mode: Option<&str>,
+) -> Result<TurnPolicy, String> {
+    let (entry, settings) = effective_settings(workspaces, workspace_id).await?;
+    policy_for_access_mode(entry, &settings, access_mode)
+}
+
+/// Policy for requests that continue an existing thread (steer, review).
+/// Returns `None` when the caller names no access mode and the workspace has
+/// no default profile, so the thread keeps the policy it was started with
+/// instead of falling back to workspace-write.
+pub(super) async fn resolve_thread_policy_override(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    access_mode: Option<&str>,
+) -> Result<Option<TurnPolicy>, String> {
+    let (entry, settings) = effective_settings(workspaces, workspace_id).await?;
+    let explicit = access_mode.is_some_and(|value| !value.trim().is_empty());
+    if !explicit && settings.default_sandbox_profile.is_none() {
+        return Ok(None);
+    }
+    policy_for_access_mode(entry, &settings, access_mode).map(Some)
+}
+
+/// The workspace and the settings its sandbox profiles come from.
+async fn effective_settings(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+) -> Result<(WorkspaceEntry, WorkspaceSettings), String> {
+    let (entry, parent_settings) = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(workspace_id)
+            .cloned()
+            .ok_or_else(|| "workspace not found".to_string())?;
+        let parent_settings = entry
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id))
+            .map(|parent| parent.settings.clone());
+        (entry, parent_settings)
+    };
+    let settings = match parent_settings {
+        Some(parent) if entry.settings.sandbox_profiles.is_empty() => parent,
+        _ => entry.settings.clone(),
+    };
+    Ok((entry, settings))
+}
+
+fn policy_for_access_mode(
+    entry: WorkspaceEntry,
+    settings: &WorkspaceSettings,
+    access_mode: Option<&str>,
+) -> Result<TurnPolicy, String> {
+    let access_mode = access_mode
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or("current");
+    let profile_name = match access_mode.strip_prefix(PROFILE_ACCESS_PREFIX) {
+        Some(name) => Some(name.trim()),
+        None if access_mode == "current" => settings.default_sandbox_profile.as_deref(),
+        None => None,
+    };
+    let (approval_policy, sandbox_policy) = match profile_name {
+        Some(name) => {
+            let profile = settings
+                .sandbox_profiles
+                .iter()
+                .find(|profile| profile.name == name)
+                .ok_or_else(|| format!("sandbox profile `{name}` not found"))?;
+            (
+                profile.approval_policy.as_str(),
+                profile_policy(profile, &entry.path),
+            )
+        }
+        None => access_mode_policy(access_mode, &entry.path),
+    };
+    Ok(TurnPolicy {
+        workspace_path: entry.path,
+        approval_policy,
+        sandbox_policy,
+    })
+}
+
+/// Trims profile names and roots, rejects duplicate names and makes sure the
+/// default profile exists.
+pub(crate) fn normalize_sandbox_profiles(settings: &mut WorkspaceSettings) -> Result<(), String> {
+    let mut names = HashSet::new();
+    for profile in &mut settings.sandbox_profiles {
+        profile.name = profile.name.trim().to_string();
+        if profile.name.is_empty() {
+            return Err("sandbox profile name is required".to_string());
+        }
+        if !names.insert(profile.name.to_lowercase()) {
+            return Err(format!("duplicate sandbox profile `{}`", profile.name));
+        }
+        let mut roots: Vec<String> = Vec::new();
+        for root in &profile.writable_roots {
+            let root = root.trim();
+            if !root.is_empty() && !roots.iter().any(|existing| existing == root) {
+                roots.push(root.to_string());
+            }
+        }
+        profile.writable_roots = roots;
+    }
+    settings.default_sandbox_profile = settings
+        .default_sandbox_profile
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+    if let Some(default) = settings.default_sandbox_profile.as_deref() {
+        if !settings
+            .sandbox_profiles
+            .iter()
+            .any(|profile| profile.name == default)
+        {
+            return Err(format!(
+                "default sandbox profile `{default}` does not exist"
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ApprovalPolicy;
+    use tokio::runtime::Runtime;
+
+    fn workspace(id: &str, path: &str, parent_id: Option<&str>) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            kind: Default::default(),
+            parent_id: parent_id.map(str::to_string),
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
+    #[test]
+    fn resolve_turn_policy_uses_profiles_and_parent_defaults() {
+        let mut parent = workspace("parent", "/repo", None);
+        parent.settings.sandbox_profiles = vec![SandboxProfile {
+            name: "cached".to_string(),
+            sandbox_mode: SandboxMode::WorkspaceWrite,
+            writable_roots: vec!["/tmp/cache".to_string(), ".build".to_string()],
+            network_access: false,
+            approval_policy: ApprovalPolicy::Untrusted,
+        }];
+        parent.settings.default_sandbox_profile = Some("cached".to_string());
+        let child = workspace("child", "/worktrees/feature", Some("parent"));
+        let workspaces = Mutex::new(HashMap::from([
+            ("parent".to_string(), parent),
+            ("child".to_string(), child),
+        ]));
+
+        let runtime = Runtime::new().expect("create tokio runtime");
+        let policy = runtime
+            .block_on(resolve_turn_policy(&workspaces, "child", None))
+            .expect("policy");
+        assert_eq!(policy.workspace_path, "/worktrees/feature");
+        assert_eq!(policy.approval_policy, "untrusted");
+        assert_eq!(
+            policy.sandbox_policy,
+            json!({
+                "type": "workspaceWrite",
+                "writableRoots": [
+                    "/worktrees/feature",
+                    "/tmp/cache",
+                    Path::new("/worktrees/feature").join(".build").to_string_lossy(),
+                ],
+                "networkAccess": false
+            })
+        );
+
+        let full = runtime
+            .block_on(resolve_turn_policy(
+                &workspaces,
+                "parent",
+                Some("full-access"),
+            ))
+            .expect("full access");
+        assert_eq!(full.approval_policy, "never");
+        assert_eq!(full.sandbox_policy, json!({ "type": "dangerFullAccess" }));
+
+        let missing = runtime.block_on(resolve_turn_policy(
+            &workspaces,
+            "parent",
+            Some("profile:nope"),
+        ));
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn normalize_sandbox_profiles_rejects_duplicates_and_unknown_default() {
+        let profile = |name: &str| SandboxProfile {
+            name: name.to_string(),
+            sandbox_mode: SandboxMode::ReadOnly,
+            writable_roots: vec![" /a ".to_string(), "/a".to_string(), " ".to_string()],
+            network_access: true,
+            approval_policy: ApprovalPolicy::OnRequest,
+        };
+        let mut settings = WorkspaceSettings {
+            sandbox_profiles: vec![profile(" strict ")],
+            default_sandbox_profile: Some("strict ".to_string()),
+            ..WorkspaceSettings::default()
+        };
+        normalize_sandbox_profiles(&mut settings).expect("valid");
+        assert_eq!(settings.sandbox_profiles[0].name, "strict");
+        assert_eq!(settings.sandbox_profiles[0].writable_roots, vec!["/a"]);
+        assert_eq!(settings.default_sandbox_profile.as_deref(), Some("strict"));
+
+        settings.sandbox_profiles.push(profile("Strict"));
+        assert!(normalize_sandbox_profiles(&mut settings).is_err());
+
+        settings.sandbox_profiles.pop();
+        settings.default_sandbox_profile = Some("other".to_string());
+        assert!(normalize_sandbox_profiles(&mut settings).is_err());
+    }
+}
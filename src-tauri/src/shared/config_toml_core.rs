@@ -2,8 +2,15 @@ use std::path::Path;
 
 use toml_edit::{value, Document, Item, Table};
 
+use crate::codex::home as codex_home;
 use crate::files::ops::{read_with_policy, write_with_policy};
 use crate::files::policy::{policy_for, FileKind, FileScope};
+use crate::types::{ConfigIssueSeverity, ConfigValidationReport};
+
+#[path = "config_toml_core/validation.rs"]
+mod validation;
+
+pub(crate) use validation::validate_config_toml;
 
 pub(crate) fn load_global_config_document(codex_home: &Path) -> Result<(bool, Document), String> {
     let policy = policy_for(FileScope::Global, FileKind::Config)?;
@@ -30,6 +37,47 @@ pub(crate) fn persist_global_config_document(
     write_with_policy(&root, policy, rendered.as_str())
 }
 
+pub(crate) fn read_global_config_text(codex_home: &Path) -> Result<String, String> {
+    let policy = policy_for(FileScope::Global, FileKind::Config)?;
+    Ok(read_with_policy(&codex_home.to_path_buf(), policy)?.content)
+}
+
+/// Validates `content`, or the saved global config.toml when it is `None`.
+pub(crate) fn validate_global_config_core(
+    content: Option<String>,
+) -> Result<ConfigValidationReport, String> {
+    let content = match content {
+        Some(content) => content,
+        None => {
+            let codex_home = codex_home::resolve_default_codex_home()
+                .ok_or_else(|| "Unable to resolve CODEX_HOME".to_string())?;
+            read_global_config_text(&codex_home)?
+        }
+    };
+    Ok(validate_config_toml(&content))
+}
+
+/// Rejects config text with schema errors before it is written; warnings are
+/// left to the editor UI.
+pub(crate) fn ensure_valid_config_toml(contents: &str, label: &str) -> Result<(), String> {
+    let report = validate_config_toml(contents);
+    if report.valid {
+        return Ok(());
+    }
+    let errors: Vec<String> = report
+        .issues
+        .iter()
+        .filter(|issue| issue.severity == ConfigIssueSeverity::Error)
+        .map(|issue| match (issue.line, issue.column) {
+            (Some(line), Some(column)) => {
+                format!("line {line}, column {column}: {}", issue.message)
+            }
+            _ => issue.message.clone(),
+        })
+        .collect();
+    Err(format!("{label} is invalid: {}", errors.join("; ")))
+}
+
 pub(crate) fn parse_document(contents: &str) -> Result<Document, String> {
     if contents.trim().is_empty() {
         return Ok(Document::new());
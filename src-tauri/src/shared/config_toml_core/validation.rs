@@ -0,0 +1,677 @@
+use std::collections::HashMap;
+
+use toml_edit::{Document, Item, Value};
+
+use crate::shared::agents_config_core::{
+    MAX_AGENT_MAX_DEPTH, MAX_AGENT_MAX_THREADS, MIN_AGENT_MAX_DEPTH, MIN_AGENT_MAX_THREADS,
+};
+use crate::types::{ConfigIssueSeverity, ConfigValidationIssue, ConfigValidationReport};
+
+#[derive(Clone, Copy)]
+enum Expect {
+    String,
+    Bool,
+    Integer,
+    Number,
+    StringArray,
+    Table,
+    /// Unknown values are only warned about; codex adds variants over time.
+    OneOf(&'static [&'static str]),
+}
+
+const APPROVAL_POLICIES: &[&str] = &["untrusted", "on-failure", "on-request", "never"];
+const SANDBOX_MODES: &[&str] = &["read-only", "workspace-write", "danger-full-access"];
+const REASONING_EFFORTS: &[&str] = &["none", "minimal", "low", "medium", "high", "xhigh"];
+const REASONING_SUMMARIES: &[&str] = &["auto", "concise", "detailed", "none"];
+const VERBOSITY_LEVELS: &[&str] = &["low", "medium", "high"];
+const LOGIN_METHODS: &[&str] = &["chatgpt", "api"];
+const FILE_OPENERS: &[&str] = &["vscode", "vscode-insiders", "windsurf", "cursor", "none"];
+const TRUST_LEVELS: &[&str] = &["trusted", "untrusted"];
+
+/// Keys valid both at the top level and inside `[profiles.<name>]`.
+const PROFILE_KEYS: &[(&str, Expect)] = &[
+    ("model", Expect::String),
+    ("review_model", Expect::String),
+    ("model_provider", Expect::String),
+    ("approval_policy", Expect::OneOf(APPROVAL_POLICIES)),
+    ("sandbox_mode", Expect::OneOf(SANDBOX_MODES)),
+    ("model_reasoning_effort", Expect::OneOf(REASONING_EFFORTS)),
+    (
+        "model_reasoning_summary",
+        Expect::OneOf(REASONING_SUMMARIES),
+    ),
+    ("model_verbosity", Expect::OneOf(VERBOSITY_LEVELS)),
+    ("model_context_window", Expect::Integer),
+    ("model_auto_compact_token_limit", Expect::Integer),
+    ("chatgpt_base_url", Expect::String),
+    ("experimental_instructions_file", Expect::String),
+    ("model_instructions_file", Expect::String),
+    ("developer_instructions", Expect::String),
+    ("include_apply_patch_tool", Expect::Bool),
+    ("tools_web_search", Expect::Bool),
+    ("features", Expect::Table),
+    ("sandbox_workspace_write", Expect::Table),
+    ("tools", Expect::Table),
+];
+
+const TOP_LEVEL_KEYS: &[(&str, Expect)] = &[
+    ("profile", Expect::String),
+    ("instructions", Expect::String),
+    ("base_instructions", Expect::String),
+    ("compact_prompt", Expect::String),
+    ("experimental_compact_prompt_file", Expect::String),
+    ("forced_login_method", Expect::OneOf(LOGIN_METHODS)),
+    ("forced_chatgpt_workspace_id", Expect::String),
+    ("cli_auth_credentials_store", Expect::String),
+    ("mcp_oauth_credentials_store", Expect::String),
+    ("file_opener", Expect::OneOf(FILE_OPENERS)),
+    ("notify", Expect::StringArray),
+    ("project_doc_max_bytes", Expect::Integer),
+    ("project_doc_fallback_filenames", Expect::StringArray),
+    ("tool_output_token_limit", Expect::Integer),
+    ("model_max_output_tokens", Expect::Integer),
+    ("model_supports_reasoning_summaries", Expect::Bool),
+    ("hide_agent_reasoning", Expect::Bool),
+    ("show_raw_agent_reasoning", Expect::Bool),
+    ("disable_paste_burst", Expect::Bool),
+    ("check_for_update_on_startup", Expect::Bool),
+    ("windows_wsl_setup_acknowledged", Expect::Bool),
+    ("experimental_use_rmcp_client", Expect::Bool),
+    ("shell_environment_policy", Expect::Table),
+    ("history", Expect::Table),
+    ("tui", Expect::Table),
+    ("otel", Expect::Table),
+    ("notice", Expect::Table),
+    ("analytics", Expect::Table),
+    ("feedback", Expect::Table),
+    ("ghost_snapshot", Expect::Table),
+    ("model_providers", Expect::Table),
+    ("projects", Expect::Table),
+];
+
+const KNOWN_FEATURES: &[&str] = &[
+    "apply_patch_freeform",
+    "apps",
+    "collaboration_modes",
+    "ghost_commit",
+    "multi_agent",
+    "remote_compaction",
+    "rmcp_client",
+    "shell_snapshot",
+    "shell_tool",
+    "skills",
+    "steer",
+    "undo",
+    "unified_exec",
+    "view_image_tool",
+    "web_search_request",
+];
+
+const MCP_SERVER_KEYS: &[(&str, Expect)] = &[
+    ("command", Expect::String),
+    ("args", Expect::StringArray),
+    ("env", Expect::Table),
+    ("env_vars", Expect::StringArray),
+    ("cwd", Expect::String),
+    ("url", Expect::String),
+    ("bearer_token_env_var", Expect::String),
+    ("http_headers", Expect::Table),
+    ("env_http_headers", Expect::Table),
+    ("enabled", Expect::Bool),
+    ("startup_timeout_sec", Expect::Number),
+    ("startup_timeout_ms", Expect::Integer),
+    ("tool_timeout_sec", Expect::Number),
+    ("enabled_tools", Expect::StringArray),
+    ("disabled_tools", Expect::StringArray),
+];
+
+const AGENT_ROLE_KEYS: &[(&str, Expect)] = &[
+    ("description", Expect::String),
+    ("config_file", Expect::String),
+];
+
+/// Line and column (both 1-based) of every key and table header, keyed by
+/// dotted path. toml_edit drops spans once a document is parsed, so this is a
+/// light line scanner that only has to be right for well-formed input.
+struct KeyLocations {
+    locations: HashMap<String, (usize, usize)>,
+}
+
+impl KeyLocations {
+    fn scan(contents: &str) -> Self {
+        let mut locations = HashMap::new();
+        let mut prefix: Vec<String> = Vec::new();
+        let mut open_string: Option<&str> = None;
+        let mut bracket_depth: i32 = 0;
+        for (index, raw_line) in contents.lines().enumerate() {
+            if let Some(delimiter) = open_string {
+                if raw_line.contains(delimiter) {
+                    open_string = None;
+                }
+                continue;
+            }
+            if bracket_depth > 0 {
+                bracket_depth += bracket_balance(raw_line);
+                continue;
+            }
+            let line = raw_line.trim_start();
+            let column = raw_line.len() - line.len() + 1;
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                let header = line
+                    .trim_start_matches('[')
+                    .split(']')
+                    .next()
+                    .unwrap_or_default();
+                prefix = split_dotted_key(header);
+                locations
+                    .entry(prefix.join("."))
+                    .or_insert((index + 1, column));
+                continue;
+            }
+            let Some(equals) = find_unquoted(line, '=') else {
+                continue;
+            };
+            let mut path = prefix.clone();
+            path.extend(split_dotted_key(&line[..equals]));
+            locations
+                .entry(path.join("."))
+                .or_insert((index + 1, column));
+
+            let value = line[equals + 1..].trim_start();
+            for delimiter in ["\"\"\"", "'''"] {
+                if let Some(rest) = value.strip_prefix(delimiter) {
+                    if !rest.contains(delimiter) {
+                        open_string = Some(delimiter);
+                    }
+                }
+            }
+            if value.starts_with('[') {
+                bracket_depth = bracket_balance(value);
+            }
+        }
+        Self { locations }
+    }
+
+    /// Falls back to the closest enclosing key so every issue gets a position.
+    fn find(&self, path: &[&str]) -> Option<(usize, usize)> {
+        (1..=path.len())
+            .rev()
+            .find_map(|len| self.locations.get(&path[..len].join(".")).copied())
+    }
+}
+
+fn find_unquoted(line: &str, target: char) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (index, ch) in line.char_indices() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == target => return Some(index),
+            None => {}
+        }
+    }
+    None
+}
+
+fn bracket_balance(text: &str) -> i32 {
+    let mut quote: Option<char> = None;
+    let mut balance = 0;
+    for ch in text.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '#' => break,
+            None if ch == '[' => balance += 1,
+            None if ch == ']' => balance -= 1,
+            None => {}
+        }
+    }
+    balance
+}
+
+fn split_dotted_key(raw: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut rest = raw.trim();
+    while !rest.is_empty() {
+        let end = find_unquoted(rest, '.').unwrap_or(rest.len());
+        let part = rest[..end].trim();
+        parts.push(part.trim_matches(|ch| ch == '"' || ch == '\'').to_string());
+        rest = rest.get(end + 1..).unwrap_or_default();
+    }
+    parts
+}
+
+fn offset_to_line_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|text| text.chars().count())
+        .unwrap_or(0)
+        + 1;
+    (line, column)
+}
+
+struct Validator {
+    locations: KeyLocations,
+    issues: Vec<ConfigValidationIssue>,
+}
+
+impl Validator {
+    fn push(&mut self, severity: ConfigIssueSeverity, path: &[&str], message: String) {
+        let location = self.locations.find(path);
+        self.issues.push(ConfigValidationIssue {
+            severity,
+            message,
+            key_path: if path.is_empty() {
+                None
+            } else {
+                Some(path.join("."))
+            },
+            line: location.map(|(line, _)| line),
+            column: location.map(|(_, column)| column),
+        });
+    }
+
+    fn error(&mut self, path: &[&str], message: String) {
+        self.push(ConfigIssueSeverity::Error, path, message);
+    }
+
+    fn warning(&mut self, path: &[&str], message: String) {
+        self.push(ConfigIssueSeverity::Warning, path, message);
+    }
+
+    fn check(&mut self, path: &[&str], item: &Item, expect: Expect) {
+        let key = path.join(".");
+        let ok = match expect {
+            Expect::String | Expect::OneOf(_) => item.is_str(),
+            Expect::Bool => item.is_bool(),
+            Expect::Integer => item.is_integer(),
+            Expect::Number => item.is_integer() || item.is_float(),
+            Expect::StringArray => item
+                .as_array()
+                .is_some_and(|array| array.iter().all(Value::is_str)),
+            Expect::Table => item.is_table_like(),
+        };
+        if !ok {
+            let expected = match expect {
+                Expect::String | Expect::OneOf(_) => "a string",
+                Expect::Bool => "a boolean",
+                Expect::Integer => "an integer",
+                Expect::Number => "a number",
+                Expect::StringArray => "an array of strings",
+                Expect::Table => "a table",
+            };
+            self.error(
+                path,
+                format!("`{key}` must be {expected}, found {}", describe(item)),
+            );
+            return;
+        }
+        if let (Expect::OneOf(allowed), Some(found)) = (expect, item.as_str()) {
+            if !allowed.contains(&found) {
+                self.warning(
+                    path,
+                    format!(
+                        "`{key}` has unknown value \"{found}\"; expected one of {}",
+                        allowed.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+
+    fn check_known(
+        &mut self,
+        path: &[&str],
+        item: &Item,
+        known: &[&[(&str, Expect)]],
+        context: &str,
+    ) {
+        let key = path.last().copied().unwrap_or_default();
+        match known
+            .iter()
+            .flat_map(|keys| keys.iter())
+            .find(|(name, _)| *name == key)
+        {
+            Some((_, expect)) => {
+                self.check(path, item, *expect);
+            }
+            None => {
+                self.warning(path, format!("unknown key `{key}` in {context}"));
+            }
+        }
+    }
+
+    fn validate_root(&mut self, document: &Document) {
+        for (key, item) in document.iter() {
+            let path = [key];
+            match key {
+                "features" => self.validate_features(&path, item),
+                "agents" => self.validate_agents(item),
+                "mcp_servers" => self.validate_mcp_servers(item),
+                "profiles" => self.validate_profiles(item),
+                "projects" => self.validate_projects(item),
+                _ => self.check_known(&path, item, &[PROFILE_KEYS, TOP_LEVEL_KEYS], "config.toml"),
+            }
+        }
+
+        if let Some(profile) = document.get("profile").and_then(Item::as_str) {
+            let exists = document
+                .get("profiles")
+                .and_then(Item::as_table_like)
+                .is_some_and(|profiles| profiles.contains_key(profile));
+            // The profile may still be supplied on the command line or by a
+            // managed config layer, so this is not worth blocking a save over.
+            if !exists {
+                self.warning(
+                    &["profile"],
+                    format!("profile \"{profile}\" is not defined under [profiles]"),
+                );
+            }
+        }
+    }
+
+    fn validate_features(&mut self, path: &[&str], item: &Item) {
+        let Some(features) = item.as_table_like() else {
+            self.check(path, item, Expect::Table);
+            return;
+        };
+        for (name, value) in features.iter() {
+            let mut feature_path = path.to_vec();
+            feature_path.push(name);
+            self.check(&feature_path, value, Expect::Bool);
+            if !KNOWN_FEATURES.contains(&name) {
+                self.warning(&feature_path, format!("unknown feature flag `{name}`"));
+            }
+        }
+    }
+
+    fn validate_agents(&mut self, item: &Item) {
+        let Some(agents) = item.as_table_like() else {
+            self.check(&["agents"], item, Expect::Table);
+            return;
+        };
+        for (name, value) in agents.iter() {
+            let path = ["agents", name];
+            let bounds = match name {
+                "max_threads" => Some((MIN_AGENT_MAX_THREADS, MAX_AGENT_MAX_THREADS)),
+                "max_depth" => Some((MIN_AGENT_MAX_DEPTH, MAX_AGENT_MAX_DEPTH)),
+                _ => None,
+            };
+            if let Some((min, max)) = bounds {
+                self.check(&path, value, Expect::Integer);
+                // Codex only rejects values that don't fit its unsigned type;
+                // the bounds are the range this app's agent editor offers.
+                if let Some(found) = value.as_integer() {
+                    if found < 0 {
+                        self.error(
+                            &path,
+                            format!("`agents.{name}` must be a non-negative integer"),
+                        );
+                    } else if found < i64::from(min) || found > i64::from(max) {
+                        self.warning(
+                            &path,
+                            format!(
+                                "`agents.{name}` is outside the {min}-{max} range the agent editor supports"
+                            ),
+                        );
+                    }
+                }
+                continue;
+            }
+            let Some(role) = value.as_table_like() else {
+                self.error(
+                    &path,
+                    format!(
+                        "agent role `{name}` must be a table, found {}",
+                        describe(value)
+                    ),
+                );
+                continue;
+            };
+            for (key, role_value) in role.iter() {
+                self.check_known(
+                    &["agents", name, key],
+                    role_value,
+                    &[AGENT_ROLE_KEYS],
+                    "an agent role",
+                );
+            }
+        }
+    }
+
+    fn validate_mcp_servers(&mut self, item: &Item) {
+        let Some(servers) = item.as_table_like() else {
+            self.check(&["mcp_servers"], item, Expect::Table);
+            return;
+        };
+        for (name, value) in servers.iter() {
+            let Some(server) = value.as_table_like() else {
+                self.error(
+                    &["mcp_servers", name],
+                    format!("MCP server `{name}` must be a table"),
+                );
+                continue;
+            };
+            for (key, server_value) in server.iter() {
+                self.check_known(
+                    &["mcp_servers", name, key],
+                    server_value,
+                    &[MCP_SERVER_KEYS],
+                    "an MCP server",
+                );
+            }
+            match (server.contains_key("command"), server.contains_key("url")) {
+                (true, true) => self.error(
+                    &["mcp_servers", name],
+                    format!("MCP server `{name}` sets both `command` and `url`"),
+                ),
+                (false, false) => self.error(
+                    &["mcp_servers", name],
+                    format!("MCP server `{name}` needs either `command` or `url`"),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn validate_profiles(&mut self, item: &Item) {
+        let Some(profiles) = item.as_table_like() else {
+            self.check(&["profiles"], item, Expect::Table);
+            return;
+        };
+        for (name, value) in profiles.iter() {
+            let Some(profile) = value.as_table_like() else {
+                self.error(
+                    &["profiles", name],
+                    format!("profile `{name}` must be a table"),
+                );
+                continue;
+            };
+            for (key, profile_value) in profile.iter() {
+                let path = ["profiles", name, key];
+                if key == "features" {
+                    self.validate_features(&path, profile_value);
+                } else {
+                    self.check_known(&path, profile_value, &[PROFILE_KEYS], "a profile");
+                }
+            }
+        }
+    }
+
+    fn validate_projects(&mut self, item: &Item) {
+        let Some(projects) = item.as_table_like() else {
+            self.check(&["projects"], item, Expect::Table);
+            return;
+        };
+        for (project, value) in projects.iter() {
+            if let Some(trust_level) = value
+                .as_table_like()
+                .and_then(|table| table.get("trust_level"))
+            {
+                self.check(
+                    &["projects", project, "trust_level"],
+                    trust_level,
+                    Expect::OneOf(TRUST_LEVELS),
+                );
+            }
+        }
+    }
+}
+
+fn describe(item: &Item) -> &'static str {
+    match item {
+        Item::None => "nothing",
+        Item::Table(_) | Item::Value(Value::InlineTable(_)) => "a table",
+        Item::ArrayOfTables(_) => "an array of tables",
+        Item::Value(Value::String(_)) => "a string",
+        Item::Value(Value::Integer(_)) => "an integer",
+        Item::Value(Value::Float(_)) => "a float",
+        Item::Value(Value::Boolean(_)) => "a boolean",
+        Item::Value(Value::Datetime(_)) => "a datetime",
+        Item::Value(Value::Array(_)) => "an array",
+    }
+}
+
+/// Checks config.toml text against the codex config schema. Only problems
+/// codex itself would refuse to load (syntax, wrong value types, malformed
+/// MCP servers) are errors and mark the report invalid; unknown keys, app UI
+/// limits and cross-references are warnings.
+pub(crate) fn validate_config_toml(contents: &str) -> ConfigValidationReport {
+    let mut validator = Validator {
+        locations: KeyLocations::scan(contents),
+        issues: Vec::new(),
+    };
+    if !contents.trim().is_empty() {
+        match contents.parse::<Document>() {
+            Ok(document) => validator.validate_root(&document),
+            Err(err) => {
+                let location = err
+                    .span()
+                    .map(|span| offset_to_line_column(contents, span.start));
+                validator.issues.push(ConfigValidationIssue {
+                    severity: ConfigIssueSeverity::Error,
+                    message: err.message().trim().to_string(),
+                    key_path: None,
+                    line: location.map(|(line, _)| line),
+                    column: location.map(|(_, column)| column),
+                });
+            }
+        }
+    }
+    let mut issues = validator.issues;
+    issues.sort_by_key(|issue| (issue.line.unwrap_or(usize::MAX), issue.column));
+    ConfigValidationReport {
+        valid: !issues
+            .iter()
+            .any(|issue| issue.severity == ConfigIssueSeverity::Error),
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(
+        report: &'a ConfigValidationReport,
+        key_path: &str,
+    ) -> Option<&'a ConfigValidationIssue> {
+        report
+            .issues
+            .iter()
+            .find(|issue| issue.key_path.as_deref() == Some(key_path))
+    }
+
+    #[test]
+    fn validate_config_toml_reports_types_unknown_keys_and_locations() {
+        let contents = r#"model = "gpt-5-codex"
+approval_policy = "sometimes"
+modle = "typo"
+profile = "missing"
+instructions = """
+fake = 1
+"""
+
+[features]
+steer = "yes"
+
+[agents]
+max_threads = 40
+
+[mcp_servers.docs]
+args = ["--stdio"]
+
+[profiles.fast]
+model_reasoning_effort = 3
+"#;
+        let report = validate_config_toml(contents);
+        assert!(!report.valid);
+
+        let unknown = find(&report, "approval_policy").expect("enum warning");
+        assert_eq!(unknown.severity, ConfigIssueSeverity::Warning);
+        assert_eq!((unknown.line, unknown.column), (Some(2), Some(1)));
+
+        let typo = find(&report, "modle").expect("unknown key");
+        assert_eq!(typo.severity, ConfigIssueSeverity::Warning);
+        assert_eq!(typo.line, Some(3));
+
+        assert_eq!(
+            find(&report, "profile").map(|issue| issue.severity),
+            Some(ConfigIssueSeverity::Warning)
+        );
+        assert!(find(&report, "fake").is_none());
+
+        let feature = find(&report, "features.steer").expect("feature type");
+        assert_eq!(feature.severity, ConfigIssueSeverity::Error);
+        assert_eq!(feature.line, Some(10));
+
+        let threads = find(&report, "agents.max_threads").expect("bounds");
+        assert_eq!(threads.severity, ConfigIssueSeverity::Warning);
+        assert!(threads.message.contains("1-12"));
+        assert_eq!(threads.line, Some(13));
+
+        let server = find(&report, "mcp_servers.docs").expect("server");
+        assert!(server.message.contains("command"));
+        assert_eq!(server.line, Some(15));
+
+        let effort = find(&report, "profiles.fast.model_reasoning_effort").expect("type");
+        assert_eq!(effort.severity, ConfigIssueSeverity::Error);
+        assert_eq!(effort.line, Some(19));
+    }
+
+    #[test]
+    fn validate_config_toml_allows_saving_app_limits_and_unknown_profiles() {
+        let report = validate_config_toml(
+            "profile = \"work\"\n\n[agents]\nmax_threads = 32\nmax_depth = 0\n",
+        );
+        assert!(report.valid);
+        assert_eq!(report.issues.len(), 3);
+        assert!(report
+            .issues
+            .iter()
+            .all(|issue| issue.severity == ConfigIssueSeverity::Warning));
+
+        let negative = validate_config_toml("[agents]\nmax_threads = -1\n");
+        assert!(!negative.valid);
+    }
+
+    #[test]
+    fn validate_config_toml_reports_syntax_error_position() {
+        let report = validate_config_toml("model = \"gpt-5\"\nbroken = \n");
+        assert!(!report.valid);
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].line, Some(2));
+
+        let clean = validate_config_toml("model = \"gpt-5\"\n[features]\nsteer = true\n");
+        assert!(clean.valid);
+        assert!(clean.issues.is_empty());
+    }
+}
@@ -7,6 +7,7 @@ use crate::codex::home as codex_home;
 use crate::files::io::TextFileResponse;
 use crate::files::ops::{read_with_policy, write_with_policy};
 use crate::files::policy::{policy_for, FileKind, FileScope};
+use crate::shared::config_toml_core;
 use crate::types::WorkspaceEntry;
 
 fn resolve_default_codex_home() -> Result<PathBuf, String> {
@@ -58,6 +59,9 @@ pub(crate) async fn file_write_core(
     content: String,
 ) -> Result<(), String> {
     let policy = policy_for(scope, kind)?;
+    if kind == FileKind::Config {
+        config_toml_core::ensure_valid_config_toml(&content, "config.toml")?;
+    }
     let root = resolve_root_core(workspaces, scope, workspace_id.as_deref()).await?;
     write_with_policy(&root, policy, &content)
 }
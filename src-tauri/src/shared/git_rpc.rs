@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 pub(crate) const METHOD_GET_GIT_STATUS: &str = "get_git_status";
+pub(crate) const METHOD_GET_GIT_STATUS_AGGREGATE: &str = "get_git_status_aggregate";
 pub(crate) const METHOD_INIT_GIT_REPO: &str = "init_git_repo";
 pub(crate) const METHOD_CREATE_GITHUB_REPO: &str = "create_github_repo";
 pub(crate) const METHOD_STAGE_GIT_FILE: &str = "stage_git_file";
@@ -17,6 +18,8 @@ pub(crate) const METHOD_FETCH_GIT: &str = "fetch_git";
 pub(crate) const METHOD_SYNC_GIT: &str = "sync_git";
 pub(crate) const METHOD_LIST_GIT_ROOTS: &str = "list_git_roots";
 pub(crate) const METHOD_GET_GIT_DIFFS: &str = "get_git_diffs";
+pub(crate) const METHOD_GET_GIT_STATUS_PAGE: &str = "get_git_status_page";
+pub(crate) const METHOD_GET_GIT_FILE_DIFF: &str = "get_git_file_diff";
 pub(crate) const METHOD_GET_GIT_LOG: &str = "get_git_log";
 pub(crate) const METHOD_GET_GIT_COMMIT_DIFF: &str = "get_git_commit_diff";
 pub(crate) const METHOD_GET_GIT_REMOTE: &str = "get_git_remote";
@@ -47,6 +50,14 @@ pub(crate) struct WorkspaceIdRequest {
     pub(crate) workspace_id: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceRootRequest {
+    pub(crate) workspace_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) root: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct InitGitRepoRequest {
@@ -84,6 +95,8 @@ pub(crate) struct CreateGitHubRepoRequiredRequest {
 pub(crate) struct WorkspacePathRequest {
     pub(crate) workspace_id: String,
     pub(crate) path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) root: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -98,6 +111,30 @@ pub(crate) struct ListGitRootsRequest {
 pub(crate) struct GetGitLogRequest {
     pub(crate) workspace_id: String,
     pub(crate) limit: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) root: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetGitStatusPageRequest {
+    pub(crate) workspace_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) root: Option<String>,
+    pub(crate) offset: Option<u32>,
+    pub(crate) limit: Option<u32>,
+    #[serde(default)]
+    pub(crate) include_stats: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GetGitFileDiffRequest {
+    pub(crate) workspace_id: String,
+    pub(crate) path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) root: Option<String>,
+    pub(crate) max_lines: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -112,6 +149,8 @@ pub(crate) struct WorkspaceShaRequest {
 pub(crate) struct WorkspaceMessageRequest {
     pub(crate) workspace_id: String,
     pub(crate) message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) root: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -5,8 +5,9 @@ use serde_json::Value;
 use tokio::sync::Mutex;
 
 use crate::types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, WorkspaceEntry,
+    AppSettings, GitCommitDiff, GitFileDiff, GitFileStatus, GitHubIssueDetail,
+    GitHubIssuesResponse, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestsResponse, GitLogResponse, GitStatusPage, WorkspaceEntry,
 };
 
 #[path = "git_ui_core/commands.rs"]
@@ -19,6 +20,8 @@ mod diff;
 mod github;
 #[path = "git_ui_core/log.rs"]
 mod log;
+#[path = "git_ui_core/paged.rs"]
+mod paged;
 
 #[cfg(test)]
 #[path = "git_ui_core/tests.rs"]
@@ -35,11 +38,31 @@ pub(crate) fn collect_workspace_diff_core(repo_root: &Path) -> Result<String, St
     diff::collect_workspace_diff(repo_root)
 }
 
+pub(crate) fn head_commit_sha_core(repo_root: &Path) -> Result<String, String> {
+    diff::head_commit_sha(repo_root)
+}
+
+pub(crate) fn changed_files_since_commit_core(
+    repo_root: &Path,
+    base: Option<&str>,
+) -> Result<Vec<GitFileStatus>, String> {
+    diff::changed_files_since_commit(repo_root, base)
+}
+
 pub(crate) async fn get_git_status_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    root: Option<String>,
 ) -> Result<Value, String> {
-    diff::get_git_status_inner(workspaces, workspace_id).await
+    diff::get_git_status_inner(workspaces, workspace_id, root).await
+}
+
+pub(crate) async fn get_git_status_aggregate_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    depth: Option<usize>,
+) -> Result<Value, String> {
+    diff::get_git_status_aggregate_inner(workspaces, workspace_id, depth).await
 }
 
 pub(crate) async fn init_git_repo_core(
@@ -73,16 +96,49 @@ pub(crate) async fn get_git_diffs_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     app_settings: &Mutex<AppSettings>,
     workspace_id: String,
+    root: Option<String>,
 ) -> Result<Vec<GitFileDiff>, String> {
-    diff::get_git_diffs_inner(workspaces, app_settings, workspace_id).await
+    diff::get_git_diffs_inner(workspaces, app_settings, workspace_id, root).await
+}
+
+pub(crate) async fn get_git_status_page_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    root: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    include_stats: bool,
+) -> Result<GitStatusPage, String> {
+    paged::get_git_status_page_inner(workspaces, workspace_id, root, offset, limit, include_stats)
+        .await
+}
+
+pub(crate) async fn get_git_file_diff_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    path: String,
+    root: Option<String>,
+    max_lines: Option<usize>,
+) -> Result<GitFileDiff, String> {
+    paged::get_git_file_diff_inner(
+        workspaces,
+        app_settings,
+        workspace_id,
+        path,
+        root,
+        max_lines,
+    )
+    .await
 }
 
 pub(crate) async fn get_git_log_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     limit: Option<usize>,
+    root: Option<String>,
 ) -> Result<GitLogResponse, String> {
-    log::get_git_log_inner(workspaces, workspace_id, limit).await
+    log::get_git_log_inner(workspaces, workspace_id, limit, root).await
 }
 
 pub(crate) async fn get_git_commit_diff_core(
@@ -105,46 +161,52 @@ pub(crate) async fn stage_git_file_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     path: String,
+    root: Option<String>,
 ) -> Result<(), String> {
-    commands::stage_git_file_inner(workspaces, workspace_id, path).await
+    commands::stage_git_file_inner(workspaces, workspace_id, path, root).await
 }
 
 pub(crate) async fn stage_git_all_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    root: Option<String>,
 ) -> Result<(), String> {
-    commands::stage_git_all_inner(workspaces, workspace_id).await
+    commands::stage_git_all_inner(workspaces, workspace_id, root).await
 }
 
 pub(crate) async fn unstage_git_file_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     path: String,
+    root: Option<String>,
 ) -> Result<(), String> {
-    commands::unstage_git_file_inner(workspaces, workspace_id, path).await
+    commands::unstage_git_file_inner(workspaces, workspace_id, path, root).await
 }
 
 pub(crate) async fn revert_git_file_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     path: String,
+    root: Option<String>,
 ) -> Result<(), String> {
-    commands::revert_git_file_inner(workspaces, workspace_id, path).await
+    commands::revert_git_file_inner(workspaces, workspace_id, path, root).await
 }
 
 pub(crate) async fn revert_git_all_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    root: Option<String>,
 ) -> Result<(), String> {
-    commands::revert_git_all_inner(workspaces, workspace_id).await
+    commands::revert_git_all_inner(workspaces, workspace_id, root).await
 }
 
 pub(crate) async fn commit_git_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     message: String,
+    root: Option<String>,
 ) -> Result<(), String> {
-    commands::commit_git_inner(workspaces, workspace_id, message).await
+    commands::commit_git_inner(workspaces, workspace_id, message, root).await
 }
 
 pub(crate) async fn push_git_core(
@@ -182,6 +244,14 @@ pub(crate) async fn get_github_issues_core(
     github::get_github_issues_inner(workspaces, workspace_id).await
 }
 
+pub(crate) async fn get_github_issue_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    issue_number: u64,
+) -> Result<GitHubIssueDetail, String> {
+    github::get_github_issue_inner(workspaces, workspace_id, issue_number).await
+}
+
 pub(crate) async fn get_github_pull_requests_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -189,6 +259,15 @@ pub(crate) async fn get_github_pull_requests_core(
     github::get_github_pull_requests_inner(workspaces, workspace_id).await
 }
 
+/// Pull request title, body and conversation comments, shaped like an issue.
+pub(crate) async fn get_github_pull_request_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+) -> Result<GitHubIssueDetail, String> {
+    github::get_github_pull_request_inner(workspaces, workspace_id, pr_number).await
+}
+
 pub(crate) async fn get_github_pull_request_diff_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -13,7 +13,7 @@ use crate::shared::process_core::tokio_command;
 use crate::types::{BranchInfo, WorkspaceEntry};
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
-use super::context::workspace_entry_for_id;
+use super::context::{resolve_selected_git_root, workspace_entry_for_id};
 
 async fn run_git_command(repo_root: &Path, args: &[&str]) -> Result<(), String> {
     let git_bin = resolve_git_binary().map_err(|e| format!("Failed to run git: {e}"))?;
@@ -401,9 +401,10 @@ pub(super) async fn stage_git_file_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     path: String,
+    root: Option<String>,
 ) -> Result<(), String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
     for path in action_paths_for_file(&repo_root, &path) {
         run_git_command(&repo_root, &["add", "-A", "--", &path]).await?;
     }
@@ -413,9 +414,10 @@ pub(super) async fn stage_git_file_inner(
 pub(super) async fn stage_git_all_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    root: Option<String>,
 ) -> Result<(), String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
     run_git_command(&repo_root, &["add", "-A"]).await
 }
 
@@ -423,9 +425,10 @@ pub(super) async fn unstage_git_file_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     path: String,
+    root: Option<String>,
 ) -> Result<(), String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
     for path in action_paths_for_file(&repo_root, &path) {
         run_git_command(&repo_root, &["restore", "--staged", "--", &path]).await?;
     }
@@ -436,9 +439,10 @@ pub(super) async fn revert_git_file_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     path: String,
+    root: Option<String>,
 ) -> Result<(), String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
     for path in action_paths_for_file(&repo_root, &path) {
         if run_git_command(
             &repo_root,
@@ -457,9 +461,10 @@ pub(super) async fn revert_git_file_inner(
 pub(super) async fn revert_git_all_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    root: Option<String>,
 ) -> Result<(), String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
     run_git_command(
         &repo_root,
         &["restore", "--staged", "--worktree", "--", "."],
@@ -472,9 +477,10 @@ pub(super) async fn commit_git_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     message: String,
+    root: Option<String>,
 ) -> Result<(), String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
     run_git_command(&repo_root, &["commit", "-m", &message]).await
 }
 
@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 use tokio::sync::Mutex;
 
@@ -24,3 +24,36 @@ pub(super) async fn resolve_repo_root_for_workspace(
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     resolve_git_root(&entry)
 }
+
+pub(super) fn has_git_marker(path: &Path) -> bool {
+    let marker = path.join(".git");
+    marker.is_dir() || marker.is_file()
+}
+
+/// Resolves the repository an action should run in. `root` is a path relative
+/// to the workspace (as returned by `list_git_roots`); when it is missing the
+/// workspace's pinned `git_root` setting is used as before.
+pub(super) fn resolve_selected_git_root(
+    entry: &WorkspaceEntry,
+    root: Option<&str>,
+) -> Result<PathBuf, String> {
+    let root = root.map(str::trim).filter(|value| !value.is_empty());
+    let Some(root) = root else {
+        return resolve_git_root(entry);
+    };
+    let relative = Path::new(root);
+    let is_nested = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !is_nested {
+        return Err(format!("Invalid git root: {root}"));
+    }
+    let root_path = PathBuf::from(&entry.path).join(relative);
+    if !root_path.is_dir() {
+        return Err(format!("Git root not found: {root}"));
+    }
+    if !has_git_marker(&root_path) {
+        return Err(format!("Not a git repository: {root}"));
+    }
+    Ok(root_path)
+}
@@ -10,15 +10,16 @@ use serde_json::{json, Value};
 use tokio::sync::Mutex;
 
 use crate::git_utils::{
-    diff_patch_to_string, diff_stats_for_path, image_mime_type, resolve_git_root,
+    diff_patch_to_string, diff_stats_for_path, image_mime_type, list_git_roots as scan_git_roots,
+    resolve_git_root,
 };
 use crate::shared::process_core::std_command;
 use crate::types::{AppSettings, GitCommitDiff, GitFileDiff, GitFileStatus, WorkspaceEntry};
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
-use super::context::workspace_entry_for_id;
+use super::context::{has_git_marker, resolve_selected_git_root, workspace_entry_for_id};
 
-const INDEX_SKIP_WORKTREE_FLAG: u16 = 0x4000;
+pub(super) const INDEX_SKIP_WORKTREE_FLAG: u16 = 0x4000;
 const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
 const MAX_TEXT_DIFF_BYTES: usize = 2 * 1024 * 1024;
 
@@ -80,7 +81,7 @@ fn read_text_lines(path: &Path) -> Option<Vec<String>> {
     Some(split_lines_preserving_newlines(content.as_ref()))
 }
 
-fn status_for_index(status: Status) -> Option<&'static str> {
+pub(super) fn status_for_index(status: Status) -> Option<&'static str> {
     if status.contains(Status::INDEX_NEW) {
         Some("A")
     } else if status.contains(Status::INDEX_MODIFIED) {
@@ -96,7 +97,7 @@ fn status_for_index(status: Status) -> Option<&'static str> {
     }
 }
 
-fn status_for_workdir(status: Status) -> Option<&'static str> {
+pub(super) fn status_for_workdir(status: Status) -> Option<&'static str> {
     if status.contains(Status::WT_NEW) {
         Some("A")
     } else if status.contains(Status::WT_MODIFIED) {
@@ -331,13 +332,132 @@ pub(super) fn collect_workspace_diff(repo_root: &Path) -> Result<String, String>
     Ok(build_combined_diff(&repo, &diff))
 }
 
+pub(super) fn head_commit_sha(repo_root: &Path) -> Result<String, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let head = repo.head().map_err(|e| e.to_string())?;
+    let commit = head.peel_to_commit().map_err(|e| e.to_string())?;
+    Ok(commit.id().to_string())
+}
+
+/// Files that differ between `base` (HEAD when `None`) and the working tree,
+/// counting commits made since `base` as well as staged, unstaged and
+/// untracked changes.
+pub(super) fn changed_files_since_commit(
+    repo_root: &Path,
+    base: Option<&str>,
+) -> Result<Vec<GitFileStatus>, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let base_tree = match base {
+        Some(sha) => {
+            let oid = git2::Oid::from_str(sha).map_err(|e| e.to_string())?;
+            let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+            Some(commit.tree().map_err(|e| e.to_string())?)
+        }
+        None => repo.head().ok().and_then(|head| head.peel_to_tree().ok()),
+    };
+
+    let mut options = DiffOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(base_tree.as_ref(), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+
+    let mut files = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+            continue;
+        };
+        let (additions, deletions) = git2::Patch::from_diff(&diff, index)
+            .ok()
+            .flatten()
+            .and_then(|patch| patch.line_stats().ok())
+            .map(|(_, additions, deletions)| (additions as i64, deletions as i64))
+            .unwrap_or((0, 0));
+        let status = match delta.status() {
+            git2::Delta::Untracked => "A",
+            status => status_for_delta(status),
+        };
+        files.push(GitFileStatus {
+            path: normalize_git_path(&path.to_string_lossy()),
+            status: status.to_string(),
+            additions,
+            deletions,
+        });
+    }
+    Ok(files)
+}
+
 pub(super) async fn get_git_status_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
+    root: Option<String>,
 ) -> Result<Value, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
+    git_status_for_repo_root(&repo_root)
+}
+
+/// Collects status for every repository in the workspace: the workspace
+/// folder itself (when it is a repo) plus the nested roots found by
+/// `list_git_roots`. Roots that fail to open are reported with an `error`
+/// instead of failing the whole call, so non-git workspaces return an empty
+/// `roots` list.
+pub(super) async fn get_git_status_aggregate_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    depth: Option<usize>,
+) -> Result<Value, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let workspace_root = PathBuf::from(&entry.path);
+    let depth = depth.unwrap_or(2).clamp(1, 6);
+
+    tokio::task::spawn_blocking(move || {
+        let mut roots = Vec::new();
+        if has_git_marker(&workspace_root) {
+            roots.push(String::new());
+        }
+        roots.extend(scan_git_roots(&workspace_root, depth, 200));
+
+        let mut results = Vec::new();
+        let mut total_additions = 0i64;
+        let mut total_deletions = 0i64;
+        let mut changed_files = 0usize;
+        for root in roots {
+            let repo_root = if root.is_empty() {
+                workspace_root.clone()
+            } else {
+                workspace_root.join(&root)
+            };
+            match git_status_for_repo_root(&repo_root) {
+                Ok(mut status) => {
+                    total_additions += status["totalAdditions"].as_i64().unwrap_or(0);
+                    total_deletions += status["totalDeletions"].as_i64().unwrap_or(0);
+                    changed_files += status["files"].as_array().map_or(0, Vec::len);
+                    if let Some(object) = status.as_object_mut() {
+                        object.insert("root".to_string(), Value::String(root));
+                    }
+                    results.push(status);
+                }
+                Err(error) => results.push(json!({ "root": root, "error": error })),
+            }
+        }
+
+        Ok(json!({
+            "roots": results,
+            "changedFiles": changed_files,
+            "totalAdditions": total_additions,
+            "totalDeletions": total_deletions,
+        }))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+fn git_status_for_repo_root(repo_root: &Path) -> Result<Value, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
 
     let branch_name = repo
         .head()
@@ -462,13 +582,115 @@ pub(super) async fn get_git_status_inner(
     }))
 }
 
+/// Builds the file diff for one delta of a HEAD-to-workdir diff, including
+/// the full old/new contents used for expanded context and image previews.
+pub(super) fn workdir_file_diff(
+    repo: &Repository,
+    repo_root: &Path,
+    head_tree: Option<&git2::Tree>,
+    diff: &git2::Diff,
+    index: usize,
+) -> Option<GitFileDiff> {
+    let delta = diff.get_delta(index)?;
+    let old_path = delta.old_file().path();
+    let new_path = delta.new_file().path();
+    let display_path = new_path.or(old_path)?;
+    let old_path_str = old_path.map(|path| path.to_string_lossy());
+    let new_path_str = new_path.map(|path| path.to_string_lossy());
+    let display_path_str = display_path.to_string_lossy();
+    let normalized_path = normalize_git_path(&display_path_str);
+    let old_image_mime = old_path_str.as_deref().and_then(image_mime_type);
+    let new_image_mime = new_path_str.as_deref().and_then(image_mime_type);
+    let is_image = old_image_mime.is_some() || new_image_mime.is_some();
+    let is_deleted = delta.status() == git2::Delta::Deleted;
+    let is_added = delta.status() == git2::Delta::Added;
+
+    let old_lines = if !is_added {
+        head_tree
+            .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
+            .and_then(|entry| repo.find_blob(entry.id()).ok())
+            .and_then(blob_to_lines)
+    } else {
+        None
+    };
+
+    let new_lines = if !is_deleted {
+        match new_path {
+            Some(path) => {
+                let full_path = repo_root.join(path);
+                read_text_lines(&full_path)
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    if is_image {
+        let old_image_data = if !is_added && old_image_mime.is_some() {
+            head_tree
+                .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
+                .and_then(|entry| repo.find_blob(entry.id()).ok())
+                .and_then(blob_to_base64)
+        } else {
+            None
+        };
+
+        let new_image_data = if !is_deleted && new_image_mime.is_some() {
+            match new_path {
+                Some(path) => {
+                    let full_path = repo_root.join(path);
+                    read_image_base64(&full_path)
+                }
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        return Some(GitFileDiff {
+            path: normalized_path,
+            diff: String::new(),
+            old_lines: None,
+            new_lines: None,
+            is_binary: true,
+            is_image: true,
+            old_image_data,
+            new_image_data,
+            old_image_mime: old_image_mime.map(str::to_string),
+            new_image_mime: new_image_mime.map(str::to_string),
+            truncated: false,
+        });
+    }
+
+    let mut patch = git2::Patch::from_diff(diff, index).ok()??;
+    let content = diff_patch_to_string(&mut patch).ok()?;
+    if content.trim().is_empty() {
+        return None;
+    }
+    Some(GitFileDiff {
+        path: normalized_path,
+        diff: content,
+        old_lines,
+        new_lines,
+        is_binary: false,
+        is_image: false,
+        old_image_data: None,
+        new_image_data: None,
+        old_image_mime: None,
+        new_image_mime: None,
+        truncated: false,
+    })
+}
+
 pub(super) async fn get_git_diffs_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     app_settings: &Mutex<AppSettings>,
     workspace_id: String,
+    root: Option<String>,
 ) -> Result<Vec<GitFileDiff>, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
     let ignore_whitespace_changes = {
         let settings = app_settings.lock().await;
         settings.git_diff_ignore_whitespace_changes
@@ -502,111 +724,18 @@ pub(super) async fn get_git_diffs_inner(
 
         let mut results = Vec::new();
         for (index, delta) in diff.deltas().enumerate() {
-            let old_path = delta.old_file().path();
-            let new_path = delta.new_file().path();
-            let display_path = new_path.or(old_path);
+            let display_path = delta.new_file().path().or_else(|| delta.old_file().path());
             let Some(display_path) = display_path else {
                 continue;
             };
             if should_skip_ignored_path_with_cache(&repo, display_path, ignored_paths.as_ref()) {
                 continue;
             }
-            let old_path_str = old_path.map(|path| path.to_string_lossy());
-            let new_path_str = new_path.map(|path| path.to_string_lossy());
-            let display_path_str = display_path.to_string_lossy();
-            let normalized_path = normalize_git_path(&display_path_str);
-            let old_image_mime = old_path_str.as_deref().and_then(image_mime_type);
-            let new_image_mime = new_path_str.as_deref().and_then(image_mime_type);
-            let is_image = old_image_mime.is_some() || new_image_mime.is_some();
-            let is_deleted = delta.status() == git2::Delta::Deleted;
-            let is_added = delta.status() == git2::Delta::Added;
-
-            let old_lines = if !is_added {
-                head_tree
-                    .as_ref()
-                    .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
-                    .and_then(|entry| repo.find_blob(entry.id()).ok())
-                    .and_then(blob_to_lines)
-            } else {
-                None
-            };
-
-            let new_lines = if !is_deleted {
-                match new_path {
-                    Some(path) => {
-                        let full_path = repo_root.join(path);
-                        read_text_lines(&full_path)
-                    }
-                    None => None,
-                }
-            } else {
-                None
-            };
-
-            if is_image {
-                let old_image_data = if !is_added && old_image_mime.is_some() {
-                    head_tree
-                        .as_ref()
-                        .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
-                        .and_then(|entry| repo.find_blob(entry.id()).ok())
-                        .and_then(blob_to_base64)
-                } else {
-                    None
-                };
-
-                let new_image_data = if !is_deleted && new_image_mime.is_some() {
-                    match new_path {
-                        Some(path) => {
-                            let full_path = repo_root.join(path);
-                            read_image_base64(&full_path)
-                        }
-                        None => None,
-                    }
-                } else {
-                    None
-                };
-
-                results.push(GitFileDiff {
-                    path: normalized_path,
-                    diff: String::new(),
-                    old_lines: None,
-                    new_lines: None,
-                    is_binary: true,
-                    is_image: true,
-                    old_image_data,
-                    new_image_data,
-                    old_image_mime: old_image_mime.map(str::to_string),
-                    new_image_mime: new_image_mime.map(str::to_string),
-                });
-                continue;
-            }
-
-            let patch = match git2::Patch::from_diff(&diff, index) {
-                Ok(patch) => patch,
-                Err(_) => continue,
-            };
-            let Some(mut patch) = patch else {
-                continue;
-            };
-            let content = match diff_patch_to_string(&mut patch) {
-                Ok(content) => content,
-                Err(_) => continue,
-            };
-            if content.trim().is_empty() {
-                continue;
+            if let Some(file_diff) =
+                workdir_file_diff(&repo, &repo_root, head_tree.as_ref(), &diff, index)
+            {
+                results.push(file_diff);
             }
-            results.push(GitFileDiff {
-                path: normalized_path,
-                diff: content,
-                old_lines,
-                new_lines,
-                is_binary: false,
-                is_image: false,
-                old_image_data: None,
-                new_image_data: None,
-                old_image_mime: None,
-                new_image_mime: None,
-            });
         }
 
         Ok(results)
@@ -7,8 +7,8 @@ use tokio::sync::Mutex;
 use crate::git_utils::{parse_github_repo, resolve_git_root};
 use crate::shared::process_core::tokio_command;
 use crate::types::{
-    GitHubIssue, GitHubIssuesResponse, GitHubPullRequest, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, WorkspaceEntry,
+    GitHubIssue, GitHubIssueDetail, GitHubIssuesResponse, GitHubPullRequest,
+    GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestsResponse, WorkspaceEntry,
 };
 use crate::utils::normalize_git_path;
 
@@ -330,13 +330,20 @@ pub(super) async fn get_github_pull_request_comments_inner(
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
     let repo_name = github_repo_from_path(&repo_root)?;
+    fetch_issue_comments(&repo_root, &repo_name, pr_number).await
+}
 
-    let comments_endpoint = format!("/repos/{repo_name}/issues/{pr_number}/comments?per_page=30");
+async fn fetch_issue_comments(
+    repo_root: &Path,
+    repo_name: &str,
+    number: u64,
+) -> Result<Vec<GitHubPullRequestComment>, String> {
+    let comments_endpoint = format!("/repos/{repo_name}/issues/{number}/comments?per_page=30");
     let jq_filter = r#"[.[] | {id, body, createdAt: .created_at, url: .html_url, author: (if .user then {login: .user.login} else null end)}]"#;
 
     let output = tokio_command("gh")
         .args(["api", &comments_endpoint, "--jq", jq_filter])
-        .current_dir(&repo_root)
+        .current_dir(repo_root)
         .output()
         .await
         .map_err(|e| format!("Failed to run gh: {e}"))?;
@@ -354,3 +361,81 @@ pub(super) async fn get_github_pull_request_comments_inner(
 
     Ok(comments)
 }
+
+pub(super) async fn get_github_issue_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    issue_number: u64,
+) -> Result<GitHubIssueDetail, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let issue_number_text = issue_number.to_string();
+
+    let output = tokio_command("gh")
+        .args([
+            "issue",
+            "view",
+            &issue_number_text,
+            "--repo",
+            &repo_name,
+            "--json",
+            "number,title,url,state,body,author",
+        ])
+        .current_dir(&repo_root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+
+    if !output.status.success() {
+        return Err(command_failure_detail(
+            &output.stdout,
+            &output.stderr,
+            "GitHub CLI command failed.",
+        ));
+    }
+
+    let mut issue: GitHubIssueDetail =
+        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    issue.comments = fetch_issue_comments(&repo_root, &repo_name, issue_number).await?;
+    Ok(issue)
+}
+
+pub(super) async fn get_github_pull_request_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    pr_number: u64,
+) -> Result<GitHubIssueDetail, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo_name = github_repo_from_path(&repo_root)?;
+    let pr_number_text = pr_number.to_string();
+
+    let output = tokio_command("gh")
+        .args([
+            "pr",
+            "view",
+            &pr_number_text,
+            "--repo",
+            &repo_name,
+            "--json",
+            "number,title,url,state,body,author",
+        ])
+        .current_dir(&repo_root)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+
+    if !output.status.success() {
+        return Err(command_failure_detail(
+            &output.stdout,
+            &output.stderr,
+            "GitHub CLI command failed.",
+        ));
+    }
+
+    let mut pull_request: GitHubIssueDetail =
+        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+    pull_request.comments = fetch_issue_comments(&repo_root, &repo_name, pr_number).await?;
+    Ok(pull_request)
+}
@@ -6,15 +6,16 @@ use tokio::sync::Mutex;
 use crate::git_utils::{commit_to_entry, resolve_git_root};
 use crate::types::{GitLogResponse, WorkspaceEntry};
 
-use super::context::workspace_entry_for_id;
+use super::context::{resolve_selected_git_root, workspace_entry_for_id};
 
 pub(super) async fn get_git_log_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     limit: Option<usize>,
+    root: Option<String>,
 ) -> Result<GitLogResponse, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
     let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
     let max_items = limit.unwrap_or(40);
     let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
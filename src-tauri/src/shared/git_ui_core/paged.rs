@@ -0,0 +1,482 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use git2::{DiffOptions, Oid, Repository, StatusOptions};
+use tokio::sync::Mutex;
+
+use crate::git_utils::diff_stats_for_path;
+use crate::types::{AppSettings, GitFileDiff, GitStatusPage, GitStatusPageEntry, WorkspaceEntry};
+use crate::utils::normalize_git_path;
+
+use super::context::{resolve_selected_git_root, workspace_entry_for_id};
+use super::diff::{
+    collect_ignored_paths_with_git, should_skip_ignored_path_with_cache, status_for_index,
+    status_for_workdir, workdir_file_diff, INDEX_SKIP_WORKTREE_FLAG,
+};
+
+const DEFAULT_STATUS_PAGE_SIZE: usize = 200;
+const MAX_STATUS_PAGE_SIZE: usize = 2000;
+const DEFAULT_FILE_DIFF_MAX_LINES: usize = 5000;
+const MAX_FILE_DIFF_BYTES: usize = 512 * 1024;
+const MAX_CACHED_FILES_PER_REPO: usize = 4096;
+/// Upper bound on how long a status snapshot is served without a rescan, for
+/// edits the workdir signal can't see (e.g. inside pre-existing untracked
+/// directories that hold no other changes, or clean files in repos past
+/// `MAX_SIGNAL_INDEX_ENTRIES`).
+const STATUS_CACHE_MAX_AGE: Duration = Duration::from_secs(30);
+/// The workdir signal stats every indexed file only up to this many index
+/// entries. That costs one `lstat` per entry per poll, which stays well under
+/// a `git status` rescan at this size; larger repos skip the sweep and pick up
+/// edits to clean files through `STATUS_CACHE_MAX_AGE` instead.
+const MAX_SIGNAL_INDEX_ENTRIES: usize = 5_000;
+
+/// Committed and staged state of a repository. Every cached entry for the
+/// repo is dropped as soon as HEAD or the index moves.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct RepoStamp {
+    head: Option<Oid>,
+    index: Option<[u8; 20]>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct FileStamp {
+    len: u64,
+    modified_ns: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct FileDiffParams {
+    ignore_whitespace_changes: bool,
+    max_lines: usize,
+}
+
+#[derive(Default)]
+struct CachedFile {
+    stamp: Option<FileStamp>,
+    stats: Option<(i64, i64)>,
+    diff: Option<(FileDiffParams, GitFileDiff)>,
+}
+
+struct RepoCache {
+    stamp: RepoStamp,
+    files: HashMap<String, CachedFile>,
+}
+
+static GIT_FILE_CACHE: OnceLock<StdMutex<HashMap<PathBuf, RepoCache>>> = OnceLock::new();
+
+/// Full, sorted status listing of a repo. Pages are sliced out of it until
+/// HEAD, the index or the workdir signal changes.
+struct StatusSnapshot {
+    stamp: RepoStamp,
+    workdir_signal: u64,
+    computed_at: Instant,
+    branch_name: String,
+    cache_key: String,
+    entries: Vec<(String, GitStatusPageEntry)>,
+}
+
+static GIT_STATUS_CACHE: OnceLock<StdMutex<HashMap<PathBuf, Arc<StatusSnapshot>>>> =
+    OnceLock::new();
+
+/// The index file ends with a hash of its own contents, which changes on
+/// every stage/unstage/commit and is cheap to read.
+fn index_checksum(repo: &Repository) -> Option<[u8; 20]> {
+    let mut file = fs::File::open(repo.path().join("index")).ok()?;
+    file.seek(SeekFrom::End(-20)).ok()?;
+    let mut checksum = [0u8; 20];
+    file.read_exact(&mut checksum).ok()?;
+    Some(checksum)
+}
+
+fn repo_stamp(repo: &Repository) -> RepoStamp {
+    RepoStamp {
+        head: repo.head().ok().and_then(|head| head.target()),
+        index: index_checksum(repo),
+    }
+}
+
+fn file_stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    let modified_ns = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+    Some(FileStamp {
+        len: metadata.len(),
+        modified_ns,
+    })
+}
+
+/// Cheap stand-in for a filesystem watcher: stats every file currently in the
+/// status listing, every indexed file while the index holds at most
+/// `MAX_SIGNAL_INDEX_ENTRIES`, and the directories holding them. Content edits
+/// move file stamps; created, deleted or renamed files move the stamp of their
+/// directory. HEAD and index changes are covered by `RepoStamp`.
+fn workdir_signal(
+    repo: &Repository,
+    repo_root: &Path,
+    entries: &[(String, GitStatusPageEntry)],
+) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    let mut dirs = BTreeSet::new();
+    dirs.insert(repo_root.to_path_buf());
+    let mut stamp_path = |relative: &Path| {
+        let path = repo_root.join(relative);
+        file_stamp(&path).hash(&mut hasher);
+        let mut parent = path.parent();
+        while let Some(dir) = parent {
+            if !dir.starts_with(repo_root) || !dirs.insert(dir.to_path_buf()) {
+                break;
+            }
+            parent = dir.parent();
+        }
+    };
+    if let Some(index) = repo
+        .index()
+        .ok()
+        .filter(|index| index.len() <= MAX_SIGNAL_INDEX_ENTRIES)
+    {
+        for entry in index.iter() {
+            stamp_path(Path::new(String::from_utf8_lossy(&entry.path).as_ref()));
+        }
+    }
+    for (raw_path, _) in entries {
+        stamp_path(Path::new(raw_path));
+    }
+    for dir in &dirs {
+        dir.hash(&mut hasher);
+        file_stamp(dir).hash(&mut hasher);
+    }
+    file_stamp(&repo.path().join("info").join("exclude")).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Runs `f` against the cache slot for `path`, resetting the slot when the
+/// repo stamp or the working-tree file changed since it was filled.
+fn with_cached_file<T>(
+    repo_root: &Path,
+    repo_stamp: &RepoStamp,
+    path: &str,
+    file_stamp: Option<FileStamp>,
+    f: impl FnOnce(&mut CachedFile) -> T,
+) -> T {
+    let cache = GIT_FILE_CACHE.get_or_init(|| StdMutex::new(HashMap::new()));
+    let mut cache = cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let repo_cache = cache
+        .entry(repo_root.to_path_buf())
+        .or_insert_with(|| RepoCache {
+            stamp: repo_stamp.clone(),
+            files: HashMap::new(),
+        });
+    if repo_cache.stamp != *repo_stamp {
+        repo_cache.stamp = repo_stamp.clone();
+        repo_cache.files.clear();
+    }
+    if repo_cache.files.len() >= MAX_CACHED_FILES_PER_REPO && !repo_cache.files.contains_key(path) {
+        repo_cache.files.clear();
+    }
+    let file = repo_cache.files.entry(path.to_string()).or_default();
+    if file.stamp != file_stamp {
+        *file = CachedFile {
+            stamp: file_stamp,
+            ..CachedFile::default()
+        };
+    }
+    f(file)
+}
+
+/// Cuts `text` at a line boundary once it exceeds either cap. Returns `None`
+/// when the text already fits.
+fn truncate_text(text: &str, max_lines: usize, max_bytes: usize) -> Option<String> {
+    let mut end = 0usize;
+    for (count, line) in text.split_inclusive('\n').enumerate() {
+        if count >= max_lines || end + line.len() > max_bytes {
+            return Some(text[..end].to_string());
+        }
+        end += line.len();
+    }
+    None
+}
+
+fn cap_file_diff(mut file_diff: GitFileDiff, max_lines: usize) -> GitFileDiff {
+    let mut truncated = false;
+    if let Some(diff) = truncate_text(&file_diff.diff, max_lines, MAX_FILE_DIFF_BYTES) {
+        file_diff.diff = diff;
+        truncated = true;
+    }
+    // Partial file contents would render misleading context, so oversized
+    // sides are dropped entirely and the client falls back to the patch.
+    for lines in [&mut file_diff.old_lines, &mut file_diff.new_lines] {
+        if lines.as_ref().is_some_and(|lines| lines.len() > max_lines) {
+            *lines = None;
+            truncated = true;
+        }
+    }
+    file_diff.truncated = truncated;
+    file_diff
+}
+
+fn collect_status_snapshot(
+    repo: &Repository,
+    repo_root: &Path,
+    stamp: RepoStamp,
+) -> Result<StatusSnapshot, String> {
+    let branch_name = repo
+        .head()
+        .ok()
+        .and_then(|head| head.shorthand().map(|s| s.to_string()))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let mut status_options = StatusOptions::new();
+    status_options
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .renames_head_to_index(true)
+        .renames_index_to_workdir(true)
+        .include_ignored(false);
+    let statuses = repo
+        .statuses(Some(&mut status_options))
+        .map_err(|e| e.to_string())?;
+    let status_paths: Vec<PathBuf> = statuses
+        .iter()
+        .filter_map(|entry| entry.path().map(PathBuf::from))
+        .filter(|path| !path.as_os_str().is_empty())
+        .collect();
+    let ignored_paths = collect_ignored_paths_with_git(repo, &status_paths);
+    let index = repo.index().ok();
+
+    let mut entries: Vec<(String, GitStatusPageEntry)> = Vec::new();
+    for entry in statuses.iter() {
+        let path = entry.path().unwrap_or("");
+        if path.is_empty() {
+            continue;
+        }
+        if should_skip_ignored_path_with_cache(repo, Path::new(path), ignored_paths.as_ref()) {
+            continue;
+        }
+        if let Some(index) = index.as_ref() {
+            if let Some(entry) = index.get_path(Path::new(path), 0) {
+                if entry.flags_extended & INDEX_SKIP_WORKTREE_FLAG != 0 {
+                    continue;
+                }
+            }
+        }
+        let status = entry.status();
+        let index_status = status_for_index(status);
+        let workdir_status = status_for_workdir(status);
+        let Some(status_str) = workdir_status.or(index_status) else {
+            continue;
+        };
+        entries.push((
+            path.to_string(),
+            GitStatusPageEntry {
+                path: normalize_git_path(path),
+                status: status_str.to_string(),
+                staged: index_status.is_some(),
+                unstaged: workdir_status.is_some(),
+                additions: None,
+                deletions: None,
+            },
+        ));
+    }
+    entries.sort_by(|a, b| a.1.path.cmp(&b.1.path));
+
+    let mut hasher = DefaultHasher::new();
+    stamp.hash(&mut hasher);
+    for (raw_path, entry) in &entries {
+        entry.path.hash(&mut hasher);
+        entry.status.hash(&mut hasher);
+        entry.staged.hash(&mut hasher);
+        file_stamp(&repo_root.join(raw_path)).hash(&mut hasher);
+    }
+    let cache_key = format!("{:016x}", hasher.finish());
+    let workdir_signal = workdir_signal(repo, repo_root, &entries);
+
+    Ok(StatusSnapshot {
+        stamp,
+        workdir_signal,
+        computed_at: Instant::now(),
+        branch_name,
+        cache_key,
+        entries,
+    })
+}
+
+/// Serves the status listing from cache while HEAD, the index checksum and
+/// the workdir signal are unchanged, so polling doesn't rerun `git status`.
+fn cached_status_snapshot(
+    repo: &Repository,
+    repo_root: &Path,
+) -> Result<Arc<StatusSnapshot>, String> {
+    let stamp = repo_stamp(repo);
+    let cache = GIT_STATUS_CACHE.get_or_init(|| StdMutex::new(HashMap::new()));
+    let cached = cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(repo_root)
+        .cloned();
+    if let Some(snapshot) = cached {
+        if snapshot.stamp == stamp
+            && snapshot.computed_at.elapsed() < STATUS_CACHE_MAX_AGE
+            && snapshot.workdir_signal == workdir_signal(repo, repo_root, &snapshot.entries)
+        {
+            return Ok(snapshot);
+        }
+    }
+    let snapshot = Arc::new(collect_status_snapshot(repo, repo_root, stamp)?);
+    cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(repo_root.to_path_buf(), Arc::clone(&snapshot));
+    Ok(snapshot)
+}
+
+fn status_page(
+    repo_root: &Path,
+    offset: usize,
+    limit: usize,
+    include_stats: bool,
+) -> Result<GitStatusPage, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let snapshot = cached_status_snapshot(&repo, repo_root)?;
+    let stamp = snapshot.stamp.clone();
+
+    let total = snapshot.entries.len();
+    let mut page: Vec<(String, GitStatusPageEntry)> = snapshot
+        .entries
+        .iter()
+        .skip(offset)
+        .take(limit)
+        .cloned()
+        .collect();
+    if include_stats {
+        let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+        for (raw_path, entry) in page.iter_mut() {
+            let current_stamp = file_stamp(&repo_root.join(raw_path.as_str()));
+            let cached = with_cached_file(repo_root, &stamp, raw_path, current_stamp, |file| {
+                file.stats
+            });
+            let (additions, deletions) = match cached {
+                Some(stats) => stats,
+                None => {
+                    let stats = diff_stats_for_path(
+                        &repo,
+                        head_tree.as_ref(),
+                        raw_path,
+                        entry.staged,
+                        entry.unstaged,
+                    )
+                    .unwrap_or((0, 0));
+                    with_cached_file(repo_root, &stamp, raw_path, current_stamp, |file| {
+                        file.stats = Some(stats)
+                    });
+                    stats
+                }
+            };
+            entry.additions = Some(additions);
+            entry.deletions = Some(deletions);
+        }
+    }
+
+    Ok(GitStatusPage {
+        branch_name: snapshot.branch_name.clone(),
+        cache_key: snapshot.cache_key.clone(),
+        total,
+        offset,
+        has_more: offset.saturating_add(page.len()) < total,
+        files: page.into_iter().map(|(_, entry)| entry).collect(),
+    })
+}
+
+fn file_diff(repo_root: &Path, path: &str, params: FileDiffParams) -> Result<GitFileDiff, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let stamp = repo_stamp(&repo);
+    let current_stamp = file_stamp(&repo_root.join(path));
+    let cached = with_cached_file(repo_root, &stamp, path, current_stamp, |file| {
+        file.diff
+            .as_ref()
+            .filter(|(cached_params, _)| *cached_params == params)
+            .map(|(_, diff)| diff.clone())
+    });
+    if let Some(cached) = cached {
+        return Ok(cached);
+    }
+
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let mut options = DiffOptions::new();
+    options
+        .pathspec(path)
+        .disable_pathspec_match(true)
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true)
+        .ignore_whitespace_change(params.ignore_whitespace_changes);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+    let file_diff = (0..diff.deltas().len())
+        .find_map(|index| workdir_file_diff(&repo, repo_root, head_tree.as_ref(), &diff, index))
+        .ok_or_else(|| format!("No changes for {path}"))?;
+    let file_diff = cap_file_diff(file_diff, params.max_lines);
+
+    with_cached_file(repo_root, &stamp, path, current_stamp, |file| {
+        file.diff = Some((params, file_diff.clone()))
+    });
+    Ok(file_diff)
+}
+
+/// Cheap status listing for large repos: no per-file line stats unless
+/// `include_stats` is set, and then only for the requested page.
+pub(super) async fn get_git_status_page_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    root: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    include_stats: bool,
+) -> Result<GitStatusPage, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
+    let offset = offset.unwrap_or(0);
+    let limit = limit
+        .unwrap_or(DEFAULT_STATUS_PAGE_SIZE)
+        .clamp(1, MAX_STATUS_PAGE_SIZE);
+    tokio::task::spawn_blocking(move || status_page(&repo_root, offset, limit, include_stats))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Diff for a single working-tree file, capped by `max_lines` and a byte
+/// limit. Results are reused until HEAD, the index or the file changes.
+pub(super) async fn get_git_file_diff_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    path: String,
+    root: Option<String>,
+    max_lines: Option<usize>,
+) -> Result<GitFileDiff, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_selected_git_root(&entry, root.as_deref())?;
+    let ignore_whitespace_changes = {
+        let settings = app_settings.lock().await;
+        settings.git_diff_ignore_whitespace_changes
+    };
+    let params = FileDiffParams {
+        ignore_whitespace_changes,
+        max_lines: max_lines.unwrap_or(DEFAULT_FILE_DIFF_MAX_LINES).max(1),
+    };
+    tokio::task::spawn_blocking(move || file_diff(&repo_root, &path, params))
+        .await
+        .map_err(|e| e.to_string())?
+}
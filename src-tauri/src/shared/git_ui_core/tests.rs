@@ -10,7 +10,9 @@ use tokio::sync::Mutex;
 use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
 
 use super::commands;
+use super::context;
 use super::diff;
+use super::paged;
 
 fn create_temp_repo() -> (PathBuf, Repository) {
     let root = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
@@ -155,7 +157,11 @@ fn get_git_status_omits_global_ignored_paths() {
 
     let runtime = Runtime::new().expect("create tokio runtime");
     let status = runtime
-        .block_on(diff::get_git_status_inner(&workspaces, "w1".to_string()))
+        .block_on(diff::get_git_status_inner(
+            &workspaces,
+            "w1".to_string(),
+            None,
+        ))
         .expect("get git status");
 
     let has_ignored = status
@@ -217,6 +223,7 @@ fn get_git_diffs_omits_global_ignored_paths() {
             &workspaces,
             &app_settings,
             "w1".to_string(),
+            None,
         ))
         .expect("get git diffs");
 
@@ -435,3 +442,274 @@ fn collect_ignored_paths_with_git_handles_large_ignored_output() {
 
     assert_eq!(ignored_paths.len(), total);
 }
+
+#[test]
+fn get_git_status_aggregate_covers_nested_repos_in_non_git_workspace() {
+    let root = std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+    for name in ["frontend", "backend"] {
+        let repo_root = root.join(name);
+        fs::create_dir_all(&repo_root).expect("create nested repo root");
+        Repository::init(&repo_root).expect("init nested repo");
+        fs::write(repo_root.join("change.txt"), "change\n").expect("write change");
+    }
+
+    let workspace = WorkspaceEntry {
+        id: "w1".to_string(),
+        name: "w1".to_string(),
+        path: root.to_string_lossy().to_string(),
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let mut entries = HashMap::new();
+    entries.insert("w1".to_string(), workspace);
+    let workspaces = Mutex::new(entries);
+
+    let runtime = Runtime::new().expect("create tokio runtime");
+    let aggregate = runtime
+        .block_on(diff::get_git_status_aggregate_inner(
+            &workspaces,
+            "w1".to_string(),
+            None,
+        ))
+        .expect("get aggregate status");
+    let mut roots: Vec<&str> = aggregate["roots"]
+        .as_array()
+        .expect("roots")
+        .iter()
+        .filter_map(|entry| entry["root"].as_str())
+        .collect();
+    roots.sort();
+    assert_eq!(roots, vec!["backend", "frontend"]);
+    assert_eq!(aggregate["changedFiles"].as_u64(), Some(2));
+
+    let status = runtime
+        .block_on(diff::get_git_status_inner(
+            &workspaces,
+            "w1".to_string(),
+            Some("backend".to_string()),
+        ))
+        .expect("get scoped status");
+    assert_eq!(status["files"].as_array().map(Vec::len), Some(1));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn resolve_selected_git_root_rejects_paths_outside_workspace() {
+    let (root, _repo) = create_temp_repo();
+    let workspace = WorkspaceEntry {
+        id: "w1".to_string(),
+        name: "w1".to_string(),
+        path: root.to_string_lossy().to_string(),
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+
+    assert_eq!(
+        context::resolve_selected_git_root(&workspace, None).expect("default root"),
+        root
+    );
+    assert!(context::resolve_selected_git_root(&workspace, Some("../other")).is_err());
+    assert!(context::resolve_selected_git_root(&workspace, Some("/tmp")).is_err());
+    assert!(context::resolve_selected_git_root(&workspace, Some("missing")).is_err());
+}
+
+fn workspaces_for_root(root: &Path) -> Mutex<HashMap<String, WorkspaceEntry>> {
+    let workspace = WorkspaceEntry {
+        id: "w1".to_string(),
+        name: "w1".to_string(),
+        path: root.to_string_lossy().to_string(),
+        kind: WorkspaceKind::Main,
+        parent_id: None,
+        worktree: None,
+        settings: WorkspaceSettings::default(),
+    };
+    let mut entries = HashMap::new();
+    entries.insert("w1".to_string(), workspace);
+    Mutex::new(entries)
+}
+
+#[test]
+fn get_git_status_page_pages_without_stats_until_requested() {
+    let (root, _repo) = create_temp_repo();
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        fs::write(root.join(name), "line\n").expect("write file");
+    }
+    let workspaces = workspaces_for_root(&root);
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    let first = runtime
+        .block_on(paged::get_git_status_page_inner(
+            &workspaces,
+            "w1".to_string(),
+            None,
+            None,
+            Some(2),
+            false,
+        ))
+        .expect("first page");
+    assert_eq!(first.total, 3);
+    assert!(first.has_more);
+    assert_eq!(first.files.len(), 2);
+    assert_eq!(first.files[0].path, "a.txt");
+    assert!(first.files[0].additions.is_none());
+
+    let second = runtime
+        .block_on(paged::get_git_status_page_inner(
+            &workspaces,
+            "w1".to_string(),
+            None,
+            Some(2),
+            Some(2),
+            true,
+        ))
+        .expect("second page");
+    assert!(!second.has_more);
+    assert_eq!(second.files.len(), 1);
+    assert_eq!(second.files[0].path, "c.txt");
+    assert_eq!(second.files[0].additions, Some(1));
+    assert_eq!(first.cache_key, second.cache_key);
+
+    fs::write(root.join("d.txt"), "line\n").expect("write file");
+    let third = runtime
+        .block_on(paged::get_git_status_page_inner(
+            &workspaces,
+            "w1".to_string(),
+            None,
+            None,
+            None,
+            false,
+        ))
+        .expect("third page");
+    assert_eq!(third.total, 4);
+    assert_ne!(third.cache_key, first.cache_key);
+}
+
+#[test]
+fn get_git_status_page_cache_sees_edits_to_clean_tracked_files() {
+    let (root, repo) = create_temp_repo();
+    fs::create_dir_all(root.join("src")).expect("create src");
+    fs::write(root.join("src/lib.rs"), "fn a() {}\n").expect("write file");
+    let mut index = repo.index().expect("repo index");
+    index.add_path(Path::new("src/lib.rs")).expect("add path");
+    index.write().expect("write index");
+    let tree_id = index.write_tree().expect("write tree");
+    let tree = repo.find_tree(tree_id).expect("find tree");
+    let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+    repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+        .expect("commit");
+    let workspaces = workspaces_for_root(&root);
+    let runtime = Runtime::new().expect("create tokio runtime");
+    let page = |runtime: &Runtime| {
+        runtime
+            .block_on(paged::get_git_status_page_inner(
+                &workspaces,
+                "w1".to_string(),
+                None,
+                None,
+                None,
+                false,
+            ))
+            .expect("status page")
+    };
+
+    let clean = page(&runtime);
+    assert_eq!(clean.total, 0);
+    assert_eq!(page(&runtime).cache_key, clean.cache_key);
+
+    fs::write(root.join("src/lib.rs"), "fn a() { changed(); }\n").expect("edit file");
+    let edited = page(&runtime);
+    assert_eq!(edited.total, 1);
+    assert_eq!(edited.files[0].path, "src/lib.rs");
+
+    fs::write(root.join("src/new.rs"), "fn b() {}\n").expect("write nested file");
+    let added = page(&runtime);
+    assert_eq!(added.total, 2);
+    assert_ne!(added.cache_key, edited.cache_key);
+}
+
+#[test]
+fn changed_files_since_commit_includes_commits_and_workdir_changes() {
+    let (root, repo) = create_temp_repo();
+    let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+    let commit_all = |message: &str| {
+        let mut index = repo.index().expect("repo index");
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .expect("add all");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+        let parents = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect::<Vec<_>>();
+        let parents = parents.iter().collect::<Vec<_>>();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .expect("commit");
+    };
+    fs::write(root.join("a.txt"), "one\n").expect("write file");
+    commit_all("base");
+    let base = diff::head_commit_sha(&root).expect("base sha");
+
+    fs::write(root.join("a.txt"), "one\ntwo\n").expect("edit file");
+    commit_all("variant work");
+    fs::write(root.join("b.txt"), "new\n").expect("write untracked file");
+
+    let mut files = diff::changed_files_since_commit(&root, Some(&base)).expect("changes");
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].path, "a.txt");
+    assert_eq!((files[0].additions, files[0].deletions), (1, 0));
+    assert_eq!(files[1].path, "b.txt");
+    assert_eq!(files[1].status, "A");
+
+    let since_head = diff::changed_files_since_commit(&root, None).expect("changes");
+    assert_eq!(since_head.len(), 1);
+    assert_eq!(since_head[0].path, "b.txt");
+}
+
+#[test]
+fn get_git_file_diff_truncates_and_refreshes_after_edits() {
+    let (root, _repo) = create_temp_repo();
+    let content: String = (0..50).map(|line| format!("line {line}\n")).collect();
+    fs::write(root.join("big.txt"), &content).expect("write file");
+    let workspaces = workspaces_for_root(&root);
+    let app_settings = Mutex::new(AppSettings::default());
+    let runtime = Runtime::new().expect("create tokio runtime");
+
+    let capped = runtime
+        .block_on(paged::get_git_file_diff_inner(
+            &workspaces,
+            &app_settings,
+            "w1".to_string(),
+            "big.txt".to_string(),
+            None,
+            Some(10),
+        ))
+        .expect("capped diff");
+    assert!(capped.truncated);
+    assert_eq!(capped.diff.lines().count(), 10);
+    assert!(capped.new_lines.is_none());
+
+    fs::write(root.join("big.txt"), "short\n").expect("rewrite file");
+    let refreshed = runtime
+        .block_on(paged::get_git_file_diff_inner(
+            &workspaces,
+            &app_settings,
+            "w1".to_string(),
+            "big.txt".to_string(),
+            None,
+            Some(10),
+        ))
+        .expect("refreshed diff");
+    assert!(!refreshed.truncated);
+    assert!(refreshed.diff.contains("+short"));
+}
@@ -1,18 +1,26 @@
-use chrono::{DateTime, Duration, Local, TimeZone, Utc};
-use serde_json::Value;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex;
 
+use crate::backend::events::{AppServerEvent, EventSink};
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
+use crate::shared::store_helpers::now_ms;
+use crate::shared::webhooks_core;
 use crate::types::{
-    LocalUsageDay, LocalUsageModel, LocalUsageSnapshot, LocalUsageTotals, WorkspaceEntry,
+    AppSettings, LocalUsageBreakdown, LocalUsageBreakdownEntry, LocalUsageBreakdownQuery,
+    LocalUsageBudgetStatus, LocalUsageDay, LocalUsageExport, LocalUsageExportQuery,
+    LocalUsageExportRow, LocalUsageModel, LocalUsageModelCost, LocalUsageSnapshot,
+    LocalUsageTotals, LocalUsageWorkspaceCost, UsageBreakdownGroup, UsageBudgetPeriod,
+    UsageBudgets, UsageExportFormat, UsageModelPricing, WorkspaceEntry,
 };
 
-#[derive(Default, Clone, Copy)]
+#[path = "local_usage_core/index.rs"]
+mod index;
+
+#[derive(Default, Clone, Copy, Serialize, Deserialize)]
 struct DailyTotals {
     input: i64,
     cached: i64,
@@ -21,64 +29,484 @@ struct DailyTotals {
     agent_runs: i64,
 }
 
-#[derive(Default, Clone, Copy)]
+impl DailyTotals {
+    fn add(&mut self, other: DailyTotals) {
+        self.input += other.input;
+        self.cached += other.cached;
+        self.output += other.output;
+        self.agent_ms += other.agent_ms;
+        self.agent_runs += other.agent_runs;
+    }
+}
+
+#[derive(Default, Clone, Copy, Serialize, Deserialize)]
 struct UsageTotals {
     input: i64,
     cached: i64,
     output: i64,
 }
 
+impl UsageTotals {
+    fn add(&mut self, other: UsageTotals) {
+        self.input += other.input;
+        self.cached += other.cached;
+        self.output += other.output;
+    }
+
+    fn tokens(&self) -> i64 {
+        self.input + self.output
+    }
+}
+
+/// Token usage is attributed to the day, model and session cwd it was
+/// reported under so cost can be rolled up along any of those axes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct UsageKey {
+    day: String,
+    model: String,
+    cwd: Option<String>,
+}
+
+/// Everything one session file contributed, per local day. The session is
+/// attributed to the first cwd it reported.
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionUsage {
+    thread_id: Option<String>,
+    cwd: Option<String>,
+    daily: HashMap<String, DailyTotals>,
+    #[serde(
+        serialize_with = "index::serialize_usage",
+        deserialize_with = "index::deserialize_usage"
+    )]
+    usage: HashMap<UsageKey, UsageTotals>,
+}
+
+impl SessionUsage {
+    fn day_mut(&mut self, day_key: String) -> &mut DailyTotals {
+        self.daily.entry(day_key).or_default()
+    }
+
+    fn track_activity(&mut self, last_activity_ms: &mut Option<i64>, timestamp_ms: i64) {
+        if let Some(prev_ms) = *last_activity_ms {
+            let delta = timestamp_ms - prev_ms;
+            if delta > 0 && delta <= MAX_ACTIVITY_GAP_MS {
+                if let Some(day_key) = day_key_for_timestamp_ms(timestamp_ms) {
+                    self.day_mut(day_key).agent_ms += delta;
+                }
+            }
+        }
+        *last_activity_ms = Some(timestamp_ms);
+    }
+
+    fn totals(&self) -> DailyTotals {
+        let mut totals = DailyTotals::default();
+        for day in self.daily.values() {
+            totals.add(*day);
+        }
+        totals
+    }
+
+    /// Restricts the session to the scanned days, or drops it when it falls
+    /// outside the window or belongs to another workspace.
+    fn for_scan(
+        &self,
+        window: &HashSet<String>,
+        workspace_path: Option<&Path>,
+    ) -> Option<SessionUsage> {
+        if let Some(filter) = workspace_path {
+            let cwd = self.cwd.as_deref()?;
+            if !path_matches_workspace(cwd, filter) {
+                return None;
+            }
+        }
+        let daily: HashMap<String, DailyTotals> = self
+            .daily
+            .iter()
+            .filter(|(day, _)| window.contains(day.as_str()))
+            .map(|(day, totals)| (day.clone(), *totals))
+            .collect();
+        if daily.is_empty() {
+            return None;
+        }
+        Some(SessionUsage {
+            thread_id: self.thread_id.clone(),
+            cwd: self.cwd.clone(),
+            daily,
+            usage: self
+                .usage
+                .iter()
+                .filter(|(key, _)| window.contains(key.day.as_str()))
+                .map(|(key, totals)| (key.clone(), *totals))
+                .collect(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct UsageScan {
+    window: HashSet<String>,
+    sessions: HashMap<PathBuf, SessionUsage>,
+}
+
+impl UsageScan {
+    fn for_days(day_keys: &[String]) -> Self {
+        Self {
+            window: day_keys.iter().cloned().collect(),
+            sessions: HashMap::new(),
+        }
+    }
+
+    fn daily_totals(&self) -> HashMap<String, DailyTotals> {
+        let mut daily: HashMap<String, DailyTotals> = HashMap::new();
+        for session in self.sessions.values() {
+            for (day, totals) in &session.daily {
+                daily.entry(day.clone()).or_default().add(*totals);
+            }
+        }
+        daily
+    }
+
+    fn usage(&self) -> impl Iterator<Item = (&UsageKey, &UsageTotals)> {
+        self.sessions
+            .values()
+            .flat_map(|session| session.usage.iter())
+    }
+}
+
+#[derive(Clone)]
+struct UsageWorkspace {
+    id: String,
+    name: String,
+    path: PathBuf,
+}
+
+#[derive(Clone, Default)]
+struct UsageContext {
+    pricing: Vec<UsageModelPricing>,
+    budgets: UsageBudgets,
+    workspaces: Vec<UsageWorkspace>,
+    /// Where the incremental scan index is persisted; `None` scans from scratch.
+    index_dir: Option<PathBuf>,
+}
+
 const MAX_ACTIVITY_GAP_MS: i64 = 2 * 60 * 1000;
+const MAX_LINE_BYTES: usize = 512_000;
+const DEFAULT_RANGE_DAYS: i64 = 30;
+const MAX_RANGE_DAYS: i64 = 366;
+const BUDGET_ALERTS_FILE: &str = "usage-budget-alerts.json";
+const BUDGET_EXCEEDED_METHOD: &str = "usage/budgetExceeded";
+/// How often budgets are re-evaluated in the background, independent of
+/// snapshot requests from clients.
+pub(crate) const USAGE_BUDGET_CHECK_INTERVAL: std::time::Duration =
+    std::time::Duration::from_secs(5 * 60);
 
 pub(crate) async fn local_usage_snapshot_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    index_dir: Option<PathBuf>,
     days: Option<u32>,
     workspace_path: Option<String>,
 ) -> Result<LocalUsageSnapshot, String> {
-    let days = days.unwrap_or(30).clamp(1, 90);
-    let workspace_path = workspace_path.and_then(|value| {
-        let trimmed = value.trim();
-        if trimmed.is_empty() {
-            None
-        } else {
-            Some(PathBuf::from(trimmed))
-        }
-    });
-    let sessions_roots = {
+    let days = days.unwrap_or(30).clamp(1, 365);
+    let workspace_path = parse_workspace_path(workspace_path);
+    let (sessions_roots, usage_workspaces) = {
         let workspaces = workspaces.lock().await;
-        resolve_sessions_roots(&workspaces, workspace_path.as_deref())
+        (
+            resolve_sessions_roots(&workspaces, workspace_path.as_deref()),
+            usage_workspaces(&workspaces),
+        )
+    };
+    let context = {
+        let settings = app_settings.lock().await;
+        UsageContext {
+            pricing: settings.usage_pricing.clone(),
+            // Budgets are account-wide, so they are only evaluated for unfiltered snapshots.
+            budgets: if workspace_path.is_none() {
+                settings.usage_budgets.clone()
+            } else {
+                UsageBudgets::default()
+            },
+            workspaces: usage_workspaces,
+            index_dir,
+        }
     };
     let snapshot = tokio::task::spawn_blocking(move || {
-        scan_local_usage(days, workspace_path.as_deref(), &sessions_roots)
+        scan_local_usage(days, workspace_path.as_deref(), &sessions_roots, &context)
     })
     .await
     .map_err(|err| err.to_string())??;
     Ok(snapshot)
 }
 
+/// Ranks workspaces, session files or cwds by the tokens, agent time and
+/// runs they used within the query's date range.
+pub(crate) async fn local_usage_breakdown_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    index_dir: Option<PathBuf>,
+    query: LocalUsageBreakdownQuery,
+) -> Result<LocalUsageBreakdown, String> {
+    let (start, end) = resolve_date_range(
+        query.start_date.as_deref(),
+        query.end_date.as_deref(),
+        Local::now().date_naive(),
+    )?;
+    let group_by = query.group_by.unwrap_or_default();
+    let limit = query.limit.unwrap_or(50).clamp(1, 500) as usize;
+    let workspace_path = parse_workspace_path(query.workspace_path);
+    let (sessions_roots, usage_workspaces) = {
+        let workspaces = workspaces.lock().await;
+        (
+            resolve_sessions_roots(&workspaces, workspace_path.as_deref()),
+            usage_workspaces(&workspaces),
+        )
+    };
+    let pricing = app_settings.lock().await.usage_pricing.clone();
+    tokio::task::spawn_blocking(move || {
+        let day_keys = day_keys_between(start, end);
+        let scan = scan_sessions(
+            &day_keys,
+            workspace_path.as_deref(),
+            &sessions_roots,
+            index_dir.as_deref(),
+        )?;
+        Ok(build_breakdown(
+            now_ms(),
+            (start, end),
+            group_by,
+            limit,
+            &scan,
+            &pricing,
+            &usage_workspaces,
+        ))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Exports per-day, per-model and per-workspace rows for a date range,
+/// using the same scan and pricing as the in-app snapshot.
+pub(crate) async fn local_usage_export_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    index_dir: Option<PathBuf>,
+    query: LocalUsageExportQuery,
+) -> Result<LocalUsageExport, String> {
+    let (start, end) = resolve_date_range(
+        query.start_date.as_deref(),
+        query.end_date.as_deref(),
+        Local::now().date_naive(),
+    )?;
+    let format = query.format.unwrap_or_default();
+    let workspace_path = parse_workspace_path(query.workspace_path);
+    let (sessions_roots, usage_workspaces) = {
+        let workspaces = workspaces.lock().await;
+        (
+            resolve_sessions_roots(&workspaces, workspace_path.as_deref()),
+            usage_workspaces(&workspaces),
+        )
+    };
+    let pricing = app_settings.lock().await.usage_pricing.clone();
+    tokio::task::spawn_blocking(move || {
+        let day_keys = day_keys_between(start, end);
+        let scan = scan_sessions(
+            &day_keys,
+            workspace_path.as_deref(),
+            &sessions_roots,
+            index_dir.as_deref(),
+        )?;
+        let rows = build_export_rows(&scan, &pricing, &usage_workspaces);
+        let content = match format {
+            UsageExportFormat::Csv => render_export_csv(&rows),
+            UsageExportFormat::Json => {
+                serde_json::to_string_pretty(&rows).map_err(|err| err.to_string())?
+            }
+        };
+        Ok(LocalUsageExport {
+            format,
+            start_date: start.format("%Y-%m-%d").to_string(),
+            end_date: end.format("%Y-%m-%d").to_string(),
+            row_count: rows.len(),
+            content,
+        })
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Emits `usage/budgetExceeded` once per budget period. The last notified
+/// period per budget is remembered in the app data dir so frequent snapshot
+/// refreshes do not repeat the alert. The alert state file is read and written
+/// on the blocking pool so snapshot handlers never stall a runtime worker.
+pub(crate) async fn notify_usage_budgets_core<E: EventSink>(
+    snapshot: &LocalUsageSnapshot,
+    data_dir: &Path,
+    event_sink: &E,
+) {
+    if !snapshot.budgets.iter().any(|budget| budget.exceeded) {
+        return;
+    }
+    let budgets = snapshot.budgets.clone();
+    let data_dir = data_dir.to_path_buf();
+    let event_sink = event_sink.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        notify_exceeded_budgets(&budgets, &data_dir, &event_sink)
+    })
+    .await;
+    if let Err(err) = result {
+        eprintln!("usage budgets: alert task failed: {err}");
+    }
+}
+
+fn notify_exceeded_budgets<E: EventSink>(
+    budgets: &[LocalUsageBudgetStatus],
+    data_dir: &Path,
+    event_sink: &E,
+) {
+    let path = data_dir.join(BUDGET_ALERTS_FILE);
+    let mut notified: HashMap<UsageBudgetPeriod, String> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+    let mut changed = false;
+    for budget in budgets.iter().filter(|budget| budget.exceeded) {
+        if notified.get(&budget.period) == Some(&budget.period_key) {
+            continue;
+        }
+        let message = json!({
+            "method": BUDGET_EXCEEDED_METHOD,
+            "params": budget,
+        });
+        webhooks_core::dispatch_app_server_event(data_dir, "", None, &message);
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: String::new(),
+            message,
+        });
+        notified.insert(budget.period.clone(), budget.period_key.clone());
+        changed = true;
+    }
+    if !changed {
+        return;
+    }
+    let write_result = std::fs::create_dir_all(data_dir)
+        .map_err(|err| err.to_string())
+        .and_then(|_| serde_json::to_string_pretty(&notified).map_err(|err| err.to_string()))
+        .and_then(|data| std::fs::write(&path, data).map_err(|err| err.to_string()));
+    if let Err(err) = write_result {
+        eprintln!("usage budgets: failed to persist alert state: {err}");
+    }
+}
+
+/// Background budget check: scans usage and raises any new budget alerts.
+/// Skips the scan entirely when no budget is configured.
+pub(crate) async fn check_usage_budgets_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    data_dir: &Path,
+    event_sink: &E,
+) {
+    let has_budgets = {
+        let settings = app_settings.lock().await;
+        let budgets = &settings.usage_budgets;
+        budgets.daily_usd.is_some() || budgets.weekly_usd.is_some() || budgets.monthly_usd.is_some()
+    };
+    if !has_budgets {
+        return;
+    }
+    match local_usage_snapshot_core(
+        workspaces,
+        app_settings,
+        Some(data_dir.to_path_buf()),
+        Some(1),
+        None,
+    )
+    .await
+    {
+        Ok(snapshot) => notify_usage_budgets_core(&snapshot, data_dir, event_sink).await,
+        Err(err) => eprintln!("usage budgets: background check failed: {err}"),
+    }
+}
+
+fn parse_workspace_path(workspace_path: Option<String>) -> Option<PathBuf> {
+    workspace_path.and_then(|value| {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(trimmed))
+        }
+    })
+}
+
+fn usage_workspaces(workspaces: &HashMap<String, WorkspaceEntry>) -> Vec<UsageWorkspace> {
+    workspaces
+        .values()
+        .map(|entry| UsageWorkspace {
+            id: entry.id.clone(),
+            name: entry.name.clone(),
+            path: PathBuf::from(&entry.path),
+        })
+        .collect()
+}
+
+/// Number of days the scan must cover so week- and month-to-date budgets
+/// can be evaluated even when the requested window is shorter.
+fn budget_window_days(budgets: &UsageBudgets, today: NaiveDate) -> u32 {
+    let mut window = 0;
+    if budgets.daily_usd.is_some() {
+        window = window.max(1);
+    }
+    if budgets.weekly_usd.is_some() {
+        window = window.max(today.weekday().num_days_from_monday() + 1);
+    }
+    if budgets.monthly_usd.is_some() {
+        window = window.max(today.day());
+    }
+    window
+}
+
 fn scan_local_usage(
     days: u32,
     workspace_path: Option<&Path>,
     sessions_roots: &[PathBuf],
+    context: &UsageContext,
 ) -> Result<LocalUsageSnapshot, String> {
-    let updated_at = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis() as i64;
-
-    let day_keys = make_day_keys(days);
-    let mut daily: HashMap<String, DailyTotals> = day_keys
-        .iter()
-        .map(|key| (key.clone(), DailyTotals::default()))
-        .collect();
-    let mut model_totals: HashMap<String, i64> = HashMap::new();
+    let updated_at = now_ms();
+    let today = Local::now().date_naive();
+    let scan_days = days.max(budget_window_days(&context.budgets, today));
+    let scan_day_keys = make_day_keys(scan_days);
+    let day_keys = scan_day_keys[scan_day_keys.len() - days as usize..].to_vec();
+    let scan = scan_sessions(
+        &scan_day_keys,
+        workspace_path,
+        sessions_roots,
+        context.index_dir.as_deref(),
+    )?;
+
+    Ok(build_snapshot(updated_at, day_keys, &scan, context, today))
+}
 
-    if sessions_roots.is_empty() {
-        return Ok(build_snapshot(updated_at, day_keys, daily, HashMap::new()));
-    }
+fn scan_sessions(
+    day_keys: &[String],
+    workspace_path: Option<&Path>,
+    sessions_roots: &[PathBuf],
+    index_dir: Option<&Path>,
+) -> Result<UsageScan, String> {
+    index::with_usage_index(index_dir, |index| {
+        scan_sessions_with_index(index, day_keys, workspace_path, sessions_roots)
+    })
+}
 
+fn scan_sessions_with_index(
+    index: &mut index::UsageIndex,
+    day_keys: &[String],
+    workspace_path: Option<&Path>,
+    sessions_roots: &[PathBuf],
+) -> Result<UsageScan, String> {
+    let mut scan = UsageScan::for_days(day_keys);
     for root in sessions_roots {
-        for day_key in &day_keys {
+        for day_key in day_keys {
             let day_dir = day_dir_for_key(root, day_key);
             if !day_dir.exists() {
                 continue;
@@ -92,27 +520,174 @@ fn scan_local_usage(
                 if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
                     continue;
                 }
-                scan_file(&path, &mut daily, &mut model_totals, workspace_path)?;
+                let Some(session) = index.session(&path) else {
+                    continue;
+                };
+                if let Some(session) = session.for_scan(&scan.window, workspace_path) {
+                    scan.sessions.insert(path, session);
+                }
             }
         }
     }
+    Ok(scan)
+}
+
+/// Matches a pricing entry by exact model name, or by name plus a dated or
+/// numbered snapshot suffix (`gpt-4.1-2025-04-14`). Variants such as
+/// `gpt-4.1-mini` or `o3-pro` never inherit the base model's rates; models
+/// without an entry are reported as unpriced.
+fn pricing_for_model<'a>(
+    pricing: &'a [UsageModelPricing],
+    model: &str,
+) -> Option<&'a UsageModelPricing> {
+    let model = model.trim().to_ascii_lowercase();
+    pricing
+        .iter()
+        .filter(|entry| {
+            let name = entry.model.trim().to_ascii_lowercase();
+            if name.is_empty() {
+                return false;
+            }
+            match model.strip_prefix(&name) {
+                Some("") => true,
+                Some(suffix) => is_snapshot_suffix(suffix),
+                None => false,
+            }
+        })
+        .max_by_key(|entry| entry.model.trim().len())
+}
 
-    Ok(build_snapshot(updated_at, day_keys, daily, model_totals))
+fn is_snapshot_suffix(suffix: &str) -> bool {
+    let Some(rest) = suffix.strip_prefix('-') else {
+        return false;
+    };
+    rest.starts_with(|ch: char| ch.is_ascii_digit())
+        && rest.chars().all(|ch| ch.is_ascii_digit() || ch == '-')
+}
+
+fn estimate_cost_usd(totals: UsageTotals, pricing: Option<&UsageModelPricing>) -> f64 {
+    let Some(pricing) = pricing else {
+        return 0.0;
+    };
+    let cached = totals.cached.clamp(0, totals.input.max(0));
+    let uncached = totals.input - cached;
+    (uncached as f64 * pricing.input_per_million
+        + cached as f64 * pricing.cached_input_per_million
+        + totals.output as f64 * pricing.output_per_million)
+        / 1_000_000.0
+}
+
+fn round_usd(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+fn workspace_for_cwd<'a>(
+    workspaces: &'a [UsageWorkspace],
+    cwd: &str,
+) -> Option<&'a UsageWorkspace> {
+    workspaces
+        .iter()
+        .filter(|workspace| path_matches_workspace(cwd, &workspace.path))
+        .max_by_key(|workspace| workspace.path.as_os_str().len())
+}
+
+fn budget_statuses(
+    budgets: &UsageBudgets,
+    daily_costs: &HashMap<String, f64>,
+    today: NaiveDate,
+) -> Vec<LocalUsageBudgetStatus> {
+    let today_key = today.format("%Y-%m-%d").to_string();
+    let week_start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let week_start_key = week_start.format("%Y-%m-%d").to_string();
+    let month_prefix = today.format("%Y-%m").to_string();
+    let iso_week = today.iso_week();
+    let spent_where = |include: &dyn Fn(&str) -> bool| -> f64 {
+        daily_costs
+            .iter()
+            .filter(|(day, _)| include(day.as_str()))
+            .map(|(_, cost)| *cost)
+            .sum()
+    };
+
+    let periods = [
+        (
+            UsageBudgetPeriod::Daily,
+            budgets.daily_usd,
+            today_key.clone(),
+            spent_where(&|day| day == today_key),
+        ),
+        (
+            UsageBudgetPeriod::Weekly,
+            budgets.weekly_usd,
+            format!("{}-W{:02}", iso_week.year(), iso_week.week()),
+            spent_where(&|day| day >= week_start_key.as_str() && day <= today_key.as_str()),
+        ),
+        (
+            UsageBudgetPeriod::Monthly,
+            budgets.monthly_usd,
+            month_prefix.clone(),
+            spent_where(&|day| day.starts_with(&month_prefix)),
+        ),
+    ];
+    periods
+        .into_iter()
+        .filter_map(|(period, limit, period_key, spent)| {
+            let limit = limit.filter(|limit| *limit > 0.0)?;
+            Some(LocalUsageBudgetStatus {
+                period,
+                period_key,
+                limit_usd: limit,
+                spent_usd: round_usd(spent),
+                exceeded: spent >= limit,
+            })
+        })
+        .collect()
 }
 
 fn build_snapshot(
     updated_at: i64,
     day_keys: Vec<String>,
-    daily: HashMap<String, DailyTotals>,
-    model_totals: HashMap<String, i64>,
+    scan: &UsageScan,
+    context: &UsageContext,
+    today: NaiveDate,
 ) -> LocalUsageSnapshot {
+    let window: HashSet<&str> = day_keys.iter().map(String::as_str).collect();
+    let mut daily_costs: HashMap<String, f64> = HashMap::new();
+    let mut model_usage: HashMap<&str, UsageTotals> = HashMap::new();
+    let mut workspace_usage: HashMap<String, (Option<&UsageWorkspace>, i64, f64)> = HashMap::new();
+    for (key, totals) in scan.usage() {
+        let cost = estimate_cost_usd(*totals, pricing_for_model(&context.pricing, &key.model));
+        *daily_costs.entry(key.day.clone()).or_insert(0.0) += cost;
+        if !window.contains(key.day.as_str()) {
+            continue;
+        }
+        model_usage
+            .entry(key.model.as_str())
+            .or_default()
+            .add(*totals);
+        let cwd = key.cwd.as_deref().unwrap_or("");
+        let workspace = workspace_for_cwd(&context.workspaces, cwd);
+        let group_key = workspace
+            .map(|workspace| workspace.id.clone())
+            .unwrap_or_else(|| cwd.to_string());
+        let entry = workspace_usage
+            .entry(group_key)
+            .or_insert((workspace, 0, 0.0));
+        entry.1 += totals.tokens();
+        entry.2 += cost;
+    }
+
+    let daily = scan.daily_totals();
     let mut days: Vec<LocalUsageDay> = Vec::with_capacity(day_keys.len());
     let mut total_tokens = 0;
+    let mut total_cost = 0.0;
 
     for day_key in &day_keys {
         let totals = daily.get(day_key).copied().unwrap_or_default();
         let total = totals.input + totals.output;
+        let cost = daily_costs.get(day_key).copied().unwrap_or(0.0);
         total_tokens += total;
+        total_cost += cost;
         days.push(LocalUsageDay {
             day: day_key.clone(),
             input_tokens: totals.input,
@@ -121,6 +696,7 @@ fn build_snapshot(
             total_tokens: total,
             agent_time_ms: totals.agent_ms,
             agent_runs: totals.agent_runs,
+            estimated_cost_usd: round_usd(cost),
         });
     }
 
@@ -128,6 +704,7 @@ fn build_snapshot(
     let last7_tokens: i64 = last7.iter().map(|day| day.total_tokens).sum();
     let last7_input: i64 = last7.iter().map(|day| day.input_tokens).sum();
     let last7_cached: i64 = last7.iter().map(|day| day.cached_input_tokens).sum();
+    let last7_cost: f64 = last7.iter().map(|day| day.estimated_cost_usd).sum();
 
     let average_daily_tokens = if last7.is_empty() {
         0
@@ -148,22 +725,66 @@ fn build_snapshot(
     let peak_day = peak.map(|day| day.day.clone());
     let peak_day_tokens = peak.map(|day| day.total_tokens).unwrap_or(0);
 
-    let mut top_models: Vec<LocalUsageModel> = model_totals
-        .into_iter()
-        .filter(|(model, tokens)| model != "unknown" && *tokens > 0)
-        .map(|(model, tokens)| LocalUsageModel {
-            model,
+    let mut model_costs: Vec<LocalUsageModelCost> = model_usage
+        .iter()
+        .map(|(model, totals)| {
+            let pricing = pricing_for_model(&context.pricing, model);
+            LocalUsageModelCost {
+                model: model.to_string(),
+                input_tokens: totals.input,
+                cached_input_tokens: totals.cached,
+                output_tokens: totals.output,
+                estimated_cost_usd: round_usd(estimate_cost_usd(*totals, pricing)),
+                priced: pricing.is_some(),
+            }
+        })
+        .collect();
+    model_costs.sort_by(|a, b| {
+        b.estimated_cost_usd
+            .total_cmp(&a.estimated_cost_usd)
+            .then_with(|| {
+                (b.input_tokens + b.output_tokens).cmp(&(a.input_tokens + a.output_tokens))
+            })
+    });
+
+    let mut top_models: Vec<LocalUsageModel> = model_costs
+        .iter()
+        .map(|cost| (cost, cost.input_tokens + cost.output_tokens))
+        .filter(|(cost, tokens)| cost.model != "unknown" && *tokens > 0)
+        .map(|(cost, tokens)| LocalUsageModel {
+            model: cost.model.clone(),
             tokens,
             share_percent: if total_tokens > 0 {
                 ((tokens as f64) / (total_tokens as f64) * 1000.0).round() / 10.0
             } else {
                 0.0
             },
+            estimated_cost_usd: cost.estimated_cost_usd,
         })
         .collect();
     top_models.sort_by(|a, b| b.tokens.cmp(&a.tokens));
     top_models.truncate(4);
 
+    let mut workspace_costs: Vec<LocalUsageWorkspaceCost> = workspace_usage
+        .into_iter()
+        .map(
+            |(group_key, (workspace, tokens, cost))| LocalUsageWorkspaceCost {
+                workspace_id: workspace.map(|workspace| workspace.id.clone()),
+                workspace_name: workspace.map(|workspace| workspace.name.clone()),
+                path: workspace
+                    .map(|workspace| workspace.path.to_string_lossy().to_string())
+                    .unwrap_or(group_key),
+                tokens,
+                estimated_cost_usd: round_usd(cost),
+            },
+        )
+        .collect();
+    workspace_costs.sort_by(|a, b| {
+        b.estimated_cost_usd
+            .total_cmp(&a.estimated_cost_usd)
+            .then_with(|| b.tokens.cmp(&a.tokens))
+    });
+
     LocalUsageSnapshot {
         updated_at,
         days,
@@ -174,245 +795,446 @@ fn build_snapshot(
             cache_hit_rate_percent,
             peak_day,
             peak_day_tokens,
+            last7_days_cost_usd: round_usd(last7_cost),
+            total_cost_usd: round_usd(total_cost),
         },
         top_models,
+        model_costs,
+        workspace_costs,
+        budgets: budget_statuses(&context.budgets, &daily_costs, today),
     }
 }
 
-fn scan_file(
-    path: &Path,
-    daily: &mut HashMap<String, DailyTotals>,
-    model_totals: &mut HashMap<String, i64>,
-    workspace_path: Option<&Path>,
-) -> Result<(), String> {
-    let file = match File::open(path) {
-        Ok(file) => file,
-        Err(_) => {
-            return Ok(());
-        }
-    };
-    let reader = BufReader::new(file);
-    let mut previous_totals: Option<UsageTotals> = None;
-    let mut current_model: Option<String> = None;
-    let mut last_activity_ms: Option<i64> = None;
-    let mut seen_runs: HashSet<i64> = HashSet::new();
-    let mut match_known = workspace_path.is_none();
-    let mut matches_workspace = workspace_path.is_none();
-
-    for line in reader.lines() {
-        let line = match line {
-            Ok(line) => line,
-            Err(_) => continue,
-        };
-        if line.len() > 512_000 {
-            continue;
-        }
+#[derive(Default)]
+struct BreakdownGroup {
+    label: String,
+    workspace_id: Option<String>,
+    cwd: Option<String>,
+    session_path: Option<String>,
+    thread_id: Option<String>,
+    sessions: i64,
+    totals: DailyTotals,
+    cost: f64,
+    first_day: Option<String>,
+    last_day: Option<String>,
+}
 
-        let value = match serde_json::from_str::<Value>(&line) {
-            Ok(value) => value,
-            Err(_) => continue,
+fn build_breakdown(
+    updated_at: i64,
+    (start, end): (NaiveDate, NaiveDate),
+    group_by: UsageBreakdownGroup,
+    limit: usize,
+    scan: &UsageScan,
+    pricing: &[UsageModelPricing],
+    workspaces: &[UsageWorkspace],
+) -> LocalUsageBreakdown {
+    let mut groups: HashMap<String, BreakdownGroup> = HashMap::new();
+    for (path, session) in &scan.sessions {
+        let cwd = session.cwd.as_deref();
+        let workspace = cwd.and_then(|cwd| workspace_for_cwd(workspaces, cwd));
+        let session_path = path.to_string_lossy().to_string();
+        let (key, label) = match group_by {
+            UsageBreakdownGroup::Workspace => match workspace {
+                Some(workspace) => (workspace.id.clone(), workspace.name.clone()),
+                None => {
+                    let cwd = cwd.unwrap_or("unknown");
+                    (cwd.to_string(), cwd.to_string())
+                }
+            },
+            UsageBreakdownGroup::Cwd => {
+                let cwd = cwd.unwrap_or("unknown");
+                (cwd.to_string(), cwd.to_string())
+            }
+            UsageBreakdownGroup::Session => {
+                let label = session.thread_id.clone().unwrap_or_else(|| {
+                    path.file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_else(|| session_path.clone())
+                });
+                (session_path.clone(), label)
+            }
         };
-        let entry_type = value
-            .get("type")
-            .and_then(|value| value.as_str())
-            .unwrap_or("");
-
-        if entry_type == "session_meta" || entry_type == "turn_context" {
-            if let Some(cwd) = extract_cwd(&value) {
-                if let Some(filter) = workspace_path {
-                    matches_workspace = path_matches_workspace(&cwd, filter);
-                    match_known = true;
-                    if !matches_workspace {
-                        break;
-                    }
+        let group = groups.entry(key).or_insert_with(|| BreakdownGroup {
+            label,
+            workspace_id: workspace.map(|workspace| workspace.id.clone()),
+            cwd: match (group_by, workspace) {
+                (UsageBreakdownGroup::Workspace, Some(workspace)) => {
+                    Some(workspace.path.to_string_lossy().to_string())
                 }
+                _ => cwd.map(str::to_string),
+            },
+            session_path: (group_by == UsageBreakdownGroup::Session).then(|| session_path.clone()),
+            thread_id: if group_by == UsageBreakdownGroup::Session {
+                session.thread_id.clone()
+            } else {
+                None
+            },
+            ..BreakdownGroup::default()
+        });
+        group.sessions += 1;
+        group.totals.add(session.totals());
+        group.cost += session
+            .usage
+            .iter()
+            .map(|(key, totals)| estimate_cost_usd(*totals, pricing_for_model(pricing, &key.model)))
+            .sum::<f64>();
+        for day in session.daily.keys() {
+            if !group.first_day.as_ref().is_some_and(|first| first <= day) {
+                group.first_day = Some(day.clone());
+            }
+            if !group.last_day.as_ref().is_some_and(|last| last >= day) {
+                group.last_day = Some(day.clone());
             }
         }
+    }
 
-        if entry_type == "turn_context" {
-            if let Some(model) = extract_model_from_turn_context(&value) {
-                current_model = Some(model);
+    let total_tokens: i64 = groups
+        .values()
+        .map(|group| group.totals.input + group.totals.output)
+        .sum();
+    let total_cost: f64 = groups.values().map(|group| group.cost).sum();
+    let mut entries: Vec<LocalUsageBreakdownEntry> = groups
+        .into_iter()
+        .map(|(key, group)| {
+            let tokens = group.totals.input + group.totals.output;
+            LocalUsageBreakdownEntry {
+                key,
+                label: group.label,
+                workspace_id: group.workspace_id,
+                cwd: group.cwd,
+                session_path: group.session_path,
+                thread_id: group.thread_id,
+                sessions: group.sessions,
+                input_tokens: group.totals.input,
+                cached_input_tokens: group.totals.cached,
+                output_tokens: group.totals.output,
+                total_tokens: tokens,
+                agent_time_ms: group.totals.agent_ms,
+                agent_runs: group.totals.agent_runs,
+                estimated_cost_usd: round_usd(group.cost),
+                share_percent: if total_tokens > 0 {
+                    ((tokens as f64) / (total_tokens as f64) * 1000.0).round() / 10.0
+                } else {
+                    0.0
+                },
+                first_day: group.first_day,
+                last_day: group.last_day,
             }
-            continue;
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.total_tokens
+            .cmp(&a.total_tokens)
+            .then_with(|| b.agent_time_ms.cmp(&a.agent_time_ms))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    let total_entries = entries.len();
+    entries.truncate(limit);
+
+    LocalUsageBreakdown {
+        updated_at,
+        start_date: start.format("%Y-%m-%d").to_string(),
+        end_date: end.format("%Y-%m-%d").to_string(),
+        group_by,
+        total_tokens,
+        total_cost_usd: round_usd(total_cost),
+        total_entries,
+        entries,
+    }
+}
+
+fn build_export_rows(
+    scan: &UsageScan,
+    pricing: &[UsageModelPricing],
+    workspaces: &[UsageWorkspace],
+) -> Vec<LocalUsageExportRow> {
+    let mut grouped: HashMap<(String, String, String), (Option<&UsageWorkspace>, UsageTotals)> =
+        HashMap::new();
+    for session in scan.sessions.values() {
+        for (key, totals) in &session.usage {
+            let cwd = key.cwd.as_deref().or(session.cwd.as_deref()).unwrap_or("");
+            let workspace = workspace_for_cwd(workspaces, cwd);
+            let path = workspace
+                .map(|workspace| workspace.path.to_string_lossy().to_string())
+                .unwrap_or_else(|| cwd.to_string());
+            grouped
+                .entry((key.day.clone(), key.model.clone(), path))
+                .or_insert((workspace, UsageTotals::default()))
+                .1
+                .add(*totals);
+        }
+    }
+    let mut rows: Vec<LocalUsageExportRow> = grouped
+        .into_iter()
+        .map(
+            |((day, model, path), (workspace, totals))| LocalUsageExportRow {
+                estimated_cost_usd: round_usd(estimate_cost_usd(
+                    totals,
+                    pricing_for_model(pricing, &model),
+                )),
+                day,
+                model,
+                workspace_id: workspace.map(|workspace| workspace.id.clone()),
+                workspace_name: workspace.map(|workspace| workspace.name.clone()),
+                path,
+                input_tokens: totals.input,
+                cached_input_tokens: totals.cached,
+                output_tokens: totals.output,
+                total_tokens: totals.tokens(),
+            },
+        )
+        .collect();
+    rows.sort_by(|a, b| {
+        a.day
+            .cmp(&b.day)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.model.cmp(&b.model))
+    });
+    rows
+}
+
+fn render_export_csv(rows: &[LocalUsageExportRow]) -> String {
+    let mut csv = String::from(
+        "day,model,workspace_id,workspace_name,path,input_tokens,cached_input_tokens,output_tokens,total_tokens,estimated_cost_usd\n",
+    );
+    for row in rows {
+        let fields = [
+            csv_field(&row.day),
+            csv_field(&row.model),
+            csv_field(row.workspace_id.as_deref().unwrap_or("")),
+            csv_field(row.workspace_name.as_deref().unwrap_or("")),
+            csv_field(&row.path),
+            row.input_tokens.to_string(),
+            row.cached_input_tokens.to_string(),
+            row.output_tokens.to_string(),
+            row.total_tokens.to_string(),
+            format!("{:.4}", row.estimated_cost_usd),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+pub(crate) fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Parser state carried across reads so lines appended to a session file
+/// continue the same token and activity accounting.
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ParseState {
+    previous_totals: Option<UsageTotals>,
+    current_model: Option<String>,
+    current_cwd: Option<String>,
+    last_activity_ms: Option<i64>,
+    seen_runs: HashSet<i64>,
+}
+
+impl ParseState {
+    /// Runs are deduplicated by timestamp, and duplicates are only ever
+    /// written next to each other, so older timestamps can be dropped.
+    fn prune_seen_runs(&mut self) {
+        if let Some(last_activity_ms) = self.last_activity_ms {
+            let cutoff = last_activity_ms - MAX_ACTIVITY_GAP_MS;
+            self.seen_runs
+                .retain(|timestamp_ms| *timestamp_ms >= cutoff);
         }
+    }
+}
 
-        if entry_type == "session_meta" {
-            continue;
+fn record_run(session: &mut SessionUsage, state: &mut ParseState, timestamp_ms: i64) {
+    if state.seen_runs.insert(timestamp_ms) {
+        if let Some(day_key) = day_key_for_timestamp_ms(timestamp_ms) {
+            session.day_mut(day_key).agent_runs += 1;
         }
+    }
+    session.track_activity(&mut state.last_activity_ms, timestamp_ms);
+}
 
-        if !matches_workspace {
-            if match_known {
-                break;
+fn process_line(line: &str, state: &mut ParseState, session: &mut SessionUsage) {
+    if line.len() > MAX_LINE_BYTES {
+        return;
+    }
+    let value = match serde_json::from_str::<Value>(line) {
+        Ok(value) => value,
+        Err(_) => return,
+    };
+    let entry_type = value
+        .get("type")
+        .and_then(|value| value.as_str())
+        .unwrap_or("");
+
+    if entry_type == "session_meta" || entry_type == "turn_context" {
+        if let Some(cwd) = extract_cwd(&value) {
+            if session.cwd.is_none() {
+                session.cwd = Some(cwd.clone());
             }
-            continue;
+            state.current_cwd = Some(cwd);
         }
+    }
 
-        if !match_known {
-            continue;
+    if entry_type == "turn_context" {
+        if let Some(model) = extract_model_from_turn_context(&value) {
+            state.current_model = Some(model);
         }
+        return;
+    }
 
-        if entry_type == "event_msg" || entry_type.is_empty() {
-            let payload = value.get("payload").and_then(|value| value.as_object());
-            let payload_type = payload
-                .and_then(|payload| payload.get("type"))
-                .and_then(|value| value.as_str());
-
-            if payload_type == Some("agent_message") {
-                if let Some(timestamp_ms) = read_timestamp_ms(&value) {
-                    if seen_runs.insert(timestamp_ms) {
-                        if let Some(day_key) = day_key_for_timestamp_ms(timestamp_ms) {
-                            if let Some(entry) = daily.get_mut(&day_key) {
-                                entry.agent_runs += 1;
-                            }
-                        }
-                    }
-                    track_activity(daily, &mut last_activity_ms, timestamp_ms);
-                }
-                continue;
-            }
+    if entry_type == "session_meta" {
+        if session.thread_id.is_none() {
+            session.thread_id = extract_session_id(&value);
+        }
+        return;
+    }
 
-            if payload_type == Some("agent_reasoning") {
-                if let Some(timestamp_ms) = read_timestamp_ms(&value) {
-                    track_activity(daily, &mut last_activity_ms, timestamp_ms);
-                }
-                continue;
+    if entry_type == "event_msg" || entry_type.is_empty() {
+        let payload = value.get("payload").and_then(|value| value.as_object());
+        let payload_type = payload
+            .and_then(|payload| payload.get("type"))
+            .and_then(|value| value.as_str());
+
+        if payload_type == Some("agent_message") {
+            if let Some(timestamp_ms) = read_timestamp_ms(&value) {
+                record_run(session, state, timestamp_ms);
             }
+            return;
+        }
 
-            if payload_type != Some("token_count") {
-                continue;
+        if payload_type == Some("agent_reasoning") {
+            if let Some(timestamp_ms) = read_timestamp_ms(&value) {
+                session.track_activity(&mut state.last_activity_ms, timestamp_ms);
             }
+            return;
+        }
 
-            let info = payload
-                .and_then(|payload| payload.get("info"))
-                .and_then(|v| v.as_object());
-            let (input, cached, output, used_total) = if let Some(info) = info {
-                if let Some(total) = find_usage_map(info, &["total_token_usage", "totalTokenUsage"])
-                {
-                    (
-                        read_i64(total, &["input_tokens", "inputTokens"]),
-                        read_i64(
-                            total,
-                            &[
-                                "cached_input_tokens",
-                                "cache_read_input_tokens",
-                                "cachedInputTokens",
-                                "cacheReadInputTokens",
-                            ],
-                        ),
-                        read_i64(total, &["output_tokens", "outputTokens"]),
-                        true,
-                    )
-                } else if let Some(last) =
-                    find_usage_map(info, &["last_token_usage", "lastTokenUsage"])
-                {
-                    (
-                        read_i64(last, &["input_tokens", "inputTokens"]),
-                        read_i64(
-                            last,
-                            &[
-                                "cached_input_tokens",
-                                "cache_read_input_tokens",
-                                "cachedInputTokens",
-                                "cacheReadInputTokens",
-                            ],
-                        ),
-                        read_i64(last, &["output_tokens", "outputTokens"]),
-                        false,
-                    )
-                } else {
-                    continue;
-                }
-            } else {
-                continue;
-            };
+        if payload_type != Some("token_count") {
+            return;
+        }
+
+        let Some(info) = payload
+            .and_then(|payload| payload.get("info"))
+            .and_then(|v| v.as_object())
+        else {
+            return;
+        };
+        let (input, cached, output, used_total) = if let Some(total) =
+            find_usage_map(info, &["total_token_usage", "totalTokenUsage"])
+        {
+            let (input, cached, output) = read_usage_counts(total);
+            (input, cached, output, true)
+        } else if let Some(last) = find_usage_map(info, &["last_token_usage", "lastTokenUsage"]) {
+            let (input, cached, output) = read_usage_counts(last);
+            (input, cached, output, false)
+        } else {
+            return;
+        };
 
-            let mut delta = UsageTotals {
+        let mut delta = UsageTotals {
+            input,
+            cached,
+            output,
+        };
+
+        if used_total {
+            let prev = state.previous_totals.unwrap_or_default();
+            delta = UsageTotals {
+                input: (input - prev.input).max(0),
+                cached: (cached - prev.cached).max(0),
+                output: (output - prev.output).max(0),
+            };
+            state.previous_totals = Some(UsageTotals {
                 input,
                 cached,
                 output,
-            };
-
-            if used_total {
-                let prev = previous_totals.unwrap_or_default();
-                delta = UsageTotals {
-                    input: (input - prev.input).max(0),
-                    cached: (cached - prev.cached).max(0),
-                    output: (output - prev.output).max(0),
-                };
-                previous_totals = Some(UsageTotals {
-                    input,
-                    cached,
-                    output,
-                });
-            } else {
-                // Some streams emit `last_token_usage` deltas between `total_token_usage` snapshots.
-                // Treat those as already-counted to avoid double-counting when the next total arrives.
-                let mut next = previous_totals.unwrap_or_default();
-                next.input += delta.input;
-                next.cached += delta.cached;
-                next.output += delta.output;
-                previous_totals = Some(next);
-            }
+            });
+        } else {
+            // Some streams emit `last_token_usage` deltas between `total_token_usage` snapshots.
+            // Treat those as already-counted to avoid double-counting when the next total arrives.
+            let mut next = state.previous_totals.unwrap_or_default();
+            next.add(delta);
+            state.previous_totals = Some(next);
+        }
 
-            if delta.input == 0 && delta.cached == 0 && delta.output == 0 {
-                continue;
-            }
+        if delta.input == 0 && delta.cached == 0 && delta.output == 0 {
+            return;
+        }
 
-            let timestamp_ms = read_timestamp_ms(&value);
-            if let Some(day_key) = timestamp_ms.and_then(|ms| day_key_for_timestamp_ms(ms)) {
-                if let Some(entry) = daily.get_mut(&day_key) {
-                    let cached = delta.cached.min(delta.input);
-                    entry.input += delta.input;
-                    entry.cached += cached;
-                    entry.output += delta.output;
-
-                    let model = current_model
-                        .clone()
-                        .or_else(|| extract_model_from_token_count(&value))
-                        .unwrap_or_else(|| "unknown".to_string());
-                    *model_totals.entry(model).or_insert(0) += delta.input + delta.output;
-                }
-            }
+        let timestamp_ms = read_timestamp_ms(&value);
+        if let Some(day_key) = timestamp_ms.and_then(day_key_for_timestamp_ms) {
+            let cached = delta.cached.min(delta.input);
+            let entry = session.day_mut(day_key.clone());
+            entry.input += delta.input;
+            entry.cached += cached;
+            entry.output += delta.output;
+
+            let model = state
+                .current_model
+                .clone()
+                .or_else(|| extract_model_from_token_count(&value))
+                .unwrap_or_else(|| "unknown".to_string());
+            let key = UsageKey {
+                day: day_key,
+                model,
+                cwd: state.current_cwd.clone(),
+            };
+            session
+                .usage
+                .entry(key)
+                .or_default()
+                .add(UsageTotals { cached, ..delta });
+        }
 
-            if let Some(timestamp_ms) = timestamp_ms {
-                track_activity(daily, &mut last_activity_ms, timestamp_ms);
-            }
-            continue;
+        if let Some(timestamp_ms) = timestamp_ms {
+            session.track_activity(&mut state.last_activity_ms, timestamp_ms);
         }
+        return;
+    }
 
-        if entry_type == "response_item" {
-            let payload = value.get("payload").and_then(|value| value.as_object());
-            let payload_type = payload
-                .and_then(|payload| payload.get("type"))
-                .and_then(|value| value.as_str());
-            let role = payload
-                .and_then(|payload| payload.get("role"))
-                .and_then(|value| value.as_str())
-                .unwrap_or("");
-
-            if role == "assistant" {
-                if let Some(timestamp_ms) = read_timestamp_ms(&value) {
-                    if seen_runs.insert(timestamp_ms) {
-                        if let Some(day_key) = day_key_for_timestamp_ms(timestamp_ms) {
-                            if let Some(entry) = daily.get_mut(&day_key) {
-                                entry.agent_runs += 1;
-                            }
-                        }
-                    }
-                    track_activity(daily, &mut last_activity_ms, timestamp_ms);
-                }
-            } else if payload_type != Some("message") {
-                if let Some(timestamp_ms) = read_timestamp_ms(&value) {
-                    track_activity(daily, &mut last_activity_ms, timestamp_ms);
-                }
+    if entry_type == "response_item" {
+        let payload = value.get("payload").and_then(|value| value.as_object());
+        let payload_type = payload
+            .and_then(|payload| payload.get("type"))
+            .and_then(|value| value.as_str());
+        let role = payload
+            .and_then(|payload| payload.get("role"))
+            .and_then(|value| value.as_str())
+            .unwrap_or("");
+
+        if role == "assistant" {
+            if let Some(timestamp_ms) = read_timestamp_ms(&value) {
+                record_run(session, state, timestamp_ms);
+            }
+        } else if payload_type != Some("message") {
+            if let Some(timestamp_ms) = read_timestamp_ms(&value) {
+                session.track_activity(&mut state.last_activity_ms, timestamp_ms);
             }
         }
     }
+}
 
-    Ok(())
+fn read_usage_counts(map: &serde_json::Map<String, Value>) -> (i64, i64, i64) {
+    (
+        read_i64(map, &["input_tokens", "inputTokens"]),
+        read_i64(
+            map,
+            &[
+                "cached_input_tokens",
+                "cache_read_input_tokens",
+                "cachedInputTokens",
+                "cacheReadInputTokens",
+            ],
+        ),
+        read_i64(map, &["output_tokens", "outputTokens"]),
+    )
 }
 
-fn extract_model_from_turn_context(value: &Value) -> Option<String> {
+pub(crate) fn extract_model_from_turn_context(value: &Value) -> Option<String> {
     let payload = value.get("payload").and_then(|value| value.as_object())?;
     if let Some(model) = payload.get("model").and_then(|value| value.as_str()) {
         return Some(model.to_string());
@@ -456,7 +1278,7 @@ fn read_i64(map: &serde_json::Map<String, Value>, keys: &[&str]) -> i64 {
         .unwrap_or(0)
 }
 
-fn read_timestamp_ms(value: &Value) -> Option<i64> {
+pub(crate) fn read_timestamp_ms(value: &Value) -> Option<i64> {
     let raw = value.get("timestamp")?;
     if let Some(text) = raw.as_str() {
         return DateTime::parse_from_rfc3339(text)
@@ -472,30 +1294,20 @@ fn read_timestamp_ms(value: &Value) -> Option<i64> {
     Some(numeric)
 }
 
-fn track_activity(
-    daily: &mut HashMap<String, DailyTotals>,
-    last_activity_ms: &mut Option<i64>,
-    timestamp_ms: i64,
-) {
-    if let Some(prev_ms) = *last_activity_ms {
-        let delta = timestamp_ms - prev_ms;
-        if delta > 0 && delta <= MAX_ACTIVITY_GAP_MS {
-            if let Some(day_key) = day_key_for_timestamp_ms(timestamp_ms) {
-                if let Some(entry) = daily.get_mut(&day_key) {
-                    entry.agent_ms += delta;
-                }
-            }
-        }
-    }
-    *last_activity_ms = Some(timestamp_ms);
-}
-
 fn day_key_for_timestamp_ms(timestamp_ms: i64) -> Option<String> {
     let utc = Utc.timestamp_millis_opt(timestamp_ms).single()?;
     Some(utc.with_timezone(&Local).format("%Y-%m-%d").to_string())
 }
 
-fn extract_cwd(value: &Value) -> Option<String> {
+pub(crate) fn extract_session_id(value: &Value) -> Option<String> {
+    value
+        .get("payload")
+        .and_then(|payload| payload.get("id"))
+        .and_then(|id| id.as_str())
+        .map(|id| id.to_string())
+}
+
+pub(crate) fn extract_cwd(value: &Value) -> Option<String> {
     value
         .get("payload")
         .and_then(|payload| payload.get("cwd"))
@@ -503,11 +1315,48 @@ fn extract_cwd(value: &Value) -> Option<String> {
         .map(|cwd| cwd.to_string())
 }
 
-fn path_matches_workspace(cwd: &str, workspace_path: &Path) -> bool {
+pub(crate) fn path_matches_workspace(cwd: &str, workspace_path: &Path) -> bool {
     let cwd_path = Path::new(cwd);
     cwd_path == workspace_path || cwd_path.starts_with(workspace_path)
 }
 
+pub(crate) fn parse_day(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date `{value}`, expected YYYY-MM-DD"))
+}
+
+fn resolve_date_range(
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+    today: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let end = match end_date.filter(|value| !value.trim().is_empty()) {
+        Some(value) => parse_day(value)?,
+        None => today,
+    };
+    let start = match start_date.filter(|value| !value.trim().is_empty()) {
+        Some(value) => parse_day(value)?,
+        None => end - Duration::days(DEFAULT_RANGE_DAYS - 1),
+    };
+    if start > end {
+        return Err("Start date must not be after end date".to_string());
+    }
+    if (end - start).num_days() >= MAX_RANGE_DAYS {
+        return Err(format!("Date range cannot exceed {MAX_RANGE_DAYS} days"));
+    }
+    Ok((start, end))
+}
+
+fn day_keys_between(start: NaiveDate, end: NaiveDate) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut day = start;
+    while day <= end {
+        keys.push(day.format("%Y-%m-%d").to_string());
+        day += Duration::days(1);
+    }
+    keys
+}
+
 fn make_day_keys(days: u32) -> Vec<String> {
     let today = Local::now().date_naive();
     (0..days)
@@ -525,7 +1374,7 @@ fn resolve_codex_sessions_root(codex_home_override: Option<PathBuf>) -> Option<P
         .map(|home| home.join("sessions"))
 }
 
-fn resolve_sessions_roots(
+pub(crate) fn resolve_sessions_roots(
     workspaces: &HashMap<String, WorkspaceEntry>,
     workspace_path: Option<&Path>,
 ) -> Vec<PathBuf> {
@@ -598,11 +1447,26 @@ mod tests {
     use super::*;
     use crate::types::{WorkspaceKind, WorkspaceSettings};
     use chrono::NaiveDateTime;
+    use std::fs::File;
     use std::io::Write;
     use std::path::Path;
     use std::{fs, path::PathBuf};
     use uuid::Uuid;
 
+    fn scan_file(
+        path: &Path,
+        scan: &mut UsageScan,
+        workspace_path: Option<&Path>,
+    ) -> Result<(), String> {
+        let Some((file, _)) = index::refresh_file(path, None) else {
+            return Ok(());
+        };
+        if let Some(session) = file.session.for_scan(&scan.window, workspace_path) {
+            scan.sessions.insert(path.to_path_buf(), session);
+        }
+        Ok(())
+    }
+
     fn write_temp_jsonl(lines: &[&str]) -> PathBuf {
         let mut path = std::env::temp_dir();
         path.push(format!(
@@ -642,12 +1506,14 @@ mod tests {
             r#"{"timestamp":"2026-01-19T12:00:01.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":10,"cached_input_tokens":0,"output_tokens":5}}}}"#,
         ]);
 
-        let mut daily: HashMap<String, DailyTotals> = HashMap::new();
-        daily.insert(day_key.to_string(), DailyTotals::default());
-        let mut model_totals: HashMap<String, i64> = HashMap::new();
-        scan_file(&path, &mut daily, &mut model_totals, None).expect("scan file");
+        let mut scan = UsageScan::for_days(&[day_key.to_string()]);
+        scan_file(&path, &mut scan, None).expect("scan file");
 
-        let totals = daily.get(day_key).copied().unwrap_or_default();
+        let totals = scan
+            .daily_totals()
+            .get(day_key)
+            .copied()
+            .unwrap_or_default();
         assert_eq!(totals.input, 10);
         assert_eq!(totals.output, 5);
     }
@@ -660,12 +1526,14 @@ mod tests {
             r#"{"timestamp":"2026-01-19T12:00:01.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":20,"cached_input_tokens":0,"output_tokens":10}}}}"#,
         ]);
 
-        let mut daily: HashMap<String, DailyTotals> = HashMap::new();
-        daily.insert(day_key.to_string(), DailyTotals::default());
-        let mut model_totals: HashMap<String, i64> = HashMap::new();
-        scan_file(&path, &mut daily, &mut model_totals, None).expect("scan file");
+        let mut scan = UsageScan::for_days(&[day_key.to_string()]);
+        scan_file(&path, &mut scan, None).expect("scan file");
 
-        let totals = daily.get(day_key).copied().unwrap_or_default();
+        let totals = scan
+            .daily_totals()
+            .get(day_key)
+            .copied()
+            .unwrap_or_default();
         assert_eq!(totals.input, 20);
         assert_eq!(totals.output, 10);
     }
@@ -679,12 +1547,14 @@ mod tests {
             r#"{"timestamp":"2026-01-19T12:00:02.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":12,"cached_input_tokens":0,"output_tokens":6}}}}"#,
         ]);
 
-        let mut daily: HashMap<String, DailyTotals> = HashMap::new();
-        daily.insert(day_key.to_string(), DailyTotals::default());
-        let mut model_totals: HashMap<String, i64> = HashMap::new();
-        scan_file(&path, &mut daily, &mut model_totals, None).expect("scan file");
+        let mut scan = UsageScan::for_days(&[day_key.to_string()]);
+        scan_file(&path, &mut scan, None).expect("scan file");
 
-        let totals = daily.get(day_key).copied().unwrap_or_default();
+        let totals = scan
+            .daily_totals()
+            .get(day_key)
+            .copied()
+            .unwrap_or_default();
         assert_eq!(totals.input, 12);
         assert_eq!(totals.output, 6);
     }
@@ -697,12 +1567,14 @@ mod tests {
             r#"{"timestamp":"2026-01-19T12:00:05.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":2,"cached_input_tokens":0,"output_tokens":2}}}}"#,
         ]);
 
-        let mut daily: HashMap<String, DailyTotals> = HashMap::new();
-        daily.insert(day_key.to_string(), DailyTotals::default());
-        let mut model_totals: HashMap<String, i64> = HashMap::new();
-        scan_file(&path, &mut daily, &mut model_totals, None).expect("scan file");
+        let mut scan = UsageScan::for_days(&[day_key.to_string()]);
+        scan_file(&path, &mut scan, None).expect("scan file");
 
-        let totals = daily.get(day_key).copied().unwrap_or_default();
+        let totals = scan
+            .daily_totals()
+            .get(day_key)
+            .copied()
+            .unwrap_or_default();
         assert_eq!(totals.agent_ms, 5_000);
     }
 
@@ -714,12 +1586,14 @@ mod tests {
             r#"{"timestamp":"2026-01-19T12:00:10.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"b"}]}}"#,
         ]);
 
-        let mut daily: HashMap<String, DailyTotals> = HashMap::new();
-        daily.insert(day_key.to_string(), DailyTotals::default());
-        let mut model_totals: HashMap<String, i64> = HashMap::new();
-        scan_file(&path, &mut daily, &mut model_totals, None).expect("scan file");
+        let mut scan = UsageScan::for_days(&[day_key.to_string()]);
+        scan_file(&path, &mut scan, None).expect("scan file");
 
-        let totals = daily.get(day_key).copied().unwrap_or_default();
+        let totals = scan
+            .daily_totals()
+            .get(day_key)
+            .copied()
+            .unwrap_or_default();
         assert_eq!(totals.agent_runs, 2);
     }
 
@@ -732,12 +1606,14 @@ mod tests {
             r#"{"timestamp":"2026-01-19T12:10:10.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":3,"cached_input_tokens":0,"output_tokens":3}}}}"#,
         ]);
 
-        let mut daily: HashMap<String, DailyTotals> = HashMap::new();
-        daily.insert(day_key.to_string(), DailyTotals::default());
-        let mut model_totals: HashMap<String, i64> = HashMap::new();
-        scan_file(&path, &mut daily, &mut model_totals, None).expect("scan file");
+        let mut scan = UsageScan::for_days(&[day_key.to_string()]);
+        scan_file(&path, &mut scan, None).expect("scan file");
 
-        let totals = daily.get(day_key).copied().unwrap_or_default();
+        let totals = scan
+            .daily_totals()
+            .get(day_key)
+            .copied()
+            .unwrap_or_default();
         assert_eq!(totals.agent_ms, 10_000);
     }
 
@@ -750,18 +1626,14 @@ mod tests {
             r#"{"timestamp":"2026-01-19T12:00:12.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":10,"cached_input_tokens":0,"output_tokens":5}}}}"#,
         ]);
 
-        let mut daily: HashMap<String, DailyTotals> = HashMap::new();
-        daily.insert(day_key.to_string(), DailyTotals::default());
-        let mut model_totals: HashMap<String, i64> = HashMap::new();
-        scan_file(
-            &path,
-            &mut daily,
-            &mut model_totals,
-            Some(Path::new("/tmp/other-project")),
-        )
-        .expect("scan file");
+        let mut scan = UsageScan::for_days(&[day_key.to_string()]);
+        scan_file(&path, &mut scan, Some(Path::new("/tmp/other-project"))).expect("scan file");
 
-        let totals = daily.get(day_key).copied().unwrap_or_default();
+        let totals = scan
+            .daily_totals()
+            .get(day_key)
+            .copied()
+            .unwrap_or_default();
         assert_eq!(totals.agent_ms, 0);
         assert_eq!(totals.input, 0);
     }
@@ -795,7 +1667,8 @@ mod tests {
         write_session_file(&root_a, &day_key, &[line_a]);
         write_session_file(&root_b, &day_key, &[line_b]);
 
-        let snapshot = scan_local_usage(2, None, &[root_a, root_b]).expect("scan usage");
+        let snapshot = scan_local_usage(2, None, &[root_a, root_b], &UsageContext::default())
+            .expect("scan usage");
         let day = snapshot
             .days
             .iter()
@@ -807,6 +1680,306 @@ mod tests {
         assert_eq!(snapshot.totals.last30_days_tokens, 11);
     }
 
+    #[test]
+    fn build_snapshot_prices_usage_per_model_and_workspace() {
+        let day_key = "2026-01-19";
+        let path = write_temp_jsonl(&[
+            r#"{"timestamp":"2026-01-19T12:00:00.000Z","type":"session_meta","payload":{"cwd":"/tmp/project-alpha/src"}}"#,
+            r#"{"timestamp":"2026-01-19T12:00:01.000Z","type":"turn_context","payload":{"model":"gpt-5-codex"}}"#,
+            r#"{"timestamp":"2026-01-19T12:00:02.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":2000000,"cached_input_tokens":1000000,"output_tokens":100000}}}}"#,
+        ]);
+        let mut scan = UsageScan::for_days(&[day_key.to_string()]);
+        scan_file(&path, &mut scan, None).expect("scan file");
+
+        let context = UsageContext {
+            pricing: vec![
+                UsageModelPricing {
+                    model: "gpt-5".to_string(),
+                    input_per_million: 100.0,
+                    cached_input_per_million: 100.0,
+                    output_per_million: 100.0,
+                },
+                UsageModelPricing {
+                    model: "gpt-5-codex".to_string(),
+                    input_per_million: 1.0,
+                    cached_input_per_million: 0.5,
+                    output_per_million: 10.0,
+                },
+            ],
+            budgets: UsageBudgets {
+                daily_usd: Some(2.0),
+                weekly_usd: Some(10.0),
+                monthly_usd: None,
+            },
+            workspaces: vec![UsageWorkspace {
+                id: "alpha".to_string(),
+                name: "Alpha".to_string(),
+                path: PathBuf::from("/tmp/project-alpha"),
+            }],
+            index_dir: None,
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 1, 19).expect("date");
+        let snapshot = build_snapshot(0, vec![day_key.to_string()], &scan, &context, today);
+
+        // 1M uncached * $1 + 1M cached * $0.5 + 0.1M output * $10.
+        assert_eq!(snapshot.days[0].estimated_cost_usd, 2.5);
+        assert_eq!(snapshot.totals.total_cost_usd, 2.5);
+        assert_eq!(snapshot.model_costs.len(), 1);
+        assert!(snapshot.model_costs[0].priced);
+        assert_eq!(snapshot.workspace_costs.len(), 1);
+        assert_eq!(
+            snapshot.workspace_costs[0].workspace_id.as_deref(),
+            Some("alpha")
+        );
+        assert_eq!(snapshot.workspace_costs[0].estimated_cost_usd, 2.5);
+
+        assert_eq!(snapshot.budgets.len(), 2);
+        let daily = &snapshot.budgets[0];
+        assert_eq!(daily.period, UsageBudgetPeriod::Daily);
+        assert!(daily.exceeded);
+        let weekly = &snapshot.budgets[1];
+        assert_eq!(weekly.period_key, "2026-W04");
+        assert!(!weekly.exceeded);
+    }
+
+    #[test]
+    fn pricing_for_model_requires_exact_or_snapshot_match() {
+        let pricing = crate::types::AppSettings::default().usage_pricing;
+        let matched =
+            |model: &str| pricing_for_model(&pricing, model).map(|entry| entry.model.as_str());
+
+        assert_eq!(matched("gpt-4.1"), Some("gpt-4.1"));
+        assert_eq!(matched("gpt-4.1-2025-04-14"), Some("gpt-4.1"));
+        assert_eq!(matched("GPT-4.1-mini"), Some("gpt-4.1-mini"));
+        assert_eq!(matched("gpt-4.1-nano-2025-04-14"), Some("gpt-4.1-nano"));
+        assert_eq!(matched("gpt-5.1-codex-mini"), Some("gpt-5.1-codex-mini"));
+        assert_eq!(matched("gpt-5-codex"), Some("gpt-5-codex"));
+        assert_eq!(matched("o3-pro"), None);
+        assert_eq!(matched("o3-deep-research"), None);
+        assert_eq!(matched("gpt-5.2"), None);
+        assert_eq!(matched("gpt-5-"), None);
+    }
+
+    #[test]
+    fn budget_window_days_covers_week_and_month_to_date() {
+        let today = NaiveDate::from_ymd_opt(2026, 1, 22).expect("date");
+        let mut budgets = UsageBudgets::default();
+        assert_eq!(budget_window_days(&budgets, today), 0);
+        budgets.weekly_usd = Some(5.0);
+        assert_eq!(budget_window_days(&budgets, today), 4);
+        budgets.monthly_usd = Some(50.0);
+        assert_eq!(budget_window_days(&budgets, today), 22);
+    }
+
+    #[test]
+    fn build_breakdown_ranks_workspaces_and_sessions_for_date_range() {
+        let root = make_temp_sessions_root();
+        write_session_file(
+            &root,
+            "2025-03-10",
+            &[
+                r#"{"timestamp":"2025-03-10T12:00:00.000Z","type":"session_meta","payload":{"id":"thread-a","cwd":"/tmp/project-alpha"}}"#.to_string(),
+                r#"{"timestamp":"2025-03-10T12:00:10.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[]}}"#.to_string(),
+                r#"{"timestamp":"2025-03-10T12:00:20.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":100,"cached_input_tokens":0,"output_tokens":50}}}}"#.to_string(),
+            ],
+        );
+        write_session_file(
+            &root,
+            "2025-03-11",
+            &[
+                r#"{"timestamp":"2025-03-11T12:00:00.000Z","type":"session_meta","payload":{"id":"thread-b","cwd":"/tmp/project-alpha/sub"}}"#.to_string(),
+                r#"{"timestamp":"2025-03-11T12:00:20.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":10,"cached_input_tokens":0,"output_tokens":5}}}}"#.to_string(),
+            ],
+        );
+        write_session_file(
+            &root,
+            "2025-03-11",
+            &[
+                r#"{"timestamp":"2025-03-11T12:00:00.000Z","type":"session_meta","payload":{"id":"thread-c","cwd":"/tmp/elsewhere"}}"#.to_string(),
+                r#"{"timestamp":"2025-03-11T12:00:20.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":500,"cached_input_tokens":0,"output_tokens":0}}}}"#.to_string(),
+            ],
+        );
+        // Outside the requested range.
+        write_session_file(
+            &root,
+            "2025-03-12",
+            &[
+                r#"{"timestamp":"2025-03-12T12:00:00.000Z","type":"session_meta","payload":{"id":"thread-d","cwd":"/tmp/project-alpha"}}"#.to_string(),
+                r#"{"timestamp":"2025-03-12T12:00:20.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":9000,"cached_input_tokens":0,"output_tokens":0}}}}"#.to_string(),
+            ],
+        );
+
+        let (start, end) = resolve_date_range(
+            Some("2025-03-10"),
+            Some("2025-03-11"),
+            NaiveDate::from_ymd_opt(2026, 1, 1).expect("date"),
+        )
+        .expect("range");
+        let scan = scan_sessions(&day_keys_between(start, end), None, &[root], None).expect("scan");
+        let workspaces = vec![UsageWorkspace {
+            id: "alpha".to_string(),
+            name: "Alpha".to_string(),
+            path: PathBuf::from("/tmp/project-alpha"),
+        }];
+
+        let by_workspace = build_breakdown(
+            0,
+            (start, end),
+            UsageBreakdownGroup::Workspace,
+            10,
+            &scan,
+            &[],
+            &workspaces,
+        );
+        assert_eq!(by_workspace.total_tokens, 665);
+        assert_eq!(by_workspace.entries.len(), 2);
+        assert_eq!(by_workspace.entries[0].key, "/tmp/elsewhere");
+        let alpha = &by_workspace.entries[1];
+        assert_eq!(alpha.workspace_id.as_deref(), Some("alpha"));
+        assert_eq!(alpha.sessions, 2);
+        assert_eq!(alpha.total_tokens, 165);
+        assert_eq!(alpha.agent_runs, 1);
+        assert_eq!(alpha.agent_time_ms, 10_000);
+        assert_eq!(alpha.first_day.as_deref(), Some("2025-03-10"));
+        assert_eq!(alpha.last_day.as_deref(), Some("2025-03-11"));
+
+        let by_session = build_breakdown(
+            0,
+            (start, end),
+            UsageBreakdownGroup::Session,
+            1,
+            &scan,
+            &[],
+            &workspaces,
+        );
+        assert_eq!(by_session.total_entries, 3);
+        assert_eq!(by_session.entries.len(), 1);
+        assert_eq!(by_session.entries[0].thread_id.as_deref(), Some("thread-c"));
+    }
+
+    #[test]
+    fn scan_sessions_with_index_parses_only_appended_lines() {
+        let root = make_temp_sessions_root();
+        let data_dir = make_temp_sessions_root();
+        let day_key = "2025-03-10";
+        let path = write_session_file(
+            &root,
+            day_key,
+            &[
+                r#"{"timestamp":"2025-03-10T12:00:00.000Z","type":"session_meta","payload":{"id":"thread-a","cwd":"/tmp/project-alpha"}}"#.to_string(),
+                r#"{"timestamp":"2025-03-10T12:00:10.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":10,"cached_input_tokens":0,"output_tokens":5}}}}"#.to_string(),
+            ],
+        );
+        let day_keys = vec![day_key.to_string()];
+        let roots = vec![root.clone()];
+        let day_tokens = |scan: &UsageScan| {
+            let totals = scan
+                .daily_totals()
+                .get(day_key)
+                .copied()
+                .unwrap_or_default();
+            (totals.input, totals.output, totals.agent_ms)
+        };
+
+        let scan = scan_sessions(&day_keys, None, &roots, Some(&data_dir)).expect("first scan");
+        assert_eq!(day_tokens(&scan), (10, 5, 0));
+        assert!(data_dir.join("local-usage-index.json").exists());
+
+        // A cumulative total followed by a line that is still being written.
+        let mut file = fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open session");
+        write!(
+            file,
+            "{}\n{}",
+            r#"{"timestamp":"2025-03-10T12:00:20.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":30,"cached_input_tokens":0,"output_tokens":8}}}}"#,
+            r#"{"timestamp":"2025-03-10T12:00:30.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":50"#,
+        )
+        .expect("append");
+        let scan = scan_sessions(&day_keys, None, &roots, Some(&data_dir)).expect("second scan");
+        assert_eq!(day_tokens(&scan), (30, 8, 10_000));
+
+        writeln!(
+            file,
+            "{}",
+            r#","cached_input_tokens":0,"output_tokens":9}}}}"#
+        )
+        .expect("finish line");
+        let scan = scan_sessions(&day_keys, None, &roots, Some(&data_dir)).expect("third scan");
+        assert_eq!(day_tokens(&scan), (50, 9, 20_000));
+
+        let stored = fs::read_to_string(data_dir.join("local-usage-index.json")).expect("index");
+        assert!(stored.contains("thread-a"));
+    }
+
+    #[test]
+    fn build_export_rows_groups_by_day_model_and_workspace() {
+        let root = make_temp_sessions_root();
+        write_session_file(
+            &root,
+            "2025-03-10",
+            &[
+                r#"{"timestamp":"2025-03-10T12:00:00.000Z","type":"session_meta","payload":{"id":"thread-a","cwd":"/tmp/project-alpha/app"}}"#.to_string(),
+                r#"{"timestamp":"2025-03-10T12:00:01.000Z","type":"turn_context","payload":{"cwd":"/tmp/project-alpha/app","model":"gpt-5-codex"}}"#.to_string(),
+                r#"{"timestamp":"2025-03-10T12:00:10.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":1000000,"cached_input_tokens":0,"output_tokens":0}}}}"#.to_string(),
+            ],
+        );
+        write_session_file(
+            &root,
+            "2025-03-10",
+            &[
+                r#"{"timestamp":"2025-03-10T13:00:00.000Z","type":"session_meta","payload":{"id":"thread-b","cwd":"/tmp/odd, "dir""}}"#.to_string(),
+                r#"{"timestamp":"2025-03-10T13:00:10.000Z","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":7,"cached_input_tokens":0,"output_tokens":3}}}}"#.to_string(),
+            ],
+        );
+        let scan = scan_sessions(&["2025-03-10".to_string()], None, &[root], None).expect("scan");
+        let pricing = vec![UsageModelPricing {
+            model: "gpt-5-codex".to_string(),
+            input_per_million: 2.0,
+            cached_input_per_million: 0.0,
+            output_per_million: 0.0,
+        }];
+        let workspaces = vec![UsageWorkspace {
+            id: "alpha".to_string(),
+            name: "Alpha".to_string(),
+            path: PathBuf::from("/tmp/project-alpha"),
+        }];
+
+        let rows = build_export_rows(&scan, &pricing, &workspaces);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].path, "/tmp/odd, \"dir\"");
+        assert_eq!(rows[0].model, "unknown");
+        assert_eq!(rows[0].total_tokens, 10);
+        assert_eq!(rows[1].workspace_id.as_deref(), Some("alpha"));
+        assert_eq!(rows[1].path, "/tmp/project-alpha");
+        assert_eq!(rows[1].estimated_cost_usd, 2.0);
+
+        let csv = render_export_csv(&rows);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("day,model,workspace_id"));
+        assert_eq!(
+            lines[1],
+            "2025-03-10,unknown,,,\"/tmp/odd, \"\"dir\"\"\",7,0,3,10,0.0000"
+        );
+        assert_eq!(
+            lines[2],
+            "2025-03-10,gpt-5-codex,alpha,Alpha,/tmp/project-alpha,1000000,0,0,1000000,2.0000"
+        );
+    }
+
+    #[test]
+    fn resolve_date_range_validates_bounds() {
+        let today = NaiveDate::from_ymd_opt(2026, 1, 31).expect("date");
+        let (start, end) = resolve_date_range(None, None, today).expect("default range");
+        assert_eq!(start, NaiveDate::from_ymd_opt(2026, 1, 2).expect("date"));
+        assert_eq!(end, today);
+        assert!(resolve_date_range(Some("2026-02-01"), Some("2026-01-01"), today).is_err());
+        assert!(resolve_date_range(Some("2024-01-01"), Some("2026-01-01"), today).is_err());
+        assert!(resolve_date_range(Some("01/02/2026"), None, today).is_err());
+    }
+
     #[test]
     fn resolve_sessions_roots_uses_single_default_root() {
         let mut workspaces = HashMap::new();
@@ -0,0 +1,165 @@
+use serde::ser::Serializer;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::UNIX_EPOCH;
+
+use super::{process_line, ParseState, SessionUsage, UsageKey, UsageTotals};
+
+const INDEX_FILE: &str = "local-usage-index.json";
+const INDEX_VERSION: u32 = 1;
+
+/// Loaded indexes keyed by their data dir, so repeated snapshots only stat
+/// session files instead of re-reading the index from disk.
+static USAGE_INDEXES: OnceLock<StdMutex<HashMap<PathBuf, UsageIndex>>> = OnceLock::new();
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct IndexedFile {
+    size: u64,
+    modified_ms: i64,
+    /// Byte offset just past the last complete line that was parsed.
+    offset: u64,
+    state: ParseState,
+    pub(super) session: SessionUsage,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub(super) struct UsageIndex {
+    version: u32,
+    files: HashMap<String, IndexedFile>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl UsageIndex {
+    /// Returns the usage for `path`, parsing only lines appended since the
+    /// file was last indexed.
+    pub(super) fn session(&mut self, path: &Path) -> Option<&SessionUsage> {
+        let key = path.to_string_lossy().to_string();
+        let cached = self.files.remove(&key);
+        let Some((file, changed)) = refresh_file(path, cached) else {
+            self.dirty = true;
+            return None;
+        };
+        self.dirty |= changed;
+        let file = self.files.entry(key).or_insert(file);
+        Some(&file.session)
+    }
+
+    fn load(path: &Path) -> Self {
+        let index = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<UsageIndex>(&data).ok())
+            .filter(|index| index.version == INDEX_VERSION);
+        index.unwrap_or_else(|| UsageIndex {
+            version: INDEX_VERSION,
+            ..UsageIndex::default()
+        })
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), String> {
+        self.files.retain(|file, _| Path::new(file).exists());
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let data = serde_json::to_vec(self).map_err(|err| err.to_string())?;
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, data).map_err(|err| err.to_string())?;
+        std::fs::rename(&temp_path, path).map_err(|err| err.to_string())?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Runs `f` against the persisted index for `index_dir`, writing it back
+/// when any session file changed. Without a dir a throwaway index is used.
+pub(super) fn with_usage_index<T>(
+    index_dir: Option<&Path>,
+    f: impl FnOnce(&mut UsageIndex) -> T,
+) -> T {
+    let Some(index_dir) = index_dir else {
+        return f(&mut UsageIndex::default());
+    };
+    let index_path = index_dir.join(INDEX_FILE);
+    let indexes = USAGE_INDEXES.get_or_init(|| StdMutex::new(HashMap::new()));
+    let mut indexes = indexes
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let index = indexes
+        .entry(index_dir.to_path_buf())
+        .or_insert_with(|| UsageIndex::load(&index_path));
+    let result = f(index);
+    if index.dirty {
+        if let Err(err) = index.save(&index_path) {
+            eprintln!("local usage: failed to persist index: {err}");
+        }
+    }
+    result
+}
+
+/// Brings `cached` up to date with the file on disk. Appended lines are
+/// parsed from the stored offset; a shrunk or rewritten file is reparsed.
+/// The flag reports whether anything was read.
+pub(super) fn refresh_file(
+    path: &Path,
+    cached: Option<IndexedFile>,
+) -> Option<(IndexedFile, bool)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let size = metadata.len();
+    let modified_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0);
+    let mut file = match cached {
+        Some(cached) if cached.size == size && cached.modified_ms == modified_ms => {
+            return Some((cached, false));
+        }
+        Some(cached) if size > cached.size && cached.offset <= size => cached,
+        _ => IndexedFile::default(),
+    };
+
+    let mut handle = File::open(path).ok()?;
+    handle.seek(SeekFrom::Start(file.offset)).ok()?;
+    let mut reader = BufReader::new(handle);
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        let read = match reader.read_until(b'\n', &mut buffer) {
+            Ok(read) => read,
+            Err(_) => break,
+        };
+        // A trailing line without a newline may still be mid-write; it is
+        // picked up on the next refresh once complete.
+        if read == 0 || buffer.last() != Some(&b'\n') {
+            break;
+        }
+        file.offset += read as u64;
+        if let Ok(line) = std::str::from_utf8(&buffer) {
+            process_line(line.trim_end(), &mut file.state, &mut file.session);
+        }
+    }
+    file.state.prune_seen_runs();
+    file.size = size;
+    file.modified_ms = modified_ms;
+    Some((file, true))
+}
+
+pub(super) fn serialize_usage<S: Serializer>(
+    usage: &HashMap<UsageKey, UsageTotals>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(usage.iter())
+}
+
+pub(super) fn deserialize_usage<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<UsageKey, UsageTotals>, D::Error> {
+    let entries = Vec::<(UsageKey, UsageTotals)>::deserialize(deserializer)?;
+    Ok(entries.into_iter().collect())
+}
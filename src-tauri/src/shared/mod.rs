@@ -1,5 +1,8 @@
 pub(crate) mod account;
+pub(crate) mod agent_audit_core;
 pub(crate) mod agents_config_core;
+pub(crate) mod auto_approval_core;
+pub(crate) mod batch_runs_core;
 pub(crate) mod codex_aux_core;
 pub(crate) mod codex_core;
 pub(crate) mod codex_update_core;
@@ -11,7 +14,14 @@ pub(crate) mod git_ui_core;
 pub(crate) mod local_usage_core;
 pub(crate) mod process_core;
 pub(crate) mod prompts_core;
+pub(crate) mod schedules_core;
 pub(crate) mod settings_core;
+pub(crate) mod store_helpers;
+pub(crate) mod thread_export_core;
+pub(crate) mod thread_search_core;
+pub(crate) mod turn_context_core;
+pub(crate) mod webhooks_core;
+pub(crate) mod workspace_bundle_core;
 pub(crate) mod workspace_rpc;
 pub(crate) mod workspaces_core;
 pub(crate) mod worktree_core;
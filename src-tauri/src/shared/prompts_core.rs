@@ -1,12 +1,28 @@
-use serde::Serialize;
-use std::collections::HashMap;
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use tokio::sync::Mutex;
 use tokio::task;
 
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
-use crate::types::WorkspaceEntry;
+use crate::git_utils::resolve_git_root;
+use crate::shared::git_ui_core;
+use crate::types::{AppSettings, GitHubIssueDetail, PromptLibraryStatus, WorkspaceEntry};
+
+#[path = "prompts_core/history.rs"]
+mod history;
+#[path = "prompts_core/libraries.rs"]
+mod libraries;
+
+pub(crate) use history::PromptRevision;
+
+const MAX_RENDERED_DIFF_BYTES: usize = 200_000;
+const MAX_RENDERED_FILE_BYTES: usize = 100_000;
+const MAX_RENDERED_ISSUE_BYTES: usize = 50_000;
 
 #[derive(Serialize, Clone)]
 pub(crate) struct CustomPromptEntry {
@@ -15,9 +31,15 @@ pub(crate) struct CustomPromptEntry {
     pub(crate) description: Option<String>,
     #[serde(rename = "argumentHint")]
     pub(crate) argument_hint: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) arguments: Vec<PromptArgument>,
     pub(crate) content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) scope: Option<String>,
+    #[serde(rename = "libraryId", skip_serializing_if = "Option::is_none")]
+    pub(crate) library_id: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) overridden: bool,
 }
 
 fn resolve_codex_home_for_workspace(
@@ -55,7 +77,10 @@ fn app_data_dir(settings_path: &Path) -> Result<PathBuf, String> {
         .ok_or_else(|| "Unable to resolve app data dir.".to_string())
 }
 
-fn workspace_prompts_dir(settings_path: &Path, entry: &WorkspaceEntry) -> Result<PathBuf, String> {
+pub(crate) fn workspace_prompts_dir(
+    settings_path: &Path,
+    entry: &WorkspaceEntry,
+) -> Result<PathBuf, String> {
     let data_dir = app_data_dir(settings_path)?;
     Ok(data_dir.join("workspaces").join(&entry.id).join("prompts"))
 }
@@ -70,9 +95,106 @@ fn prompt_roots_for_workspace(
     if let Some(global_dir) = default_prompts_dir_for_workspace(workspaces, entry) {
         roots.push(global_dir);
     }
+    for location in libraries::library_locations(&app_data_dir(settings_path)?) {
+        roots.push(location.prompts_dir);
+        roots.push(location.overrides_dir);
+    }
     Ok(roots)
 }
 
+/// Returns the library a prompt path belongs to and whether it is a local
+/// override rather than the read-only upstream copy.
+fn library_for_path(
+    settings_path: &Path,
+    path: &Path,
+) -> Option<(libraries::LibraryLocation, bool)> {
+    let data_dir = app_data_dir(settings_path).ok()?;
+    let canonical_path = path.canonicalize().ok()?;
+    let within = |dir: &Path| {
+        dir.canonicalize()
+            .is_ok_and(|dir| canonical_path.starts_with(dir))
+    };
+    libraries::library_locations(&data_dir)
+        .into_iter()
+        .find_map(|location| {
+            if within(&location.overrides_dir) {
+                Some((location, true))
+            } else if within(&location.prompts_dir) {
+                Some((location, false))
+            } else {
+                None
+            }
+        })
+}
+
+/// Like `ensure_path_within_roots`, but also accepts prompts that were deleted
+/// as long as their directory is still an allowed root.
+fn ensure_prompt_location_allowed(path: &Path, roots: &[PathBuf]) -> Result<(), String> {
+    if path.exists() {
+        return ensure_path_within_roots(path, roots);
+    }
+    let parent = path.parent().ok_or("Invalid prompt path.".to_string())?;
+    ensure_path_within_roots(parent, roots)
+}
+
+async fn require_prompt_location(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    settings_path: &Path,
+    workspace_id: &str,
+    path: &Path,
+) -> Result<WorkspaceEntry, String> {
+    let workspaces = workspaces.lock().await;
+    let entry = require_workspace_entry(&workspaces, workspace_id)?;
+    let roots = prompt_roots_for_workspace(settings_path, &workspaces, &entry)?;
+    ensure_prompt_location_allowed(path, &roots)?;
+    Ok(entry)
+}
+
+/// History bookkeeping is best effort; a failed snapshot never blocks an edit.
+fn snapshot_prompt_baseline(settings_path: &Path, prompt_path: &Path) {
+    if let Ok(data_dir) = app_data_dir(settings_path) {
+        let _ = history::ensure_baseline(&data_dir, prompt_path);
+    }
+}
+
+fn record_prompt_revision(settings_path: &Path, prompt_path: &Path, content: &str, action: &str) {
+    if let Ok(data_dir) = app_data_dir(settings_path) {
+        let _ = history::record_revision(&data_dir, prompt_path, content, action);
+    }
+}
+
+fn move_prompt_history(settings_path: &Path, from: &Path, to: &Path) {
+    if let Ok(data_dir) = app_data_dir(settings_path) {
+        let _ = history::move_history(&data_dir, from, to);
+    }
+}
+
+/// Writes a prompt file outside the editor (e.g. a bundle import), keeping the
+/// revision history so the overwrite can be restored.
+pub(crate) fn write_prompt_file(
+    settings_path: &Path,
+    path: &Path,
+    content: &str,
+    action: &str,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    snapshot_prompt_baseline(settings_path, path);
+    fs::write(path, content).map_err(|err| err.to_string())?;
+    record_prompt_revision(settings_path, path, content, action);
+    Ok(())
+}
+
+fn ensure_not_library_base(settings_path: &Path, path: &Path) -> Result<(), String> {
+    match library_for_path(settings_path, path) {
+        Some((_, false)) => {
+            Err("Library prompts are read-only; edit them to create a local override.".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
 fn ensure_path_within_roots(path: &Path, roots: &[PathBuf]) -> Result<(), String> {
     let canonical_path = path
         .canonicalize()
@@ -87,6 +209,23 @@ fn ensure_path_within_roots(path: &Path, roots: &[PathBuf]) -> Result<(), String
     Err("Prompt path is not within allowed directories.".to_string())
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptRenderResult {
+    pub(crate) content: String,
+    pub(crate) arguments: BTreeMap<String, String>,
+    pub(crate) unresolved: Vec<String>,
+    pub(crate) truncated: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptRevisionDiff {
+    pub(crate) from_revision: String,
+    pub(crate) to_revision: Option<String>,
+    pub(crate) diff: String,
+}
+
 #[cfg(unix)]
 fn is_cross_device_error(err: &std::io::Error) -> bool {
     err.raw_os_error() == Some(libc::EXDEV)
@@ -108,71 +247,227 @@ fn move_file(src: &Path, dest: &Path) -> Result<(), String> {
     }
 }
 
-fn parse_frontmatter(content: &str) -> (Option<String>, Option<String>, String) {
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PromptArgumentKind {
+    String,
+    Number,
+    Boolean,
+    Choice,
+}
+
+impl Default for PromptArgumentKind {
+    fn default() -> Self {
+        Self::String
+    }
+}
+
+impl PromptArgumentKind {
+    fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "number" | "int" | "integer" | "float" => Self::Number,
+            "boolean" | "bool" => Self::Boolean,
+            "choice" | "enum" => Self::Choice,
+            _ => Self::String,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::Choice => "choice",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptArgument {
+    pub(crate) name: String,
+    #[serde(default, rename = "type")]
+    pub(crate) kind: PromptArgumentKind,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) default: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) choices: Vec<String>,
+    #[serde(default)]
+    pub(crate) required: bool,
+}
+
+impl PromptArgument {
+    fn set_field(&mut self, key: &str, value: String) {
+        match key {
+            "name" => self.name = value,
+            "type" | "kind" => self.kind = PromptArgumentKind::parse(&value),
+            "description" => self.description = Some(value),
+            "default" => self.default = Some(value),
+            "choices" | "options" => self.choices = parse_inline_list(&value),
+            "required" => {
+                self.required =
+                    value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("yes")
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Option<Self> {
+        let valid_name = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+        if !valid_name {
+            return None;
+        }
+        if !self.choices.is_empty() {
+            self.kind = PromptArgumentKind::Choice;
+        }
+        Some(self)
+    }
+}
+
+struct PromptFrontmatter {
+    description: Option<String>,
+    argument_hint: Option<String>,
+    arguments: Vec<PromptArgument>,
+    body: String,
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    if value.len() >= 2 {
+        let bytes = value.as_bytes();
+        let first = bytes[0];
+        let last = bytes[bytes.len() - 1];
+        if (first == b'"' && last == b'"') || (first == b'\'' && last == b'\'') {
+            return value[1..value.len() - 1].replace("\\\"", "\"");
+        }
+    }
+    value.to_string()
+}
+
+fn parse_inline_list(value: &str) -> Vec<String> {
+    let value = value.trim();
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(value);
+    inner
+        .split([',', '|'])
+        .map(unquote)
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn parse_frontmatter(content: &str) -> PromptFrontmatter {
+    let mut frontmatter = PromptFrontmatter {
+        description: None,
+        argument_hint: None,
+        arguments: Vec::new(),
+        body: content.to_string(),
+    };
     let mut segments = content.split_inclusive('\n');
     let Some(first_segment) = segments.next() else {
-        return (None, None, String::new());
+        frontmatter.body = String::new();
+        return frontmatter;
     };
     let first_line = first_segment.trim_end_matches(['\r', '\n']);
     if first_line.trim() != "---" {
-        return (None, None, content.to_string());
+        return frontmatter;
     }
 
     let mut description: Option<String> = None;
     let mut argument_hint: Option<String> = None;
+    let mut arguments: Vec<PromptArgument> = Vec::new();
+    // `arguments:` opens an indented list of `- name: ...` items; it ends at
+    // the next unindented key.
+    let mut in_arguments = false;
+    let mut current_argument: Option<PromptArgument> = None;
     let mut frontmatter_closed = false;
     let mut consumed = first_segment.len();
 
     for segment in segments {
         let line = segment.trim_end_matches(['\r', '\n']);
         let trimmed = line.trim();
+        consumed += segment.len();
 
         if trimmed == "---" {
             frontmatter_closed = true;
-            consumed += segment.len();
             break;
         }
 
         if trimmed.is_empty() || trimmed.starts_with('#') {
-            consumed += segment.len();
             continue;
         }
 
-        if let Some((key, value)) = trimmed.split_once(':') {
-            let mut val = value.trim().to_string();
-            if val.len() >= 2 {
-                let bytes = val.as_bytes();
-                let first = bytes[0];
-                let last = bytes[bytes.len() - 1];
-                if (first == b'"' && last == b'"') || (first == b'\'' && last == b'\'') {
-                    val = val[1..val.len().saturating_sub(1)].to_string();
+        let indented = line.starts_with([' ', '\t']) || trimmed.starts_with("- ");
+        if in_arguments && indented {
+            let item = match trimmed.strip_prefix("- ") {
+                Some(rest) => {
+                    if let Some(argument) = current_argument.take().and_then(PromptArgument::finish)
+                    {
+                        arguments.push(argument);
+                    }
+                    current_argument = Some(PromptArgument::default());
+                    rest.trim()
                 }
+                None => trimmed,
+            };
+            if let (Some(argument), Some((key, value))) =
+                (current_argument.as_mut(), item.split_once(':'))
+            {
+                argument.set_field(&key.trim().to_ascii_lowercase(), unquote(value));
+            }
+            continue;
+        }
+        if in_arguments {
+            in_arguments = false;
+            if let Some(argument) = current_argument.take().and_then(PromptArgument::finish) {
+                arguments.push(argument);
             }
+        }
+
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let val = unquote(value);
             match key.trim().to_ascii_lowercase().as_str() {
                 "description" => description = Some(val),
                 "argument-hint" | "argument_hint" => argument_hint = Some(val),
+                "arguments" => in_arguments = true,
                 _ => {}
             }
         }
-
-        consumed += segment.len();
     }
 
     if !frontmatter_closed {
-        return (None, None, content.to_string());
+        return frontmatter;
+    }
+    if let Some(argument) = current_argument.take().and_then(PromptArgument::finish) {
+        arguments.push(argument);
     }
 
-    let body = if consumed >= content.len() {
+    frontmatter.description = description;
+    frontmatter.argument_hint = argument_hint;
+    frontmatter.arguments = arguments;
+    frontmatter.body = if consumed >= content.len() {
         String::new()
     } else {
         content[consumed..].to_string()
     };
-    (description, argument_hint, body)
+    frontmatter
+}
+
+fn quote_frontmatter_value(value: &str) -> String {
+    format!("\"{}\"", value.trim().replace('"', "\\\""))
 }
 
 fn build_prompt_contents(
     description: Option<String>,
     argument_hint: Option<String>,
+    arguments: &[PromptArgument],
     content: String,
 ) -> String {
     let has_meta = description
@@ -180,7 +475,8 @@ fn build_prompt_contents(
         .is_some_and(|value| !value.trim().is_empty())
         || argument_hint
             .as_ref()
-            .is_some_and(|value| !value.trim().is_empty());
+            .is_some_and(|value| !value.trim().is_empty())
+        || !arguments.is_empty();
     if !has_meta {
         return content;
     }
@@ -189,8 +485,8 @@ fn build_prompt_contents(
         let trimmed = description.trim();
         if !trimmed.is_empty() {
             output.push_str(&format!(
-                "description: \"{}\"\n",
-                trimmed.replace('"', "\\\"")
+                "description: {}\n",
+                quote_frontmatter_value(trimmed)
             ));
         }
     }
@@ -198,17 +494,42 @@ fn build_prompt_contents(
         let trimmed = argument_hint.trim();
         if !trimmed.is_empty() {
             output.push_str(&format!(
-                "argument-hint: \"{}\"\n",
-                trimmed.replace('"', "\\\"")
+                "argument-hint: {}\n",
+                quote_frontmatter_value(trimmed)
             ));
         }
     }
+    if !arguments.is_empty() {
+        output.push_str("arguments:\n");
+        for argument in arguments {
+            output.push_str(&format!("  - name: {}\n", argument.name));
+            output.push_str(&format!("    type: {}\n", argument.kind.as_str()));
+            if let Some(description) = argument.description.as_deref() {
+                output.push_str(&format!(
+                    "    description: {}\n",
+                    quote_frontmatter_value(description)
+                ));
+            }
+            if let Some(default) = argument.default.as_deref() {
+                output.push_str(&format!(
+                    "    default: {}\n",
+                    quote_frontmatter_value(default)
+                ));
+            }
+            if !argument.choices.is_empty() {
+                output.push_str(&format!("    choices: [{}]\n", argument.choices.join(", ")));
+            }
+            if argument.required {
+                output.push_str("    required: true\n");
+            }
+        }
+    }
     output.push_str("---\n");
     output.push_str(&content);
     output
 }
 
-fn sanitize_prompt_name(name: &str) -> Result<String, String> {
+pub(crate) fn sanitize_prompt_name(name: &str) -> Result<String, String> {
     let trimmed = name.trim();
     if trimmed.is_empty() {
         return Err("Prompt name is required.".to_string());
@@ -222,6 +543,23 @@ fn sanitize_prompt_name(name: &str) -> Result<String, String> {
     Ok(trimmed.to_string())
 }
 
+fn read_prompt_entry(path: &Path, scope: Option<&str>) -> Option<CustomPromptEntry> {
+    let name = path.file_stem().and_then(|s| s.to_str())?.to_string();
+    let content = fs::read_to_string(path).ok()?;
+    let frontmatter = parse_frontmatter(&content);
+    Some(CustomPromptEntry {
+        name,
+        path: path.to_string_lossy().to_string(),
+        description: frontmatter.description,
+        argument_hint: frontmatter.argument_hint,
+        arguments: frontmatter.arguments,
+        content: frontmatter.body,
+        scope: scope.map(|value| value.to_string()),
+        library_id: None,
+        overridden: false,
+    })
+}
+
 fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry> {
     let mut out: Vec<CustomPromptEntry> = Vec::new();
     let entries = match fs::read_dir(dir) {
@@ -243,29 +581,28 @@ fn discover_prompts_in(dir: &Path, scope: Option<&str>) -> Vec<CustomPromptEntry
         if !is_md {
             continue;
         }
-        let Some(name) = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .map(str::to_string)
-        else {
-            continue;
-        };
-        let content = match fs::read_to_string(&path) {
-            Ok(content) => content,
-            Err(_) => continue,
-        };
-        let (description, argument_hint, body) = parse_frontmatter(&content);
+        if let Some(prompt) = read_prompt_entry(&path, scope) {
+            out.push(prompt);
+        }
+    }
+
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    out
+}
+
+fn discover_library_prompts(location: &libraries::LibraryLocation) -> Vec<CustomPromptEntry> {
+    let mut out = discover_prompts_in(&location.prompts_dir, Some("library"));
+    for prompt in discover_prompts_in(&location.overrides_dir, Some("library")) {
+        out.retain(|base| base.name != prompt.name);
         out.push(CustomPromptEntry {
-            name,
-            path: path.to_string_lossy().to_string(),
-            description,
-            argument_hint,
-            content: body,
-            scope: scope.map(|value| value.to_string()),
+            overridden: true,
+            ..prompt
         });
     }
-
     out.sort_by(|a, b| a.name.cmp(&b.name));
+    for prompt in &mut out {
+        prompt.library_id = Some(location.id.clone());
+    }
     out
 }
 
@@ -285,6 +622,9 @@ pub(crate) async fn prompts_list_core(
             .and_then(|entry| default_prompts_dir_for_workspace(&workspaces, entry));
         (workspace_dir, global_dir)
     };
+    let library_locations = app_data_dir(settings_path)
+        .map(|data_dir| libraries::library_locations(&data_dir))
+        .unwrap_or_default();
 
     task::spawn_blocking(move || {
         let mut out = Vec::new();
@@ -296,6 +636,9 @@ pub(crate) async fn prompts_list_core(
             let _ = fs::create_dir_all(&dir);
             out.extend(discover_prompts_in(&dir, Some("global")));
         }
+        for location in &library_locations {
+            out.extend(discover_library_prompts(location));
+        }
         out
     })
     .await
@@ -362,15 +705,24 @@ pub(crate) async fn prompts_create_core(
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|err| err.to_string())?;
     }
-    let body = build_prompt_contents(description.clone(), argument_hint.clone(), content.clone());
-    fs::write(&path, body).map_err(|err| err.to_string())?;
+    let body = build_prompt_contents(
+        description.clone(),
+        argument_hint.clone(),
+        &[],
+        content.clone(),
+    );
+    fs::write(&path, &body).map_err(|err| err.to_string())?;
+    record_prompt_revision(settings_path, &path, &body, "create");
     Ok(CustomPromptEntry {
         name,
         path: path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        arguments: Vec::new(),
         content,
         scope: Some(resolved_scope.to_string()),
+        library_id: None,
+        overridden: false,
     })
 }
 
@@ -395,19 +747,49 @@ pub(crate) async fn prompts_update_core(
         let roots = prompt_roots_for_workspace(settings_path, &workspaces, &entry)?;
         ensure_path_within_roots(&target_path, &roots)?;
     }
-    let dir = target_path
-        .parent()
-        .ok_or("Unable to resolve prompt directory.".to_string())?;
+    // Edits to a library prompt are written as a local override so the
+    // synced checkout stays untouched.
+    let library = library_for_path(settings_path, &target_path);
+    let editing_library_base = matches!(library, Some((_, false)));
+    let dir = match &library {
+        Some((location, false)) => {
+            fs::create_dir_all(&location.overrides_dir).map_err(|err| err.to_string())?;
+            location.overrides_dir.clone()
+        }
+        _ => target_path
+            .parent()
+            .ok_or("Unable to resolve prompt directory.".to_string())?
+            .to_path_buf(),
+    };
     let next_path = dir.join(format!("{name}.md"));
     if next_path != target_path && next_path.exists() {
         return Err("Prompt with that name already exists.".to_string());
     }
-    let body = build_prompt_contents(description.clone(), argument_hint.clone(), content.clone());
-    fs::write(&next_path, body).map_err(|err| err.to_string())?;
-    if next_path != target_path {
+    // The editor only round-trips description and argument hint, so keep any
+    // typed arguments already declared in the file.
+    let arguments = fs::read_to_string(&target_path)
+        .map(|existing| parse_frontmatter(&existing).arguments)
+        .unwrap_or_default();
+    let body = build_prompt_contents(
+        description.clone(),
+        argument_hint.clone(),
+        &arguments,
+        content.clone(),
+    );
+    if editing_library_base {
+        snapshot_prompt_baseline(settings_path, &next_path);
+    } else {
+        snapshot_prompt_baseline(settings_path, &target_path);
+    }
+    fs::write(&next_path, &body).map_err(|err| err.to_string())?;
+    if next_path != target_path && !editing_library_base {
         fs::remove_file(&target_path).map_err(|err| err.to_string())?;
+        move_prompt_history(settings_path, &target_path, &next_path);
     }
-    let scope = {
+    record_prompt_revision(settings_path, &next_path, &body, "update");
+    let scope = if library.is_some() {
+        Some("library".to_string())
+    } else {
         let workspaces = workspaces.lock().await;
         let entry = require_workspace_entry(&workspaces, &workspace_id)?;
         let workspace_dir = workspace_prompts_dir(settings_path, &entry)?;
@@ -422,8 +804,11 @@ pub(crate) async fn prompts_update_core(
         path: next_path.to_string_lossy().to_string(),
         description,
         argument_hint,
+        arguments,
         content,
         scope,
+        library_id: library.as_ref().map(|(location, _)| location.id.clone()),
+        overridden: library.is_some(),
     })
 }
 
@@ -443,6 +828,12 @@ pub(crate) async fn prompts_delete_core(
         let roots = prompt_roots_for_workspace(settings_path, &workspaces, &entry)?;
         ensure_path_within_roots(&target, &roots)?;
     }
+    ensure_not_library_base(settings_path, &target)?;
+    // Keep the final content so a deleted prompt can be restored later.
+    snapshot_prompt_baseline(settings_path, &target);
+    if let Ok(content) = fs::read_to_string(&target) {
+        record_prompt_revision(settings_path, &target, &content, "delete");
+    }
     fs::remove_file(&target).map_err(|err| err.to_string())
 }
 
@@ -463,6 +854,7 @@ pub(crate) async fn prompts_move_core(
         prompt_roots_for_workspace(settings_path, &workspaces, &entry)?
     };
     ensure_path_within_roots(&target_path, &roots)?;
+    ensure_not_library_base(settings_path, &target_path)?;
     let file_name = target_path
         .file_name()
         .and_then(|value| value.to_str())
@@ -488,8 +880,9 @@ pub(crate) async fn prompts_move_core(
         fs::create_dir_all(parent).map_err(|err| err.to_string())?;
     }
     move_file(&target_path, &next_path)?;
+    move_prompt_history(settings_path, &target_path, &next_path);
     let content = fs::read_to_string(&next_path).unwrap_or_default();
-    let (description, argument_hint, body) = parse_frontmatter(&content);
+    let frontmatter = parse_frontmatter(&content);
     let name = next_path
         .file_stem()
         .and_then(|value| value.to_str())
@@ -498,9 +891,513 @@ pub(crate) async fn prompts_move_core(
     Ok(CustomPromptEntry {
         name,
         path: next_path.to_string_lossy().to_string(),
-        description,
-        argument_hint,
-        content: body,
+        description: frontmatter.description,
+        argument_hint: frontmatter.argument_hint,
+        arguments: frontmatter.arguments,
+        content: frontmatter.body,
         scope: Some(scope),
+        library_id: None,
+        overridden: false,
+    })
+}
+
+pub(crate) async fn prompts_history_list_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    settings_path: &Path,
+    workspace_id: String,
+    path: String,
+) -> Result<Vec<PromptRevision>, String> {
+    let target = PathBuf::from(&path);
+    require_prompt_location(workspaces, settings_path, &workspace_id, &target).await?;
+    Ok(history::list_revisions(
+        &app_data_dir(settings_path)?,
+        &target,
+    ))
+}
+
+/// Diffs two revisions, or a revision against the file on disk when
+/// `to_revision` is omitted.
+pub(crate) async fn prompts_history_diff_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    settings_path: &Path,
+    workspace_id: String,
+    path: String,
+    from_revision: String,
+    to_revision: Option<String>,
+) -> Result<PromptRevisionDiff, String> {
+    let target = PathBuf::from(&path);
+    require_prompt_location(workspaces, settings_path, &workspace_id, &target).await?;
+    let data_dir = app_data_dir(settings_path)?;
+    let old = history::read_revision(&data_dir, &target, &from_revision)?;
+    let (new, new_label) = match to_revision.as_deref() {
+        Some(revision) => (
+            history::read_revision(&data_dir, &target, revision)?,
+            revision.to_string(),
+        ),
+        None => (
+            fs::read_to_string(&target).unwrap_or_default(),
+            "current".to_string(),
+        ),
+    };
+    let diff = history::diff_texts(&old, &new, &from_revision, &new_label)?;
+    Ok(PromptRevisionDiff {
+        from_revision,
+        to_revision,
+        diff,
+    })
+}
+
+pub(crate) async fn prompts_history_restore_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    settings_path: &Path,
+    workspace_id: String,
+    path: String,
+    revision_id: String,
+) -> Result<CustomPromptEntry, String> {
+    let target = PathBuf::from(&path);
+    let entry = require_prompt_location(workspaces, settings_path, &workspace_id, &target).await?;
+    ensure_not_library_base(settings_path, &target)?;
+    let data_dir = app_data_dir(settings_path)?;
+    let content = history::read_revision(&data_dir, &target, &revision_id)?;
+    snapshot_prompt_baseline(settings_path, &target);
+    fs::write(&target, &content).map_err(|err| err.to_string())?;
+    record_prompt_revision(settings_path, &target, &content, "restore");
+
+    let library = library_for_path(settings_path, &target);
+    let scope = if library.is_some() {
+        "library"
+    } else if target.starts_with(workspace_prompts_dir(settings_path, &entry)?) {
+        "workspace"
+    } else {
+        "global"
+    };
+    let mut prompt = read_prompt_entry(&target, Some(scope))
+        .ok_or("Unable to read restored prompt.".to_string())?;
+    prompt.overridden = library.is_some();
+    prompt.library_id = library.map(|(location, _)| location.id);
+    Ok(prompt)
+}
+
+pub(crate) async fn prompt_libraries_sync_core(
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &Path,
+) -> Result<Vec<PromptLibraryStatus>, String> {
+    let libraries = app_settings.lock().await.prompt_libraries.clone();
+    let data_dir = app_data_dir(settings_path)?;
+    libraries::sync_prompt_libraries(libraries, &data_dir).await
+}
+
+pub(crate) fn prompt_libraries_status_core(
+    settings_path: &Path,
+) -> Result<Vec<PromptLibraryStatus>, String> {
+    Ok(libraries::read_manifest(&app_data_dir(settings_path)?))
+}
+
+/// Keeps library checkouts current for the lifetime of the process.
+pub(crate) async fn run_prompt_library_sync_loop(
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &Path,
+) {
+    loop {
+        if let Err(err) = prompt_libraries_sync_core(app_settings, settings_path).await {
+            eprintln!("prompt library sync failed: {err}");
+        }
+        tokio::time::sleep(libraries::SYNC_INTERVAL).await;
+    }
+}
+
+/// Hands a local override back to the library. Folder libraries get the file
+/// in place and the override is dropped. Git checkouts must stay clean to keep
+/// syncing, so the file is exported under the library's `upstream` dir for the
+/// user to commit, and the override stays active until the change lands.
+pub(crate) async fn prompts_upstream_override_core(
+    settings_path: &Path,
+    library_id: String,
+    name: String,
+) -> Result<CustomPromptEntry, String> {
+    let name = sanitize_prompt_name(&name)?;
+    let data_dir = app_data_dir(settings_path)?;
+    let location = libraries::library_locations(&data_dir)
+        .into_iter()
+        .find(|location| location.id == library_id)
+        .ok_or("Prompt library not found.".to_string())?;
+    let file_name = format!("{name}.md");
+    let source = location.overrides_dir.join(&file_name);
+    if !source.is_file() {
+        return Err("No local override for that prompt.".to_string());
+    }
+    let target_dir = location
+        .export_dir
+        .clone()
+        .unwrap_or_else(|| location.prompts_dir.clone());
+    fs::create_dir_all(&target_dir).map_err(|err| err.to_string())?;
+    let target = target_dir.join(&file_name);
+    fs::copy(&source, &target).map_err(|err| err.to_string())?;
+    if location.export_dir.is_none() {
+        fs::remove_file(&source).map_err(|err| err.to_string())?;
+    }
+    let mut prompt = read_prompt_entry(&target, Some("library"))
+        .ok_or("Unable to read upstreamed prompt.".to_string())?;
+    prompt.library_id = Some(location.id);
+    Ok(prompt)
+}
+
+fn template_tokens(content: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let token = after[..end].trim().to_string();
+        if !token.is_empty() && !tokens.contains(&token) {
+            tokens.push(token);
+        }
+        rest = &after[end + 2..];
+    }
+    tokens
+}
+
+fn substitute_tokens(content: &str, values: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        match values.get(after[..end].trim()) {
+            Some(value) => output.push_str(value),
+            None => output.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn argument_value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(value) => Some(value.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn resolve_prompt_arguments(
+    declared: &[PromptArgument],
+    provided: &HashMap<String, Value>,
+) -> Result<BTreeMap<String, String>, String> {
+    let mut resolved = BTreeMap::new();
+    for argument in declared {
+        let name = &argument.name;
+        let value = provided
+            .get(name)
+            .and_then(argument_value_to_string)
+            .filter(|value| !value.trim().is_empty())
+            .or_else(|| argument.default.clone());
+        let Some(value) = value else {
+            if argument.required {
+                return Err(format!("Missing required argument `{name}`."));
+            }
+            resolved.insert(name.clone(), String::new());
+            continue;
+        };
+        let value = match argument.kind {
+            PromptArgumentKind::String => value,
+            PromptArgumentKind::Number => {
+                let trimmed = value.trim();
+                if trimmed.parse::<f64>().is_err() {
+                    return Err(format!("Argument `{name}` must be a number."));
+                }
+                trimmed.to_string()
+            }
+            PromptArgumentKind::Boolean => match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "yes" | "1" => "true".to_string(),
+                "false" | "no" | "0" => "false".to_string(),
+                _ => return Err(format!("Argument `{name}` must be true or false.")),
+            },
+            PromptArgumentKind::Choice => {
+                if !argument.choices.is_empty() && !argument.choices.contains(&value) {
+                    return Err(format!(
+                        "Argument `{name}` must be one of: {}.",
+                        argument.choices.join(", ")
+                    ));
+                }
+                value
+            }
+        };
+        resolved.insert(name.clone(), value);
+    }
+    // Undeclared arguments still expand so ad-hoc placeholders keep working.
+    for (name, value) in provided {
+        if resolved.contains_key(name) {
+            continue;
+        }
+        if let Some(value) = argument_value_to_string(value) {
+            resolved.insert(name.clone(), value);
+        }
+    }
+    Ok(resolved)
+}
+
+fn truncate_to_bytes(value: String, max_bytes: usize) -> (String, bool) {
+    if value.len() <= max_bytes {
+        return (value, false);
+    }
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    let mut truncated = value[..end].to_string();
+    truncated.push_str("\n… (truncated)");
+    (truncated, true)
+}
+
+fn read_workspace_text(root: &Path, relative_path: &str) -> Result<(String, bool), String> {
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    let canonical_path = canonical_root
+        .join(relative_path)
+        .canonicalize()
+        .map_err(|err| format!("Failed to open file `{relative_path}`: {err}"))?;
+    if !canonical_path.starts_with(&canonical_root) || !canonical_path.is_file() {
+        return Err(format!("Invalid file path `{relative_path}`."));
+    }
+    let file = fs::File::open(&canonical_path)
+        .map_err(|err| format!("Failed to open file `{relative_path}`: {err}"))?;
+    let mut buffer = Vec::new();
+    file.take(MAX_RENDERED_FILE_BYTES as u64 + 1)
+        .read_to_end(&mut buffer)
+        .map_err(|err| format!("Failed to read file `{relative_path}`: {err}"))?;
+    Ok(truncate_to_bytes(
+        String::from_utf8_lossy(&buffer).to_string(),
+        MAX_RENDERED_FILE_BYTES,
+    ))
+}
+
+fn current_branch(repo_root: &Path) -> Result<String, String> {
+    let repo = Repository::open(repo_root).map_err(|err| err.to_string())?;
+    let head = repo.head().map_err(|err| err.to_string())?;
+    Ok(head.shorthand().unwrap_or("HEAD").to_string())
+}
+
+fn format_issue(issue: &GitHubIssueDetail) -> String {
+    let mut output = format!(
+        "#{} {} ({})\n{}\n",
+        issue.number, issue.title, issue.state, issue.url
+    );
+    if !issue.body.trim().is_empty() {
+        output.push('\n');
+        output.push_str(issue.body.trim());
+        output.push('\n');
+    }
+    for comment in &issue.comments {
+        let author = comment
+            .author
+            .as_ref()
+            .map(|author| author.login.as_str())
+            .unwrap_or("unknown");
+        output.push_str(&format!(
+            "\n@{author} ({}):\n{}\n",
+            comment.created_at,
+            comment.body.trim()
+        ));
+    }
+    output
+}
+
+pub(crate) async fn prompts_render_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    settings_path: &Path,
+    workspace_id: String,
+    path: String,
+    args: HashMap<String, Value>,
+    selection: Option<String>,
+) -> Result<PromptRenderResult, String> {
+    let target_path = PathBuf::from(&path);
+    if !target_path.exists() {
+        return Err("Prompt not found.".to_string());
+    }
+    let entry = {
+        let workspaces = workspaces.lock().await;
+        let entry = require_workspace_entry(&workspaces, &workspace_id)?;
+        let roots = prompt_roots_for_workspace(settings_path, &workspaces, &entry)?;
+        ensure_path_within_roots(&target_path, &roots)?;
+        entry
+    };
+    let raw = fs::read_to_string(&target_path).map_err(|err| err.to_string())?;
+    let frontmatter = parse_frontmatter(&raw);
+    let arguments = resolve_prompt_arguments(&frontmatter.arguments, &args)?;
+
+    let workspace_root = PathBuf::from(&entry.path);
+    let mut values: HashMap<String, String> = HashMap::new();
+    let mut truncated = false;
+    for token in template_tokens(&frontmatter.body) {
+        if let Some(value) = arguments.get(&token) {
+            values.insert(token, value.clone());
+            continue;
+        }
+        let value = match token.as_str() {
+            "selection" => selection.clone().unwrap_or_default(),
+            "branch" => current_branch(&resolve_git_root(&entry)?)?,
+            "git_diff" => {
+                let repo_root = resolve_git_root(&entry)?;
+                let diff = task::spawn_blocking(move || {
+                    git_ui_core::collect_workspace_diff_core(&repo_root)
+                })
+                .await
+                .map_err(|err| err.to_string())??;
+                let (diff, cut) = truncate_to_bytes(diff, MAX_RENDERED_DIFF_BYTES);
+                truncated |= cut;
+                diff
+            }
+            _ => {
+                if let Some(file_path) = token.strip_prefix("file:") {
+                    let (content, cut) = read_workspace_text(&workspace_root, file_path.trim())?;
+                    truncated |= cut;
+                    content
+                } else if let Some(number) = token.strip_prefix("issue:") {
+                    let number = number
+                        .trim()
+                        .trim_start_matches('#')
+                        .parse::<u64>()
+                        .map_err(|_| format!("Invalid issue number in `{{{{{token}}}}}`."))?;
+                    let issue = git_ui_core::get_github_issue_core(
+                        workspaces,
+                        workspace_id.clone(),
+                        number,
+                    )
+                    .await?;
+                    let (issue, cut) =
+                        truncate_to_bytes(format_issue(&issue), MAX_RENDERED_ISSUE_BYTES);
+                    truncated |= cut;
+                    issue
+                } else {
+                    continue;
+                }
+            }
+        };
+        values.insert(token, value);
+    }
+
+    let content = substitute_tokens(&frontmatter.body, &values);
+    let unresolved = template_tokens(&frontmatter.body)
+        .into_iter()
+        .filter(|token| !values.contains_key(token))
+        .collect();
+    Ok(PromptRenderResult {
+        content,
+        arguments,
+        unresolved,
+        truncated,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_frontmatter_reads_typed_arguments() {
+        let content = "---\ndescription: \"Review\"\narguments:\n  - name: target\n    type: choice\n    choices: [main, develop]\n    default: main\n  - name: depth\n    type: number\n    required: true\nargument-hint: \"[target]\"\n---\nCompare {{branch}} to {{target}}\n";
+        let frontmatter = parse_frontmatter(content);
+        assert_eq!(frontmatter.description.as_deref(), Some("Review"));
+        assert_eq!(frontmatter.argument_hint.as_deref(), Some("[target]"));
+        assert_eq!(frontmatter.body, "Compare {{branch}} to {{target}}\n");
+        assert_eq!(frontmatter.arguments.len(), 2);
+        assert_eq!(frontmatter.arguments[0].kind, PromptArgumentKind::Choice);
+        assert_eq!(frontmatter.arguments[0].choices, vec!["main", "develop"]);
+        assert_eq!(frontmatter.arguments[0].default.as_deref(), Some("main"));
+        assert_eq!(frontmatter.arguments[1].kind, PromptArgumentKind::Number);
+        assert!(frontmatter.arguments[1].required);
+
+        let rebuilt = build_prompt_contents(
+            frontmatter.description.clone(),
+            frontmatter.argument_hint.clone(),
+            &frontmatter.arguments,
+            frontmatter.body.clone(),
+        );
+        assert_eq!(parse_frontmatter(&rebuilt).arguments, frontmatter.arguments);
+    }
+
+    #[test]
+    fn prompt_history_records_changes_and_survives_renames() {
+        let data_dir =
+            std::env::temp_dir().join(format!("prompt-history-{}", uuid::Uuid::new_v4()));
+        let original = data_dir.join("prompts").join("review.md");
+        let renamed = data_dir.join("prompts").join("audit.md");
+
+        history::record_revision(&data_dir, &original, "v1\n", "create").expect("record v1");
+        history::record_revision(&data_dir, &original, "v1\n", "update").expect("dedupe");
+        history::record_revision(&data_dir, &original, "v2\n", "update").expect("record v2");
+        history::move_history(&data_dir, &original, &renamed).expect("move history");
+
+        let revisions = history::list_revisions(&data_dir, &renamed);
+        assert!(history::list_revisions(&data_dir, &original).is_empty());
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].action, "update");
+        let newest = history::read_revision(&data_dir, &renamed, &revisions[0].id).expect("read");
+        let oldest = history::read_revision(&data_dir, &renamed, &revisions[1].id).expect("read");
+        let diff = history::diff_texts(&oldest, &newest, "old", "new").expect("diff");
+        let _ = fs::remove_dir_all(&data_dir);
+
+        assert_eq!(newest, "v2\n");
+        assert!(diff.contains("-v1") && diff.contains("+v2"));
+    }
+
+    #[test]
+    fn discover_library_prompts_prefers_local_overrides() {
+        let root = std::env::temp_dir().join(format!("prompt-library-{}", uuid::Uuid::new_v4()));
+        let location = libraries::LibraryLocation {
+            id: "team".to_string(),
+            prompts_dir: root.join("checkout"),
+            overrides_dir: root.join("overrides"),
+            export_dir: None,
+        };
+        fs::create_dir_all(&location.prompts_dir).expect("create prompts dir");
+        fs::create_dir_all(&location.overrides_dir).expect("create overrides dir");
+        fs::write(location.prompts_dir.join("review.md"), "upstream").expect("write prompt");
+        fs::write(location.prompts_dir.join("ship.md"), "ship it").expect("write prompt");
+        fs::write(location.overrides_dir.join("review.md"), "local").expect("write override");
+
+        let prompts = discover_library_prompts(&location);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(prompts.len(), 2);
+        assert_eq!(prompts[0].name, "review");
+        assert_eq!(prompts[0].content, "local");
+        assert!(prompts[0].overridden);
+        assert!(!prompts[1].overridden);
+        assert!(prompts
+            .iter()
+            .all(|prompt| prompt.library_id.as_deref() == Some("team")
+                && prompt.scope.as_deref() == Some("library")));
+    }
+
+    #[test]
+    fn resolve_prompt_arguments_applies_defaults_and_types() {
+        let declared = parse_frontmatter(
+            "---\narguments:\n  - name: target\n    choices: main | develop\n    default: main\n  - name: strict\n    type: boolean\n---\n",
+        )
+        .arguments;
+        let mut provided = HashMap::new();
+        provided.insert("strict".to_string(), Value::Bool(true));
+        let resolved = resolve_prompt_arguments(&declared, &provided).expect("resolve");
+        assert_eq!(resolved.get("target").map(String::as_str), Some("main"));
+        assert_eq!(resolved.get("strict").map(String::as_str), Some("true"));
+
+        provided.insert("target".to_string(), Value::String("release".to_string()));
+        let err = resolve_prompt_arguments(&declared, &provided).expect_err("bad choice");
+        assert!(err.contains("must be one of"));
+
+        let values: HashMap<String, String> = resolved.into_iter().collect();
+        assert_eq!(
+            substitute_tokens("on {{ target }} keep {{unknown}}", &values),
+            "on main keep {{unknown}}"
+        );
+    }
+}
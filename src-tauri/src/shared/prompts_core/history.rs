@@ -0,0 +1,156 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use git2::Patch;
+use serde::{Deserialize, Serialize};
+
+use crate::git_utils::diff_patch_to_string;
+use crate::shared::store_helpers::now_ms;
+
+const HISTORY_DIR: &str = "prompt-history";
+const HISTORY_INDEX_FILE: &str = "history.json";
+const MAX_REVISIONS_PER_PROMPT: usize = 50;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptRevision {
+    pub(crate) id: String,
+    pub(crate) created_at_ms: i64,
+    pub(crate) action: String,
+    pub(crate) size: usize,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct PromptHistoryIndex {
+    path: String,
+    revisions: Vec<PromptRevision>,
+}
+
+/// History is keyed by the prompt's file path (FNV-1a), so deleted prompts
+/// can still be looked up and restored by the path they lived at.
+fn history_dir(data_dir: &Path, prompt_path: &Path) -> PathBuf {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in prompt_path.to_string_lossy().as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    data_dir.join(HISTORY_DIR).join(format!("{hash:016x}"))
+}
+
+fn read_index(dir: &Path) -> PromptHistoryIndex {
+    fs::read_to_string(dir.join(HISTORY_INDEX_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(dir: &Path, index: &PromptHistoryIndex) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(index).map_err(|err| err.to_string())?;
+    let tmp_path = dir.join(format!("{HISTORY_INDEX_FILE}.tmp"));
+    fs::write(&tmp_path, data).map_err(|err| err.to_string())?;
+    fs::rename(&tmp_path, dir.join(HISTORY_INDEX_FILE)).map_err(|err| err.to_string())
+}
+
+pub(super) fn record_revision(
+    data_dir: &Path,
+    prompt_path: &Path,
+    content: &str,
+    action: &str,
+) -> Result<(), String> {
+    let dir = history_dir(data_dir, prompt_path);
+    fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    let mut index = read_index(&dir);
+    index.path = prompt_path.to_string_lossy().to_string();
+    if action != "delete" {
+        let unchanged = index
+            .revisions
+            .last()
+            .and_then(|latest| fs::read_to_string(dir.join(format!("{}.md", latest.id))).ok())
+            .is_some_and(|latest| latest == content);
+        if unchanged {
+            return Ok(());
+        }
+    }
+    let mut created_at_ms = now_ms();
+    if let Some(latest) = index.revisions.last() {
+        created_at_ms = created_at_ms.max(latest.created_at_ms + 1);
+    }
+    let id = created_at_ms.to_string();
+    fs::write(dir.join(format!("{id}.md")), content).map_err(|err| err.to_string())?;
+    index.revisions.push(PromptRevision {
+        id,
+        created_at_ms,
+        action: action.to_string(),
+        size: content.len(),
+    });
+    while index.revisions.len() > MAX_REVISIONS_PER_PROMPT {
+        let dropped = index.revisions.remove(0);
+        let _ = fs::remove_file(dir.join(format!("{}.md", dropped.id)));
+    }
+    write_index(&dir, &index)
+}
+
+/// Prompts written before history existed get their current content recorded
+/// before the first tracked change, so that change can be undone.
+pub(super) fn ensure_baseline(data_dir: &Path, prompt_path: &Path) -> Result<(), String> {
+    let dir = history_dir(data_dir, prompt_path);
+    if !read_index(&dir).revisions.is_empty() {
+        return Ok(());
+    }
+    match fs::read_to_string(prompt_path) {
+        Ok(content) => record_revision(data_dir, prompt_path, &content, "initial"),
+        Err(_) => Ok(()),
+    }
+}
+
+pub(super) fn move_history(data_dir: &Path, from: &Path, to: &Path) -> Result<(), String> {
+    let from_dir = history_dir(data_dir, from);
+    let to_dir = history_dir(data_dir, to);
+    if !from_dir.exists() || from_dir == to_dir {
+        return Ok(());
+    }
+    let _ = fs::remove_dir_all(&to_dir);
+    fs::rename(&from_dir, &to_dir).map_err(|err| err.to_string())?;
+    let mut index = read_index(&to_dir);
+    index.path = to.to_string_lossy().to_string();
+    write_index(&to_dir, &index)
+}
+
+pub(super) fn list_revisions(data_dir: &Path, prompt_path: &Path) -> Vec<PromptRevision> {
+    let mut revisions = read_index(&history_dir(data_dir, prompt_path)).revisions;
+    revisions.reverse();
+    revisions
+}
+
+pub(super) fn read_revision(
+    data_dir: &Path,
+    prompt_path: &Path,
+    revision_id: &str,
+) -> Result<String, String> {
+    let dir = history_dir(data_dir, prompt_path);
+    let known = read_index(&dir)
+        .revisions
+        .iter()
+        .any(|revision| revision.id == revision_id);
+    if !known {
+        return Err("Prompt revision not found.".to_string());
+    }
+    fs::read_to_string(dir.join(format!("{revision_id}.md"))).map_err(|err| err.to_string())
+}
+
+pub(super) fn diff_texts(
+    old: &str,
+    new: &str,
+    old_label: &str,
+    new_label: &str,
+) -> Result<String, String> {
+    let mut patch = Patch::from_buffers(
+        old.as_bytes(),
+        Some(Path::new(old_label)),
+        new.as_bytes(),
+        Some(Path::new(new_label)),
+        None,
+    )
+    .map_err(|err| err.to_string())?;
+    diff_patch_to_string(&mut patch).map_err(|err| err.to_string())
+}
@@ -0,0 +1,225 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use crate::shared::git_core::run_git_command;
+use crate::shared::store_helpers::now_ms;
+use crate::types::{PromptLibrarySettings, PromptLibraryStatus};
+
+const LIBRARIES_DIR: &str = "prompt-libraries";
+const MANIFEST_FILE: &str = "libraries.json";
+pub(super) const SYNC_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+static SYNC_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+pub(super) struct LibraryLocation {
+    pub(super) id: String,
+    pub(super) prompts_dir: PathBuf,
+    pub(super) overrides_dir: PathBuf,
+    /// Where upstreamed overrides are written for git libraries, mirroring the
+    /// checkout layout. `None` for folder sources, which are edited in place.
+    pub(super) export_dir: Option<PathBuf>,
+}
+
+fn libraries_root(data_dir: &Path) -> PathBuf {
+    data_dir.join(LIBRARIES_DIR)
+}
+
+fn overrides_dir(data_dir: &Path, library_id: &str) -> PathBuf {
+    libraries_root(data_dir).join(library_id).join("overrides")
+}
+
+fn checkout_dir(data_dir: &Path, library_id: &str) -> PathBuf {
+    libraries_root(data_dir).join(library_id).join("checkout")
+}
+
+fn export_dir(data_dir: &Path, library_id: &str, prompts_dir: &Path) -> Option<PathBuf> {
+    let relative = prompts_dir
+        .strip_prefix(checkout_dir(data_dir, library_id))
+        .ok()?;
+    Some(
+        libraries_root(data_dir)
+            .join(library_id)
+            .join("upstream")
+            .join(relative),
+    )
+}
+
+pub(super) fn read_manifest(data_dir: &Path) -> Vec<PromptLibraryStatus> {
+    fs::read_to_string(libraries_root(data_dir).join(MANIFEST_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_manifest(data_dir: &Path, statuses: &[PromptLibraryStatus]) -> Result<(), String> {
+    let root = libraries_root(data_dir);
+    fs::create_dir_all(&root).map_err(|err| err.to_string())?;
+    let data = serde_json::to_string_pretty(statuses).map_err(|err| err.to_string())?;
+    let tmp_path = root.join(format!("{MANIFEST_FILE}.tmp"));
+    fs::write(&tmp_path, data).map_err(|err| err.to_string())?;
+    fs::rename(&tmp_path, root.join(MANIFEST_FILE)).map_err(|err| err.to_string())
+}
+
+/// Synced libraries with a usable prompts dir; base prompts are read-only and
+/// local edits land in the per-library overrides dir.
+pub(super) fn library_locations(data_dir: &Path) -> Vec<LibraryLocation> {
+    read_manifest(data_dir)
+        .into_iter()
+        .filter_map(|status| {
+            let prompts_dir = PathBuf::from(status.prompts_dir?);
+            Some(LibraryLocation {
+                overrides_dir: overrides_dir(data_dir, &status.id),
+                export_dir: export_dir(data_dir, &status.id, &prompts_dir),
+                id: status.id,
+                prompts_dir,
+            })
+        })
+        .collect()
+}
+
+fn validate_library_id(id: &str) -> Result<String, String> {
+    let trimmed = id.trim();
+    let valid = !trimmed.is_empty()
+        && trimmed
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_');
+    if valid {
+        Ok(trimmed.to_string())
+    } else {
+        Err(format!("Invalid prompt library id `{id}`."))
+    }
+}
+
+fn library_prompts_dir(base: &Path, subdir: Option<&str>) -> Result<PathBuf, String> {
+    let Some(subdir) = subdir.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(base.to_path_buf());
+    };
+    let relative = Path::new(subdir);
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(format!("Invalid prompt library subdir `{subdir}`."));
+    }
+    Ok(base.join(relative))
+}
+
+fn is_folder_source(library: &PromptLibrarySettings) -> bool {
+    library.git_ref.is_none() && Path::new(library.source.trim()).is_dir()
+}
+
+async fn resolve_revision(checkout: &PathBuf, git_ref: Option<&str>) -> Result<String, String> {
+    let candidates = match git_ref {
+        Some(git_ref) => vec![format!("origin/{git_ref}"), git_ref.to_string()],
+        None => vec!["origin/HEAD".to_string()],
+    };
+    for candidate in &candidates {
+        let spec = format!("{candidate}^{{commit}}");
+        if let Ok(revision) =
+            run_git_command(checkout, &["rev-parse", "--verify", "--quiet", &spec]).await
+        {
+            return Ok(revision);
+        }
+    }
+    Err(format!(
+        "Unable to resolve ref `{}`.",
+        git_ref.unwrap_or("HEAD")
+    ))
+}
+
+async fn sync_git_checkout(
+    library_dir: &Path,
+    library: &PromptLibrarySettings,
+) -> Result<(PathBuf, String), String> {
+    let source = library.source.trim();
+    let git_ref = library
+        .git_ref
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    if source.starts_with('-') || git_ref.is_some_and(|value| value.starts_with('-')) {
+        return Err("Invalid prompt library source or ref.".to_string());
+    }
+    let checkout = library_dir.join("checkout");
+    if !checkout.join(".git").exists() {
+        fs::create_dir_all(library_dir).map_err(|err| err.to_string())?;
+        let _ = fs::remove_dir_all(&checkout);
+        run_git_command(
+            &library_dir.to_path_buf(),
+            &["clone", "--quiet", "--no-checkout", source, "checkout"],
+        )
+        .await?;
+    } else {
+        // Hand edits in the working tree would be thrown away by the forced
+        // checkout below.
+        let changes = run_git_command(&checkout, &["status", "--porcelain"]).await?;
+        if !changes.is_empty() {
+            return Err("Checkout has local changes; skipped update.".to_string());
+        }
+        run_git_command(&checkout, &["remote", "set-url", "origin", source]).await?;
+        run_git_command(
+            &checkout,
+            &["fetch", "--quiet", "--prune", "--tags", "origin"],
+        )
+        .await?;
+    }
+    let revision = resolve_revision(&checkout, git_ref).await?;
+    run_git_command(
+        &checkout,
+        &["checkout", "--quiet", "--force", "--detach", &revision],
+    )
+    .await?;
+    Ok((checkout, revision))
+}
+
+async fn sync_library(
+    data_dir: &Path,
+    library: &PromptLibrarySettings,
+) -> Result<(PathBuf, Option<String>), String> {
+    let id = validate_library_id(&library.id)?;
+    if is_folder_source(library) {
+        let dir = library_prompts_dir(Path::new(library.source.trim()), library.subdir.as_deref())?;
+        return Ok((dir, None));
+    }
+    let (checkout, revision) =
+        sync_git_checkout(&libraries_root(data_dir).join(id), library).await?;
+    let dir = library_prompts_dir(&checkout, library.subdir.as_deref())?;
+    Ok((dir, Some(revision)))
+}
+
+pub(super) async fn sync_prompt_libraries(
+    libraries: Vec<PromptLibrarySettings>,
+    data_dir: &Path,
+) -> Result<Vec<PromptLibraryStatus>, String> {
+    let _guard = SYNC_LOCK.get_or_init(|| Mutex::new(())).lock().await;
+    let previous = read_manifest(data_dir);
+    let mut statuses = Vec::new();
+    for library in libraries.into_iter().filter(|library| library.enabled) {
+        let last = previous.iter().find(|status| status.id == library.id);
+        let mut status = PromptLibraryStatus {
+            id: library.id.clone(),
+            name: library.name.clone(),
+            source: library.source.clone(),
+            git_ref: library.git_ref.clone(),
+            prompts_dir: last.and_then(|status| status.prompts_dir.clone()),
+            revision: last.and_then(|status| status.revision.clone()),
+            last_synced_at_ms: last.and_then(|status| status.last_synced_at_ms),
+            error: None,
+        };
+        match sync_library(data_dir, &library).await {
+            Ok((dir, revision)) => {
+                status.prompts_dir = Some(dir.to_string_lossy().to_string());
+                status.revision = revision;
+                status.last_synced_at_ms = Some(now_ms());
+            }
+            Err(err) => status.error = Some(err),
+        }
+        statuses.push(status);
+    }
+    write_manifest(data_dir, &statuses)?;
+    Ok(statuses)
+}
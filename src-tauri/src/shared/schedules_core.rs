@@ -0,0 +1,883 @@
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, Mutex};
+use tokio::time::timeout;
+use uuid::Uuid;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::shared::codex_core;
+use crate::shared::store_helpers::{now_ms, read_json, trim_optional, write_json, StoreLock};
+use crate::types::{
+    ScheduledRun, ScheduledRunInput, ScheduledRunRecord, ScheduledRunStatus, ScheduledRunTrigger,
+    WorkspaceEntry,
+};
+
+#[path = "schedules_core/cron.rs"]
+mod cron;
+
+use cron::parse_cron;
+
+const SCHEDULES_FILE: &str = "schedules.json";
+const SCHEDULE_RUNS_FILE: &str = "schedule-runs.json";
+const MAX_RUN_HISTORY: usize = 500;
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+const DEFAULT_MAX_RUN_MINUTES: u32 = 120;
+const SCHEDULE_TICK_INTERVAL: Duration = Duration::from_secs(30);
+const SCHEDULE_CLOCK_LEASE_FILE: &str = "schedule-clock.lease";
+/// Renewed every tick; a holder that misses three ticks is presumed gone.
+const SCHEDULE_CLOCK_LEASE_TTL: Duration = Duration::from_secs(90);
+
+/// Serializes read-modify-write cycles on the schedule files; runs finish on
+/// their own tasks and would otherwise race each other.
+static STORE_LOCK: StoreLock = StoreLock::new();
+
+/// A run that has been recorded as `running` and is waiting for the host to
+/// connect its workspace and drive the turn.
+#[derive(Debug, Clone)]
+pub(crate) struct ScheduledRunJob {
+    pub(crate) schedule: ScheduledRun,
+    pub(crate) run_id: String,
+}
+
+/// Hands due runs from the schedule clock (and `schedules_run_now`) to the
+/// host's worker task, which owns the state needed to spawn sessions.
+#[derive(Clone)]
+pub(crate) struct ScheduleRunner {
+    tx: mpsc::UnboundedSender<ScheduledRunJob>,
+    rx: Arc<Mutex<Option<mpsc::UnboundedReceiver<ScheduledRunJob>>>>,
+}
+
+impl Default for ScheduleRunner {
+    fn default() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        Self {
+            tx,
+            rx: Arc::new(Mutex::new(Some(rx))),
+        }
+    }
+}
+
+impl ScheduleRunner {
+    /// Returns the job stream once; the host's worker loop owns it.
+    pub(crate) async fn take_jobs(&self) -> Option<mpsc::UnboundedReceiver<ScheduledRunJob>> {
+        self.rx.lock().await.take()
+    }
+
+    fn enqueue(&self, job: ScheduledRunJob) {
+        if self.tx.send(job).is_err() {
+            eprintln!("scheduled runs: worker is not running; run left pending");
+        }
+    }
+}
+
+fn schedules_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(SCHEDULES_FILE)
+}
+
+fn runs_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(SCHEDULE_RUNS_FILE)
+}
+
+fn read_schedules(data_dir: &Path) -> Result<Vec<ScheduledRun>, String> {
+    read_json(&schedules_path(data_dir))
+}
+
+fn write_schedules(data_dir: &Path, schedules: &[ScheduledRun]) -> Result<(), String> {
+    write_json(&schedules_path(data_dir), &schedules)
+}
+
+fn read_runs(data_dir: &Path) -> Result<Vec<ScheduledRunRecord>, String> {
+    read_json(&runs_path(data_dir))
+}
+
+fn write_runs(data_dir: &Path, runs: &mut Vec<ScheduledRunRecord>) -> Result<(), String> {
+    if runs.len() > MAX_RUN_HISTORY {
+        let excess = runs.len() - MAX_RUN_HISTORY;
+        runs.drain(..excess);
+    }
+    write_json(&runs_path(data_dir), runs)
+}
+
+fn update_run<F>(data_dir: &Path, run_id: &str, update: F) -> Result<ScheduledRunRecord, String>
+where
+    F: FnOnce(&mut ScheduledRunRecord),
+{
+    let _guard = STORE_LOCK.lock();
+    let mut runs = read_runs(data_dir)?;
+    let run = runs
+        .iter_mut()
+        .find(|run| run.run_id == run_id)
+        .ok_or_else(|| format!("Scheduled run `{run_id}` not found."))?;
+    update(run);
+    let updated = run.clone();
+    write_runs(data_dir, &mut runs)?;
+    Ok(updated)
+}
+
+fn next_run_at_ms<Tz: TimeZone>(expression: &str, after: &DateTime<Tz>) -> Option<i64> {
+    parse_cron(expression)
+        .ok()?
+        .next_after(after)
+        .map(|next| next.timestamp_millis())
+}
+
+async fn normalize_input(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    input: ScheduledRunInput,
+) -> Result<ScheduledRunInput, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Schedule name is required.".to_string());
+    }
+    let prompt = input.prompt.trim().to_string();
+    if prompt.is_empty() {
+        return Err("Schedule prompt is required.".to_string());
+    }
+    let cron = input.cron.trim().to_string();
+    parse_cron(&cron)?;
+    if !workspaces.lock().await.contains_key(&input.workspace_id) {
+        return Err("workspace not found".to_string());
+    }
+    Ok(ScheduledRunInput {
+        name,
+        workspace_id: input.workspace_id,
+        prompt,
+        cron,
+        model: trim_optional(input.model),
+        effort: trim_optional(input.effort),
+        access_mode: trim_optional(input.access_mode),
+        enabled: input.enabled,
+        max_duration_minutes: input.max_duration_minutes.map(|minutes| minutes.max(1)),
+    })
+}
+
+fn new_run_record(
+    schedule: &ScheduledRun,
+    trigger: ScheduledRunTrigger,
+    started_at_ms: i64,
+) -> ScheduledRunRecord {
+    ScheduledRunRecord {
+        run_id: Uuid::new_v4().to_string(),
+        schedule_id: schedule.id.clone(),
+        schedule_name: schedule.name.clone(),
+        workspace_id: schedule.workspace_id.clone(),
+        trigger,
+        status: ScheduledRunStatus::Running,
+        started_at_ms,
+        finished_at_ms: None,
+        thread_id: None,
+        turn_id: None,
+        approval_requests: 0,
+        error: None,
+        owner_pid: Some(std::process::id()),
+    }
+}
+
+fn emit_schedule_event<E: EventSink>(
+    event_sink: &E,
+    workspace_id: &str,
+    method: &str,
+    params: Value,
+) {
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message: json!({
+            "method": method,
+            "params": params,
+        }),
+    });
+}
+
+pub(crate) fn schedules_list_core(data_dir: &Path) -> Result<Vec<ScheduledRun>, String> {
+    let _guard = STORE_LOCK.lock();
+    read_schedules(data_dir)
+}
+
+pub(crate) async fn schedules_create_core(
+    data_dir: &Path,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    input: ScheduledRunInput,
+) -> Result<ScheduledRun, String> {
+    let input = normalize_input(workspaces, input).await?;
+    let enabled = input.enabled.unwrap_or(true);
+    let schedule = ScheduledRun {
+        id: Uuid::new_v4().to_string(),
+        next_run_at_ms: if enabled {
+            next_run_at_ms(&input.cron, &Local::now())
+        } else {
+            None
+        },
+        name: input.name,
+        workspace_id: input.workspace_id,
+        prompt: input.prompt,
+        cron: input.cron,
+        model: input.model,
+        effort: input.effort,
+        access_mode: input.access_mode,
+        enabled,
+        max_duration_minutes: input.max_duration_minutes,
+        created_at_ms: now_ms(),
+        last_run_at_ms: None,
+    };
+    let _guard = STORE_LOCK.lock();
+    let mut schedules = read_schedules(data_dir)?;
+    schedules.push(schedule.clone());
+    write_schedules(data_dir, &schedules)?;
+    Ok(schedule)
+}
+
+pub(crate) async fn schedules_update_core(
+    data_dir: &Path,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    id: String,
+    input: ScheduledRunInput,
+) -> Result<ScheduledRun, String> {
+    let input = normalize_input(workspaces, input).await?;
+    let _guard = STORE_LOCK.lock();
+    let mut schedules = read_schedules(data_dir)?;
+    let schedule = schedules
+        .iter_mut()
+        .find(|schedule| schedule.id == id)
+        .ok_or_else(|| "Schedule not found.".to_string())?;
+    schedule.enabled = input.enabled.unwrap_or(schedule.enabled);
+    schedule.next_run_at_ms = if schedule.enabled {
+        next_run_at_ms(&input.cron, &Local::now())
+    } else {
+        None
+    };
+    schedule.name = input.name;
+    schedule.workspace_id = input.workspace_id;
+    schedule.prompt = input.prompt;
+    schedule.cron = input.cron;
+    schedule.model = input.model;
+    schedule.effort = input.effort;
+    schedule.access_mode = input.access_mode;
+    schedule.max_duration_minutes = input.max_duration_minutes;
+    let updated = schedule.clone();
+    write_schedules(data_dir, &schedules)?;
+    Ok(updated)
+}
+
+/// Removes the schedule; its run history is kept.
+pub(crate) fn schedules_delete_core(data_dir: &Path, id: String) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock();
+    let mut schedules = read_schedules(data_dir)?;
+    let before = schedules.len();
+    schedules.retain(|schedule| schedule.id != id);
+    if schedules.len() == before {
+        return Err("Schedule not found.".to_string());
+    }
+    write_schedules(data_dir, &schedules)
+}
+
+/// Returns run history, newest first.
+pub(crate) fn schedules_history_core(
+    data_dir: &Path,
+    schedule_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<ScheduledRunRecord>, String> {
+    let _guard = STORE_LOCK.lock();
+    let runs = read_runs(data_dir)?;
+    Ok(runs
+        .into_iter()
+        .rev()
+        .filter(|run| match schedule_id.as_deref() {
+            Some(schedule_id) => run.schedule_id == schedule_id,
+            None => true,
+        })
+        .take(limit.unwrap_or(DEFAULT_HISTORY_LIMIT))
+        .collect())
+}
+
+pub(crate) fn schedules_run_now_core(
+    data_dir: &Path,
+    runner: &ScheduleRunner,
+    id: String,
+) -> Result<ScheduledRunRecord, String> {
+    let (record, job) = {
+        let _guard = STORE_LOCK.lock();
+        let mut schedules = read_schedules(data_dir)?;
+        let mut runs = read_runs(data_dir)?;
+        let schedule = schedules
+            .iter_mut()
+            .find(|schedule| schedule.id == id)
+            .ok_or_else(|| "Schedule not found.".to_string())?;
+        if runs
+            .iter()
+            .any(|run| run.schedule_id == schedule.id && run.status == ScheduledRunStatus::Running)
+        {
+            return Err("This schedule already has a run in progress.".to_string());
+        }
+        let now = now_ms();
+        schedule.last_run_at_ms = Some(now);
+        let record = new_run_record(schedule, ScheduledRunTrigger::Manual, now);
+        let job = ScheduledRunJob {
+            schedule: schedule.clone(),
+            run_id: record.run_id.clone(),
+        };
+        runs.push(record.clone());
+        write_schedules(data_dir, &schedules)?;
+        write_runs(data_dir, &mut runs)?;
+        (record, job)
+    };
+    runner.enqueue(job);
+    Ok(record)
+}
+
+/// Records a run for every enabled schedule whose next run time has passed and
+/// queues it. A schedule whose previous run is still going gets a `skipped`
+/// record instead, and runs missed while the process was down fire once.
+pub(crate) fn enqueue_due_schedules(
+    data_dir: &Path,
+    runner: &ScheduleRunner,
+    now: DateTime<Local>,
+) -> Result<Vec<ScheduledRunRecord>, String> {
+    let now_ms = now.timestamp_millis();
+    let mut jobs = Vec::new();
+    let mut recorded = Vec::new();
+    {
+        let _guard = STORE_LOCK.lock();
+        let mut schedules = read_schedules(data_dir)?;
+        let mut runs = read_runs(data_dir)?;
+        let mut changed = false;
+        for schedule in schedules.iter_mut().filter(|schedule| schedule.enabled) {
+            let due = match schedule.next_run_at_ms {
+                Some(next) => next <= now_ms,
+                None => {
+                    schedule.next_run_at_ms = next_run_at_ms(&schedule.cron, &now);
+                    changed = true;
+                    false
+                }
+            };
+            if !due {
+                continue;
+            }
+            changed = true;
+            schedule.last_run_at_ms = Some(now_ms);
+            schedule.next_run_at_ms = next_run_at_ms(&schedule.cron, &now);
+            let mut record = new_run_record(schedule, ScheduledRunTrigger::Schedule, now_ms);
+            let busy = runs.iter().any(|run| {
+                run.schedule_id == schedule.id && run.status == ScheduledRunStatus::Running
+            });
+            if busy {
+                record.status = ScheduledRunStatus::Skipped;
+                record.finished_at_ms = Some(now_ms);
+                record.error = Some("Previous run was still in progress.".to_string());
+            } else {
+                jobs.push(ScheduledRunJob {
+                    schedule: schedule.clone(),
+                    run_id: record.run_id.clone(),
+                });
+            }
+            runs.push(record.clone());
+            recorded.push(record);
+        }
+        if changed {
+            write_schedules(data_dir, &schedules)?;
+            write_runs(data_dir, &mut runs)?;
+        }
+    }
+    for job in jobs {
+        runner.enqueue(job);
+    }
+    Ok(recorded)
+}
+
+/// Owner of the schedule clock for a data dir. The desktop app and a local
+/// daemon can share one data dir; only the lease holder fires schedules.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClockLease {
+    owner: String,
+    pid: u32,
+    expires_at_ms: i64,
+}
+
+fn clock_lease_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(SCHEDULE_CLOCK_LEASE_FILE)
+}
+
+fn read_clock_lease(path: &Path) -> Option<ClockLease> {
+    let data = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+/// Takes or renews the clock lease for `owner`. Returns `false` while another
+/// live process holds it.
+fn acquire_clock_lease(data_dir: &Path, owner: &str, now_ms: i64) -> Result<bool, String> {
+    std::fs::create_dir_all(data_dir).map_err(|err| err.to_string())?;
+    let path = clock_lease_path(data_dir);
+    let lease = ClockLease {
+        owner: owner.to_string(),
+        pid: std::process::id(),
+        expires_at_ms: now_ms + SCHEDULE_CLOCK_LEASE_TTL.as_millis() as i64,
+    };
+    let tmp_path = data_dir.join(format!("{SCHEDULE_CLOCK_LEASE_FILE}.{owner}.tmp"));
+    let data = serde_json::to_string(&lease).map_err(|err| err.to_string())?;
+    std::fs::write(&tmp_path, data)
+        .map_err(|err| format!("Failed to write {}: {err}", tmp_path.display()))?;
+
+    let result = match read_clock_lease(&path) {
+        Some(current) if current.owner == owner => std::fs::rename(&tmp_path, &path)
+            .map(|_| true)
+            .map_err(|err| format!("Failed to renew {}: {err}", path.display())),
+        Some(current) if current.expires_at_ms > now_ms => Ok(false),
+        _ => {
+            // Missing, expired or unreadable: replace it. Linking fails if
+            // another process created a lease in the meantime, so only one of
+            // them wins; a loser that removed a fresh lease finds out at its
+            // next renewal.
+            let _ = std::fs::remove_file(&path);
+            match std::fs::hard_link(&tmp_path, &path) {
+                Ok(()) => Ok(true),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+                Err(err) => Err(format!("Failed to create {}: {err}", path.display())),
+            }
+        }
+    };
+    let _ = std::fs::remove_file(&tmp_path);
+    result
+}
+
+#[cfg(unix)]
+fn is_pid_running(pid: u32) -> bool {
+    let result = unsafe { libc::kill(pid as i32, 0) };
+    if result == 0 {
+        return true;
+    }
+    match std::io::Error::last_os_error().raw_os_error() {
+        Some(code) => code != libc::ESRCH,
+        None => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_pid_running(_pid: u32) -> bool {
+    // No cheap liveness probe here; such runs are recovered once they outlive
+    // their time limit.
+    true
+}
+
+/// Marks runs left `running` by a process that is gone as interrupted. A run
+/// counts as orphaned when its owner pid no longer exists, when it predates
+/// owner tracking, or when it has outlived its time limit (which also covers
+/// a reused pid). Runs owned by live processes, such as a "run now" in the
+/// app while the daemon holds the clock, are left alone.
+fn recover_interrupted_runs(data_dir: &Path, now_ms: i64) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock();
+    let limits: HashMap<String, u32> = read_schedules(data_dir)?
+        .into_iter()
+        .filter_map(|schedule| Some((schedule.id, schedule.max_duration_minutes?)))
+        .collect();
+    let mut runs = read_runs(data_dir)?;
+    let own_pid = std::process::id();
+    let mut changed = false;
+    for run in runs
+        .iter_mut()
+        .filter(|run| run.status == ScheduledRunStatus::Running)
+    {
+        let limit_ms = i64::from(
+            limits
+                .get(&run.schedule_id)
+                .copied()
+                .unwrap_or(DEFAULT_MAX_RUN_MINUTES),
+        ) * 60_000;
+        let expired =
+            now_ms - run.started_at_ms > limit_ms + SCHEDULE_CLOCK_LEASE_TTL.as_millis() as i64;
+        let owner_gone = match run.owner_pid {
+            Some(pid) => pid != own_pid && !is_pid_running(pid),
+            None => true,
+        };
+        if !owner_gone && !expired {
+            continue;
+        }
+        run.status = ScheduledRunStatus::Interrupted;
+        run.finished_at_ms = Some(now_ms);
+        run.error = Some("The process stopped before the run finished.".to_string());
+        changed = true;
+    }
+    if changed {
+        write_runs(data_dir, &mut runs)?;
+    }
+    Ok(())
+}
+
+/// One clock tick: renews the lease and, while holding it, recovers orphaned
+/// runs and queues due schedules. Returns whether the lease is held.
+fn run_clock_tick(data_dir: &Path, owner: &str, runner: &ScheduleRunner) -> Result<bool, String> {
+    if !acquire_clock_lease(data_dir, owner, now_ms())? {
+        return Ok(false);
+    }
+    if let Err(err) = recover_interrupted_runs(data_dir, now_ms()) {
+        eprintln!("scheduled runs: failed to recover interrupted runs: {err}");
+    }
+    if let Err(err) = enqueue_due_schedules(data_dir, runner, Local::now()) {
+        eprintln!("scheduled runs: {err}");
+    }
+    Ok(true)
+}
+
+/// Queues due schedules for the lifetime of the process while it holds the
+/// data dir's clock lease. Each tick does its file I/O on the blocking pool.
+pub(crate) async fn run_schedule_clock(data_dir: &Path, runner: &ScheduleRunner) {
+    let owner = Uuid::new_v4().to_string();
+    let mut holding = false;
+    loop {
+        let tick = {
+            let data_dir = data_dir.to_path_buf();
+            let owner = owner.clone();
+            let runner = runner.clone();
+            tokio::task::spawn_blocking(move || run_clock_tick(&data_dir, &owner, &runner))
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|result| result)
+        };
+        match tick {
+            Ok(held) => {
+                if holding && !held {
+                    eprintln!("scheduled runs: another process took over the schedule clock");
+                }
+                holding = held;
+            }
+            Err(err) => eprintln!("scheduled runs: {err}"),
+        }
+        tokio::time::sleep(SCHEDULE_TICK_INTERVAL).await;
+    }
+}
+
+fn response_thread_id(response: &Value) -> Option<String> {
+    let result = response.get("result").unwrap_or(response);
+    result
+        .get("thread")
+        .and_then(|thread| thread.get("id"))
+        .or_else(|| result.get("threadId"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn response_error(response: &Value) -> Option<String> {
+    response.get("error").map(|error| {
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("app-server returned an error")
+            .to_string()
+    })
+}
+
+fn turn_outcome(turn: Option<&Value>) -> (ScheduledRunStatus, Option<String>) {
+    let status = turn
+        .and_then(|turn| turn.get("status"))
+        .and_then(Value::as_str)
+        .unwrap_or("completed");
+    match status {
+        "failed" => (
+            ScheduledRunStatus::Failed,
+            turn.and_then(|turn| turn.get("error"))
+                .and_then(|error| error.get("message"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .or_else(|| Some("Turn failed.".to_string())),
+        ),
+        "interrupted" => (ScheduledRunStatus::Interrupted, None),
+        _ => (ScheduledRunStatus::Completed, None),
+    }
+}
+
+async fn watch_scheduled_turn<E: EventSink>(
+    data_dir: &Path,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    event_sink: &E,
+    job: &ScheduledRunJob,
+    thread_id: &str,
+    events: &mut mpsc::UnboundedReceiver<Value>,
+) -> Result<(ScheduledRunStatus, Option<String>), String> {
+    let schedule = &job.schedule;
+    let response = codex_core::send_user_message_core(
+        sessions,
+        workspaces,
+        schedule.workspace_id.clone(),
+        thread_id.to_string(),
+        schedule.prompt.clone(),
+        schedule.model.clone(),
+        schedule.effort.clone(),
+        None,
+        schedule.access_mode.clone(),
+        None,
+        None,
+        Vec::new(),
+        None,
+    )
+    .await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    let turn_id = response
+        .get("result")
+        .and_then(|result| result.get("turn"))
+        .and_then(|turn| turn.get("id"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    if turn_id.is_some() {
+        let turn_id = turn_id.clone();
+        update_run(data_dir, &job.run_id, |run| run.turn_id = turn_id)?;
+    }
+
+    let minutes = schedule
+        .max_duration_minutes
+        .unwrap_or(DEFAULT_MAX_RUN_MINUTES);
+    let watched = timeout(Duration::from_secs(u64::from(minutes) * 60), async {
+        loop {
+            let Some(event) = events.recv().await else {
+                return Err("Session closed before the turn finished.".to_string());
+            };
+            let method = event.get("method").and_then(Value::as_str).unwrap_or("");
+            let params = event.get("params");
+            if method == "turn/completed" {
+                let turn = params.and_then(|params| params.get("turn"));
+                let completed_id = turn.and_then(|turn| turn.get("id")).and_then(Value::as_str);
+                if turn_id.is_some() && completed_id.is_some() && completed_id != turn_id.as_deref()
+                {
+                    continue;
+                }
+                return Ok(turn_outcome(turn));
+            }
+            if method.ends_with("requestApproval") {
+                let run = update_run(data_dir, &job.run_id, |run| run.approval_requests += 1)?;
+                emit_schedule_event(
+                    event_sink,
+                    &schedule.workspace_id,
+                    "schedule/approvalRequested",
+                    json!({ "run": run, "request": event }),
+                );
+            }
+        }
+    })
+    .await;
+
+    match watched {
+        Ok(result) => result,
+        Err(_) => {
+            if let Some(turn_id) = turn_id {
+                let _ = codex_core::turn_interrupt_core(
+                    sessions,
+                    schedule.workspace_id.clone(),
+                    thread_id.to_string(),
+                    turn_id,
+                )
+                .await;
+            }
+            Ok((
+                ScheduledRunStatus::TimedOut,
+                Some(format!("Turn did not finish within {minutes} minutes.")),
+            ))
+        }
+    }
+}
+
+async fn drive_scheduled_run<E, F>(
+    data_dir: &Path,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    event_sink: &E,
+    job: &ScheduledRunJob,
+    connect: F,
+) -> Result<(ScheduledRunStatus, Option<String>), String>
+where
+    E: EventSink,
+    F: Future<Output = Result<(), String>>,
+{
+    let workspace_id = job.schedule.workspace_id.clone();
+    connect.await?;
+    let response =
+        codex_core::start_thread_core(sessions, workspaces, workspace_id.clone()).await?;
+    if let Some(error) = response_error(&response) {
+        return Err(error);
+    }
+    let thread_id = response_thread_id(&response)
+        .ok_or_else(|| "thread/start did not return a thread id".to_string())?;
+    let _ = codex_core::set_thread_name_core(
+        sessions,
+        workspace_id.clone(),
+        thread_id.clone(),
+        job.schedule.name.clone(),
+    )
+    .await;
+    let run = update_run(data_dir, &job.run_id, |run| {
+        run.thread_id = Some(thread_id.clone())
+    })?;
+    emit_schedule_event(event_sink, &workspace_id, "schedule/runStarted", json!(run));
+
+    let session = sessions
+        .lock()
+        .await
+        .get(&workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not connected".to_string())?;
+    let (tx, mut events) = mpsc::unbounded_channel();
+    session
+        .thread_observers
+        .lock()
+        .await
+        .insert(thread_id.clone(), tx);
+    let outcome = watch_scheduled_turn(
+        data_dir,
+        sessions,
+        workspaces,
+        event_sink,
+        job,
+        &thread_id,
+        &mut events,
+    )
+    .await;
+    session.thread_observers.lock().await.remove(&thread_id);
+    outcome
+}
+
+/// Starts a thread for the job, sends the schedule's prompt and waits for the
+/// turn to finish. `connect` makes sure the workspace session is running.
+pub(crate) async fn execute_scheduled_run_core<E, F>(
+    data_dir: &Path,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    event_sink: &E,
+    job: ScheduledRunJob,
+    connect: F,
+) -> Result<ScheduledRunRecord, String>
+where
+    E: EventSink,
+    F: Future<Output = Result<(), String>>,
+{
+    let (status, error) = match drive_scheduled_run(
+        data_dir, sessions, workspaces, event_sink, &job, connect,
+    )
+    .await
+    {
+        Ok(outcome) => outcome,
+        Err(err) => (ScheduledRunStatus::Failed, Some(err)),
+    };
+    let run = update_run(data_dir, &job.run_id, |run| {
+        run.status = status;
+        run.error = error;
+        run.finished_at_ms = Some(now_ms());
+    })?;
+    emit_schedule_event(
+        event_sink,
+        &job.schedule.workspace_id,
+        "schedule/runCompleted",
+        json!(run),
+    );
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::runtime::Runtime;
+
+    fn temp_data_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-schedules-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn due_schedules_are_queued_skipped_while_busy_and_recovered() {
+        let data_dir = temp_data_dir();
+        let workspace = WorkspaceEntry {
+            id: "ws-1".to_string(),
+            name: "repo".to_string(),
+            path: "/tmp/repo".to_string(),
+            kind: Default::default(),
+            parent_id: None,
+            worktree: None,
+            settings: Default::default(),
+        };
+        let workspaces = Mutex::new(HashMap::from([("ws-1".to_string(), workspace)]));
+        let runtime = Runtime::new().expect("create tokio runtime");
+        let schedule = runtime
+            .block_on(schedules_create_core(
+                &data_dir,
+                &workspaces,
+                ScheduledRunInput {
+                    name: " Nightly audit ".to_string(),
+                    workspace_id: "ws-1".to_string(),
+                    prompt: "Audit dependencies".to_string(),
+                    cron: "0 3 * * *".to_string(),
+                    model: Some(" ".to_string()),
+                    effort: None,
+                    access_mode: Some("read-only".to_string()),
+                    enabled: None,
+                    max_duration_minutes: None,
+                },
+            ))
+            .expect("create schedule");
+        assert_eq!(schedule.name, "Nightly audit");
+        assert_eq!(schedule.model, None);
+        let next = schedule.next_run_at_ms.expect("next run");
+
+        let runner = ScheduleRunner::default();
+        let mut jobs = runtime.block_on(runner.take_jobs()).expect("jobs");
+        let before = Local.timestamp_millis_opt(next - 1).unwrap();
+        assert!(enqueue_due_schedules(&data_dir, &runner, before)
+            .expect("tick")
+            .is_empty());
+
+        let due = Local.timestamp_millis_opt(next).unwrap();
+        let recorded = enqueue_due_schedules(&data_dir, &runner, due).expect("tick");
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].status, ScheduledRunStatus::Running);
+        let job = jobs.try_recv().expect("queued job");
+        assert_eq!(job.run_id, recorded[0].run_id);
+
+        let later = Local.timestamp_millis_opt(next + 2 * 86_400_000).unwrap();
+        let skipped = enqueue_due_schedules(&data_dir, &runner, later).expect("tick");
+        assert_eq!(skipped[0].status, ScheduledRunStatus::Skipped);
+        assert!(jobs.try_recv().is_err());
+        assert!(schedules_run_now_core(&data_dir, &runner, schedule.id.clone()).is_err());
+
+        recover_interrupted_runs(&data_dir, next).expect("recover");
+        let history =
+            schedules_history_core(&data_dir, Some(schedule.id.clone()), None).expect("history");
+        assert_eq!(history[1].status, ScheduledRunStatus::Running);
+
+        recover_interrupted_runs(&data_dir, next + 2 * 86_400_000).expect("recover");
+        let history =
+            schedules_history_core(&data_dir, Some(schedule.id.clone()), None).expect("history");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].status, ScheduledRunStatus::Interrupted);
+
+        let manual = schedules_run_now_core(&data_dir, &runner, schedule.id).expect("run now");
+        assert_eq!(manual.trigger, ScheduledRunTrigger::Manual);
+        assert_eq!(jobs.try_recv().expect("manual job").run_id, manual.run_id);
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn only_one_process_holds_the_clock_lease() {
+        let data_dir = temp_data_dir();
+        let ttl = SCHEDULE_CLOCK_LEASE_TTL.as_millis() as i64;
+
+        assert!(acquire_clock_lease(&data_dir, "desktop", 1_000).expect("acquire"));
+        assert!(!acquire_clock_lease(&data_dir, "daemon", 2_000).expect("contend"));
+        assert!(acquire_clock_lease(&data_dir, "desktop", 3_000).expect("renew"));
+        assert!(!acquire_clock_lease(&data_dir, "daemon", 3_000 + ttl - 1).expect("contend"));
+
+        assert!(acquire_clock_lease(&data_dir, "daemon", 3_000 + ttl).expect("take over"));
+        assert!(!acquire_clock_lease(&data_dir, "desktop", 3_000 + ttl).expect("lost"));
+        let lease = read_clock_lease(&clock_lease_path(&data_dir)).expect("lease");
+        assert_eq!(lease.owner, "daemon");
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -0,0 +1,224 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+/// How far ahead `next_after` searches before giving up on expressions such as
+/// `0 0 31 2 *` that can never fire.
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+fn parse_value(raw: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let lowered = raw.to_ascii_lowercase();
+    if let Some(index) = names.iter().position(|name| *name == lowered) {
+        return Ok(index as u32 + min);
+    }
+    let value = raw
+        .parse::<u32>()
+        .map_err(|_| format!("invalid value `{raw}`"))?;
+    if value < min || value > max {
+        return Err(format!("value `{raw}` is outside {min}-{max}"));
+    }
+    Ok(value)
+}
+
+/// Parses one cron field into a bitmask. Supports `*`, lists, ranges and
+/// steps (`*/15`, `1-5`, `0,30`, `10-40/10`).
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<(u64, bool), String> {
+    let mut mask = 0u64;
+    let mut restricted = false;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid step `{step}`"))?;
+                (range, step)
+            }
+            None => (item, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            restricted = true;
+            (
+                parse_value(start, min, max, names)?,
+                parse_value(end, min, max, names)?,
+            )
+        } else {
+            restricted = true;
+            let start = parse_value(range, min, max, names)?;
+            (start, if item.contains('/') { max } else { start })
+        };
+        if start > end {
+            return Err(format!("invalid range `{range}`"));
+        }
+        if range == "*" && step > 1 {
+            restricted = true;
+        }
+        let mut value = start;
+        while value <= end {
+            mask |= 1 << value;
+            value += step;
+        }
+    }
+    Ok((mask, restricted))
+}
+
+pub(crate) fn parse_cron(expression: &str) -> Result<CronSchedule, String> {
+    let expression = expression.trim();
+    let expanded = match expression.to_ascii_lowercase().as_str() {
+        "@hourly" => "0 * * * *".to_string(),
+        "@daily" | "@midnight" => "0 0 * * *".to_string(),
+        "@weekly" => "0 0 * * 0".to_string(),
+        "@monthly" => "0 0 1 * *".to_string(),
+        "@yearly" | "@annually" => "0 0 1 1 *".to_string(),
+        _ => expression.to_string(),
+    };
+    let fields: Vec<&str> = expanded.split_whitespace().collect();
+    if fields.len() != 5 {
+        return Err(format!(
+            "Cron expression `{expression}` must have 5 fields (minute hour day month weekday)."
+        ));
+    }
+    let wrap = |name: &str, result: Result<(u64, bool), String>| {
+        result.map_err(|err| format!("Invalid {name} field in `{expression}`: {err}."))
+    };
+    let (minutes, _) = wrap("minute", parse_field(fields[0], 0, 59, &[]))?;
+    let (hours, _) = wrap("hour", parse_field(fields[1], 0, 23, &[]))?;
+    let (days, days_restricted) = wrap("day", parse_field(fields[2], 1, 31, &[]))?;
+    let (months, _) = wrap("month", parse_field(fields[3], 1, 12, &MONTH_NAMES))?;
+    let (mut weekdays, weekdays_restricted) =
+        wrap("weekday", parse_field(fields[4], 0, 7, &WEEKDAY_NAMES))?;
+    // Both 0 and 7 mean Sunday.
+    if weekdays & (1 << 7) != 0 {
+        weekdays = (weekdays & !(1 << 7)) | 1;
+    }
+    Ok(CronSchedule {
+        minutes,
+        hours,
+        days,
+        months,
+        weekdays,
+        days_restricted,
+        weekdays_restricted,
+    })
+}
+
+impl CronSchedule {
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        // Classic cron: when both day fields are restricted either may match.
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            (true, false) => day,
+            (false, true) => weekday,
+            (false, false) => true,
+        }
+    }
+
+    fn next_naive_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut candidate = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = after + Duration::days(MAX_LOOKAHEAD_DAYS);
+        while candidate <= limit {
+            if self.months & (1 << candidate.month()) == 0 {
+                let (year, month) = if candidate.month() == 12 {
+                    (candidate.year() + 1, 1)
+                } else {
+                    (candidate.year(), candidate.month() + 1)
+                };
+                candidate = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.matches_day(candidate.date()) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if self.hours & (1 << candidate.hour()) == 0 {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << candidate.minute()) == 0 {
+                candidate += Duration::minutes(1);
+                continue;
+            }
+            return Some(candidate);
+        }
+        None
+    }
+
+    /// Returns the first matching minute strictly after `after`, evaluated in
+    /// the timezone of `after`. Local times skipped by DST are not matched.
+    pub(crate) fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let timezone = after.timezone();
+        let mut cursor = after.naive_local();
+        loop {
+            let next = self.next_naive_after(cursor)?;
+            if let Some(resolved) = timezone.from_local_datetime(&next).earliest() {
+                return Some(resolved);
+            }
+            cursor = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("timestamp")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn next_after_handles_steps_ranges_and_weekdays() {
+        let every_quarter = parse_cron("*/15 * * * *").expect("parse");
+        assert_eq!(
+            every_quarter.next_after(&at("2026-03-04T10:07:30Z")),
+            Some(at("2026-03-04T10:15:00Z"))
+        );
+
+        let weekday_mornings = parse_cron("30 7 * * mon-fri").expect("parse");
+        // 2026-03-06 is a Friday, so the next run is the following Monday.
+        assert_eq!(
+            weekday_mornings.next_after(&at("2026-03-06T08:00:00Z")),
+            Some(at("2026-03-09T07:30:00Z"))
+        );
+
+        let nightly = parse_cron("@daily").expect("parse");
+        assert_eq!(
+            nightly.next_after(&at("2026-12-31T23:59:00Z")),
+            Some(at("2027-01-01T00:00:00Z"))
+        );
+
+        let never = parse_cron("0 0 31 2 *").expect("parse");
+        assert_eq!(never.next_after(&at("2026-01-01T00:00:00Z")), None);
+    }
+
+    #[test]
+    fn parse_cron_rejects_malformed_expressions() {
+        assert!(parse_cron("* * * *").is_err());
+        assert!(parse_cron("60 * * * *").is_err());
+        assert!(parse_cron("*/0 * * * *").is_err());
+        assert!(parse_cron("0 9 * * funday").is_err());
+        assert!(parse_cron("0 9 * * 7").is_ok());
+    }
+}
@@ -0,0 +1,53 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Serializes read-modify-write cycles on one store's files. Each store owns
+/// its own lock so unrelated stores never wait on each other.
+pub(crate) struct StoreLock(Mutex<()>);
+
+impl StoreLock {
+    pub(crate) const fn new() -> Self {
+        Self(Mutex::new(()))
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, ()> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+pub(crate) fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Reads a JSON file, returning the default value when it does not exist.
+pub(crate) fn read_json<T: DeserializeOwned + Default>(path: &Path) -> Result<T, String> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let data = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    serde_json::from_str(&data).map_err(|err| format!("Failed to parse {}: {err}", path.display()))
+}
+
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let data = serde_json::to_string_pretty(value).map_err(|err| err.to_string())?;
+    std::fs::write(path, data).map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+/// Trims the value and drops it when nothing is left.
+pub(crate) fn trim_optional(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
@@ -0,0 +1,776 @@
+use chrono::{DateTime, Local, TimeZone, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::shared::codex_core;
+use crate::types::{ThreadExport, ThreadExportFormat, WorkspaceEntry};
+
+/// Command output and diffs beyond this many characters are truncated so a
+/// single noisy build log does not bury the conversation.
+const MAX_BLOCK_CHARS: usize = 20_000;
+const MAX_FILE_STEM_CHARS: usize = 60;
+const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct FileChangeEntry {
+    path: String,
+    kind: String,
+    diff: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum TranscriptEntry {
+    #[serde(rename_all = "camelCase")]
+    User { text: String, images: Vec<String> },
+    #[serde(rename_all = "camelCase")]
+    Agent { text: String },
+    #[serde(rename_all = "camelCase")]
+    Reasoning { summary: String },
+    #[serde(rename_all = "camelCase")]
+    Command {
+        command: String,
+        cwd: String,
+        status: String,
+        exit_code: Option<i64>,
+        output: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    FileChange {
+        status: String,
+        changes: Vec<FileChangeEntry>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Tool {
+        title: String,
+        detail: String,
+        status: String,
+        output: String,
+    },
+    /// A command or patch the user declined when asked for approval.
+    #[serde(rename_all = "camelCase")]
+    ApprovalDeclined { subject: String },
+    #[serde(rename_all = "camelCase")]
+    TurnError { message: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Transcript {
+    thread_id: String,
+    title: String,
+    workspace_name: Option<String>,
+    workspace_path: Option<String>,
+    created_at: Option<String>,
+    entries: Vec<TranscriptEntry>,
+}
+
+fn as_str<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+fn join_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.trim().to_string(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| {
+                part.as_str()
+                    .or_else(|| part.get("text").and_then(Value::as_str))
+            })
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        _ => String::new(),
+    }
+}
+
+fn command_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" "),
+        Some(Value::String(command)) => command.clone(),
+        _ => String::new(),
+    }
+}
+
+fn change_kind(change: &Value) -> String {
+    let kind = match change.get("kind") {
+        Some(Value::String(kind)) => kind.as_str(),
+        Some(kind) => as_str(kind, "type"),
+        None => "",
+    };
+    match kind.to_ascii_lowercase().as_str() {
+        "add" => "A",
+        "delete" => "D",
+        _ => "M",
+    }
+    .to_string()
+}
+
+fn user_entry(item: &Value) -> Option<TranscriptEntry> {
+    let mut text_parts = Vec::new();
+    let mut images = Vec::new();
+    for input in item
+        .get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        match as_str(input, "type") {
+            "text" => text_parts.push(as_str(input, "text").to_string()),
+            "skill" => text_parts.push(format!("${}", as_str(input, "name"))),
+            "image" | "localImage" => {
+                let source = ["url", "path"]
+                    .iter()
+                    .map(|key| as_str(input, key))
+                    .find(|value| !value.is_empty())
+                    .unwrap_or("");
+                if !source.is_empty() && !source.starts_with("data:") {
+                    images.push(source.to_string());
+                } else if !source.is_empty() {
+                    images.push("(inline image)".to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    let text = text_parts.join(" ").trim().to_string();
+    (!text.is_empty() || !images.is_empty()).then_some(TranscriptEntry::User { text, images })
+}
+
+/// Maps one app-server thread item to transcript entries. A declined command
+/// or patch yields an extra approval entry after the item itself.
+fn item_entries(item: &Value) -> Vec<TranscriptEntry> {
+    let status = as_str(item, "status").to_string();
+    let declined = status.eq_ignore_ascii_case("declined");
+    match as_str(item, "type") {
+        "userMessage" => user_entry(item).into_iter().collect(),
+        "agentMessage" => {
+            let text = as_str(item, "text").trim().to_string();
+            if text.is_empty() {
+                Vec::new()
+            } else {
+                vec![TranscriptEntry::Agent { text }]
+            }
+        }
+        "reasoning" => {
+            let summary = join_text(item.get("summary"));
+            if summary.is_empty() {
+                Vec::new()
+            } else {
+                vec![TranscriptEntry::Reasoning { summary }]
+            }
+        }
+        "commandExecution" => {
+            let command = command_text(item.get("command"));
+            let mut entries = vec![TranscriptEntry::Command {
+                command: command.clone(),
+                cwd: as_str(item, "cwd").to_string(),
+                status,
+                exit_code: item.get("exitCode").and_then(Value::as_i64),
+                output: as_str(item, "aggregatedOutput").to_string(),
+            }];
+            if declined {
+                entries.push(TranscriptEntry::ApprovalDeclined {
+                    subject: format!("command `{command}`"),
+                });
+            }
+            entries
+        }
+        "fileChange" => {
+            let changes: Vec<FileChangeEntry> = item
+                .get("changes")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter(|change| !as_str(change, "path").is_empty())
+                .map(|change| FileChangeEntry {
+                    path: as_str(change, "path").to_string(),
+                    kind: change_kind(change),
+                    diff: as_str(change, "diff").to_string(),
+                })
+                .collect();
+            let subject = changes
+                .iter()
+                .map(|change| change.path.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let mut entries = vec![TranscriptEntry::FileChange { status, changes }];
+            if declined {
+                entries.push(TranscriptEntry::ApprovalDeclined {
+                    subject: format!("changes to {subject}"),
+                });
+            }
+            entries
+        }
+        "mcpToolCall" => {
+            let server = as_str(item, "server");
+            let tool = as_str(item, "tool");
+            let output = match item.get("result").or_else(|| item.get("error")) {
+                Some(Value::String(text)) => text.clone(),
+                Some(Value::Null) | None => String::new(),
+                Some(other) => serde_json::to_string_pretty(other).unwrap_or_default(),
+            };
+            vec![TranscriptEntry::Tool {
+                title: format!("Tool: {server} / {tool}"),
+                detail: item
+                    .get("arguments")
+                    .map(|arguments| serde_json::to_string_pretty(arguments).unwrap_or_default())
+                    .unwrap_or_default(),
+                status,
+                output,
+            }]
+        }
+        "webSearch" => vec![TranscriptEntry::Tool {
+            title: "Web search".to_string(),
+            detail: as_str(item, "query").to_string(),
+            status,
+            output: String::new(),
+        }],
+        _ => Vec::new(),
+    }
+}
+
+fn thread_title(thread: &Value, thread_id: &str) -> String {
+    let name = as_str(thread, "name").trim();
+    if !name.is_empty() {
+        return name.to_string();
+    }
+    let preview = as_str(thread, "preview")
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("");
+    if preview.is_empty() {
+        return format!("Thread {thread_id}");
+    }
+    if preview.chars().count() > 80 {
+        let truncated: String = preview.chars().take(79).collect();
+        return format!("{truncated}…");
+    }
+    preview.to_string()
+}
+
+fn format_timestamp(value: Option<&Value>) -> Option<String> {
+    let seconds = value?.as_i64()?;
+    // The app-server reports seconds; tolerate millisecond values as well.
+    let seconds = if seconds > 10_000_000_000 {
+        seconds / 1000
+    } else {
+        seconds
+    };
+    let utc: DateTime<Utc> = Utc.timestamp_opt(seconds, 0).single()?;
+    Some(utc.with_timezone(&Local).to_rfc3339())
+}
+
+fn build_transcript(
+    thread: &Value,
+    thread_id: &str,
+    workspace: Option<&WorkspaceEntry>,
+) -> Transcript {
+    let mut entries = Vec::new();
+    for turn in thread
+        .get("turns")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        for item in turn
+            .get("items")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            entries.extend(item_entries(item));
+        }
+        if as_str(turn, "status") == "failed" {
+            let message = turn
+                .get("error")
+                .map(|error| as_str(error, "message"))
+                .filter(|message| !message.is_empty())
+                .unwrap_or("Turn failed.");
+            entries.push(TranscriptEntry::TurnError {
+                message: message.to_string(),
+            });
+        }
+    }
+    Transcript {
+        thread_id: thread_id.to_string(),
+        title: thread_title(thread, thread_id),
+        workspace_name: workspace.map(|entry| entry.name.clone()),
+        workspace_path: workspace.map(|entry| entry.path.clone()),
+        created_at: format_timestamp(thread.get("createdAt")),
+        entries,
+    }
+}
+
+fn truncate_block(value: &str) -> String {
+    let total = value.chars().count();
+    if total <= MAX_BLOCK_CHARS {
+        return value.trim_end().to_string();
+    }
+    let kept: String = value.chars().take(MAX_BLOCK_CHARS).collect();
+    format!(
+        "{}\n… ({} more characters truncated)",
+        kept.trim_end(),
+        total - MAX_BLOCK_CHARS
+    )
+}
+
+/// Wraps `body` in a code fence longer than any backtick run inside it.
+fn fence(language: &str, body: &str) -> String {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in body.chars() {
+        if ch == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    let ticks = "`".repeat(longest.max(2) + 1);
+    format!("{ticks}{language}\n{body}\n{ticks}\n")
+}
+
+fn render_markdown(transcript: &Transcript) -> String {
+    let mut out = format!("# {}\n\n", transcript.title);
+    out.push_str(&format!("- Thread: `{}`\n", transcript.thread_id));
+    if let (Some(name), Some(path)) = (&transcript.workspace_name, &transcript.workspace_path) {
+        out.push_str(&format!("- Workspace: {name} (`{path}`)\n"));
+    }
+    if let Some(created_at) = &transcript.created_at {
+        out.push_str(&format!("- Started: {created_at}\n"));
+    }
+    out.push_str(&format!("- Exported: {}\n", Local::now().to_rfc3339()));
+
+    for entry in &transcript.entries {
+        out.push('\n');
+        match entry {
+            TranscriptEntry::User { text, images } => {
+                out.push_str("## User\n\n");
+                if !text.is_empty() {
+                    out.push_str(text);
+                    out.push('\n');
+                }
+                for image in images {
+                    out.push_str(&format!("\n- Image: `{image}`\n"));
+                }
+            }
+            TranscriptEntry::Agent { text } => {
+                out.push_str("## Assistant\n\n");
+                out.push_str(text);
+                out.push('\n');
+            }
+            TranscriptEntry::Reasoning { summary } => {
+                out.push_str("> **Reasoning**\n>\n");
+                for line in summary.lines() {
+                    out.push_str(&format!("> {line}\n"));
+                }
+            }
+            TranscriptEntry::Command {
+                command,
+                cwd,
+                status,
+                exit_code,
+                output,
+            } => {
+                let outcome = match exit_code {
+                    Some(code) => format!("exit {code}"),
+                    None => status.clone(),
+                };
+                out.push_str(&format!("**Command** ({outcome})"));
+                if !cwd.is_empty() {
+                    out.push_str(&format!(" in `{cwd}`"));
+                }
+                out.push_str("\n\n");
+                out.push_str(&fence("sh", command));
+                if !output.trim().is_empty() {
+                    out.push('\n');
+                    out.push_str(&fence("text", &truncate_block(output)));
+                }
+            }
+            TranscriptEntry::FileChange { status, changes } => {
+                out.push_str(&format!("**File changes** ({status})\n\n"));
+                for change in changes {
+                    out.push_str(&format!("- {} `{}`\n", change.kind, change.path));
+                }
+                for change in changes.iter().filter(|change| !change.diff.is_empty()) {
+                    out.push('\n');
+                    out.push_str(&fence("diff", &truncate_block(&change.diff)));
+                }
+            }
+            TranscriptEntry::Tool {
+                title,
+                detail,
+                status,
+                output,
+            } => {
+                out.push_str(&format!("**{title}** ({status})\n"));
+                if !detail.is_empty() {
+                    out.push('\n');
+                    out.push_str(&fence("", &truncate_block(detail)));
+                }
+                if !output.is_empty() {
+                    out.push('\n');
+                    out.push_str(&fence("", &truncate_block(output)));
+                }
+            }
+            TranscriptEntry::ApprovalDeclined { subject } => {
+                out.push_str(&format!("**Approval declined:** {subject}\n"));
+            }
+            TranscriptEntry::TurnError { message } => {
+                out.push_str(&format!("**Turn failed:** {message}\n"));
+            }
+        }
+    }
+    out
+}
+
+fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn html_diff(diff: &str) -> String {
+    truncate_block(diff)
+        .lines()
+        .map(|line| {
+            let class = if line.starts_with("+++") || line.starts_with("---") {
+                "meta"
+            } else if line.starts_with('+') {
+                "add"
+            } else if line.starts_with('-') {
+                "del"
+            } else if line.starts_with("@@") {
+                "hunk"
+            } else {
+                "ctx"
+            };
+            format!("<span class=\"{class}\">{}</span>", escape_html(line))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+const HTML_STYLE: &str = "\
+body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:920px;margin:2rem auto;padding:0 1rem;color:#1f2328;background:#fff;line-height:1.5}\
+h1{font-size:1.6rem;margin-bottom:.25rem}\
+.meta-list{color:#59636e;font-size:.9rem;margin:0 0 1.5rem;padding-left:1.2rem}\
+.entry{border:1px solid #d1d9e0;border-radius:8px;padding:.75rem 1rem;margin:1rem 0}\
+.entry h2{font-size:.8rem;text-transform:uppercase;letter-spacing:.05em;color:#59636e;margin:0 0 .5rem}\
+.user{background:#f6f8fa}.reasoning{border-style:dashed;color:#59636e}\
+.declined,.error{border-color:#cf222e;color:#cf222e}\
+.text{white-space:pre-wrap;margin:0}\
+pre{background:#f6f8fa;border-radius:6px;padding:.75rem;overflow-x:auto;font-size:.85rem;margin:.5rem 0 0}\
+.add{color:#116329;background:#dafbe1}.del{color:#82071e;background:#ffebe9}.hunk{color:#8250df}.meta{color:#59636e}\
+";
+
+fn render_html(transcript: &Transcript) -> String {
+    let title = escape_html(&transcript.title);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<ul class=\"meta-list\">\n"
+    );
+    out.push_str(&format!(
+        "<li>Thread: <code>{}</code></li>\n",
+        escape_html(&transcript.thread_id)
+    ));
+    if let (Some(name), Some(path)) = (&transcript.workspace_name, &transcript.workspace_path) {
+        out.push_str(&format!(
+            "<li>Workspace: {} (<code>{}</code>)</li>\n",
+            escape_html(name),
+            escape_html(path)
+        ));
+    }
+    if let Some(created_at) = &transcript.created_at {
+        out.push_str(&format!("<li>Started: {}</li>\n", escape_html(created_at)));
+    }
+    out.push_str(&format!(
+        "<li>Exported: {}</li>\n</ul>\n",
+        escape_html(&Local::now().to_rfc3339())
+    ));
+
+    for entry in &transcript.entries {
+        let (class, heading, body) = match entry {
+            TranscriptEntry::User { text, images } => {
+                let mut body = format!("<p class=\"text\">{}</p>", escape_html(text));
+                for image in images {
+                    body.push_str(&format!(
+                        "\n<p>Image: <code>{}</code></p>",
+                        escape_html(image)
+                    ));
+                }
+                ("user", "User".to_string(), body)
+            }
+            TranscriptEntry::Agent { text } => (
+                "agent",
+                "Assistant".to_string(),
+                format!("<p class=\"text\">{}</p>", escape_html(text)),
+            ),
+            TranscriptEntry::Reasoning { summary } => (
+                "reasoning",
+                "Reasoning".to_string(),
+                format!("<p class=\"text\">{}</p>", escape_html(summary)),
+            ),
+            TranscriptEntry::Command {
+                command,
+                cwd,
+                status,
+                exit_code,
+                output,
+            } => {
+                let outcome = match exit_code {
+                    Some(code) => format!("exit {code}"),
+                    None => status.clone(),
+                };
+                let mut body = format!("<pre><code>$ {}</code></pre>", escape_html(command));
+                if !output.trim().is_empty() {
+                    body.push_str(&format!(
+                        "\n<pre><code>{}</code></pre>",
+                        escape_html(&truncate_block(output))
+                    ));
+                }
+                let location = if cwd.is_empty() {
+                    String::new()
+                } else {
+                    format!(" in {cwd}")
+                };
+                ("command", format!("Command ({outcome}){location}"), body)
+            }
+            TranscriptEntry::FileChange { status, changes } => {
+                let mut body = String::from("<ul>");
+                for change in changes {
+                    body.push_str(&format!(
+                        "<li>{} <code>{}</code></li>",
+                        change.kind,
+                        escape_html(&change.path)
+                    ));
+                }
+                body.push_str("</ul>");
+                for change in changes.iter().filter(|change| !change.diff.is_empty()) {
+                    body.push_str(&format!(
+                        "\n<pre><code>{}</code></pre>",
+                        html_diff(&change.diff)
+                    ));
+                }
+                ("files", format!("File changes ({status})"), body)
+            }
+            TranscriptEntry::Tool {
+                title,
+                detail,
+                status,
+                output,
+            } => {
+                let mut body = String::new();
+                for block in [detail, output] {
+                    if !block.is_empty() {
+                        body.push_str(&format!(
+                            "<pre><code>{}</code></pre>",
+                            escape_html(&truncate_block(block))
+                        ));
+                    }
+                }
+                ("tool", format!("{title} ({status})"), body)
+            }
+            TranscriptEntry::ApprovalDeclined { subject } => (
+                "declined",
+                "Approval declined".to_string(),
+                format!("<p class=\"text\">{}</p>", escape_html(subject)),
+            ),
+            TranscriptEntry::TurnError { message } => (
+                "error",
+                "Turn failed".to_string(),
+                format!("<p class=\"text\">{}</p>", escape_html(message)),
+            ),
+        };
+        out.push_str(&format!(
+            "<section class=\"entry {class}\">\n<h2>{}</h2>\n{body}\n</section>\n",
+            escape_html(&heading)
+        ));
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn file_stem(title: &str) -> String {
+    let mut stem = String::new();
+    for ch in title.chars() {
+        if ch.is_ascii_alphanumeric() {
+            stem.push(ch.to_ascii_lowercase());
+        } else if !stem.is_empty() && !stem.ends_with('-') {
+            stem.push('-');
+        }
+        if stem.len() >= MAX_FILE_STEM_CHARS {
+            break;
+        }
+    }
+    let stem = stem.trim_end_matches('-');
+    if stem.is_empty() {
+        "thread".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+fn render_export(
+    thread: &Value,
+    thread_id: &str,
+    workspace: Option<&WorkspaceEntry>,
+    format: ThreadExportFormat,
+) -> Result<ThreadExport, String> {
+    let transcript = build_transcript(thread, thread_id, workspace);
+    let (content, extension) = match format {
+        ThreadExportFormat::Markdown => (render_markdown(&transcript), "md"),
+        ThreadExportFormat::Html => (render_html(&transcript), "html"),
+        ThreadExportFormat::Json => {
+            let archive = json!({
+                "version": EXPORT_SCHEMA_VERSION,
+                "exportedAt": Local::now().to_rfc3339(),
+                "workspaceId": workspace.map(|entry| entry.id.clone()),
+                "transcript": transcript,
+                "thread": thread,
+            });
+            let content = serde_json::to_string_pretty(&archive).map_err(|err| err.to_string())?;
+            (content, "json")
+        }
+    };
+    Ok(ThreadExport {
+        format,
+        file_name: format!("{}.{extension}", file_stem(&transcript.title)),
+        title: transcript.title,
+        content,
+    })
+}
+
+/// Reads the thread with all turns and renders it as Markdown, self-contained
+/// HTML, or a JSON archive that keeps the raw app-server thread alongside the
+/// normalized transcript.
+pub(crate) async fn export_thread_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    thread_id: String,
+    format: ThreadExportFormat,
+) -> Result<ThreadExport, String> {
+    let response =
+        codex_core::read_thread_with_turns_core(sessions, workspace_id.clone(), thread_id.clone())
+            .await?;
+    if let Some(error) = response.get("error") {
+        return Err(error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("Failed to read thread.")
+            .to_string());
+    }
+    let thread = response
+        .get("result")
+        .and_then(|result| result.get("thread"))
+        .ok_or_else(|| "thread/read response did not include the thread.".to_string())?;
+    let workspace = workspaces.lock().await.get(&workspace_id).cloned();
+    render_export(thread, &thread_id, workspace.as_ref(), format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_thread() -> Value {
+        json!({
+            "id": "thr_1",
+            "preview": "Fix the <login> loop\nmore detail",
+            "createdAt": 1_760_000_000,
+            "turns": [{
+                "id": "turn_1",
+                "status": "failed",
+                "error": { "message": "rate limited" },
+                "items": [
+                    { "type": "userMessage", "id": "u1", "content": [
+                        { "type": "text", "text": "Fix the <login> loop" },
+                        { "type": "localImage", "path": "/tmp/shot.png" }
+                    ] },
+                    { "type": "reasoning", "id": "r1", "summary": ["Look at auth.ts"] },
+                    { "type": "commandExecution", "id": "c1", "command": ["rg", "login"],
+                      "cwd": "/repo", "status": "completed", "exitCode": 0,
+                      "aggregatedOutput": "src/auth.ts: login()\n" },
+                    { "type": "commandExecution", "id": "c2", "command": "rm -rf build",
+                      "status": "declined" },
+                    { "type": "fileChange", "id": "f1", "status": "completed", "changes": [
+                        { "path": "src/auth.ts", "kind": { "type": "update" },
+                          "diff": "@@ -1 +1 @@\n-old\n+new" }
+                    ] },
+                    { "type": "agentMessage", "id": "a1", "text": "Fixed ```the``` loop." }
+                ]
+            }]
+        })
+    }
+
+    #[test]
+    fn markdown_export_covers_every_item_kind() {
+        let export = render_export(
+            &sample_thread(),
+            "thr_1",
+            None,
+            ThreadExportFormat::Markdown,
+        )
+        .expect("export");
+        assert_eq!(export.title, "Fix the <login> loop");
+        assert_eq!(export.file_name, "fix-the-login-loop.md");
+        let content = export.content;
+        assert!(content.starts_with("# Fix the <login> loop\n"));
+        assert!(content.contains("## User\n\nFix the <login> loop\n"));
+        assert!(content.contains("- Image: `/tmp/shot.png`"));
+        assert!(content.contains("> **Reasoning**\n>\n> Look at auth.ts\n"));
+        assert!(content.contains("**Command** (exit 0) in `/repo`\n\n```sh\nrg login\n```\n"));
+        assert!(content.contains("**Approval declined:** command `rm -rf build`"));
+        assert!(content.contains("- M `src/auth.ts`\n"));
+        assert!(content.contains("```diff\n@@ -1 +1 @@\n-old\n+new\n```\n"));
+        assert!(content.contains("## Assistant\n\nFixed ```the``` loop.\n"));
+        assert!(content.contains("**Turn failed:** rate limited"));
+        assert_eq!(fence("", "a ```b``` c"), "````\na ```b``` c\n````\n");
+    }
+
+    #[test]
+    fn html_and_json_exports_escape_and_archive() {
+        let html = render_export(&sample_thread(), "thr_1", None, ThreadExportFormat::Html)
+            .expect("html export");
+        assert_eq!(html.file_name, "fix-the-login-loop.html");
+        assert!(html
+            .content
+            .contains("<title>Fix the &lt;login&gt; loop</title>"));
+        assert!(!html.content.contains("<login>"));
+        assert!(html.content.contains("<span class=\"add\">+new</span>"));
+        assert!(html.content.contains("<section class=\"entry declined\">"));
+
+        let archive = render_export(&sample_thread(), "thr_1", None, ThreadExportFormat::Json)
+            .expect("json export");
+        let parsed: Value = serde_json::from_str(&archive.content).expect("valid json");
+        assert_eq!(parsed["version"], 1);
+        assert_eq!(parsed["thread"]["id"], "thr_1");
+        let entries = parsed["transcript"]["entries"].as_array().expect("entries");
+        assert_eq!(entries[0]["type"], "user");
+        assert_eq!(entries[3]["type"], "command");
+        assert_eq!(entries[4]["type"], "approvalDeclined");
+        assert_eq!(entries.last().expect("last")["type"], "turnError");
+    }
+}
@@ -0,0 +1,736 @@
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex as StdMutex, OnceLock};
+use std::time::UNIX_EPOCH;
+use tokio::sync::Mutex;
+
+use crate::shared::local_usage_core::{
+    extract_cwd, extract_model_from_turn_context, extract_session_id, parse_day,
+    path_matches_workspace, read_timestamp_ms, resolve_sessions_roots,
+};
+use crate::types::{
+    ThreadSearchQuery, ThreadSearchResponse, ThreadSearchResult, ThreadSearchSnippet,
+    WorkspaceEntry,
+};
+
+const INDEX_FILE: &str = "thread-search-index.json";
+const INDEX_VERSION: u32 = 1;
+const MAX_LINE_BYTES: usize = 512_000;
+/// Longer messages (pasted logs, generated files) are indexed by prefix only
+/// to keep the persisted index small.
+const MAX_MESSAGE_CHARS: usize = 8_000;
+const DEFAULT_LIMIT: u32 = 50;
+const MAX_LIMIT: u32 = 500;
+const MAX_SNIPPETS: usize = 3;
+const SNIPPET_CONTEXT_BYTES: usize = 60;
+const TITLE_MAX_CHARS: usize = 80;
+
+/// Loaded indexes keyed by their data dir, like the local usage index.
+static SEARCH_INDEXES: OnceLock<StdMutex<HashMap<PathBuf, SearchIndex>>> = OnceLock::new();
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedMessage {
+    role: String,
+    text: String,
+    timestamp_ms: Option<i64>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedThread {
+    size: u64,
+    modified_ms: i64,
+    /// Byte offset just past the last complete line that was parsed.
+    offset: u64,
+    thread_id: Option<String>,
+    cwd: Option<String>,
+    models: Vec<String>,
+    started_at_ms: Option<i64>,
+    updated_at_ms: Option<i64>,
+    messages: Vec<IndexedMessage>,
+}
+
+impl IndexedThread {
+    fn process_line(&mut self, line: &str) {
+        if line.len() > MAX_LINE_BYTES {
+            return;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            return;
+        };
+        let timestamp_ms = read_timestamp_ms(&value);
+        if let Some(timestamp_ms) = timestamp_ms {
+            self.started_at_ms = Some(
+                self.started_at_ms
+                    .map_or(timestamp_ms, |started| started.min(timestamp_ms)),
+            );
+            self.updated_at_ms = Some(
+                self.updated_at_ms
+                    .map_or(timestamp_ms, |updated| updated.max(timestamp_ms)),
+            );
+        }
+        match value.get("type").and_then(Value::as_str).unwrap_or("") {
+            "session_meta" => {
+                if self.thread_id.is_none() {
+                    self.thread_id = extract_session_id(&value);
+                }
+                if self.cwd.is_none() {
+                    self.cwd = extract_cwd(&value);
+                }
+            }
+            "turn_context" => {
+                if self.cwd.is_none() {
+                    self.cwd = extract_cwd(&value);
+                }
+                if let Some(model) = extract_model_from_turn_context(&value) {
+                    if !self.models.contains(&model) {
+                        self.models.push(model);
+                    }
+                }
+            }
+            "event_msg" => {
+                let payload = value.get("payload");
+                let role = match payload
+                    .and_then(|payload| payload.get("type"))
+                    .and_then(Value::as_str)
+                {
+                    Some("user_message") => "user",
+                    Some("agent_message") => "assistant",
+                    _ => return,
+                };
+                let text = payload
+                    .and_then(|payload| payload.get("message"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .trim();
+                if text.is_empty() {
+                    return;
+                }
+                self.messages.push(IndexedMessage {
+                    role: role.to_string(),
+                    text: text.chars().take(MAX_MESSAGE_CHARS).collect(),
+                    timestamp_ms,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn title(&self) -> String {
+        let first_line = self
+            .messages
+            .iter()
+            .find(|message| message.role == "user")
+            .and_then(|message| {
+                message
+                    .text
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+            })
+            .unwrap_or("");
+        if first_line.is_empty() {
+            return "Untitled thread".to_string();
+        }
+        if first_line.chars().count() <= TITLE_MAX_CHARS {
+            return first_line.to_string();
+        }
+        let truncated: String = first_line.chars().take(TITLE_MAX_CHARS - 1).collect();
+        format!("{truncated}…")
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct SearchIndex {
+    version: u32,
+    files: HashMap<String, IndexedThread>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl SearchIndex {
+    /// Returns the indexed thread for `path`, parsing only lines appended
+    /// since the file was last indexed.
+    fn thread(&mut self, path: &Path) -> Option<&IndexedThread> {
+        let key = path.to_string_lossy().to_string();
+        let cached = self.files.remove(&key);
+        let Some((thread, changed)) = refresh_thread(path, cached) else {
+            self.dirty = true;
+            return None;
+        };
+        self.dirty |= changed;
+        Some(self.files.entry(key).or_insert(thread))
+    }
+
+    fn load(path: &Path) -> Self {
+        let index = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|data| serde_json::from_str::<SearchIndex>(&data).ok())
+            .filter(|index| index.version == INDEX_VERSION);
+        index.unwrap_or_else(|| SearchIndex {
+            version: INDEX_VERSION,
+            ..SearchIndex::default()
+        })
+    }
+
+    fn save(&mut self, path: &Path) -> Result<(), String> {
+        self.files.retain(|file, _| Path::new(file).exists());
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let data = serde_json::to_vec(self).map_err(|err| err.to_string())?;
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, data).map_err(|err| err.to_string())?;
+        std::fs::rename(&temp_path, path).map_err(|err| err.to_string())?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+fn with_search_index<T>(index_dir: Option<&Path>, f: impl FnOnce(&mut SearchIndex) -> T) -> T {
+    let Some(index_dir) = index_dir else {
+        return f(&mut SearchIndex::default());
+    };
+    let index_path = index_dir.join(INDEX_FILE);
+    let indexes = SEARCH_INDEXES.get_or_init(|| StdMutex::new(HashMap::new()));
+    let mut indexes = indexes
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let index = indexes
+        .entry(index_dir.to_path_buf())
+        .or_insert_with(|| SearchIndex::load(&index_path));
+    let result = f(index);
+    if index.dirty {
+        if let Err(err) = index.save(&index_path) {
+            eprintln!("thread search: failed to persist index: {err}");
+        }
+    }
+    result
+}
+
+fn refresh_thread(path: &Path, cached: Option<IndexedThread>) -> Option<(IndexedThread, bool)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let size = metadata.len();
+    let modified_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0);
+    let mut thread = match cached {
+        Some(cached) if cached.size == size && cached.modified_ms == modified_ms => {
+            return Some((cached, false));
+        }
+        Some(cached) if size > cached.size && cached.offset <= size => cached,
+        _ => IndexedThread::default(),
+    };
+
+    let mut handle = File::open(path).ok()?;
+    handle.seek(SeekFrom::Start(thread.offset)).ok()?;
+    let mut reader = BufReader::new(handle);
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        let read = match reader.read_until(b'\n', &mut buffer) {
+            Ok(read) => read,
+            Err(_) => break,
+        };
+        if read == 0 || buffer.last() != Some(&b'\n') {
+            break;
+        }
+        thread.offset += read as u64;
+        if let Ok(line) = std::str::from_utf8(&buffer) {
+            thread.process_line(line.trim_end());
+        }
+    }
+    thread.size = size;
+    thread.modified_ms = modified_ms;
+    Some((thread, true))
+}
+
+/// Lists rollout files under `<sessions>/YYYY/MM/DD/`, newest day first.
+fn collect_session_files(root: &Path) -> Vec<PathBuf> {
+    fn sorted_children(dir: &Path, want_dirs: bool) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut children: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                if want_dirs {
+                    path.is_dir()
+                } else {
+                    path.extension().and_then(|ext| ext.to_str()) == Some("jsonl")
+                }
+            })
+            .collect();
+        children.sort();
+        children.reverse();
+        children
+    }
+
+    let mut files = Vec::new();
+    for year in sorted_children(root, true) {
+        for month in sorted_children(&year, true) {
+            for day in sorted_children(&month, true) {
+                files.extend(sorted_children(&day, false));
+            }
+        }
+    }
+    files
+}
+
+/// Splits a query into lowercase terms; `"quoted text"` stays one term.
+fn parse_query_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in query.chars() {
+        if ch == '"' {
+            quoted = !quoted;
+            if !quoted && !current.trim().is_empty() {
+                terms.push(current.trim().to_string());
+                current.clear();
+            }
+            continue;
+        }
+        if ch.is_whitespace() && !quoted {
+            if !current.is_empty() {
+                terms.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        current.push(ch);
+    }
+    if !current.trim().is_empty() {
+        terms.push(current.trim().to_string());
+    }
+    let mut seen = HashSet::new();
+    terms
+        .into_iter()
+        .map(|term| term.to_ascii_lowercase())
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+struct SearchFilter {
+    terms: Vec<String>,
+    workspace_path: Option<PathBuf>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    model: Option<String>,
+}
+
+impl SearchFilter {
+    fn new(query: &ThreadSearchQuery, workspace_path: Option<PathBuf>) -> Result<Self, String> {
+        let day = |value: Option<&String>| -> Result<Option<NaiveDate>, String> {
+            value
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+                .map(parse_day)
+                .transpose()
+        };
+        let start = day(query.start_date.as_ref())?;
+        let end = day(query.end_date.as_ref())?;
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Err("Start date must not be after end date".to_string());
+            }
+        }
+        Ok(Self {
+            terms: parse_query_terms(&query.query),
+            workspace_path,
+            start,
+            end,
+            model: query
+                .model
+                .as_deref()
+                .map(str::trim)
+                .filter(|model| !model.is_empty())
+                .map(str::to_ascii_lowercase),
+        })
+    }
+
+    fn accepts_metadata(&self, thread: &IndexedThread) -> bool {
+        if let Some(workspace_path) = self.workspace_path.as_deref() {
+            match thread.cwd.as_deref() {
+                Some(cwd) if path_matches_workspace(cwd, workspace_path) => {}
+                _ => return false,
+            }
+        }
+        if self.start.is_some() || self.end.is_some() {
+            let Some(day) = thread.updated_at_ms.and_then(local_day) else {
+                return false;
+            };
+            if self.start.is_some_and(|start| day < start) || self.end.is_some_and(|end| day > end)
+            {
+                return false;
+            }
+        }
+        if let Some(model) = self.model.as_deref() {
+            let matches = thread
+                .models
+                .iter()
+                .any(|candidate| candidate.to_ascii_lowercase().starts_with(model));
+            if !matches {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn local_day(timestamp_ms: i64) -> Option<NaiveDate> {
+    let utc = Utc.timestamp_millis_opt(timestamp_ms).single()?;
+    Some(utc.with_timezone(&Local).date_naive())
+}
+
+/// Cuts a window around the earliest term match. Matching is ASCII
+/// case-insensitive, which keeps byte offsets valid in the original text.
+fn snippet(text: &str, lowered: &str, terms: &[String]) -> String {
+    let Some((position, length)) = terms
+        .iter()
+        .filter_map(|term| lowered.find(term.as_str()).map(|index| (index, term.len())))
+        .min()
+    else {
+        return String::new();
+    };
+    let mut start = position.saturating_sub(SNIPPET_CONTEXT_BYTES);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (position + length + SNIPPET_CONTEXT_BYTES).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+    let body = text[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "{}{body}{}",
+        if start > 0 { "…" } else { "" },
+        if end < text.len() { "…" } else { "" }
+    )
+}
+
+struct WorkspaceRef {
+    id: String,
+    name: String,
+    path: PathBuf,
+}
+
+fn workspace_for_cwd<'a>(workspaces: &'a [WorkspaceRef], cwd: &str) -> Option<&'a WorkspaceRef> {
+    workspaces
+        .iter()
+        .filter(|workspace| path_matches_workspace(cwd, &workspace.path))
+        .max_by_key(|workspace| workspace.path.as_os_str().len())
+}
+
+fn match_thread(
+    thread: &IndexedThread,
+    thread_id: &str,
+    filter: &SearchFilter,
+    workspaces: &[WorkspaceRef],
+) -> Option<ThreadSearchResult> {
+    if !filter.accepts_metadata(thread) {
+        return None;
+    }
+    let mut match_count = 0;
+    let mut snippets = Vec::new();
+    if !filter.terms.is_empty() {
+        let mut found = vec![false; filter.terms.len()];
+        for message in &thread.messages {
+            let lowered = message.text.to_ascii_lowercase();
+            let mut message_matches = 0;
+            for (term, found) in filter.terms.iter().zip(found.iter_mut()) {
+                let count = lowered.matches(term.as_str()).count();
+                if count > 0 {
+                    *found = true;
+                    message_matches += count;
+                }
+            }
+            if message_matches == 0 {
+                continue;
+            }
+            match_count += message_matches;
+            if snippets.len() < MAX_SNIPPETS {
+                snippets.push(ThreadSearchSnippet {
+                    role: message.role.clone(),
+                    text: snippet(&message.text, &lowered, &filter.terms),
+                    timestamp_ms: message.timestamp_ms,
+                });
+            }
+        }
+        if found.contains(&false) {
+            return None;
+        }
+    }
+    let workspace = thread
+        .cwd
+        .as_deref()
+        .and_then(|cwd| workspace_for_cwd(workspaces, cwd));
+    Some(ThreadSearchResult {
+        thread_id: thread_id.to_string(),
+        title: thread.title(),
+        workspace_id: workspace.map(|workspace| workspace.id.clone()),
+        workspace_name: workspace.map(|workspace| workspace.name.clone()),
+        cwd: thread.cwd.clone(),
+        models: thread.models.clone(),
+        started_at_ms: thread.started_at_ms,
+        updated_at_ms: thread.updated_at_ms,
+        match_count,
+        snippets,
+    })
+}
+
+fn search_index(
+    index: &mut SearchIndex,
+    sessions_roots: &[PathBuf],
+    filter: &SearchFilter,
+    workspaces: &[WorkspaceRef],
+    limit: usize,
+) -> ThreadSearchResponse {
+    let mut seen = HashSet::new();
+    let mut results = Vec::new();
+    for root in sessions_roots {
+        for path in collect_session_files(root) {
+            let Some(thread) = index.thread(&path) else {
+                continue;
+            };
+            let Some(thread_id) = thread.thread_id.clone() else {
+                continue;
+            };
+            if !seen.insert(thread_id.clone()) {
+                continue;
+            }
+            if let Some(result) = match_thread(thread, &thread_id, filter, workspaces) {
+                results.push(result);
+            }
+        }
+    }
+    results.sort_by(|left, right| {
+        right
+            .match_count
+            .cmp(&left.match_count)
+            .then_with(|| right.updated_at_ms.cmp(&left.updated_at_ms))
+    });
+    let total_matches = results.len();
+    results.truncate(limit);
+    ThreadSearchResponse {
+        results,
+        total_matches,
+        indexed_threads: seen.len(),
+    }
+}
+
+/// Searches user and assistant messages of every local Codex session. The
+/// index lives in `index_dir` and only re-reads session files that changed.
+pub(crate) async fn search_threads_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    index_dir: Option<PathBuf>,
+    query: ThreadSearchQuery,
+) -> Result<ThreadSearchResponse, String> {
+    let (sessions_roots, workspace_refs, workspace_path) = {
+        let workspaces = workspaces.lock().await;
+        let workspace_path = match query.workspace_id.as_deref() {
+            Some(workspace_id) => Some(PathBuf::from(
+                &workspaces
+                    .get(workspace_id)
+                    .ok_or("workspace not found")?
+                    .path,
+            )),
+            None => None,
+        };
+        let workspace_refs: Vec<WorkspaceRef> = workspaces
+            .values()
+            .map(|entry| WorkspaceRef {
+                id: entry.id.clone(),
+                name: entry.name.clone(),
+                path: PathBuf::from(&entry.path),
+            })
+            .collect();
+        (
+            resolve_sessions_roots(&workspaces, workspace_path.as_deref()),
+            workspace_refs,
+            workspace_path,
+        )
+    };
+    let filter = SearchFilter::new(&query, workspace_path)?;
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT) as usize;
+    tokio::task::spawn_blocking(move || {
+        with_search_index(index_dir.as_deref(), |index| {
+            search_index(index, &sessions_roots, &filter, &workspace_refs, limit)
+        })
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn write_session(root: &Path, day: &str, name: &str, lines: &[Value]) -> PathBuf {
+        let mut parts = day.split('-');
+        let dir = root
+            .join(parts.next().unwrap())
+            .join(parts.next().unwrap())
+            .join(parts.next().unwrap());
+        std::fs::create_dir_all(&dir).expect("create day dir");
+        let path = dir.join(format!("{name}.jsonl"));
+        let content: String = lines.iter().map(|line| format!("{line}\n")).collect();
+        std::fs::write(&path, content).expect("write session");
+        path
+    }
+
+    fn session_lines(
+        id: &str,
+        cwd: &str,
+        model: &str,
+        timestamp: &str,
+        user: &str,
+        agent: &str,
+    ) -> Vec<Value> {
+        vec![
+            json!({ "timestamp": timestamp, "type": "session_meta", "payload": { "id": id, "cwd": cwd } }),
+            json!({ "timestamp": timestamp, "type": "turn_context", "payload": { "cwd": cwd, "model": model } }),
+            json!({ "timestamp": timestamp, "type": "event_msg", "payload": { "type": "user_message", "message": user } }),
+            json!({ "timestamp": timestamp, "type": "event_msg", "payload": { "type": "agent_message", "message": agent } }),
+        ]
+    }
+
+    #[test]
+    fn parses_words_and_quoted_phrases() {
+        assert_eq!(
+            parse_query_terms(r#"Auth  "token refresh" auth"#),
+            vec!["auth".to_string(), "token refresh".to_string()]
+        );
+        assert!(parse_query_terms("   ").is_empty());
+    }
+
+    #[test]
+    fn searches_sessions_by_text_workspace_date_and_model() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-search-{}", Uuid::new_v4()));
+        write_session(
+            &root,
+            "2026-03-02",
+            "rollout-a",
+            &session_lines(
+                "thread-a",
+                "/work/api",
+                "gpt-5-codex",
+                "2026-03-02T10:00:00Z",
+                "Fix the auth TOKEN refresh loop",
+                "The token refresh now retries once before logging out.",
+            ),
+        );
+        let older = write_session(
+            &root,
+            "2026-02-01",
+            "rollout-b",
+            &session_lines(
+                "thread-b",
+                "/work/web",
+                "gpt-5",
+                "2026-02-01T10:00:00Z",
+                "Style the settings page",
+                "Updated the token colors.",
+            ),
+        );
+        let workspaces = vec![WorkspaceRef {
+            id: "ws-api".to_string(),
+            name: "API".to_string(),
+            path: PathBuf::from("/work/api"),
+        }];
+        let mut index = SearchIndex::default();
+        let search = |index: &mut SearchIndex, query: ThreadSearchQuery, path: Option<&str>| {
+            let filter = SearchFilter::new(&query, path.map(PathBuf::from)).expect("filter");
+            search_index(index, &[root.clone()], &filter, &workspaces, 10)
+        };
+
+        let response = search(
+            &mut index,
+            ThreadSearchQuery {
+                query: "\"token refresh\"".to_string(),
+                ..ThreadSearchQuery::default()
+            },
+            None,
+        );
+        assert_eq!(response.indexed_threads, 2);
+        assert_eq!(response.results.len(), 1);
+        let hit = &response.results[0];
+        assert_eq!(hit.thread_id, "thread-a");
+        assert_eq!(hit.title, "Fix the auth TOKEN refresh loop");
+        assert_eq!(hit.workspace_id.as_deref(), Some("ws-api"));
+        assert_eq!(hit.match_count, 2);
+        assert_eq!(hit.snippets[0].text, "Fix the auth TOKEN refresh loop");
+
+        let token = |query: ThreadSearchQuery| ThreadSearchQuery {
+            query: "token".to_string(),
+            ..query
+        };
+        assert_eq!(
+            search(&mut index, token(ThreadSearchQuery::default()), None)
+                .results
+                .len(),
+            2
+        );
+        let by_workspace = search(
+            &mut index,
+            token(ThreadSearchQuery::default()),
+            Some("/work/web"),
+        );
+        assert_eq!(by_workspace.results[0].thread_id, "thread-b");
+        assert_eq!(by_workspace.results.len(), 1);
+        let by_date = search(
+            &mut index,
+            token(ThreadSearchQuery {
+                start_date: Some("2026-03-01".to_string()),
+                ..ThreadSearchQuery::default()
+            }),
+            None,
+        );
+        assert_eq!(by_date.results.len(), 1);
+        let by_model = search(
+            &mut index,
+            token(ThreadSearchQuery {
+                model: Some("GPT-5-CODEX".to_string()),
+                ..ThreadSearchQuery::default()
+            }),
+            None,
+        );
+        assert_eq!(by_model.results[0].thread_id, "thread-a");
+        assert_eq!(by_model.results.len(), 1);
+
+        // Appended lines are picked up incrementally.
+        let mut appended = std::fs::read_to_string(&older).expect("read session");
+        appended.push_str(&format!(
+            "{}\n",
+            json!({ "timestamp": "2026-02-01T11:00:00Z", "type": "event_msg",
+                    "payload": { "type": "agent_message", "message": "Also fixed the refresh button." } })
+        ));
+        std::fs::write(&older, appended).expect("append session");
+        let refreshed = search(
+            &mut index,
+            ThreadSearchQuery {
+                query: "refresh button".to_string(),
+                ..ThreadSearchQuery::default()
+            },
+            None,
+        );
+        assert_eq!(refreshed.results.len(), 1);
+        assert_eq!(refreshed.results[0].thread_id, "thread-b");
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,531 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+use tokio::sync::Mutex;
+
+use crate::shared::{git_ui_core, workspaces_core};
+use crate::types::{AppSettings, GitHubIssueDetail, TurnContextAttachment, WorkspaceEntry};
+
+const MAX_ATTACHMENTS: usize = 10;
+/// Per-attachment cap on rendered content, in bytes.
+const MAX_ATTACHMENT_BYTES: usize = 64 * 1024;
+/// Cap across all attachments of a single message, in bytes.
+const MAX_TOTAL_ATTACHMENT_BYTES: usize = 192 * 1024;
+
+/// Opening tag of a rendered attachment. The frontend recognizes text inputs
+/// starting with it and shows them as attachment chips instead of prose.
+const ATTACHMENT_TAG: &str = "context_attachment";
+
+struct ResolvedAttachment {
+    kind: &'static str,
+    label: String,
+    content: String,
+    /// Terminal output keeps its most recent bytes when truncated.
+    keep_tail: bool,
+}
+
+fn floor_char_boundary(content: &str, index: usize) -> usize {
+    let mut index = index.min(content.len());
+    while !content.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(content: &str, index: usize) -> usize {
+    let mut index = index.min(content.len());
+    while !content.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+fn truncate_content(content: &str, max_bytes: usize, keep_tail: bool) -> (&str, usize) {
+    if content.len() <= max_bytes {
+        return (content, 0);
+    }
+    let kept = if keep_tail {
+        &content[ceil_char_boundary(content, content.len() - max_bytes)..]
+    } else {
+        &content[..floor_char_boundary(content, max_bytes)]
+    };
+    (kept, content.len() - kept.len())
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Keeps a closing tag inside the content from ending the attachment early.
+/// Each `</context_attachment`, counting any backslashes after the `<`, gains
+/// one more backslash; the frontend drops it again when parsing.
+fn escape_body(body: &str) -> String {
+    let closing = format!("/{ATTACHMENT_TAG}");
+    let mut escaped = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(index) = rest.find(&closing) {
+        let before = &rest[..index];
+        escaped.push_str(before);
+        if before.trim_end_matches('\\').ends_with('<') {
+            escaped.push('\\');
+        }
+        escaped.push_str(&closing);
+        rest = &rest[index + closing.len()..];
+    }
+    escaped.push_str(rest);
+    escaped
+}
+
+fn render_attachment(attachment: &ResolvedAttachment, budget: &mut usize) -> Value {
+    let (content, omitted) = truncate_content(
+        &attachment.content,
+        MAX_ATTACHMENT_BYTES.min(*budget),
+        attachment.keep_tail,
+    );
+    *budget -= content.len();
+    let mut body = String::new();
+    if omitted > 0 && attachment.keep_tail {
+        body.push_str(&format!("[... {omitted} earlier bytes omitted]\n"));
+    }
+    body.push_str(content.trim_end_matches('\n'));
+    if omitted > 0 && !attachment.keep_tail {
+        body.push_str(&format!("\n[... {omitted} more bytes omitted]"));
+    }
+    let text = format!(
+        "<{ATTACHMENT_TAG} kind=\"{}\" label=\"{}\" truncated=\"{}\">\n{}\n</{ATTACHMENT_TAG}>",
+        attachment.kind,
+        escape_attribute(&attachment.label),
+        omitted > 0,
+        escape_body(&body),
+    );
+    json!({ "type": "text", "text": text })
+}
+
+fn select_lines(
+    path: &str,
+    content: &str,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+) -> Result<(String, String), String> {
+    if start_line.is_none() && end_line.is_none() {
+        return Ok((path.to_string(), content.to_string()));
+    }
+    let lines: Vec<&str> = content.lines().collect();
+    let start = start_line.unwrap_or(1);
+    if start == 0 || end_line.is_some_and(|end| end < start) {
+        return Err(format!("Invalid line range for {path}."));
+    }
+    if start > lines.len() {
+        return Err(format!(
+            "Line {start} is past the end of {path} ({} lines).",
+            lines.len()
+        ));
+    }
+    let end = end_line.unwrap_or(lines.len()).min(lines.len());
+    let label = if start == end {
+        format!("{path}:{start}")
+    } else {
+        format!("{path}:{start}-{end}")
+    };
+    Ok((label, lines[start - 1..end].join("\n")))
+}
+
+/// Drops ANSI escape sequences and carriage returns from terminal output.
+fn strip_terminal_escapes(output: &str) -> String {
+    let mut result = String::with_capacity(output.len());
+    let mut chars = output.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\u{1b}' => match chars.next() {
+                // CSI: parameters and intermediates up to a final byte in @..~.
+                Some('[') => {
+                    for next in chars.by_ref() {
+                        if ('@'..='~').contains(&next) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: terminated by BEL or ST (ESC \).
+                Some(']') => {
+                    while let Some(next) = chars.next() {
+                        if next == '\u{7}' {
+                            break;
+                        }
+                        if next == '\u{1b}' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            '\r' | '\u{7}' | '\u{8}' => {}
+            _ => result.push(ch),
+        }
+    }
+    result
+}
+
+fn render_github_detail(
+    kind: &str,
+    detail: &GitHubIssueDetail,
+    diffs: Option<String>,
+) -> (String, String) {
+    let label = format!("{kind} #{}: {}", detail.number, detail.title);
+    let mut content = format!("{} #{} {}\n", kind, detail.number, detail.title);
+    if !detail.state.is_empty() {
+        content.push_str(&format!("State: {}\n", detail.state));
+    }
+    if let Some(author) = detail.author.as_ref() {
+        content.push_str(&format!("Author: {}\n", author.login));
+    }
+    content.push_str(&format!("URL: {}\n", detail.url));
+    let body = detail.body.trim();
+    if !body.is_empty() {
+        content.push_str(&format!("\n{body}\n"));
+    }
+    for comment in &detail.comments {
+        let author = comment
+            .author
+            .as_ref()
+            .map(|author| author.login.as_str())
+            .unwrap_or("unknown");
+        content.push_str(&format!(
+            "\n--- Comment by {author} at {}\n{}\n",
+            comment.created_at,
+            comment.body.trim()
+        ));
+    }
+    if let Some(diffs) = diffs {
+        content.push_str(&format!("\n--- Diff\n{diffs}"));
+    }
+    (label, content)
+}
+
+async fn resolve_commit(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: &str,
+    sha: &str,
+) -> Result<(String, String), String> {
+    let repo_root =
+        git_ui_core::resolve_repo_root_for_workspace_core(workspaces, workspace_id.to_string())
+            .await?;
+    let (full_sha, header) = {
+        let repo = git2::Repository::open(&repo_root).map_err(|err| err.to_string())?;
+        let commit = repo
+            .revparse_single(sha.trim())
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|err| format!("Commit `{sha}` not found: {}", err.message()))?;
+        let author = commit.author();
+        let header = format!(
+            "commit {}\nAuthor: {} <{}>\n\n{}\n",
+            commit.id(),
+            author.name().unwrap_or_default(),
+            author.email().unwrap_or_default(),
+            commit.message().unwrap_or_default().trim_end()
+        );
+        (commit.id().to_string(), header)
+    };
+    let diffs = git_ui_core::get_git_commit_diff_core(
+        workspaces,
+        app_settings,
+        workspace_id.to_string(),
+        full_sha.clone(),
+    )
+    .await?;
+    let mut content = header;
+    for diff in diffs {
+        content.push_str(&format!("\n--- {} ({})\n", diff.path, diff.status));
+        if diff.is_binary {
+            content.push_str("(binary file)\n");
+        } else {
+            content.push_str(&diff.diff);
+        }
+    }
+    Ok((full_sha.chars().take(12).collect(), content))
+}
+
+async fn resolve_attachment<F, T>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: &str,
+    attachment: TurnContextAttachment,
+    read_file: &F,
+    terminal_output: &T,
+) -> Result<ResolvedAttachment, String>
+where
+    F: Fn(&PathBuf, &str) -> Result<String, String>,
+    T: Fn(&str) -> Result<String, String>,
+{
+    let resolved = match attachment {
+        TurnContextAttachment::File {
+            path,
+            start_line,
+            end_line,
+        } => {
+            let path = path.trim().to_string();
+            let content = workspaces_core::read_workspace_file_core(
+                workspaces,
+                workspace_id,
+                &path,
+                read_file,
+            )
+            .await?;
+            let (label, content) = select_lines(&path, &content, start_line, end_line)?;
+            ResolvedAttachment {
+                kind: "file",
+                label,
+                content,
+                keep_tail: false,
+            }
+        }
+        TurnContextAttachment::Diff => {
+            let repo_root = git_ui_core::resolve_repo_root_for_workspace_core(
+                workspaces,
+                workspace_id.to_string(),
+            )
+            .await?;
+            let diff = git_ui_core::collect_workspace_diff_core(&repo_root)?;
+            ResolvedAttachment {
+                kind: "diff",
+                label: "Uncommitted changes".to_string(),
+                content: if diff.trim().is_empty() {
+                    "No uncommitted changes.".to_string()
+                } else {
+                    diff
+                },
+                keep_tail: false,
+            }
+        }
+        TurnContextAttachment::Commit { sha } => {
+            let (label, content) =
+                resolve_commit(workspaces, app_settings, workspace_id, &sha).await?;
+            ResolvedAttachment {
+                kind: "commit",
+                label,
+                content,
+                keep_tail: false,
+            }
+        }
+        TurnContextAttachment::Issue { number } => {
+            let issue =
+                git_ui_core::get_github_issue_core(workspaces, workspace_id.to_string(), number)
+                    .await?;
+            let (label, content) = render_github_detail("Issue", &issue, None);
+            ResolvedAttachment {
+                kind: "issue",
+                label,
+                content,
+                keep_tail: false,
+            }
+        }
+        TurnContextAttachment::PullRequest { number } => {
+            let pull_request = git_ui_core::get_github_pull_request_core(
+                workspaces,
+                workspace_id.to_string(),
+                number,
+            )
+            .await?;
+            let diffs = git_ui_core::get_github_pull_request_diff_core(
+                workspaces,
+                workspace_id.to_string(),
+                number,
+            )
+            .await?
+            .into_iter()
+            .map(|diff| format!("--- {} ({})\n{}", diff.path, diff.status, diff.diff))
+            .collect::<Vec<_>>()
+            .join("\n");
+            let (label, content) = render_github_detail("Pull request", &pull_request, Some(diffs));
+            ResolvedAttachment {
+                kind: "pullRequest",
+                label,
+                content,
+                keep_tail: false,
+            }
+        }
+        TurnContextAttachment::Terminal { terminal_id } => {
+            let output = terminal_output(&terminal_id)?;
+            ResolvedAttachment {
+                kind: "terminal",
+                label: format!("Terminal {terminal_id}"),
+                content: strip_terminal_escapes(&output),
+                keep_tail: true,
+            }
+        }
+    };
+    Ok(resolved)
+}
+
+/// Resolves message attachments into `text` turn input items, one per
+/// attachment, applying the per-attachment and per-message size caps.
+/// `read_file` reads a workspace-relative path the way `read_workspace_file`
+/// does; `terminal_output` returns the recent output of a workspace terminal.
+pub(crate) async fn resolve_turn_attachments_core<F, T>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: &str,
+    attachments: Vec<TurnContextAttachment>,
+    read_file: F,
+    terminal_output: T,
+) -> Result<Vec<Value>, String>
+where
+    F: Fn(&PathBuf, &str) -> Result<String, String>,
+    T: Fn(&str) -> Result<String, String>,
+{
+    if attachments.len() > MAX_ATTACHMENTS {
+        return Err(format!(
+            "A message can carry at most {MAX_ATTACHMENTS} attachments."
+        ));
+    }
+    let mut budget = MAX_TOTAL_ATTACHMENT_BYTES;
+    let mut items = Vec::with_capacity(attachments.len());
+    let mut seen: Vec<TurnContextAttachment> = Vec::new();
+    for attachment in attachments {
+        if seen.contains(&attachment) {
+            continue;
+        }
+        seen.push(attachment.clone());
+        let resolved = resolve_attachment(
+            workspaces,
+            app_settings,
+            workspace_id,
+            attachment,
+            &read_file,
+            &terminal_output,
+        )
+        .await?;
+        items.push(render_attachment(&resolved, &mut budget));
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::runtime::Runtime;
+    use uuid::Uuid;
+
+    #[test]
+    fn attachments_deserialize_from_tagged_payloads() {
+        let attachments: Vec<TurnContextAttachment> = serde_json::from_value(json!([
+            { "type": "file", "path": "src/main.rs", "startLine": 3, "endLine": 9 },
+            { "type": "diff" },
+            { "type": "pullRequest", "number": 42 },
+            { "type": "terminal", "terminalId": "term-1" },
+        ]))
+        .expect("parse attachments");
+        assert_eq!(
+            attachments,
+            vec![
+                TurnContextAttachment::File {
+                    path: "src/main.rs".to_string(),
+                    start_line: Some(3),
+                    end_line: Some(9),
+                },
+                TurnContextAttachment::Diff,
+                TurnContextAttachment::PullRequest { number: 42 },
+                TurnContextAttachment::Terminal {
+                    terminal_id: "term-1".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn closing_tags_in_content_are_escaped() {
+        let attachment = ResolvedAttachment {
+            kind: "file",
+            label: "notes.md".to_string(),
+            content: "a </context_attachment>\nb <\\/context_attachment>\nc /context_attachment"
+                .to_string(),
+            keep_tail: false,
+        };
+        let mut budget = MAX_TOTAL_ATTACHMENT_BYTES;
+        let item = render_attachment(&attachment, &mut budget);
+        assert_eq!(
+            item["text"],
+            "<context_attachment kind=\"file\" label=\"notes.md\" truncated=\"false\">\na <\\/context_attachment>\nb <\\\\/context_attachment>\nc /context_attachment\n</context_attachment>"
+        );
+    }
+
+    #[test]
+    fn file_ranges_and_terminal_output_respect_caps() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-context-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&root).expect("create temp dir");
+        let body: String = (1..=10).map(|line| format!("line {line}\n")).collect();
+        std::fs::write(root.join("notes.txt"), body).expect("write file");
+        let workspace = WorkspaceEntry {
+            id: "ws-1".to_string(),
+            name: "repo".to_string(),
+            path: root.to_string_lossy().to_string(),
+            kind: Default::default(),
+            parent_id: None,
+            worktree: None,
+            settings: Default::default(),
+        };
+        let workspaces = Mutex::new(HashMap::from([("ws-1".to_string(), workspace)]));
+        let app_settings = Mutex::new(AppSettings::default());
+        let terminal_noise = format!(
+            "\u{1b}[32mok\u{1b}[0m\r\n{}",
+            "x".repeat(MAX_ATTACHMENT_BYTES)
+        );
+        let runtime = Runtime::new().expect("create tokio runtime");
+
+        let items = runtime
+            .block_on(resolve_turn_attachments_core(
+                &workspaces,
+                &app_settings,
+                "ws-1",
+                vec![
+                    TurnContextAttachment::File {
+                        path: "notes.txt".to_string(),
+                        start_line: Some(2),
+                        end_line: Some(3),
+                    },
+                    TurnContextAttachment::Terminal {
+                        terminal_id: "term-1".to_string(),
+                    },
+                ],
+                |root, path| {
+                    std::fs::read_to_string(root.join(path)).map_err(|err| err.to_string())
+                },
+                |_| Ok(terminal_noise.clone()),
+            ))
+            .expect("resolve attachments");
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0]["text"],
+            "<context_attachment kind=\"file\" label=\"notes.txt:2-3\" truncated=\"false\">\nline 2\nline 3\n</context_attachment>"
+        );
+        let terminal = items[1]["text"].as_str().expect("terminal text");
+        assert!(terminal.starts_with(
+            "<context_attachment kind=\"terminal\" label=\"Terminal term-1\" truncated=\"true\">\n[... "
+        ));
+        assert!(!terminal.contains('\u{1b}'));
+        assert!(terminal.len() < MAX_ATTACHMENT_BYTES + 200);
+
+        let out_of_range = runtime.block_on(resolve_turn_attachments_core(
+            &workspaces,
+            &app_settings,
+            "ws-1",
+            vec![TurnContextAttachment::File {
+                path: "notes.txt".to_string(),
+                start_line: Some(20),
+                end_line: None,
+            }],
+            |root, path| std::fs::read_to_string(root.join(path)).map_err(|err| err.to_string()),
+            |_| Err("no terminals".to_string()),
+        ));
+        assert!(out_of_range.is_err());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
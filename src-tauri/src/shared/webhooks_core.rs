@@ -0,0 +1,833 @@
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io::{BufRead, BufReader, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::shared::store_helpers::{now_ms, read_json, trim_optional, write_json, StoreLock};
+use crate::types::{
+    WebhookConfig, WebhookDelivery, WebhookDeliveryStatus, WebhookInfo, WebhookInput,
+};
+
+const WEBHOOKS_FILE: &str = "webhooks.json";
+const WEBHOOK_DELIVERIES_FILE: &str = "webhook-deliveries.jsonl";
+const MAX_DELIVERY_HISTORY: usize = 500;
+/// The log gets one line per attempt; past this size it is compacted to the
+/// latest state of the newest `MAX_DELIVERY_HISTORY` deliveries.
+const MAX_DELIVERY_LOG_BYTES: u64 = 2 * 1024 * 1024;
+const DEFAULT_DELIVERY_LIMIT: usize = 50;
+const MAX_DELIVERY_ATTEMPTS: u32 = 5;
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+const HMAC_BLOCK_SIZE: usize = 64;
+
+const APPROVAL_REQUESTED_EVENT: &str = "approval/requested";
+const TEST_EVENT: &str = "webhook/test";
+const EVENT_HEADER: &str = "X-CodexMonitor-Event";
+const DELIVERY_HEADER: &str = "X-CodexMonitor-Delivery";
+const TIMESTAMP_HEADER: &str = "X-CodexMonitor-Timestamp";
+const SIGNATURE_HEADER: &str = "X-CodexMonitor-Signature";
+
+/// Serializes read-modify-write cycles on the webhook files; deliveries finish
+/// on their own tasks and would otherwise race each other.
+static STORE_LOCK: StoreLock = StoreLock::new();
+
+/// Parsed webhook configs per data dir. The reader loop consults this for
+/// every app-server notification, so it must not touch the disk each time.
+static WEBHOOK_CACHE: OnceLock<std::sync::Mutex<HashMap<PathBuf, Arc<Vec<WebhookConfig>>>>> =
+    OnceLock::new();
+
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    max_attempts: u32,
+    initial_delay: Duration,
+}
+
+const EVENT_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: MAX_DELIVERY_ATTEMPTS,
+    initial_delay: INITIAL_RETRY_DELAY,
+};
+
+const TEST_RETRY_POLICY: RetryPolicy = RetryPolicy {
+    max_attempts: 1,
+    initial_delay: INITIAL_RETRY_DELAY,
+};
+
+fn lock_cache() -> std::sync::MutexGuard<'static, HashMap<PathBuf, Arc<Vec<WebhookConfig>>>> {
+    WEBHOOK_CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn http_client() -> &'static reqwest::Client {
+    HTTP_CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+fn webhooks_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(WEBHOOKS_FILE)
+}
+
+fn deliveries_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(WEBHOOK_DELIVERIES_FILE)
+}
+
+fn read_webhooks(data_dir: &Path) -> Result<Vec<WebhookConfig>, String> {
+    read_json(&webhooks_path(data_dir))
+}
+
+fn write_webhooks(data_dir: &Path, webhooks: Vec<WebhookConfig>) -> Result<(), String> {
+    write_json(&webhooks_path(data_dir), &webhooks)?;
+    lock_cache().insert(data_dir.to_path_buf(), Arc::new(webhooks));
+    Ok(())
+}
+
+/// Loads the configs on a blocking thread and fills the cache.
+async fn load_webhooks(data_dir: &Path) -> Arc<Vec<WebhookConfig>> {
+    let path = data_dir.to_path_buf();
+    let webhooks = tokio::task::spawn_blocking(move || read_webhooks(&path))
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|result| result)
+        .unwrap_or_else(|err| {
+            eprintln!("webhooks: {err}");
+            Vec::new()
+        });
+    lock_cache()
+        .entry(data_dir.to_path_buf())
+        .or_insert_with(|| Arc::new(webhooks))
+        .clone()
+}
+
+/// Folds the log into the latest state of each delivery, oldest first.
+fn read_deliveries(data_dir: &Path) -> Vec<WebhookDelivery> {
+    let Ok(file) = std::fs::File::open(deliveries_path(data_dir)) else {
+        return Vec::new();
+    };
+    let mut deliveries: Vec<WebhookDelivery> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for delivery in BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<WebhookDelivery>(&line).ok())
+    {
+        match positions.get(&delivery.id) {
+            Some(&position) => deliveries[position] = delivery,
+            None => {
+                positions.insert(delivery.id.clone(), deliveries.len());
+                deliveries.push(delivery);
+            }
+        }
+    }
+    deliveries
+}
+
+fn compact_deliveries(data_dir: &Path) -> Result<(), String> {
+    let mut deliveries = read_deliveries(data_dir);
+    if deliveries.len() > MAX_DELIVERY_HISTORY {
+        let excess = deliveries.len() - MAX_DELIVERY_HISTORY;
+        deliveries.drain(..excess);
+    }
+    let mut data = String::new();
+    for delivery in &deliveries {
+        data.push_str(&serde_json::to_string(delivery).map_err(|err| err.to_string())?);
+        data.push('\n');
+    }
+    let path = deliveries_path(data_dir);
+    std::fs::write(&path, data).map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+/// Appends the delivery's current state to the log.
+fn record_delivery(data_dir: &Path, delivery: &WebhookDelivery) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock();
+    std::fs::create_dir_all(data_dir).map_err(|err| err.to_string())?;
+    let path = deliveries_path(data_dir);
+    let mut line = serde_json::to_string(delivery).map_err(|err| err.to_string())?;
+    line.push('\n');
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|err| format!("Failed to open {}: {err}", path.display()))?;
+    file.write_all(line.as_bytes())
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    let size = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+    if size > MAX_DELIVERY_LOG_BYTES {
+        compact_deliveries(data_dir)?;
+    }
+    Ok(())
+}
+
+fn trim_list(values: Vec<String>) -> Vec<String> {
+    let mut trimmed: Vec<String> = Vec::new();
+    for value in values {
+        let value = value.trim();
+        if !value.is_empty() && !trimmed.iter().any(|existing| existing == value) {
+            trimmed.push(value.to_string());
+        }
+    }
+    trimmed
+}
+
+fn normalize_input(input: WebhookInput) -> Result<WebhookInput, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Webhook name is required.".to_string());
+    }
+    let url = input.url.trim().to_string();
+    let parsed = reqwest::Url::parse(&url).map_err(|err| format!("Invalid webhook URL: {err}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("Webhook URL must use http or https.".to_string());
+    }
+    let events = trim_list(input.events);
+    if events.is_empty() {
+        return Err("Select at least one event for the webhook.".to_string());
+    }
+    Ok(WebhookInput {
+        name,
+        url,
+        enabled: input.enabled,
+        events,
+        workspace_ids: trim_list(input.workspace_ids),
+        secret: trim_optional(input.secret),
+        clear_secret: input.clear_secret,
+    })
+}
+
+/// Secrets never leave the backend; clients only learn whether one is set.
+fn webhook_info(webhook: &WebhookConfig) -> WebhookInfo {
+    WebhookInfo {
+        id: webhook.id.clone(),
+        name: webhook.name.clone(),
+        url: webhook.url.clone(),
+        enabled: webhook.enabled,
+        events: webhook.events.clone(),
+        workspace_ids: webhook.workspace_ids.clone(),
+        has_secret: webhook.secret.is_some(),
+        created_at_ms: webhook.created_at_ms,
+    }
+}
+
+/// Maps an app-server method to the event name webhooks subscribe to. The
+/// per-kind approval requests collapse into a single `approval/requested`.
+fn webhook_event_name(method: &str) -> &str {
+    if method.ends_with("requestApproval") {
+        APPROVAL_REQUESTED_EVENT
+    } else {
+        method
+    }
+}
+
+/// Streaming notifications (`item/agentMessage/delta`,
+/// `item/commandExecution/outputDelta`, ...) fire per token or chunk.
+fn is_streaming_event(event: &str) -> bool {
+    let name = event.rsplit('/').next().unwrap_or(event);
+    name == "delta" || name.ends_with("Delta")
+}
+
+/// Wildcards never match streaming events; those must be named exactly.
+fn subscription_matches(pattern: &str, event: &str) -> bool {
+    if pattern == event {
+        return true;
+    }
+    if is_streaming_event(event) {
+        return false;
+    }
+    if pattern == "*" {
+        return true;
+    }
+    pattern.strip_suffix("/*").is_some_and(|prefix| {
+        event
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+fn webhook_wants(webhook: &WebhookConfig, event: &str, workspace_id: &str) -> bool {
+    webhook.enabled
+        && (webhook.workspace_ids.is_empty()
+            || webhook.workspace_ids.iter().any(|id| id == workspace_id))
+        && webhook
+            .events
+            .iter()
+            .any(|pattern| subscription_matches(pattern, event))
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut block = [0u8; HMAC_BLOCK_SIZE];
+    if key.len() > HMAC_BLOCK_SIZE {
+        let digest = Sha256::digest(key);
+        block[..digest.len()].copy_from_slice(&digest);
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(block.map(|byte| byte ^ 0x36));
+    inner.update(message);
+    let inner = inner.finalize();
+    let mut outer = Sha256::new();
+    outer.update(block.map(|byte| byte ^ 0x5c));
+    outer.update(inner);
+    outer.finalize().to_vec()
+}
+
+/// Value of the signature header: `sha256=` followed by the hex HMAC-SHA256,
+/// keyed with the webhook secret, of `{timestamp}.{body}` where `timestamp` is
+/// the `X-CodexMonitor-Timestamp` header (Unix ms of this attempt). Receivers
+/// should reject stale timestamps to stop replays.
+pub(crate) fn webhook_signature(secret: &str, timestamp_ms: i64, body: &[u8]) -> String {
+    let mut message = format!("{timestamp_ms}.").into_bytes();
+    message.extend_from_slice(body);
+    let mut signature = String::from("sha256=");
+    for byte in hmac_sha256(secret.as_bytes(), &message) {
+        let _ = write!(&mut signature, "{byte:02x}");
+    }
+    signature
+}
+
+fn new_delivery(
+    webhook: &WebhookConfig,
+    event: &str,
+    workspace_id: &str,
+    thread_id: Option<&str>,
+) -> WebhookDelivery {
+    WebhookDelivery {
+        id: Uuid::new_v4().to_string(),
+        webhook_id: webhook.id.clone(),
+        webhook_name: webhook.name.clone(),
+        event: event.to_string(),
+        workspace_id: workspace_id.to_string(),
+        thread_id: thread_id.map(str::to_string),
+        status: WebhookDeliveryStatus::Pending,
+        attempts: 0,
+        response_status: None,
+        error: None,
+        created_at_ms: now_ms(),
+        finished_at_ms: None,
+    }
+}
+
+async fn persist_delivery(data_dir: &Path, delivery: &WebhookDelivery) {
+    let data_dir = data_dir.to_path_buf();
+    let snapshot = delivery.clone();
+    let result = tokio::task::spawn_blocking(move || record_delivery(&data_dir, &snapshot))
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|result| result);
+    if let Err(err) = result {
+        eprintln!("webhooks: failed to record delivery {}: {err}", delivery.id);
+    }
+}
+
+async fn post_once(
+    webhook: &WebhookConfig,
+    delivery: &WebhookDelivery,
+    body: &[u8],
+) -> Result<u16, String> {
+    let timestamp_ms = now_ms();
+    let mut request = http_client()
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, &delivery.event)
+        .header(DELIVERY_HEADER, &delivery.id)
+        .header(TIMESTAMP_HEADER, timestamp_ms.to_string())
+        .body(body.to_vec());
+    if let Some(secret) = webhook.secret.as_deref() {
+        request = request.header(
+            SIGNATURE_HEADER,
+            webhook_signature(secret, timestamp_ms, body),
+        );
+    }
+    let response = request.send().await.map_err(|err| err.to_string())?;
+    Ok(response.status().as_u16())
+}
+
+/// POSTs one event, retrying network errors, 429s and 5xx responses with
+/// exponential backoff. Every attempt is written to the delivery log.
+async fn deliver(
+    data_dir: &Path,
+    webhook: &WebhookConfig,
+    mut delivery: WebhookDelivery,
+    message: &Value,
+    policy: RetryPolicy,
+) -> WebhookDelivery {
+    let payload = json!({
+        "id": delivery.id,
+        "event": delivery.event,
+        "webhookId": delivery.webhook_id,
+        "workspaceId": delivery.workspace_id,
+        "threadId": delivery.thread_id,
+        "timestamp": delivery.created_at_ms,
+        "message": message,
+    });
+    let body = match serde_json::to_vec(&payload) {
+        Ok(body) => body,
+        Err(err) => {
+            delivery.status = WebhookDeliveryStatus::Failed;
+            delivery.error = Some(err.to_string());
+            delivery.finished_at_ms = Some(now_ms());
+            persist_delivery(data_dir, &delivery).await;
+            return delivery;
+        }
+    };
+    persist_delivery(data_dir, &delivery).await;
+
+    let mut delay = policy.initial_delay;
+    loop {
+        delivery.attempts += 1;
+        let retryable = match post_once(webhook, &delivery, &body).await {
+            Ok(status) => {
+                delivery.response_status = Some(status);
+                if (200..300).contains(&status) {
+                    delivery.status = WebhookDeliveryStatus::Delivered;
+                    delivery.error = None;
+                    false
+                } else {
+                    delivery.error = Some(format!("HTTP {status}"));
+                    status == 429 || status >= 500
+                }
+            }
+            Err(err) => {
+                delivery.response_status = None;
+                delivery.error = Some(err);
+                true
+            }
+        };
+        let finished = delivery.status == WebhookDeliveryStatus::Delivered
+            || !retryable
+            || delivery.attempts >= policy.max_attempts;
+        if finished {
+            if delivery.status != WebhookDeliveryStatus::Delivered {
+                delivery.status = WebhookDeliveryStatus::Failed;
+            }
+            delivery.finished_at_ms = Some(now_ms());
+        }
+        persist_delivery(data_dir, &delivery).await;
+        if finished {
+            return delivery;
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RETRY_DELAY);
+    }
+}
+
+/// Queues a delivery to every enabled webhook subscribed to the message's
+/// event. Returns immediately; deliveries run on their own tasks.
+pub(crate) fn dispatch_app_server_event(
+    data_dir: &Path,
+    workspace_id: &str,
+    thread_id: Option<&str>,
+    message: &Value,
+) {
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        return;
+    };
+    let cached = lock_cache().get(data_dir).cloned();
+    match cached {
+        Some(webhooks) => queue_deliveries(
+            &webhooks,
+            data_dir,
+            workspace_id,
+            thread_id,
+            method,
+            message,
+        ),
+        None => {
+            let data_dir = data_dir.to_path_buf();
+            let workspace_id = workspace_id.to_string();
+            let thread_id = thread_id.map(str::to_string);
+            let method = method.to_string();
+            let message = message.clone();
+            tokio::spawn(async move {
+                let webhooks = load_webhooks(&data_dir).await;
+                queue_deliveries(
+                    &webhooks,
+                    &data_dir,
+                    &workspace_id,
+                    thread_id.as_deref(),
+                    &method,
+                    &message,
+                );
+            });
+        }
+    }
+}
+
+fn queue_deliveries(
+    webhooks: &[WebhookConfig],
+    data_dir: &Path,
+    workspace_id: &str,
+    thread_id: Option<&str>,
+    method: &str,
+    message: &Value,
+) {
+    let event = webhook_event_name(method);
+    for webhook in webhooks
+        .iter()
+        .filter(|webhook| webhook_wants(webhook, event, workspace_id))
+    {
+        let delivery = new_delivery(webhook, event, workspace_id, thread_id);
+        let data_dir = data_dir.to_path_buf();
+        let webhook = webhook.clone();
+        let message = message.clone();
+        tokio::spawn(async move {
+            deliver(&data_dir, &webhook, delivery, &message, EVENT_RETRY_POLICY).await;
+        });
+    }
+}
+
+pub(crate) fn webhooks_list_core(data_dir: &Path) -> Result<Vec<WebhookInfo>, String> {
+    let _guard = STORE_LOCK.lock();
+    Ok(read_webhooks(data_dir)?.iter().map(webhook_info).collect())
+}
+
+pub(crate) fn webhooks_create_core(
+    data_dir: &Path,
+    input: WebhookInput,
+) -> Result<WebhookInfo, String> {
+    let input = normalize_input(input)?;
+    let webhook = WebhookConfig {
+        id: Uuid::new_v4().to_string(),
+        name: input.name,
+        url: input.url,
+        enabled: input.enabled.unwrap_or(true),
+        events: input.events,
+        workspace_ids: input.workspace_ids,
+        secret: input.secret.filter(|_| !input.clear_secret),
+        created_at_ms: now_ms(),
+    };
+    let info = webhook_info(&webhook);
+    let _guard = STORE_LOCK.lock();
+    let mut webhooks = read_webhooks(data_dir)?;
+    webhooks.push(webhook);
+    write_webhooks(data_dir, webhooks)?;
+    Ok(info)
+}
+
+/// Replaces the webhook's settings. The stored secret is kept unless the
+/// input sets a new one or asks to clear it.
+pub(crate) fn webhooks_update_core(
+    data_dir: &Path,
+    id: String,
+    input: WebhookInput,
+) -> Result<WebhookInfo, String> {
+    let input = normalize_input(input)?;
+    let _guard = STORE_LOCK.lock();
+    let mut webhooks = read_webhooks(data_dir)?;
+    let webhook = webhooks
+        .iter_mut()
+        .find(|webhook| webhook.id == id)
+        .ok_or_else(|| "Webhook not found.".to_string())?;
+    webhook.enabled = input.enabled.unwrap_or(webhook.enabled);
+    webhook.name = input.name;
+    webhook.url = input.url;
+    webhook.events = input.events;
+    webhook.workspace_ids = input.workspace_ids;
+    if input.clear_secret {
+        webhook.secret = None;
+    } else if input.secret.is_some() {
+        webhook.secret = input.secret;
+    }
+    let updated = webhook_info(webhook);
+    write_webhooks(data_dir, webhooks)?;
+    Ok(updated)
+}
+
+/// Removes the webhook; its delivery log is kept.
+pub(crate) fn webhooks_delete_core(data_dir: &Path, id: String) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock();
+    let mut webhooks = read_webhooks(data_dir)?;
+    let before = webhooks.len();
+    webhooks.retain(|webhook| webhook.id != id);
+    if webhooks.len() == before {
+        return Err("Webhook not found.".to_string());
+    }
+    write_webhooks(data_dir, webhooks)
+}
+
+/// Returns the delivery log, newest first.
+pub(crate) fn webhook_deliveries_core(
+    data_dir: &Path,
+    webhook_id: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<WebhookDelivery>, String> {
+    let _guard = STORE_LOCK.lock();
+    let deliveries = read_deliveries(data_dir);
+    Ok(deliveries
+        .into_iter()
+        .rev()
+        .filter(|delivery| match webhook_id.as_deref() {
+            Some(webhook_id) => delivery.webhook_id == webhook_id,
+            None => true,
+        })
+        .take(limit.unwrap_or(DEFAULT_DELIVERY_LIMIT))
+        .collect())
+}
+
+/// Sends a single `webhook/test` event, even to a disabled webhook, and waits
+/// for the outcome instead of retrying.
+pub(crate) async fn webhooks_test_core(
+    data_dir: &Path,
+    id: String,
+) -> Result<WebhookDelivery, String> {
+    let webhook = {
+        let _guard = STORE_LOCK.lock();
+        read_webhooks(data_dir)?
+    }
+    .into_iter()
+    .find(|webhook| webhook.id == id)
+    .ok_or_else(|| "Webhook not found.".to_string())?;
+    let delivery = new_delivery(&webhook, TEST_EVENT, "", None);
+    let message = json!({
+        "method": TEST_EVENT,
+        "params": { "webhookId": webhook.id, "name": webhook.name },
+    });
+    Ok(deliver(data_dir, &webhook, delivery, &message, TEST_RETRY_POLICY).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::runtime::Runtime;
+
+    fn temp_data_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-webhooks-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    /// Minimal HTTP/1.1 stand-in: answers each request with the next status
+    /// in `statuses` and keeps the raw requests it received.
+    async fn serve_statuses(
+        listener: TcpListener,
+        statuses: Vec<u16>,
+        requests: Arc<Mutex<Vec<String>>>,
+    ) {
+        for status in statuses {
+            let (mut stream, _) = listener.accept().await.expect("accept");
+            let mut raw = Vec::new();
+            let mut buffer = [0u8; 4096];
+            loop {
+                let read = stream.read(&mut buffer).await.expect("read request");
+                if read == 0 {
+                    break;
+                }
+                raw.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&raw);
+                let Some(header_end) = text.find("\r\n\r\n") else {
+                    continue;
+                };
+                let content_length = text[..header_end]
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        if name.eq_ignore_ascii_case("content-length") {
+                            value.trim().parse::<usize>().ok()
+                        } else {
+                            None
+                        }
+                    })
+                    .unwrap_or(0);
+                if raw.len() >= header_end + 4 + content_length {
+                    break;
+                }
+            }
+            requests
+                .lock()
+                .unwrap()
+                .push(String::from_utf8_lossy(&raw).into_owned());
+            let response = format!(
+                "HTTP/1.1 {status} Status\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+            );
+            stream
+                .write_all(response.as_bytes())
+                .await
+                .expect("write response");
+            let _ = stream.shutdown().await;
+        }
+    }
+
+    #[test]
+    fn signature_matches_reference_hmac_sha256() {
+        let hex = |bytes: Vec<u8>| {
+            bytes
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect::<String>()
+        };
+        assert_eq!(
+            hex(hmac_sha256(
+                b"key",
+                b"The quick brown fox jumps over the lazy dog"
+            )),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+        assert_eq!(
+            webhook_signature("key", 1_700_000_000_000, b"{}"),
+            format!("sha256={}", hex(hmac_sha256(b"key", b"1700000000000.{}")))
+        );
+    }
+
+    #[test]
+    fn subscriptions_match_exact_wildcard_and_approval_events() {
+        let webhook = WebhookConfig {
+            id: "hook-1".to_string(),
+            name: "CI".to_string(),
+            url: "http://127.0.0.1/hook".to_string(),
+            enabled: true,
+            events: vec!["turn/completed".to_string(), "item/*".to_string()],
+            workspace_ids: vec!["ws-1".to_string()],
+            secret: None,
+            created_at_ms: 0,
+        };
+        assert!(webhook_wants(&webhook, "turn/completed", "ws-1"));
+        assert!(webhook_wants(&webhook, "item/completed", "ws-1"));
+        assert!(!webhook_wants(&webhook, "item/agentMessage/delta", "ws-1"));
+        assert!(!webhook_wants(
+            &webhook,
+            "item/commandExecution/outputDelta",
+            "ws-1"
+        ));
+        assert!(!subscription_matches(
+            "*",
+            "item/reasoning/summaryTextDelta"
+        ));
+        assert!(subscription_matches(
+            "item/agentMessage/delta",
+            "item/agentMessage/delta"
+        ));
+        assert!(!webhook_wants(&webhook, "items/completed", "ws-1"));
+        assert!(!webhook_wants(&webhook, "turn/completed", "ws-2"));
+        assert_eq!(
+            webhook_event_name("item/commandExecution/requestApproval"),
+            APPROVAL_REQUESTED_EVENT
+        );
+        assert!(subscription_matches("*", APPROVAL_REQUESTED_EVENT));
+    }
+
+    #[test]
+    fn updates_keep_the_secret_and_responses_never_include_it() {
+        let data_dir = temp_data_dir();
+        let input = |secret: Option<&str>, clear_secret: bool| WebhookInput {
+            name: "CI".to_string(),
+            url: "https://example.com/hook".to_string(),
+            enabled: None,
+            events: vec!["turn/completed".to_string()],
+            workspace_ids: Vec::new(),
+            secret: secret.map(str::to_string),
+            clear_secret,
+        };
+        let created =
+            webhooks_create_core(&data_dir, input(Some("s3cret"), false)).expect("create");
+        let serialized = serde_json::to_value(&created).expect("serialize");
+        assert_eq!(serialized["hasSecret"], true);
+        assert!(serialized.get("secret").is_none());
+
+        let updated = webhooks_update_core(&data_dir, created.id.clone(), input(None, false))
+            .expect("update without secret");
+        assert!(updated.has_secret);
+        let stored = read_webhooks(&data_dir).expect("webhooks");
+        assert_eq!(stored[0].secret.as_deref(), Some("s3cret"));
+
+        webhooks_update_core(&data_dir, created.id.clone(), input(Some("rotated"), false))
+            .expect("rotate secret");
+        let stored = read_webhooks(&data_dir).expect("webhooks");
+        assert_eq!(stored[0].secret.as_deref(), Some("rotated"));
+
+        let cleared = webhooks_update_core(&data_dir, created.id, input(Some("ignored"), true))
+            .expect("clear secret");
+        assert!(!cleared.has_secret);
+        let listed = webhooks_list_core(&data_dir).expect("list");
+        assert!(!listed[0].has_secret);
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn deliveries_retry_server_errors_and_sign_the_body() {
+        let data_dir = temp_data_dir();
+        let runtime = Runtime::new().expect("create tokio runtime");
+        runtime.block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let address = listener.local_addr().expect("local addr");
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let server = tokio::spawn(serve_statuses(listener, vec![503, 200], requests.clone()));
+
+            let created = webhooks_create_core(
+                &data_dir,
+                WebhookInput {
+                    name: " Local ".to_string(),
+                    url: format!("http://{address}/hook"),
+                    enabled: None,
+                    events: vec!["turn/completed".to_string()],
+                    workspace_ids: Vec::new(),
+                    secret: Some("s3cret".to_string()),
+                    clear_secret: false,
+                },
+            )
+            .expect("create webhook");
+            assert_eq!(created.name, "Local");
+            assert!(created.has_secret);
+            let webhook = read_webhooks(&data_dir).expect("webhooks").remove(0);
+
+            let message = json!({ "method": "turn/completed", "params": { "threadId": "t-1" } });
+            let delivery = new_delivery(&webhook, "turn/completed", "ws-1", Some("t-1"));
+            let policy = RetryPolicy {
+                max_attempts: 3,
+                initial_delay: Duration::from_millis(10),
+            };
+            let delivery = deliver(&data_dir, &webhook, delivery, &message, policy).await;
+            server.await.expect("server task");
+
+            assert_eq!(delivery.status, WebhookDeliveryStatus::Delivered);
+            assert_eq!(delivery.attempts, 2);
+            assert_eq!(delivery.response_status, Some(200));
+
+            let requests = requests.lock().unwrap().clone();
+            assert_eq!(requests.len(), 2);
+            let (head, body) = requests[1].split_once("\r\n\r\n").expect("request body");
+            let timestamp_ms = head
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case(TIMESTAMP_HEADER)
+                        .then(|| value.trim().parse::<i64>().ok())
+                        .flatten()
+                })
+                .expect("timestamp header");
+            let signature = webhook_signature("s3cret", timestamp_ms, body.as_bytes());
+            assert!(
+                head.lines()
+                    .any(|line| line
+                        .eq_ignore_ascii_case(&format!("{SIGNATURE_HEADER}: {signature}")))
+            );
+            let payload: Value = serde_json::from_str(body).expect("json body");
+            assert_eq!(payload["event"], "turn/completed");
+            assert_eq!(payload["threadId"], "t-1");
+
+            let log = webhook_deliveries_core(&data_dir, Some(webhook.id.clone()), None)
+                .expect("delivery log");
+            assert_eq!(log.len(), 1);
+            assert_eq!(log[0].status, WebhookDeliveryStatus::Delivered);
+            assert_eq!(log[0].attempts, 2);
+        });
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -0,0 +1,804 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::codex::home::resolve_default_codex_home;
+use crate::shared::agents_config_core::{self, CreateAgentInput, SetAgentsCoreInput};
+use crate::shared::prompts_core;
+use crate::shared::store_helpers::now_ms;
+use crate::storage::{write_settings, write_workspaces};
+use crate::types::{
+    AppSettings, OpenAppTarget, WorkspaceEntry, WorkspaceGroup, WorkspaceKind, WorkspaceSettings,
+};
+
+pub(crate) const WORKSPACE_BUNDLE_VERSION: u32 = 1;
+const PROMPT_SCOPE_GLOBAL: &str = "global";
+const PROMPT_SCOPE_WORKSPACE: &str = "workspace";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundle {
+    pub(crate) version: u32,
+    #[serde(default)]
+    pub(crate) exported_at_ms: i64,
+    #[serde(default)]
+    pub(crate) workspace_groups: Vec<WorkspaceGroup>,
+    #[serde(default)]
+    pub(crate) workspaces: Vec<WorkspaceBundleWorkspace>,
+    #[serde(default)]
+    pub(crate) open_app_targets: Vec<OpenAppTarget>,
+    #[serde(default)]
+    pub(crate) selected_open_app_id: Option<String>,
+    #[serde(default)]
+    pub(crate) global_worktrees_folder: Option<String>,
+    #[serde(default)]
+    pub(crate) prompts: Vec<WorkspaceBundlePrompt>,
+    #[serde(default)]
+    pub(crate) agents_settings: Option<SetAgentsCoreInput>,
+    #[serde(default)]
+    pub(crate) agents: Vec<WorkspaceBundleAgent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundleWorkspace {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) settings: WorkspaceSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundlePrompt {
+    pub(crate) scope: String,
+    #[serde(default)]
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) name: String,
+    pub(crate) content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundleAgent {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+    #[serde(default)]
+    pub(crate) config_toml: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WorkspaceBundlePathRemap {
+    pub(crate) from: String,
+    pub(crate) to: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundleImportOptions {
+    #[serde(default)]
+    pub(crate) path_remaps: Vec<WorkspaceBundlePathRemap>,
+    #[serde(default)]
+    pub(crate) overwrite_existing: bool,
+    #[serde(default)]
+    pub(crate) skip_prompts: bool,
+    #[serde(default)]
+    pub(crate) skip_agents: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceBundleImportSummary {
+    pub(crate) workspaces_added: u32,
+    pub(crate) workspaces_updated: u32,
+    pub(crate) groups_imported: u32,
+    pub(crate) open_app_targets_imported: u32,
+    pub(crate) prompts_imported: u32,
+    pub(crate) agents_imported: u32,
+    pub(crate) warnings: Vec<String>,
+}
+
+fn is_path_separator(ch: char) -> bool {
+    ch == '/' || ch == '\\'
+}
+
+/// Rewrites `path` using the longest matching `from` prefix. Prefixes only
+/// match on path component boundaries, so `/Users/a` never rewrites `/Users/ab`.
+pub(crate) fn remap_bundle_path(path: &str, remaps: &[WorkspaceBundlePathRemap]) -> String {
+    let mut best: Option<(&str, usize)> = None;
+    for remap in remaps {
+        let from = remap.from.trim().trim_end_matches(is_path_separator);
+        if from.is_empty() {
+            continue;
+        }
+        let Some(rest) = path.strip_prefix(from) else {
+            continue;
+        };
+        if !rest.is_empty() && !rest.starts_with(is_path_separator) {
+            continue;
+        }
+        if best.map_or(true, |(_, len)| from.len() > len) {
+            best = Some((
+                remap.to.trim().trim_end_matches(is_path_separator),
+                from.len(),
+            ));
+        }
+    }
+    match best {
+        Some((to, len)) => format!("{to}{}", &path[len..]),
+        None => path.to_string(),
+    }
+}
+
+fn remap_optional_path(
+    path: Option<String>,
+    remaps: &[WorkspaceBundlePathRemap],
+) -> Option<String> {
+    path.map(|value| remap_bundle_path(&value, remaps))
+}
+
+fn merge_option<T>(current: &mut Option<T>, incoming: Option<T>, overwrite: bool) {
+    if incoming.is_some() && (overwrite || current.is_none()) {
+        *current = incoming;
+    }
+}
+
+fn global_prompts_dir() -> Option<PathBuf> {
+    resolve_default_codex_home().map(|home| home.join("prompts"))
+}
+
+fn collect_bundle_prompts(
+    dir: &Path,
+    scope: &str,
+    workspace_id: Option<&str>,
+) -> Vec<WorkspaceBundlePrompt> {
+    let mut out = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return out;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let is_md = path
+            .extension()
+            .and_then(|value| value.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+        if !is_md {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|value| value.to_str()) else {
+            continue;
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        out.push(WorkspaceBundlePrompt {
+            scope: scope.to_string(),
+            workspace_id: workspace_id.map(str::to_string),
+            name: name.to_string(),
+            content,
+        });
+    }
+    out.sort_by(|left, right| left.name.cmp(&right.name));
+    out
+}
+
+fn collect_bundle_agents() -> (Option<SetAgentsCoreInput>, Vec<WorkspaceBundleAgent>) {
+    let Ok(settings) = agents_config_core::get_agents_settings_core() else {
+        return (None, Vec::new());
+    };
+    let agents = settings
+        .agents
+        .iter()
+        .filter(|agent| agent.managed_by_app)
+        .map(|agent| WorkspaceBundleAgent {
+            name: agent.name.clone(),
+            description: agent.description.clone(),
+            config_toml: agents_config_core::read_agent_config_toml_core(&agent.name)
+                .ok()
+                .filter(|content| !content.trim().is_empty()),
+        })
+        .collect();
+    (
+        Some(SetAgentsCoreInput {
+            multi_agent_enabled: settings.multi_agent_enabled,
+            max_threads: settings.max_threads,
+            max_depth: settings.max_depth,
+        }),
+        agents,
+    )
+}
+
+/// Builds a portable bundle from the current workspaces and settings. Worktree
+/// workspaces are left out because they are recreated from their parents.
+pub(crate) async fn export_workspace_bundle_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    settings_path: &Path,
+) -> Result<WorkspaceBundle, String> {
+    let entries: Vec<WorkspaceEntry> = {
+        let workspaces = workspaces.lock().await;
+        let mut entries: Vec<_> = workspaces
+            .values()
+            .filter(|entry| !matches!(entry.kind, WorkspaceKind::Worktree))
+            .cloned()
+            .collect();
+        entries.sort_by(|left, right| left.name.cmp(&right.name));
+        entries
+    };
+    let settings = app_settings.lock().await.clone();
+
+    let mut prompts = Vec::new();
+    for entry in &entries {
+        if let Ok(dir) = prompts_core::workspace_prompts_dir(settings_path, entry) {
+            prompts.extend(collect_bundle_prompts(
+                &dir,
+                PROMPT_SCOPE_WORKSPACE,
+                Some(&entry.id),
+            ));
+        }
+    }
+    if let Some(dir) = global_prompts_dir() {
+        prompts.extend(collect_bundle_prompts(&dir, PROMPT_SCOPE_GLOBAL, None));
+    }
+    let (agents_settings, agents) = collect_bundle_agents();
+
+    Ok(WorkspaceBundle {
+        version: WORKSPACE_BUNDLE_VERSION,
+        exported_at_ms: now_ms(),
+        workspace_groups: settings.workspace_groups,
+        workspaces: entries
+            .into_iter()
+            .map(|entry| WorkspaceBundleWorkspace {
+                id: entry.id,
+                name: entry.name,
+                path: entry.path,
+                settings: entry.settings,
+            })
+            .collect(),
+        open_app_targets: settings.open_app_targets,
+        selected_open_app_id: Some(settings.selected_open_app_id),
+        global_worktrees_folder: settings.global_worktrees_folder,
+        prompts,
+        agents_settings,
+        agents,
+    })
+}
+
+fn import_settings(
+    settings: &mut AppSettings,
+    bundle: &WorkspaceBundle,
+    options: &WorkspaceBundleImportOptions,
+    summary: &mut WorkspaceBundleImportSummary,
+) -> HashMap<String, String> {
+    let remaps = &options.path_remaps;
+    let mut group_ids = HashMap::new();
+    for group in &bundle.workspace_groups {
+        let existing = settings
+            .workspace_groups
+            .iter_mut()
+            .find(|current| current.id == group.id || current.name == group.name);
+        let local_id = match existing {
+            Some(current) => {
+                if options.overwrite_existing {
+                    current.name = group.name.clone();
+                    current.sort_order = group.sort_order;
+                }
+                merge_option(
+                    &mut current.copies_folder,
+                    remap_optional_path(group.copies_folder.clone(), remaps),
+                    options.overwrite_existing,
+                );
+                current.id.clone()
+            }
+            None => {
+                settings.workspace_groups.push(WorkspaceGroup {
+                    copies_folder: remap_optional_path(group.copies_folder.clone(), remaps),
+                    ..group.clone()
+                });
+                group.id.clone()
+            }
+        };
+        summary.groups_imported += 1;
+        group_ids.insert(group.id.clone(), local_id);
+    }
+
+    for target in &bundle.open_app_targets {
+        match settings
+            .open_app_targets
+            .iter_mut()
+            .find(|current| current.id == target.id)
+        {
+            Some(current) if options.overwrite_existing => *current = target.clone(),
+            Some(_) => continue,
+            None => settings.open_app_targets.push(target.clone()),
+        }
+        summary.open_app_targets_imported += 1;
+    }
+    if options.overwrite_existing {
+        if let Some(selected) = bundle.selected_open_app_id.as_ref() {
+            if settings
+                .open_app_targets
+                .iter()
+                .any(|target| &target.id == selected)
+            {
+                settings.selected_open_app_id = selected.clone();
+            }
+        }
+    }
+
+    merge_option(
+        &mut settings.global_worktrees_folder,
+        remap_optional_path(bundle.global_worktrees_folder.clone(), remaps),
+        options.overwrite_existing,
+    );
+    group_ids
+}
+
+fn import_workspaces(
+    workspaces: &mut HashMap<String, WorkspaceEntry>,
+    bundle: &WorkspaceBundle,
+    options: &WorkspaceBundleImportOptions,
+    group_ids: &HashMap<String, String>,
+    summary: &mut WorkspaceBundleImportSummary,
+) -> HashMap<String, String> {
+    let remaps = &options.path_remaps;
+    let overwrite = options.overwrite_existing;
+    let mut workspace_ids = HashMap::new();
+    let mut inserted = Vec::new();
+    for workspace in &bundle.workspaces {
+        let path = remap_bundle_path(&workspace.path, remaps);
+        if !Path::new(&path).is_dir() {
+            summary.warnings.push(format!(
+                "Workspace `{}` path does not exist: {path}",
+                workspace.name
+            ));
+        }
+        let mut incoming = workspace.settings.clone();
+        incoming.worktrees_folder = remap_optional_path(incoming.worktrees_folder, remaps);
+        incoming.git_root = remap_optional_path(incoming.git_root, remaps);
+        incoming.group_id = incoming
+            .group_id
+            .and_then(|group_id| group_ids.get(&group_id).cloned());
+
+        let existing = workspaces
+            .values_mut()
+            .find(|entry| !matches!(entry.kind, WorkspaceKind::Worktree) && entry.path == path);
+        if let Some(entry) = existing {
+            let current = &mut entry.settings;
+            merge_option(&mut current.group_id, incoming.group_id, overwrite);
+            merge_option(&mut current.git_root, incoming.git_root, overwrite);
+            merge_option(
+                &mut current.launch_script,
+                incoming.launch_script,
+                overwrite,
+            );
+            merge_option(
+                &mut current.launch_scripts,
+                incoming.launch_scripts,
+                overwrite,
+            );
+            merge_option(
+                &mut current.worktree_setup_script,
+                incoming.worktree_setup_script,
+                overwrite,
+            );
+            merge_option(
+                &mut current.worktrees_folder,
+                incoming.worktrees_folder,
+                overwrite,
+            );
+            workspace_ids.insert(workspace.id.clone(), entry.id.clone());
+            summary.workspaces_updated += 1;
+            continue;
+        }
+
+        let id = if workspace.id.trim().is_empty() || workspaces.contains_key(&workspace.id) {
+            Uuid::new_v4().to_string()
+        } else {
+            workspace.id.clone()
+        };
+        workspaces.insert(
+            id.clone(),
+            WorkspaceEntry {
+                id: id.clone(),
+                name: workspace.name.clone(),
+                path,
+                kind: WorkspaceKind::Main,
+                parent_id: None,
+                worktree: None,
+                settings: incoming,
+            },
+        );
+        workspace_ids.insert(workspace.id.clone(), id.clone());
+        inserted.push(id);
+        summary.workspaces_added += 1;
+    }
+
+    // Only new entries carry bundle ids; workspaces matched by path keep
+    // their local clone source.
+    for local_id in &inserted {
+        let Some(entry) = workspaces.get_mut(local_id) else {
+            continue;
+        };
+        let mapped = entry
+            .settings
+            .clone_source_workspace_id
+            .as_ref()
+            .and_then(|source_id| workspace_ids.get(source_id).cloned());
+        if mapped.is_some() {
+            entry.settings.clone_source_workspace_id = mapped;
+        }
+    }
+    workspace_ids
+}
+
+fn import_prompts(
+    workspaces: &HashMap<String, WorkspaceEntry>,
+    settings_path: &Path,
+    bundle: &WorkspaceBundle,
+    options: &WorkspaceBundleImportOptions,
+    workspace_ids: &HashMap<String, String>,
+    summary: &mut WorkspaceBundleImportSummary,
+) {
+    for prompt in &bundle.prompts {
+        let name = match prompts_core::sanitize_prompt_name(&prompt.name) {
+            Ok(name) => name,
+            Err(err) => {
+                summary
+                    .warnings
+                    .push(format!("Skipped prompt `{}`: {err}", prompt.name));
+                continue;
+            }
+        };
+        let dir = match prompt.scope.as_str() {
+            PROMPT_SCOPE_GLOBAL => global_prompts_dir(),
+            PROMPT_SCOPE_WORKSPACE => prompt
+                .workspace_id
+                .as_ref()
+                .and_then(|id| workspace_ids.get(id))
+                .and_then(|id| workspaces.get(id))
+                .and_then(|entry| prompts_core::workspace_prompts_dir(settings_path, entry).ok()),
+            _ => None,
+        };
+        let Some(dir) = dir else {
+            summary.warnings.push(format!(
+                "Skipped prompt `{name}`: unable to resolve {} prompts directory.",
+                prompt.scope
+            ));
+            continue;
+        };
+        let path = dir.join(format!("{name}.md"));
+        if path.exists() && !options.overwrite_existing {
+            summary
+                .warnings
+                .push(format!("Skipped prompt `{name}`: it already exists."));
+            continue;
+        }
+        match prompts_core::write_prompt_file(settings_path, &path, &prompt.content, "import") {
+            Ok(()) => summary.prompts_imported += 1,
+            Err(err) => summary
+                .warnings
+                .push(format!("Failed to write prompt `{name}`: {err}")),
+        }
+    }
+}
+
+fn import_agents(
+    bundle: &WorkspaceBundle,
+    options: &WorkspaceBundleImportOptions,
+    summary: &mut WorkspaceBundleImportSummary,
+) {
+    if let Some(input) = bundle.agents_settings.clone() {
+        if options.overwrite_existing {
+            if let Err(err) = agents_config_core::set_agents_core_settings_core(input) {
+                summary
+                    .warnings
+                    .push(format!("Failed to apply agent settings: {err}"));
+            }
+        }
+    }
+    if bundle.agents.is_empty() {
+        return;
+    }
+    let existing: Vec<String> = match agents_config_core::get_agents_settings_core() {
+        Ok(settings) => settings
+            .agents
+            .into_iter()
+            .map(|agent| agent.name)
+            .collect(),
+        Err(err) => {
+            summary.warnings.push(format!("Skipped agents: {err}"));
+            return;
+        }
+    };
+    for agent in &bundle.agents {
+        let exists = existing
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(agent.name.trim()));
+        if exists && !options.overwrite_existing {
+            summary.warnings.push(format!(
+                "Skipped agent `{}`: it already exists.",
+                agent.name
+            ));
+            continue;
+        }
+        if !exists {
+            let created = agents_config_core::create_agent_core(CreateAgentInput {
+                name: agent.name.clone(),
+                description: agent.description.clone(),
+                developer_instructions: None,
+                template: None,
+                model: None,
+                reasoning_effort: None,
+            });
+            if let Err(err) = created {
+                summary
+                    .warnings
+                    .push(format!("Skipped agent `{}`: {err}", agent.name));
+                continue;
+            }
+        }
+        if let Some(content) = agent.config_toml.as_deref() {
+            if let Err(err) = agents_config_core::write_agent_config_toml_core(&agent.name, content)
+            {
+                summary.warnings.push(format!(
+                    "Failed to write agent `{}` config: {err}",
+                    agent.name
+                ));
+                continue;
+            }
+        }
+        summary.agents_imported += 1;
+    }
+}
+
+/// Merges a bundle into the local workspaces and settings. Existing workspaces
+/// are matched by (remapped) path; existing values are kept unless
+/// `overwrite_existing` is set.
+pub(crate) async fn import_workspace_bundle_core(
+    bundle: WorkspaceBundle,
+    options: WorkspaceBundleImportOptions,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    storage_path: &Path,
+    settings_path: &Path,
+) -> Result<WorkspaceBundleImportSummary, String> {
+    if bundle.version > WORKSPACE_BUNDLE_VERSION {
+        return Err(format!(
+            "Unsupported workspace bundle version {} (expected {} or older).",
+            bundle.version, WORKSPACE_BUNDLE_VERSION
+        ));
+    }
+    let mut summary = WorkspaceBundleImportSummary::default();
+
+    let group_ids = {
+        let mut current = app_settings.lock().await;
+        let mut next = current.clone();
+        let group_ids = import_settings(&mut next, &bundle, &options, &mut summary);
+        write_settings(&settings_path.to_path_buf(), &next)?;
+        *current = next;
+        group_ids
+    };
+
+    let mut workspaces = workspaces.lock().await;
+    let mut next = workspaces.clone();
+    let workspace_ids = import_workspaces(&mut next, &bundle, &options, &group_ids, &mut summary);
+    let list: Vec<_> = next.values().cloned().collect();
+    write_workspaces(&storage_path.to_path_buf(), &list)?;
+    *workspaces = next;
+
+    if !options.skip_prompts {
+        import_prompts(
+            &workspaces,
+            settings_path,
+            &bundle,
+            &options,
+            &workspace_ids,
+            &mut summary,
+        );
+    }
+    drop(workspaces);
+    if !options.skip_agents {
+        import_agents(&bundle, &options, &mut summary);
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        import_workspace_bundle_core, import_workspaces, remap_bundle_path, WorkspaceBundle,
+        WorkspaceBundleImportOptions, WorkspaceBundleImportSummary, WorkspaceBundlePathRemap,
+        WorkspaceBundleWorkspace, WORKSPACE_BUNDLE_VERSION,
+    };
+    use crate::storage::read_workspaces;
+    use crate::types::{
+        AppSettings, WorkspaceEntry, WorkspaceGroup, WorkspaceKind, WorkspaceSettings,
+    };
+    use std::collections::HashMap;
+    use tokio::runtime::Runtime;
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    fn remap(from: &str, to: &str) -> WorkspaceBundlePathRemap {
+        WorkspaceBundlePathRemap {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn remap_uses_longest_prefix_on_component_boundaries() {
+        let remaps = vec![
+            remap("/Users/alex", "/home/alex"),
+            remap("/Users/alex/code/", "/srv/code"),
+        ];
+        assert_eq!(
+            remap_bundle_path("/Users/alex/code/app", &remaps),
+            "/srv/code/app"
+        );
+        assert_eq!(
+            remap_bundle_path("/Users/alex/notes", &remaps),
+            "/home/alex/notes"
+        );
+        assert_eq!(
+            remap_bundle_path("/Users/alexa/app", &remaps),
+            "/Users/alexa/app"
+        );
+        assert_eq!(remap_bundle_path("/Users/alex", &remaps), "/home/alex");
+    }
+
+    #[test]
+    fn import_remaps_paths_and_maps_group_ids() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-bundle-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("repo")).expect("create temp dir");
+        let storage_path = dir.join("workspaces.json");
+        let settings_path = dir.join("settings.json");
+
+        let mut app_settings = AppSettings::default();
+        app_settings.workspace_groups = vec![WorkspaceGroup {
+            id: "local-group".to_string(),
+            name: "Work".to_string(),
+            sort_order: None,
+            copies_folder: None,
+        }];
+        let bundle = WorkspaceBundle {
+            version: WORKSPACE_BUNDLE_VERSION,
+            exported_at_ms: 0,
+            workspace_groups: vec![WorkspaceGroup {
+                id: "bundle-group".to_string(),
+                name: "Work".to_string(),
+                sort_order: None,
+                copies_folder: None,
+            }],
+            workspaces: vec![WorkspaceBundleWorkspace {
+                id: "ws-1".to_string(),
+                name: "repo".to_string(),
+                path: "/Users/alex/repo".to_string(),
+                settings: WorkspaceSettings {
+                    group_id: Some("bundle-group".to_string()),
+                    worktrees_folder: Some("/Users/alex/worktrees".to_string()),
+                    worktree_setup_script: Some("npm install".to_string()),
+                    ..WorkspaceSettings::default()
+                },
+            }],
+            open_app_targets: Vec::new(),
+            selected_open_app_id: None,
+            global_worktrees_folder: None,
+            prompts: Vec::new(),
+            agents_settings: None,
+            agents: Vec::new(),
+        };
+        let options = WorkspaceBundleImportOptions {
+            path_remaps: vec![remap("/Users/alex", &dir.to_string_lossy())],
+            skip_prompts: true,
+            skip_agents: true,
+            ..WorkspaceBundleImportOptions::default()
+        };
+
+        let workspaces = Mutex::new(HashMap::new());
+        let app_settings = Mutex::new(app_settings);
+        let runtime = Runtime::new().expect("create tokio runtime");
+        let summary = runtime
+            .block_on(import_workspace_bundle_core(
+                bundle,
+                options,
+                &workspaces,
+                &app_settings,
+                &storage_path,
+                &settings_path,
+            ))
+            .expect("import bundle");
+
+        assert_eq!(summary.workspaces_added, 1);
+        assert!(summary.warnings.is_empty());
+        let stored = read_workspaces(&storage_path).expect("read workspaces");
+        let entry = stored.get("ws-1").expect("imported workspace");
+        assert_eq!(entry.path, dir.join("repo").to_string_lossy());
+        assert_eq!(entry.settings.group_id.as_deref(), Some("local-group"));
+        assert_eq!(
+            entry.settings.worktrees_folder,
+            Some(dir.join("worktrees").to_string_lossy().to_string())
+        );
+        assert_eq!(
+            runtime.block_on(app_settings.lock()).workspace_groups.len(),
+            1
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn import_keeps_clone_sources_of_existing_workspaces() {
+        let bundle_workspace =
+            |id: &str, path: &str, source: Option<&str>| WorkspaceBundleWorkspace {
+                id: id.to_string(),
+                name: id.to_string(),
+                path: path.to_string(),
+                settings: WorkspaceSettings {
+                    clone_source_workspace_id: source.map(str::to_string),
+                    ..WorkspaceSettings::default()
+                },
+            };
+        let mut workspaces = HashMap::new();
+        workspaces.insert(
+            "local-app".to_string(),
+            WorkspaceEntry {
+                id: "local-app".to_string(),
+                name: "app".to_string(),
+                path: "/repos/app".to_string(),
+                kind: WorkspaceKind::Main,
+                parent_id: None,
+                worktree: None,
+                settings: WorkspaceSettings {
+                    clone_source_workspace_id: Some("local-origin".to_string()),
+                    ..WorkspaceSettings::default()
+                },
+            },
+        );
+        let bundle = WorkspaceBundle {
+            version: WORKSPACE_BUNDLE_VERSION,
+            exported_at_ms: 0,
+            workspace_groups: Vec::new(),
+            workspaces: vec![
+                bundle_workspace("bundle-app", "/repos/app", Some("bundle-other")),
+                bundle_workspace("bundle-copy", "/repos/copy", Some("bundle-app")),
+                bundle_workspace("bundle-orphan", "/repos/orphan", Some("elsewhere")),
+            ],
+            open_app_targets: Vec::new(),
+            selected_open_app_id: None,
+            global_worktrees_folder: None,
+            prompts: Vec::new(),
+            agents_settings: None,
+            agents: Vec::new(),
+        };
+        let mut summary = WorkspaceBundleImportSummary::default();
+        let ids = import_workspaces(
+            &mut workspaces,
+            &bundle,
+            &WorkspaceBundleImportOptions::default(),
+            &HashMap::new(),
+            &mut summary,
+        );
+
+        let clone_source = |id: &str| workspaces[id].settings.clone_source_workspace_id.clone();
+        assert_eq!(clone_source("local-app").as_deref(), Some("local-origin"));
+        assert_eq!(
+            clone_source(&ids["bundle-copy"]).as_deref(),
+            Some("local-app")
+        );
+        assert_eq!(
+            clone_source(&ids["bundle-orphan"]).as_deref(),
+            Some("elsewhere")
+        );
+    }
+}
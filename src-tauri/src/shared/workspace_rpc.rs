@@ -2,7 +2,8 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::types::WorkspaceSettings;
+use crate::shared::workspace_bundle_core::{WorkspaceBundle, WorkspaceBundleImportOptions};
+use crate::types::{BatchRunInput, WorkspaceSettings};
 
 #[allow(dead_code)]
 pub(crate) fn to_params<T: Serialize>(request: &T) -> Result<Value, String> {
@@ -108,3 +109,32 @@ pub(crate) struct OpenWorkspaceInRequest {
 pub(crate) struct GetOpenAppIconRequest {
     pub(crate) app_name: String,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ImportWorkspaceBundleRequest {
+    pub(crate) bundle: WorkspaceBundle,
+    #[serde(default)]
+    pub(crate) options: WorkspaceBundleImportOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceDoctorRequest {
+    #[serde(default)]
+    pub(crate) workspace_id: Option<String>,
+    #[serde(default)]
+    pub(crate) apply_fixes: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchRunStartRequest {
+    pub(crate) input: BatchRunInput,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchRunIdRequest {
+    pub(crate) batch_id: String,
+}
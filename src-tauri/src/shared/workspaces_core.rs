@@ -1,9 +1,11 @@
 mod connect;
 mod crud_persistence;
+mod doctor;
 mod git_orchestration;
 mod helpers;
 mod io;
 mod runtime_codex_args;
+mod setup_jobs;
 mod worktree;
 
 pub(crate) use connect::connect_workspace_core;
@@ -11,6 +13,7 @@ pub(crate) use crud_persistence::{
     add_clone_core, add_workspace_core, add_workspace_from_git_url_core, remove_workspace_core,
     update_workspace_settings_core,
 };
+pub(crate) use doctor::workspace_doctor_core;
 pub(crate) use git_orchestration::{apply_worktree_changes_core, run_git_command_unit};
 pub(crate) use helpers::{is_workspace_path_dir_core, list_workspaces_core};
 pub(crate) use io::{
@@ -20,6 +23,10 @@ pub(crate) use io::{
 pub(crate) use runtime_codex_args::{
     set_workspace_runtime_codex_args_core, WorkspaceRuntimeCodexArgsResult,
 };
+pub(crate) use setup_jobs::{
+    worktree_setup_cancel_core, worktree_setup_log_core, worktree_setup_retry_core,
+    worktree_setup_run_core, WorktreeSetupJobs,
+};
 pub(crate) use worktree::{
     add_worktree_core, remove_worktree_core, rename_worktree_core, rename_worktree_upstream_core,
     worktree_setup_mark_ran_core, worktree_setup_status_core,
@@ -177,9 +177,13 @@ mod tests {
             hidden_thread_ids: Mutex::new(HashSet::new()),
             next_id: AtomicU64::new(0),
             background_thread_callbacks: Mutex::new(HashMap::new()),
+            thread_observers: Mutex::new(HashMap::new()),
             owner_workspace_id: "test-owner".to_string(),
             workspace_ids: Mutex::new(HashSet::from(["test-owner".to_string()])),
             workspace_roots: Mutex::new(HashMap::new()),
+            data_dir: None,
+            approval_tracker: Default::default(),
+            audit_tracker: Default::default(),
         })
     }
 
@@ -9,6 +9,7 @@ use uuid::Uuid;
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
+use crate::shared::codex_core::normalize_sandbox_profiles;
 use crate::shared::process_core::kill_child_process_tree;
 use crate::shared::{git_core, worktree_core};
 use crate::storage::write_workspaces;
@@ -557,6 +558,7 @@ where
     FSpawn: Fn(WorkspaceEntry, Option<String>, Option<String>, Option<PathBuf>) -> FutSpawn,
     FutSpawn: Future<Output = Result<Arc<WorkspaceSession>, String>>,
 {
+    normalize_sandbox_profiles(&mut settings)?;
     settings.worktree_setup_script = normalize_setup_script(settings.worktree_setup_script);
     settings.worktrees_folder = settings
         .worktrees_folder
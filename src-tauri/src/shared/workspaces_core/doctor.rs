@@ -0,0 +1,507 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
+use crate::git_utils::resolve_git_root;
+use crate::shared::git_core;
+use crate::storage::write_workspaces;
+use crate::types::{
+    WorkspaceDoctorFinding, WorkspaceDoctorFix, WorkspaceDoctorReport, WorkspaceDoctorSeverity,
+    WorkspaceEntry, WorktreeSetupRunState,
+};
+
+use super::helpers::{
+    normalize_setup_script, worktree_setup_marker_path, write_worktree_setup_marker,
+};
+use super::setup_jobs::{peek_last_setup_run, record_interrupted_setup_run, WorktreeSetupJobs};
+
+const CHECK_PATH: &str = "path";
+const CHECK_GIT_ROOT: &str = "gitRoot";
+const CHECK_WORKTREE_PARENT: &str = "worktreeParent";
+const CHECK_WORKTREE_BRANCH: &str = "worktreeBranch";
+const CHECK_SETUP_MARKER: &str = "setupMarker";
+const CHECK_CODEX_HOME: &str = "codexHome";
+const CHECK_SESSION: &str = "session";
+
+fn passed(check: &str, message: impl Into<String>) -> WorkspaceDoctorFinding {
+    WorkspaceDoctorFinding {
+        check: check.to_string(),
+        severity: WorkspaceDoctorSeverity::Ok,
+        message: message.into(),
+        suggested_fix: None,
+        fix: None,
+        fixed: false,
+        fix_error: None,
+    }
+}
+
+fn problem(
+    check: &str,
+    severity: WorkspaceDoctorSeverity,
+    message: impl Into<String>,
+    suggested_fix: impl Into<String>,
+    fix: Option<WorkspaceDoctorFix>,
+) -> WorkspaceDoctorFinding {
+    WorkspaceDoctorFinding {
+        check: check.to_string(),
+        severity,
+        message: message.into(),
+        suggested_fix: Some(suggested_fix.into()),
+        fix,
+        fixed: false,
+        fix_error: None,
+    }
+}
+
+fn check_path(entry: &WorkspaceEntry, parent: Option<&WorkspaceEntry>) -> WorkspaceDoctorFinding {
+    if Path::new(&entry.path).is_dir() {
+        return passed(CHECK_PATH, format!("{} exists.", entry.path));
+    }
+    if entry.kind.is_worktree() && parent.is_some() {
+        return problem(
+            CHECK_PATH,
+            WorkspaceDoctorSeverity::Error,
+            format!("Worktree folder {} no longer exists.", entry.path),
+            "Prune stale worktree metadata in the parent repository, then remove this worktree.",
+            Some(WorkspaceDoctorFix::PruneWorktrees),
+        );
+    }
+    problem(
+        CHECK_PATH,
+        WorkspaceDoctorSeverity::Error,
+        format!("Workspace folder {} no longer exists.", entry.path),
+        "Remove the workspace or add it again from its new location.",
+        None,
+    )
+}
+
+async fn check_git_root(entry: &WorkspaceEntry) -> WorkspaceDoctorFinding {
+    let configured = entry
+        .settings
+        .git_root
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let root = match resolve_git_root(entry) {
+        Ok(root) => root,
+        Err(err) => {
+            return problem(
+                CHECK_GIT_ROOT,
+                WorkspaceDoctorSeverity::Error,
+                err,
+                "Clear the configured git root so the workspace folder is used.",
+                Some(WorkspaceDoctorFix::ClearGitRoot),
+            );
+        }
+    };
+    if !root.is_dir() {
+        return problem(
+            CHECK_GIT_ROOT,
+            WorkspaceDoctorSeverity::Warning,
+            format!("Git root {} is not available.", root.display()),
+            "Restore the workspace folder or remove the workspace.",
+            None,
+        );
+    }
+    match git_core::run_git_command(&root, &["rev-parse", "--git-dir"]).await {
+        Ok(_) => passed(
+            CHECK_GIT_ROOT,
+            format!("{} is a git repository.", root.display()),
+        ),
+        Err(err) => problem(
+            CHECK_GIT_ROOT,
+            if configured.is_some() {
+                WorkspaceDoctorSeverity::Error
+            } else {
+                WorkspaceDoctorSeverity::Warning
+            },
+            format!("{} is not a git repository: {err}", root.display()),
+            if configured.is_some() {
+                "Clear the configured git root or point it at a repository."
+            } else {
+                "Set a git root in workspace settings if the repository lives in a subfolder."
+            },
+            configured.map(|_| WorkspaceDoctorFix::ClearGitRoot),
+        ),
+    }
+}
+
+async fn check_worktree(
+    entry: &WorkspaceEntry,
+    parent: Option<&WorkspaceEntry>,
+) -> Vec<WorkspaceDoctorFinding> {
+    let mut findings = Vec::new();
+    let Some(parent) = parent else {
+        findings.push(problem(
+            CHECK_WORKTREE_PARENT,
+            WorkspaceDoctorSeverity::Error,
+            "The parent workspace for this worktree is missing.",
+            "Add the parent repository again, or remove this worktree workspace.",
+            None,
+        ));
+        return findings;
+    };
+    let parent_root = match resolve_git_root(parent) {
+        Ok(root) if root.is_dir() => root,
+        _ => {
+            findings.push(problem(
+                CHECK_WORKTREE_PARENT,
+                WorkspaceDoctorSeverity::Error,
+                format!("Parent workspace {} is not available.", parent.path),
+                "Restore the parent repository or remove this worktree workspace.",
+                None,
+            ));
+            return findings;
+        }
+    };
+    findings.push(passed(
+        CHECK_WORKTREE_PARENT,
+        format!("Parent workspace {} is present.", parent.name),
+    ));
+
+    let Some(branch) = entry.worktree.as_ref().map(|info| info.branch.clone()) else {
+        return findings;
+    };
+    findings.push(
+        match git_core::git_branch_exists(&parent_root, &branch).await {
+            Ok(true) => passed(CHECK_WORKTREE_BRANCH, format!("Branch {branch} exists.")),
+            Ok(false) => problem(
+                CHECK_WORKTREE_BRANCH,
+                WorkspaceDoctorSeverity::Warning,
+                format!("Branch {branch} no longer exists in the parent repository."),
+                "Rename the worktree to an existing branch or recreate the branch.",
+                None,
+            ),
+            Err(err) => problem(
+                CHECK_WORKTREE_BRANCH,
+                WorkspaceDoctorSeverity::Warning,
+                format!("Unable to check branch {branch}: {err}"),
+                "Verify the parent repository with git status.",
+                None,
+            ),
+        },
+    );
+    findings
+}
+
+async fn check_setup_marker(
+    entry: &WorkspaceEntry,
+    jobs: &WorktreeSetupJobs,
+    data_dir: &PathBuf,
+) -> Option<WorkspaceDoctorFinding> {
+    let marker_exists = worktree_setup_marker_path(data_dir, &entry.id).exists();
+    if !entry.kind.is_worktree() {
+        return marker_exists.then(|| {
+            problem(
+                CHECK_SETUP_MARKER,
+                WorkspaceDoctorSeverity::Warning,
+                "A worktree setup marker exists for a workspace that is not a worktree.",
+                "Remove the stale setup marker.",
+                Some(WorkspaceDoctorFix::RemoveSetupMarker),
+            )
+        });
+    }
+    let script = normalize_setup_script(entry.settings.worktree_setup_script.clone());
+    // Read-only: a stale `running` record is only rewritten when fixes are applied.
+    let last_run = peek_last_setup_run(jobs, data_dir, &entry.id).await;
+    let last_state = last_run.as_ref().map(|(run, _)| run.state);
+    let stale = last_run.as_ref().is_some_and(|(_, stale)| *stale);
+    let finding = match (marker_exists, last_state) {
+        (false, Some(WorktreeSetupRunState::Succeeded)) => problem(
+            CHECK_SETUP_MARKER,
+            WorkspaceDoctorSeverity::Warning,
+            "The last setup run succeeded but the setup marker is missing.",
+            "Write the setup marker so setup is not offered again.",
+            Some(WorkspaceDoctorFix::WriteSetupMarker),
+        ),
+        (false, Some(WorktreeSetupRunState::Failed)) if stale => problem(
+            CHECK_SETUP_MARKER,
+            WorkspaceDoctorSeverity::Warning,
+            "The last setup run was interrupted before it finished.",
+            "Record the interrupted run, then retry the worktree setup script.",
+            Some(WorkspaceDoctorFix::RecordInterruptedSetupRun),
+        ),
+        (false, Some(WorktreeSetupRunState::Failed | WorktreeSetupRunState::Cancelled)) => problem(
+            CHECK_SETUP_MARKER,
+            WorkspaceDoctorSeverity::Warning,
+            "The last setup run did not complete.",
+            "Retry the worktree setup script.",
+            None,
+        ),
+        (false, None) if script.is_some() => problem(
+            CHECK_SETUP_MARKER,
+            WorkspaceDoctorSeverity::Warning,
+            "The worktree setup script has not run yet.",
+            "Run the worktree setup script.",
+            None,
+        ),
+        _ => passed(CHECK_SETUP_MARKER, "Setup marker is consistent."),
+    };
+    Some(finding)
+}
+
+fn check_codex_home(
+    entry: &WorkspaceEntry,
+    parent: Option<&WorkspaceEntry>,
+) -> WorkspaceDoctorFinding {
+    let Some(home) =
+        resolve_workspace_codex_home(entry, parent).or_else(resolve_default_codex_home)
+    else {
+        return problem(
+            CHECK_CODEX_HOME,
+            WorkspaceDoctorSeverity::Error,
+            "Unable to resolve CODEX_HOME.",
+            "Set CODEX_HOME or make sure the home directory is available.",
+            None,
+        );
+    };
+    if let Err(err) = std::fs::read_dir(&home) {
+        return problem(
+            CHECK_CODEX_HOME,
+            WorkspaceDoctorSeverity::Error,
+            format!("CODEX_HOME {} is not readable: {err}", home.display()),
+            "Create the directory or fix its permissions.",
+            None,
+        );
+    }
+    let config_path = home.join("config.toml");
+    if config_path.exists() {
+        if let Err(err) = std::fs::read_to_string(&config_path) {
+            return problem(
+                CHECK_CODEX_HOME,
+                WorkspaceDoctorSeverity::Error,
+                format!("{} is not readable: {err}", config_path.display()),
+                "Fix the permissions of config.toml.",
+                None,
+            );
+        }
+    }
+    passed(
+        CHECK_CODEX_HOME,
+        format!("CODEX_HOME {} is readable.", home.display()),
+    )
+}
+
+async fn check_session(
+    entry: &WorkspaceEntry,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+) -> WorkspaceDoctorFinding {
+    let session = sessions.lock().await.get(&entry.id).cloned();
+    let Some(session) = session else {
+        return problem(
+            CHECK_SESSION,
+            WorkspaceDoctorSeverity::Warning,
+            "The workspace is not connected.",
+            "Connect the workspace.",
+            None,
+        );
+    };
+    let mut child = session.child.lock().await;
+    match child.try_wait() {
+        Ok(None) => passed(CHECK_SESSION, "Codex app-server is running."),
+        Ok(Some(status)) => problem(
+            CHECK_SESSION,
+            WorkspaceDoctorSeverity::Error,
+            format!("Codex app-server exited ({status})."),
+            "Reconnect the workspace.",
+            None,
+        ),
+        Err(err) => problem(
+            CHECK_SESSION,
+            WorkspaceDoctorSeverity::Warning,
+            format!("Unable to query the codex app-server process: {err}"),
+            "Reconnect the workspace.",
+            None,
+        ),
+    }
+}
+
+async fn apply_fix(
+    fix: WorkspaceDoctorFix,
+    entry: &WorkspaceEntry,
+    parent: Option<&WorkspaceEntry>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    jobs: &WorktreeSetupJobs,
+    storage_path: &PathBuf,
+    data_dir: &PathBuf,
+) -> Result<(), String> {
+    match fix {
+        WorkspaceDoctorFix::ClearGitRoot => {
+            let mut workspaces = workspaces.lock().await;
+            let current = workspaces
+                .get_mut(&entry.id)
+                .ok_or_else(|| "workspace not found".to_string())?;
+            current.settings.git_root = None;
+            let list: Vec<_> = workspaces.values().cloned().collect();
+            write_workspaces(storage_path, &list)
+        }
+        WorkspaceDoctorFix::PruneWorktrees => {
+            let parent = parent.ok_or_else(|| "parent workspace not found".to_string())?;
+            let parent_root = resolve_git_root(parent)?;
+            git_core::run_git_command(&parent_root, &["worktree", "prune"])
+                .await
+                .map(|_| ())
+        }
+        WorkspaceDoctorFix::WriteSetupMarker => write_worktree_setup_marker(data_dir, &entry.id),
+        WorkspaceDoctorFix::RemoveSetupMarker => {
+            std::fs::remove_file(worktree_setup_marker_path(data_dir, &entry.id))
+                .map_err(|err| format!("Failed to remove worktree setup marker: {err}"))
+        }
+        WorkspaceDoctorFix::RecordInterruptedSetupRun => {
+            record_interrupted_setup_run(jobs, data_dir, &entry.id).await
+        }
+    }
+}
+
+async fn diagnose_workspace(
+    entry: &WorkspaceEntry,
+    parent: Option<&WorkspaceEntry>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    jobs: &WorktreeSetupJobs,
+    data_dir: &PathBuf,
+) -> Vec<WorkspaceDoctorFinding> {
+    let mut findings = vec![check_path(entry, parent)];
+    if findings[0].severity == WorkspaceDoctorSeverity::Ok {
+        findings.push(check_git_root(entry).await);
+    }
+    if entry.kind.is_worktree() {
+        findings.extend(check_worktree(entry, parent).await);
+    }
+    findings.extend(check_setup_marker(entry, jobs, data_dir).await);
+    findings.push(check_codex_home(entry, parent));
+    findings.push(check_session(entry, sessions).await);
+    findings
+}
+
+/// Runs health checks for one workspace (or all of them when `workspace_id` is
+/// `None`). With `apply_fixes`, findings that carry a `fix` are repaired in place.
+pub(crate) async fn workspace_doctor_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    jobs: &WorktreeSetupJobs,
+    storage_path: &PathBuf,
+    data_dir: &PathBuf,
+    workspace_id: Option<String>,
+    apply_fixes: bool,
+) -> Result<Vec<WorkspaceDoctorReport>, String> {
+    let targets: Vec<(WorkspaceEntry, Option<WorkspaceEntry>)> = {
+        let workspaces = workspaces.lock().await;
+        let mut entries: Vec<&WorkspaceEntry> = match workspace_id.as_deref() {
+            Some(id) => vec![workspaces
+                .get(id)
+                .ok_or_else(|| "workspace not found".to_string())?],
+            None => workspaces.values().collect(),
+        };
+        entries.sort_by(|left, right| left.name.cmp(&right.name));
+        entries
+            .into_iter()
+            .map(|entry| {
+                let parent = entry
+                    .parent_id
+                    .as_ref()
+                    .and_then(|parent_id| workspaces.get(parent_id))
+                    .cloned();
+                (entry.clone(), parent)
+            })
+            .collect()
+    };
+
+    let mut reports = Vec::with_capacity(targets.len());
+    for (entry, parent) in targets {
+        let mut findings =
+            diagnose_workspace(&entry, parent.as_ref(), sessions, jobs, data_dir).await;
+        if apply_fixes {
+            for finding in findings.iter_mut() {
+                let Some(fix) = finding.fix else {
+                    continue;
+                };
+                match apply_fix(
+                    fix,
+                    &entry,
+                    parent.as_ref(),
+                    workspaces,
+                    jobs,
+                    storage_path,
+                    data_dir,
+                )
+                .await
+                {
+                    Ok(()) => finding.fixed = true,
+                    Err(err) => finding.fix_error = Some(err),
+                }
+            }
+        }
+        let healthy = findings
+            .iter()
+            .all(|finding| finding.severity != WorkspaceDoctorSeverity::Error || finding.fixed);
+        reports.push(WorkspaceDoctorReport {
+            workspace_id: entry.id,
+            workspace_name: entry.name,
+            healthy,
+            findings,
+        });
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_path, check_setup_marker, write_worktree_setup_marker, WorktreeSetupJobs};
+    use crate::types::{
+        WorkspaceDoctorFix, WorkspaceDoctorSeverity, WorkspaceEntry, WorkspaceKind,
+        WorkspaceSettings,
+    };
+    use std::path::PathBuf;
+    use tokio::runtime::Runtime;
+    use uuid::Uuid;
+
+    fn entry(id: &str, path: &str, kind: WorkspaceKind) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            kind,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
+    #[test]
+    fn missing_worktree_path_suggests_prune() {
+        let parent = entry("parent", "/repo", WorkspaceKind::Main);
+        let missing = format!("/tmp/codex-monitor-missing-{}", Uuid::new_v4());
+        let worktree = entry("wt", &missing, WorkspaceKind::Worktree);
+
+        let finding = check_path(&worktree, Some(&parent));
+        assert_eq!(finding.severity, WorkspaceDoctorSeverity::Error);
+        assert_eq!(finding.fix, Some(WorkspaceDoctorFix::PruneWorktrees));
+
+        let finding = check_path(&entry("main", &missing, WorkspaceKind::Main), None);
+        assert_eq!(finding.fix, None);
+    }
+
+    #[test]
+    fn stale_marker_on_main_workspace_is_removable() {
+        let data_dir: PathBuf =
+            std::env::temp_dir().join(format!("codex-monitor-doctor-{}", Uuid::new_v4()));
+        let main = entry("main", "/repo", WorkspaceKind::Main);
+        let jobs = WorktreeSetupJobs::default();
+        let runtime = Runtime::new().expect("create tokio runtime");
+
+        let finding = runtime.block_on(check_setup_marker(&main, &jobs, &data_dir));
+        assert!(finding.is_none());
+
+        write_worktree_setup_marker(&data_dir, "main").expect("write marker");
+        let finding = runtime
+            .block_on(check_setup_marker(&main, &jobs, &data_dir))
+            .expect("marker finding");
+        assert_eq!(finding.fix, Some(WorkspaceDoctorFix::RemoveSetupMarker));
+
+        let _ = std::fs::remove_dir_all(&data_dir);
+    }
+}
@@ -63,6 +63,23 @@ pub(crate) fn worktree_setup_marker_path(data_dir: &PathBuf, workspace_id: &str)
         .join(format!("{workspace_id}.{WORKTREE_SETUP_MARKER_EXT}"))
 }
 
+pub(crate) fn write_worktree_setup_marker(
+    data_dir: &PathBuf,
+    workspace_id: &str,
+) -> Result<(), String> {
+    let marker_path = worktree_setup_marker_path(data_dir, workspace_id);
+    if let Some(parent) = marker_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to prepare worktree marker directory: {err}"))?;
+    }
+    let ran_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    std::fs::write(&marker_path, format!("ran_at={ran_at}\n"))
+        .map_err(|err| format!("Failed to write worktree setup marker: {err}"))
+}
+
 pub(crate) fn is_workspace_path_dir_core(path: &str) -> bool {
     normalize_workspace_path_input(path).is_dir()
 }
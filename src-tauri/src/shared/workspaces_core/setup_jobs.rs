@@ -0,0 +1,577 @@
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::shared::store_helpers::now_ms;
+use crate::types::{WorkspaceEntry, WorktreeSetupLog, WorktreeSetupRun, WorktreeSetupRunState};
+
+use super::helpers::{
+    normalize_setup_script, write_worktree_setup_marker, WORKTREE_SETUP_MARKERS_DIR,
+};
+
+const SETUP_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const SETUP_OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_SETUP_LOG_READ_BYTES: u64 = 512_000;
+
+/// Tracks setup scripts that are currently executing, keyed by workspace id.
+#[derive(Clone, Default)]
+pub(crate) struct WorktreeSetupJobs {
+    running: Arc<Mutex<HashMap<String, RunningSetupJob>>>,
+}
+
+#[derive(Clone)]
+struct RunningSetupJob {
+    run_id: String,
+    child: Arc<Mutex<Child>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl WorktreeSetupJobs {
+    pub(crate) async fn is_running(&self, workspace_id: &str) -> bool {
+        self.running.lock().await.contains_key(workspace_id)
+    }
+}
+
+pub(crate) fn worktree_setup_log_path(data_dir: &Path, workspace_id: &str) -> PathBuf {
+    data_dir
+        .join(WORKTREE_SETUP_MARKERS_DIR)
+        .join(format!("{workspace_id}.log"))
+}
+
+pub(crate) fn worktree_setup_run_path(data_dir: &Path, workspace_id: &str) -> PathBuf {
+    data_dir
+        .join(WORKTREE_SETUP_MARKERS_DIR)
+        .join(format!("{workspace_id}.run.json"))
+}
+
+pub(crate) fn read_worktree_setup_run(
+    data_dir: &Path,
+    workspace_id: &str,
+) -> Option<WorktreeSetupRun> {
+    let data = std::fs::read_to_string(worktree_setup_run_path(data_dir, workspace_id)).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+fn write_worktree_setup_run(data_dir: &Path, run: &WorktreeSetupRun) -> Result<(), String> {
+    let path = worktree_setup_run_path(data_dir, &run.workspace_id);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to prepare worktree setup directory: {err}"))?;
+    }
+    let data = serde_json::to_string_pretty(run).map_err(|err| err.to_string())?;
+    std::fs::write(&path, data)
+        .map_err(|err| format!("Failed to write worktree setup run record: {err}"))
+}
+
+/// Returns the last recorded run without writing anything. A stale `running` record (left
+/// behind by a restart mid-run) is reported as `failed`; the flag says whether it was.
+pub(crate) async fn peek_last_setup_run(
+    jobs: &WorktreeSetupJobs,
+    data_dir: &Path,
+    workspace_id: &str,
+) -> Option<(WorktreeSetupRun, bool)> {
+    let mut run = read_worktree_setup_run(data_dir, workspace_id)?;
+    let stale = run.state == WorktreeSetupRunState::Running && !jobs.is_running(workspace_id).await;
+    if stale {
+        run.state = WorktreeSetupRunState::Failed;
+        run.error = Some("Setup run was interrupted before it finished.".to_string());
+    }
+    Some((run, stale))
+}
+
+/// Returns the last recorded run, persisting the downgrade of a stale `running` record to
+/// `failed` so callers can offer a retry.
+pub(crate) async fn resolve_last_setup_run(
+    jobs: &WorktreeSetupJobs,
+    data_dir: &Path,
+    workspace_id: &str,
+) -> Option<WorktreeSetupRun> {
+    let (run, stale) = peek_last_setup_run(jobs, data_dir, workspace_id).await?;
+    if stale {
+        let _ = write_worktree_setup_run(data_dir, &run);
+    }
+    Some(run)
+}
+
+/// Persists the downgrade of a stale `running` record; a no-op otherwise.
+pub(crate) async fn record_interrupted_setup_run(
+    jobs: &WorktreeSetupJobs,
+    data_dir: &Path,
+    workspace_id: &str,
+) -> Result<(), String> {
+    match peek_last_setup_run(jobs, data_dir, workspace_id).await {
+        Some((run, true)) => write_worktree_setup_run(data_dir, &run),
+        _ => Ok(()),
+    }
+}
+
+fn setup_script_command(script: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        let mut command = tokio_command("cmd");
+        command.arg("/D").arg("/C").arg(script);
+        command
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let shell = std::env::var("SHELL")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| "/bin/sh".to_string());
+        let mut command = tokio_command(shell);
+        command.arg("-lc").arg(script);
+        command
+    }
+}
+
+pub(crate) fn worktree_setup_env(
+    entry: &WorkspaceEntry,
+    parent: Option<&WorkspaceEntry>,
+) -> Vec<(String, String)> {
+    let mut env = vec![
+        ("CODEX_MONITOR_WORKSPACE_ID".to_string(), entry.id.clone()),
+        (
+            "CODEX_MONITOR_WORKSPACE_NAME".to_string(),
+            entry.name.clone(),
+        ),
+        (
+            "CODEX_MONITOR_WORKSPACE_PATH".to_string(),
+            entry.path.clone(),
+        ),
+    ];
+    if let Some(worktree) = entry.worktree.as_ref() {
+        env.push((
+            "CODEX_MONITOR_WORKTREE_BRANCH".to_string(),
+            worktree.branch.clone(),
+        ));
+    }
+    if let Some(parent) = parent {
+        env.push((
+            "CODEX_MONITOR_PARENT_WORKSPACE_ID".to_string(),
+            parent.id.clone(),
+        ));
+        env.push((
+            "CODEX_MONITOR_PARENT_WORKSPACE_PATH".to_string(),
+            parent.path.clone(),
+        ));
+    }
+    env
+}
+
+fn emit_setup_event<E: EventSink>(
+    event_sink: &E,
+    workspace_id: &str,
+    method: &str,
+    params: serde_json::Value,
+) {
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message: json!({
+            "method": method,
+            "params": params,
+        }),
+    });
+}
+
+fn spawn_output_reader<R, E>(
+    reader: R,
+    stream: &'static str,
+    log: Arc<std::sync::Mutex<std::fs::File>>,
+    event_sink: E,
+    workspace_id: String,
+    run_id: String,
+) -> tokio::task::JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+    E: EventSink,
+{
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(reader);
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_until(b'\n', &mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let line = String::from_utf8_lossy(&buffer).to_string();
+                    if let Ok(mut file) = log.lock() {
+                        let _ = file.write_all(line.as_bytes());
+                    }
+                    emit_setup_event(
+                        &event_sink,
+                        &workspace_id,
+                        "worktree/setupOutput",
+                        json!({
+                            "workspaceId": workspace_id,
+                            "runId": run_id,
+                            "stream": stream,
+                            "data": line,
+                        }),
+                    );
+                }
+            }
+        }
+    })
+}
+
+async fn wait_for_setup_exit(child: &Arc<Mutex<Child>>) -> Result<Option<i32>, String> {
+    loop {
+        {
+            let mut child = child.lock().await;
+            match child.try_wait() {
+                Ok(Some(status)) => return Ok(status.code()),
+                Ok(None) => {}
+                Err(err) => return Err(format!("Failed to wait for setup script: {err}")),
+            }
+        }
+        tokio::time::sleep(SETUP_WAIT_POLL_INTERVAL).await;
+    }
+}
+
+/// Starts the workspace's worktree setup script as a background job. Output is streamed as
+/// `worktree/setupOutput` app-server events and appended to a per-worktree log file; the run
+/// record is persisted next to the setup marker so status survives restarts.
+pub(crate) async fn worktree_setup_run_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    jobs: &WorktreeSetupJobs,
+    workspace_id: &str,
+    data_dir: &PathBuf,
+    event_sink: E,
+) -> Result<WorktreeSetupRun, String> {
+    let (entry, parent) = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(workspace_id)
+            .cloned()
+            .ok_or_else(|| "workspace not found".to_string())?;
+        let parent = entry
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id))
+            .cloned();
+        (entry, parent)
+    };
+    if !entry.kind.is_worktree() {
+        return Err("Not a worktree workspace.".to_string());
+    }
+    let script = normalize_setup_script(entry.settings.worktree_setup_script.clone())
+        .ok_or_else(|| "No worktree setup script configured.".to_string())?;
+    if !Path::new(&entry.path).is_dir() {
+        return Err(format!("Worktree folder not found: {}", entry.path));
+    }
+
+    let mut running = jobs.running.lock().await;
+    if running.contains_key(&entry.id) {
+        return Err("Worktree setup is already running.".to_string());
+    }
+
+    let attempt = read_worktree_setup_run(data_dir, &entry.id)
+        .map(|run| run.attempt.saturating_add(1))
+        .unwrap_or(1);
+    let log_path = worktree_setup_log_path(data_dir, &entry.id);
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to prepare worktree setup directory: {err}"))?;
+    }
+    let mut log_file = std::fs::File::create(&log_path)
+        .map_err(|err| format!("Failed to create worktree setup log: {err}"))?;
+    let _ = writeln!(log_file, "$ {}", script.trim());
+
+    let mut command = setup_script_command(&script);
+    command
+        .current_dir(&entry.path)
+        .envs(worktree_setup_env(&entry, parent.as_ref()))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    command.process_group(0);
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("Failed to start worktree setup script: {err}"))?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    let run = WorktreeSetupRun {
+        run_id: Uuid::new_v4().to_string(),
+        workspace_id: entry.id.clone(),
+        state: WorktreeSetupRunState::Running,
+        attempt,
+        script: script.clone(),
+        started_at_ms: now_ms(),
+        finished_at_ms: None,
+        duration_ms: None,
+        exit_code: None,
+        error: None,
+    };
+    if let Err(error) = write_worktree_setup_run(data_dir, &run) {
+        kill_child_process_tree(&mut child).await;
+        return Err(error);
+    }
+
+    let job = RunningSetupJob {
+        run_id: run.run_id.clone(),
+        child: Arc::new(Mutex::new(child)),
+        cancelled: Arc::new(AtomicBool::new(false)),
+    };
+    running.insert(entry.id.clone(), job.clone());
+    drop(running);
+
+    emit_setup_event(
+        &event_sink,
+        &entry.id,
+        "worktree/setupStarted",
+        json!({ "workspaceId": entry.id, "run": run }),
+    );
+
+    let log = Arc::new(std::sync::Mutex::new(log_file));
+    let mut readers = Vec::new();
+    if let Some(stdout) = stdout {
+        readers.push(spawn_output_reader(
+            stdout,
+            "stdout",
+            Arc::clone(&log),
+            event_sink.clone(),
+            entry.id.clone(),
+            run.run_id.clone(),
+        ));
+    }
+    if let Some(stderr) = stderr {
+        readers.push(spawn_output_reader(
+            stderr,
+            "stderr",
+            Arc::clone(&log),
+            event_sink.clone(),
+            entry.id.clone(),
+            run.run_id.clone(),
+        ));
+    }
+
+    let jobs = jobs.clone();
+    let data_dir = data_dir.clone();
+    let started = Instant::now();
+    let mut finished = run.clone();
+    tokio::spawn(async move {
+        let exit = wait_for_setup_exit(&job.child).await;
+        for reader in readers {
+            // Background processes started by the script can keep the pipes open after the
+            // shell itself exits; don't let them hold the run record hostage.
+            let _ = tokio::time::timeout(SETUP_OUTPUT_DRAIN_TIMEOUT, reader).await;
+        }
+        finished.finished_at_ms = Some(now_ms());
+        finished.duration_ms = Some(started.elapsed().as_millis() as i64);
+        match exit {
+            Ok(code) => {
+                finished.exit_code = code;
+                finished.state = if job.cancelled.load(Ordering::SeqCst) {
+                    WorktreeSetupRunState::Cancelled
+                } else if code == Some(0) {
+                    WorktreeSetupRunState::Succeeded
+                } else {
+                    WorktreeSetupRunState::Failed
+                };
+            }
+            Err(error) => {
+                finished.state = WorktreeSetupRunState::Failed;
+                finished.error = Some(error);
+            }
+        }
+        if finished.state == WorktreeSetupRunState::Succeeded {
+            if let Err(error) = write_worktree_setup_marker(&data_dir, &finished.workspace_id) {
+                finished.error = Some(error);
+            }
+        }
+        if let Ok(mut file) = log.lock() {
+            let _ = writeln!(
+                file,
+                "\n[{}] exit code {} after {} ms",
+                finished.state.as_str(),
+                finished
+                    .exit_code
+                    .map(|code| code.to_string())
+                    .unwrap_or_else(|| "none".to_string()),
+                finished.duration_ms.unwrap_or(0)
+            );
+        }
+        if let Err(error) = write_worktree_setup_run(&data_dir, &finished) {
+            eprintln!(
+                "worktree_setup_run: failed to persist run record for {}: {error}",
+                finished.workspace_id
+            );
+        }
+        {
+            let mut running = jobs.running.lock().await;
+            if running
+                .get(&finished.workspace_id)
+                .is_some_and(|current| current.run_id == finished.run_id)
+            {
+                running.remove(&finished.workspace_id);
+            }
+        }
+        emit_setup_event(
+            &event_sink,
+            &finished.workspace_id,
+            "worktree/setupFinished",
+            json!({ "workspaceId": finished.workspace_id, "run": finished }),
+        );
+    });
+
+    Ok(run)
+}
+
+/// Re-runs the setup script after a failed or cancelled attempt.
+pub(crate) async fn worktree_setup_retry_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    jobs: &WorktreeSetupJobs,
+    workspace_id: &str,
+    data_dir: &PathBuf,
+    event_sink: E,
+) -> Result<WorktreeSetupRun, String> {
+    let previous = resolve_last_setup_run(jobs, data_dir, workspace_id)
+        .await
+        .ok_or_else(|| "No previous setup run to retry.".to_string())?;
+    if previous.state == WorktreeSetupRunState::Running {
+        return Err("Worktree setup is already running.".to_string());
+    }
+    worktree_setup_run_core(workspaces, jobs, workspace_id, data_dir, event_sink).await
+}
+
+pub(crate) async fn worktree_setup_cancel_core(
+    jobs: &WorktreeSetupJobs,
+    workspace_id: &str,
+) -> Result<(), String> {
+    let job = jobs
+        .running
+        .lock()
+        .await
+        .get(workspace_id)
+        .cloned()
+        .ok_or_else(|| "No worktree setup is running.".to_string())?;
+    job.cancelled.store(true, Ordering::SeqCst);
+    let mut child = job.child.lock().await;
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // The script runs in its own process group; take down everything it spawned.
+        unsafe {
+            libc::kill(-(pid as i32), libc::SIGKILL);
+        }
+    }
+    kill_child_process_tree(&mut child).await;
+    Ok(())
+}
+
+pub(crate) async fn worktree_setup_log_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    data_dir: &PathBuf,
+) -> Result<WorktreeSetupLog, String> {
+    if !workspaces.lock().await.contains_key(workspace_id) {
+        return Err("workspace not found".to_string());
+    }
+    read_setup_log_tail(&worktree_setup_log_path(data_dir, workspace_id))
+}
+
+fn read_setup_log_tail(path: &Path) -> Result<WorktreeSetupLog, String> {
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(WorktreeSetupLog {
+                content: String::new(),
+                truncated: false,
+            });
+        }
+        Err(err) => return Err(format!("Failed to open worktree setup log: {err}")),
+    };
+    let len = file
+        .metadata()
+        .map_err(|err| format!("Failed to read worktree setup log: {err}"))?
+        .len();
+    let truncated = len > MAX_SETUP_LOG_READ_BYTES;
+    if truncated {
+        file.seek(SeekFrom::Start(len - MAX_SETUP_LOG_READ_BYTES))
+            .map_err(|err| format!("Failed to read worktree setup log: {err}"))?;
+    }
+    let mut buffer = Vec::new();
+    file.read_to_end(&mut buffer)
+        .map_err(|err| format!("Failed to read worktree setup log: {err}"))?;
+    Ok(WorktreeSetupLog {
+        content: String::from_utf8_lossy(&buffer).to_string(),
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_setup_log_tail, worktree_setup_env, MAX_SETUP_LOG_READ_BYTES};
+    use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings, WorktreeInfo};
+    use uuid::Uuid;
+
+    fn entry(id: &str, path: &str, kind: WorkspaceKind) -> WorkspaceEntry {
+        WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            kind,
+            parent_id: None,
+            worktree: None,
+            settings: WorkspaceSettings::default(),
+        }
+    }
+
+    #[test]
+    fn setup_env_includes_worktree_and_parent_context() {
+        let parent = entry("parent", "/repo", WorkspaceKind::Main);
+        let mut worktree = entry("wt", "/worktrees/wt", WorkspaceKind::Worktree);
+        worktree.parent_id = Some("parent".to_string());
+        worktree.worktree = Some(WorktreeInfo {
+            branch: "feature/x".to_string(),
+        });
+
+        let env = worktree_setup_env(&worktree, Some(&parent));
+        let lookup = |key: &str| {
+            env.iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(
+            lookup("CODEX_MONITOR_WORKSPACE_PATH"),
+            Some("/worktrees/wt")
+        );
+        assert_eq!(lookup("CODEX_MONITOR_WORKTREE_BRANCH"), Some("feature/x"));
+        assert_eq!(lookup("CODEX_MONITOR_PARENT_WORKSPACE_PATH"), Some("/repo"));
+    }
+
+    #[test]
+    fn setup_log_tail_reports_missing_and_truncated_logs() {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-setup-log-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+
+        let missing = read_setup_log_tail(&dir.join("missing.log")).expect("missing log");
+        assert!(missing.content.is_empty());
+        assert!(!missing.truncated);
+
+        let log_path = dir.join("big.log");
+        let mut content = "a".repeat(MAX_SETUP_LOG_READ_BYTES as usize);
+        content.push_str("tail");
+        std::fs::write(&log_path, &content).expect("write log");
+        let tail = read_setup_log_tail(&log_path).expect("read tail");
+        assert!(tail.truncated);
+        assert!(tail.content.ends_with("tail"));
+        assert_eq!(tail.content.len(), MAX_SETUP_LOG_READ_BYTES as usize);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
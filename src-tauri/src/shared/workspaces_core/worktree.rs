@@ -12,17 +12,19 @@ use crate::codex::home::resolve_workspace_codex_home;
 use crate::storage::write_workspaces;
 use crate::types::{
     AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
-    WorktreeSetupStatus,
+    WorktreeSetupRunState, WorktreeSetupStatus,
 };
 
 use super::connect::{kill_session_by_id, take_live_shared_session, workspace_session_spawn_lock};
 use super::helpers::{
     copy_agents_md_from_parent_to_worktree, normalize_setup_script, workspace_path_to_string,
-    worktree_setup_marker_path, AGENTS_MD_FILE_NAME,
+    worktree_setup_marker_path, write_worktree_setup_marker, AGENTS_MD_FILE_NAME,
 };
+use super::setup_jobs::{resolve_last_setup_run, WorktreeSetupJobs};
 
 pub(crate) async fn worktree_setup_status_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    jobs: &WorktreeSetupJobs,
     workspace_id: &str,
     data_dir: &PathBuf,
 ) -> Result<WorktreeSetupStatus, String> {
@@ -40,9 +42,21 @@ pub(crate) async fn worktree_setup_status_core(
     } else {
         false
     };
-    let should_run = entry.kind.is_worktree() && script.is_some() && !marker_exists;
+    let last_run = if entry.kind.is_worktree() {
+        resolve_last_setup_run(jobs, data_dir, &entry.id).await
+    } else {
+        None
+    };
+    let is_running = last_run
+        .as_ref()
+        .is_some_and(|run| run.state == WorktreeSetupRunState::Running);
+    let should_run = entry.kind.is_worktree() && script.is_some() && !marker_exists && !is_running;
 
-    Ok(WorktreeSetupStatus { should_run, script })
+    Ok(WorktreeSetupStatus {
+        should_run,
+        script,
+        last_run,
+    })
 }
 
 pub(crate) async fn worktree_setup_mark_ran_core(
@@ -60,18 +74,7 @@ pub(crate) async fn worktree_setup_mark_ran_core(
     if !entry.kind.is_worktree() {
         return Err("Not a worktree workspace.".to_string());
     }
-    let marker_path = worktree_setup_marker_path(data_dir, &entry.id);
-    if let Some(parent) = marker_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|err| format!("Failed to prepare worktree marker directory: {err}"))?;
-    }
-    let ran_at = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|duration| duration.as_secs())
-        .unwrap_or(0);
-    std::fs::write(&marker_path, format!("ran_at={ran_at}\n"))
-        .map_err(|err| format!("Failed to write worktree setup marker: {err}"))?;
-    Ok(())
+    write_worktree_setup_marker(data_dir, &entry.id)
 }
 
 pub(crate) async fn add_worktree_core<
@@ -7,6 +7,8 @@ use tokio::sync::Mutex;
 
 use crate::dictation::DictationState;
 use crate::shared::codex_core::CodexLoginCancelState;
+use crate::shared::schedules_core::ScheduleRunner;
+use crate::shared::workspaces_core::WorktreeSetupJobs;
 use crate::storage::{read_settings, read_workspaces};
 use crate::types::{AppSettings, TcpDaemonState, TcpDaemonStatus, WorkspaceEntry};
 
@@ -41,6 +43,8 @@ pub(crate) struct AppState {
     pub(crate) dictation: Mutex<DictationState>,
     pub(crate) codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
     pub(crate) tcp_daemon: Mutex<TcpDaemonRuntime>,
+    pub(crate) worktree_setup_jobs: WorktreeSetupJobs,
+    pub(crate) schedule_runner: ScheduleRunner,
 }
 
 impl AppState {
@@ -64,6 +68,8 @@ impl AppState {
             dictation: Mutex::new(DictationState::default()),
             codex_login_cancels: Mutex::new(HashMap::new()),
             tcp_daemon: Mutex::new(TcpDaemonRuntime::default()),
+            worktree_setup_jobs: WorktreeSetupJobs::default(),
+            schedule_runner: ScheduleRunner::default(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -16,8 +17,12 @@ pub(crate) struct TerminalSession {
     pub(crate) master: Mutex<Box<dyn portable_pty::MasterPty + Send>>,
     pub(crate) writer: Mutex<Box<dyn Write + Send>>,
     pub(crate) child: Mutex<Box<dyn portable_pty::Child + Send>>,
+    /// Tail of the terminal's output, kept for turn context attachments.
+    pub(crate) recent_output: std::sync::Mutex<String>,
 }
 
+const MAX_RECENT_OUTPUT_BYTES: usize = 64 * 1024;
+
 #[derive(Debug, Serialize, Clone)]
 pub(crate) struct TerminalSessionInfo {
     id: String,
@@ -27,6 +32,41 @@ fn terminal_key(workspace_id: &str, terminal_id: &str) -> String {
     format!("{workspace_id}:{terminal_id}")
 }
 
+fn record_recent_output(session: &TerminalSession, data: &str) {
+    let mut recent = session
+        .recent_output
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    recent.push_str(data);
+    if recent.len() > MAX_RECENT_OUTPUT_BYTES {
+        let mut cut = recent.len() - MAX_RECENT_OUTPUT_BYTES;
+        while !recent.is_char_boundary(cut) {
+            cut += 1;
+        }
+        recent.drain(..cut);
+    }
+}
+
+/// Recent output of every open terminal in the workspace, keyed by terminal id.
+pub(crate) async fn terminal_output_snapshot(
+    state: &AppState,
+    workspace_id: &str,
+) -> HashMap<String, String> {
+    let prefix = terminal_key(workspace_id, "");
+    let sessions = state.terminal_sessions.lock().await;
+    sessions
+        .iter()
+        .filter_map(|(key, session)| {
+            let terminal_id = key.strip_prefix(&prefix)?;
+            let recent = session
+                .recent_output
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            Some((terminal_id.to_string(), recent.clone()))
+        })
+        .collect()
+}
+
 fn is_terminal_closed_error(message: &str) -> bool {
     let lower = message.to_ascii_lowercase();
     lower.contains("broken pipe")
@@ -120,6 +160,7 @@ fn spawn_terminal_reader(
                         match std::str::from_utf8(&pending) {
                             Ok(decoded) => {
                                 if !decoded.is_empty() {
+                                    record_recent_output(&session, decoded);
                                     let payload = TerminalOutput {
                                         workspace_id: workspace_id.clone(),
                                         terminal_id: terminal_id.clone(),
@@ -143,6 +184,7 @@ fn spawn_terminal_reader(
                                 let chunk =
                                     String::from_utf8_lossy(&pending[..valid_up_to]).to_string();
                                 if !chunk.is_empty() {
+                                    record_recent_output(&session, &chunk);
                                     let payload = TerminalOutput {
                                         workspace_id: workspace_id.clone(),
                                         terminal_id: terminal_id.clone(),
@@ -256,6 +298,7 @@ pub(crate) async fn terminal_open(
         master: Mutex::new(pair.master),
         writer: Mutex::new(writer),
         child: Mutex::new(child),
+        recent_output: std::sync::Mutex::new(String::new()),
     });
     let session_id = session.id.clone();
 
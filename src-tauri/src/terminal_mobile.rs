@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::Serialize;
 use tauri::{AppHandle, State};
 
@@ -58,3 +60,10 @@ pub(crate) async fn terminal_close(
 ) -> Result<(), String> {
     Err(UNSUPPORTED_MESSAGE.to_string())
 }
+
+pub(crate) async fn terminal_output_snapshot(
+    _state: &AppState,
+    _workspace_id: &str,
+) -> HashMap<String, String> {
+    HashMap::new()
+}
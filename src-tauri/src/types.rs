@@ -28,6 +28,34 @@ pub(crate) struct GitFileDiff {
     pub(crate) old_image_mime: Option<String>,
     #[serde(rename = "newImageMime")]
     pub(crate) new_image_mime: Option<String>,
+    #[serde(default)]
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitStatusPageEntry {
+    pub(crate) path: String,
+    pub(crate) status: String,
+    pub(crate) staged: bool,
+    pub(crate) unstaged: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) additions: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) deletions: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GitStatusPage {
+    pub(crate) branch_name: String,
+    /// Changes whenever HEAD or the index changes, so clients can tell when a
+    /// previously fetched page is stale.
+    pub(crate) cache_key: String,
+    pub(crate) total: usize,
+    pub(crate) offset: usize,
+    pub(crate) files: Vec<GitStatusPageEntry>,
+    pub(crate) has_more: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -54,137 +82,1109 @@ pub(crate) struct GitCommitDiff {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct GitLogEntry {
-    pub(crate) sha: String,
-    pub(crate) summary: String,
-    pub(crate) author: String,
-    pub(crate) timestamp: i64,
+pub(crate) struct GitLogEntry {
+    pub(crate) sha: String,
+    pub(crate) summary: String,
+    pub(crate) author: String,
+    pub(crate) timestamp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitLogResponse {
+    pub(crate) total: usize,
+    pub(crate) entries: Vec<GitLogEntry>,
+    #[serde(default)]
+    pub(crate) ahead: usize,
+    #[serde(default)]
+    pub(crate) behind: usize,
+    #[serde(default, rename = "aheadEntries")]
+    pub(crate) ahead_entries: Vec<GitLogEntry>,
+    #[serde(default, rename = "behindEntries")]
+    pub(crate) behind_entries: Vec<GitLogEntry>,
+    #[serde(default)]
+    pub(crate) upstream: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubIssue {
+    pub(crate) number: u64,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    #[serde(rename = "updatedAt")]
+    pub(crate) updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubIssuesResponse {
+    pub(crate) total: usize,
+    pub(crate) issues: Vec<GitHubIssue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubIssueDetail {
+    pub(crate) number: u64,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    #[serde(default)]
+    pub(crate) state: String,
+    #[serde(default)]
+    pub(crate) body: String,
+    #[serde(default)]
+    pub(crate) author: Option<GitHubPullRequestAuthor>,
+    #[serde(default)]
+    pub(crate) comments: Vec<GitHubPullRequestComment>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubPullRequestAuthor {
+    pub(crate) login: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubPullRequest {
+    pub(crate) number: u64,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    #[serde(rename = "updatedAt")]
+    pub(crate) updated_at: String,
+    #[serde(rename = "createdAt")]
+    pub(crate) created_at: String,
+    pub(crate) body: String,
+    #[serde(rename = "headRefName")]
+    pub(crate) head_ref_name: String,
+    #[serde(rename = "baseRefName")]
+    pub(crate) base_ref_name: String,
+    #[serde(rename = "isDraft")]
+    pub(crate) is_draft: bool,
+    #[serde(default)]
+    pub(crate) author: Option<GitHubPullRequestAuthor>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubPullRequestsResponse {
+    pub(crate) total: usize,
+    #[serde(rename = "pullRequests")]
+    pub(crate) pull_requests: Vec<GitHubPullRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubPullRequestDiff {
+    pub(crate) path: String,
+    pub(crate) status: String,
+    pub(crate) diff: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubPullRequestComment {
+    pub(crate) id: u64,
+    #[serde(default)]
+    pub(crate) body: String,
+    #[serde(rename = "createdAt")]
+    pub(crate) created_at: String,
+    #[serde(default)]
+    pub(crate) url: String,
+    #[serde(default)]
+    pub(crate) author: Option<GitHubPullRequestAuthor>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageDay {
+    pub(crate) day: String,
+    pub(crate) input_tokens: i64,
+    pub(crate) cached_input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) total_tokens: i64,
+    #[serde(default)]
+    pub(crate) agent_time_ms: i64,
+    #[serde(default)]
+    pub(crate) agent_runs: i64,
+    #[serde(default)]
+    pub(crate) estimated_cost_usd: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageTotals {
+    pub(crate) last7_days_tokens: i64,
+    pub(crate) last30_days_tokens: i64,
+    pub(crate) average_daily_tokens: i64,
+    pub(crate) cache_hit_rate_percent: f64,
+    pub(crate) peak_day: Option<String>,
+    pub(crate) peak_day_tokens: i64,
+    #[serde(default)]
+    pub(crate) last7_days_cost_usd: f64,
+    #[serde(default)]
+    pub(crate) total_cost_usd: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageModel {
+    pub(crate) model: String,
+    pub(crate) tokens: i64,
+    pub(crate) share_percent: f64,
+    #[serde(default)]
+    pub(crate) estimated_cost_usd: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageModelCost {
+    pub(crate) model: String,
+    pub(crate) input_tokens: i64,
+    pub(crate) cached_input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) estimated_cost_usd: f64,
+    /// False when no pricing entry matched the model; its cost is reported as 0.
+    pub(crate) priced: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageWorkspaceCost {
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) workspace_name: Option<String>,
+    /// Workspace path when the session cwd maps to a known workspace,
+    /// otherwise the raw cwd (or empty when the session recorded none).
+    pub(crate) path: String,
+    pub(crate) tokens: i64,
+    pub(crate) estimated_cost_usd: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum UsageBudgetPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageBudgetStatus {
+    pub(crate) period: UsageBudgetPeriod,
+    /// Identifies the current period (`2026-10-18`, `2026-W42`, `2026-10`).
+    pub(crate) period_key: String,
+    pub(crate) limit_usd: f64,
+    pub(crate) spent_usd: f64,
+    pub(crate) exceeded: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageSnapshot {
+    pub(crate) updated_at: i64,
+    pub(crate) days: Vec<LocalUsageDay>,
+    pub(crate) totals: LocalUsageTotals,
+    #[serde(default)]
+    pub(crate) top_models: Vec<LocalUsageModel>,
+    #[serde(default)]
+    pub(crate) model_costs: Vec<LocalUsageModelCost>,
+    #[serde(default)]
+    pub(crate) workspace_costs: Vec<LocalUsageWorkspaceCost>,
+    #[serde(default)]
+    pub(crate) budgets: Vec<LocalUsageBudgetStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum UsageBreakdownGroup {
+    Workspace,
+    Session,
+    Cwd,
+}
+
+impl Default for UsageBreakdownGroup {
+    fn default() -> Self {
+        UsageBreakdownGroup::Workspace
+    }
+}
+
+/// Dates are local `YYYY-MM-DD` and inclusive; omitted bounds default to
+/// the 30 days ending today.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageBreakdownQuery {
+    #[serde(default)]
+    pub(crate) start_date: Option<String>,
+    #[serde(default)]
+    pub(crate) end_date: Option<String>,
+    #[serde(default)]
+    pub(crate) group_by: Option<UsageBreakdownGroup>,
+    #[serde(default)]
+    pub(crate) workspace_path: Option<String>,
+    #[serde(default)]
+    pub(crate) limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageBreakdownEntry {
+    /// Workspace id, session file path or cwd depending on the grouping.
+    pub(crate) key: String,
+    pub(crate) label: String,
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) cwd: Option<String>,
+    pub(crate) session_path: Option<String>,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) sessions: i64,
+    pub(crate) input_tokens: i64,
+    pub(crate) cached_input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) total_tokens: i64,
+    pub(crate) agent_time_ms: i64,
+    pub(crate) agent_runs: i64,
+    pub(crate) estimated_cost_usd: f64,
+    pub(crate) share_percent: f64,
+    pub(crate) first_day: Option<String>,
+    pub(crate) last_day: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageBreakdown {
+    pub(crate) updated_at: i64,
+    pub(crate) start_date: String,
+    pub(crate) end_date: String,
+    pub(crate) group_by: UsageBreakdownGroup,
+    pub(crate) total_tokens: i64,
+    pub(crate) total_cost_usd: f64,
+    /// Number of groups before `limit` was applied.
+    pub(crate) total_entries: usize,
+    pub(crate) entries: Vec<LocalUsageBreakdownEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum UsageExportFormat {
+    Csv,
+    Json,
+}
+
+impl Default for UsageExportFormat {
+    fn default() -> Self {
+        UsageExportFormat::Csv
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageExportQuery {
+    #[serde(default)]
+    pub(crate) start_date: Option<String>,
+    #[serde(default)]
+    pub(crate) end_date: Option<String>,
+    #[serde(default)]
+    pub(crate) workspace_path: Option<String>,
+    #[serde(default)]
+    pub(crate) format: Option<UsageExportFormat>,
+}
+
+/// One exported row: usage for a single day, model and workspace (or raw
+/// cwd when the session ran outside every known workspace).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageExportRow {
+    pub(crate) day: String,
+    pub(crate) model: String,
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) workspace_name: Option<String>,
+    pub(crate) path: String,
+    pub(crate) input_tokens: i64,
+    pub(crate) cached_input_tokens: i64,
+    pub(crate) output_tokens: i64,
+    pub(crate) total_tokens: i64,
+    pub(crate) estimated_cost_usd: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ThreadExportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl Default for ThreadExportFormat {
+    fn default() -> Self {
+        ThreadExportFormat::Markdown
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadExport {
+    pub(crate) format: ThreadExportFormat,
+    pub(crate) title: String,
+    /// Suggested file name, including the extension for `format`.
+    pub(crate) file_name: String,
+    pub(crate) content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadSearchQuery {
+    /// Words and `"quoted phrases"`; every one must appear in the thread.
+    #[serde(default)]
+    pub(crate) query: String,
+    #[serde(default)]
+    pub(crate) workspace_id: Option<String>,
+    #[serde(default)]
+    pub(crate) start_date: Option<String>,
+    #[serde(default)]
+    pub(crate) end_date: Option<String>,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadSearchSnippet {
+    pub(crate) role: String,
+    pub(crate) text: String,
+    #[serde(default)]
+    pub(crate) timestamp_ms: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadSearchResult {
+    pub(crate) thread_id: String,
+    pub(crate) title: String,
+    #[serde(default)]
+    pub(crate) workspace_id: Option<String>,
+    #[serde(default)]
+    pub(crate) workspace_name: Option<String>,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    pub(crate) models: Vec<String>,
+    #[serde(default)]
+    pub(crate) started_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) updated_at_ms: Option<i64>,
+    pub(crate) match_count: usize,
+    pub(crate) snippets: Vec<ThreadSearchSnippet>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadSearchResponse {
+    pub(crate) results: Vec<ThreadSearchResult>,
+    /// Matches found before `limit` was applied.
+    pub(crate) total_matches: usize,
+    pub(crate) indexed_threads: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalUsageExport {
+    pub(crate) format: UsageExportFormat,
+    pub(crate) start_date: String,
+    pub(crate) end_date: String,
+    pub(crate) row_count: usize,
+    /// Rendered CSV, or a JSON array of rows.
+    pub(crate) content: String,
+}
+
+/// Estimated USD rates per million tokens. `model` matches by prefix, so an
+/// entry for `gpt-5-codex` also prices dated snapshots of that model.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageModelPricing {
+    pub(crate) model: String,
+    pub(crate) input_per_million: f64,
+    #[serde(default)]
+    pub(crate) cached_input_per_million: f64,
+    pub(crate) output_per_million: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageBudgets {
+    #[serde(default)]
+    pub(crate) daily_usd: Option<f64>,
+    #[serde(default)]
+    pub(crate) weekly_usd: Option<f64>,
+    #[serde(default)]
+    pub(crate) monthly_usd: Option<f64>,
+}
+
+/// One position of a `prefix_rule` pattern: a literal argument or a list of
+/// accepted alternatives.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub(crate) enum RulePatternToken {
+    Literal(String),
+    Alternatives(Vec<String>),
+}
+
+/// A `match`/`not_match` example, written either as a shell string or argv.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub(crate) enum RuleExample {
+    Command(String),
+    Argv(Vec<String>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum RuleDecision {
+    Allow,
+    Prompt,
+    Forbidden,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRuleSpec {
+    pub(crate) pattern: Vec<RulePatternToken>,
+    pub(crate) decision: RuleDecision,
+    #[serde(default)]
+    pub(crate) justification: Option<String>,
+    #[serde(default, rename = "match")]
+    pub(crate) match_examples: Vec<RuleExample>,
+    #[serde(default, rename = "notMatch")]
+    pub(crate) not_match_examples: Vec<RuleExample>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRuleEntry {
+    pub(crate) index: usize,
+    pub(crate) line: usize,
+    #[serde(flatten)]
+    pub(crate) spec: ApprovalRuleSpec,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRulesFile {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    pub(crate) rules: Vec<ApprovalRuleEntry>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRuleMatch {
+    pub(crate) file: String,
+    pub(crate) index: usize,
+    pub(crate) line: usize,
+    pub(crate) decision: RuleDecision,
+    pub(crate) pattern: Vec<RulePatternToken>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApprovalRuleTestResult {
+    pub(crate) command: Vec<String>,
+    /// Most restrictive decision among matching rules, if any matched.
+    pub(crate) decision: Option<RuleDecision>,
+    pub(crate) matches: Vec<ApprovalRuleMatch>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum AutoApprovalRequestKind {
+    Exec,
+    ApplyPatch,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AutoApprovalDecision {
+    Accept,
+    Decline,
+}
+
+/// A backend-side approval rule. Every non-empty criterion must match; an
+/// empty list matches anything.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AutoApprovalRule {
+    #[serde(default)]
+    pub(crate) id: String,
+    pub(crate) name: String,
+    #[serde(default = "default_auto_approval_rule_enabled")]
+    pub(crate) enabled: bool,
+    pub(crate) decision: AutoApprovalDecision,
+    #[serde(default)]
+    pub(crate) kinds: Vec<AutoApprovalRequestKind>,
+    #[serde(default)]
+    pub(crate) workspace_ids: Vec<String>,
+    #[serde(default)]
+    pub(crate) thread_ids: Vec<String>,
+    /// Globs over each segment of the command line (`git status*`, `npm test`).
+    #[serde(default)]
+    pub(crate) commands: Vec<String>,
+    /// Globs over touched files, relative to the workspace root when inside it.
+    /// Exec requests are matched by their working directory.
+    #[serde(default)]
+    pub(crate) paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AutoApprovalPolicy {
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) rules: Vec<AutoApprovalRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AutoApprovalOutcome {
+    Accept,
+    Decline,
+    /// No rule matched; the request was left for the UI.
+    Prompt,
+}
+
+impl AutoApprovalOutcome {
+    /// The decision sent to the app-server, if the policy answered.
+    pub(crate) fn decision(self) -> Option<&'static str> {
+        match self {
+            AutoApprovalOutcome::Accept => Some("accept"),
+            AutoApprovalOutcome::Decline => Some("decline"),
+            AutoApprovalOutcome::Prompt => None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AutoApprovalRequest {
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) thread_id: Option<String>,
+    pub(crate) method: String,
+    #[serde(default)]
+    pub(crate) params: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AutoApprovalEvaluation {
+    pub(crate) outcome: AutoApprovalOutcome,
+    #[serde(default)]
+    pub(crate) kind: Option<AutoApprovalRequestKind>,
+    #[serde(default)]
+    pub(crate) command: Option<String>,
+    #[serde(default)]
+    pub(crate) paths: Vec<String>,
+    #[serde(default)]
+    pub(crate) rule_id: Option<String>,
+    #[serde(default)]
+    pub(crate) rule_name: Option<String>,
+}
+
+/// Emitted as `codex/autoApproval` when the policy answered a request. The
+/// decision itself is recorded in the agent audit log.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AutoApprovalEvent {
+    pub(crate) timestamp_ms: i64,
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) thread_id: Option<String>,
+    pub(crate) request_id: serde_json::Value,
+    pub(crate) method: String,
+    #[serde(flatten)]
+    pub(crate) evaluation: AutoApprovalEvaluation,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum AgentAuditKind {
+    TurnStarted,
+    TurnCompleted,
+    CommandExecuted,
+    FileChanged,
+    ApprovalRequested,
+    ApprovalDecided,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentAuditFileChange {
+    pub(crate) path: String,
+    /// `add`, `delete` or `update`.
+    pub(crate) kind: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentAuditEntry {
+    pub(crate) id: String,
+    pub(crate) timestamp_ms: i64,
+    pub(crate) workspace_id: String,
+    pub(crate) kind: AgentAuditKind,
+    #[serde(default)]
+    pub(crate) thread_id: Option<String>,
+    #[serde(default)]
+    pub(crate) turn_id: Option<String>,
+    #[serde(default)]
+    pub(crate) item_id: Option<String>,
+    #[serde(default)]
+    pub(crate) status: Option<String>,
+    #[serde(default)]
+    pub(crate) command: Option<String>,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+    #[serde(default)]
+    pub(crate) exit_code: Option<i64>,
+    #[serde(default)]
+    pub(crate) duration_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) files: Vec<AgentAuditFileChange>,
+    #[serde(default)]
+    pub(crate) method: Option<String>,
+    #[serde(default)]
+    pub(crate) request_id: Option<serde_json::Value>,
+    #[serde(default)]
+    pub(crate) decision: Option<String>,
+    /// `policy` or `user` for approval decisions.
+    #[serde(default)]
+    pub(crate) decided_by: Option<String>,
+    #[serde(default)]
+    pub(crate) rule_name: Option<String>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentAuditQuery {
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) thread_id: Option<String>,
+    /// Empty returns every kind.
+    #[serde(default)]
+    pub(crate) kinds: Vec<AgentAuditKind>,
+    #[serde(default)]
+    pub(crate) start_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) end_ms: Option<i64>,
+    /// Case-insensitive text matched against commands, paths, methods and errors.
+    #[serde(default)]
+    pub(crate) text: Option<String>,
+    #[serde(default)]
+    pub(crate) limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AgentAuditExport {
+    pub(crate) format: UsageExportFormat,
+    pub(crate) workspace_id: String,
+    pub(crate) entry_count: usize,
+    pub(crate) file_name: String,
+    /// Rendered CSV, or a JSON array of entries, oldest first.
+    pub(crate) content: String,
+}
+
+/// Context attached to a user message, resolved by the backend into turn input.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub(crate) enum TurnContextAttachment {
+    /// A workspace file, optionally limited to an inclusive 1-based line range.
+    File {
+        path: String,
+        #[serde(default)]
+        start_line: Option<usize>,
+        #[serde(default)]
+        end_line: Option<usize>,
+    },
+    /// Uncommitted changes in the workspace repository.
+    Diff,
+    Commit {
+        sha: String,
+    },
+    Issue {
+        number: u64,
+    },
+    PullRequest {
+        number: u64,
+    },
+    /// Recent output of one of the workspace's terminals.
+    Terminal {
+        terminal_id: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookConfig {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) url: String,
+    #[serde(default = "default_webhook_enabled")]
+    pub(crate) enabled: bool,
+    /// App-server methods to forward (`turn/completed`, `error`, ...), `*` for
+    /// everything, or a prefix wildcard such as `item/*`. Wildcards skip
+    /// streaming `*/delta` events, which must be listed by name. Approval
+    /// prompts are published as `approval/requested`.
+    pub(crate) events: Vec<String>,
+    /// Restricts deliveries to these workspaces; empty means all workspaces.
+    #[serde(default)]
+    pub(crate) workspace_ids: Vec<String>,
+    /// Shared secret for the `X-CodexMonitor-Signature` HMAC-SHA256 header.
+    #[serde(default)]
+    pub(crate) secret: Option<String>,
+    pub(crate) created_at_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookInput {
+    pub(crate) name: String,
+    pub(crate) url: String,
+    #[serde(default)]
+    pub(crate) enabled: Option<bool>,
+    pub(crate) events: Vec<String>,
+    #[serde(default)]
+    pub(crate) workspace_ids: Vec<String>,
+    /// New signing secret. Omitted on update, the stored secret is kept.
+    #[serde(default)]
+    pub(crate) secret: Option<String>,
+    /// Removes the stored secret on update; `secret` is ignored when set.
+    #[serde(default)]
+    pub(crate) clear_secret: bool,
+}
+
+/// What clients see of a webhook: the config without its secret.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookInfo {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) url: String,
+    pub(crate) enabled: bool,
+    pub(crate) events: Vec<String>,
+    pub(crate) workspace_ids: Vec<String>,
+    pub(crate) has_secret: bool,
+    pub(crate) created_at_ms: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WebhookDelivery {
+    pub(crate) id: String,
+    pub(crate) webhook_id: String,
+    pub(crate) webhook_name: String,
+    pub(crate) event: String,
+    pub(crate) workspace_id: String,
+    #[serde(default)]
+    pub(crate) thread_id: Option<String>,
+    pub(crate) status: WebhookDeliveryStatus,
+    #[serde(default)]
+    pub(crate) attempts: u32,
+    #[serde(default)]
+    pub(crate) response_status: Option<u16>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+    pub(crate) created_at_ms: i64,
+    #[serde(default)]
+    pub(crate) finished_at_ms: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ConfigIssueSeverity {
+    Error,
+    Warning,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct GitLogResponse {
-    pub(crate) total: usize,
-    pub(crate) entries: Vec<GitLogEntry>,
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigValidationIssue {
+    pub(crate) severity: ConfigIssueSeverity,
+    pub(crate) message: String,
+    /// Dotted key the issue is about, e.g. `mcp_servers.docs.command`.
     #[serde(default)]
-    pub(crate) ahead: usize,
+    pub(crate) key_path: Option<String>,
     #[serde(default)]
-    pub(crate) behind: usize,
-    #[serde(default, rename = "aheadEntries")]
-    pub(crate) ahead_entries: Vec<GitLogEntry>,
-    #[serde(default, rename = "behindEntries")]
-    pub(crate) behind_entries: Vec<GitLogEntry>,
+    pub(crate) line: Option<usize>,
     #[serde(default)]
-    pub(crate) upstream: Option<String>,
+    pub(crate) column: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct GitHubIssue {
-    pub(crate) number: u64,
-    pub(crate) title: String,
-    pub(crate) url: String,
-    #[serde(rename = "updatedAt")]
-    pub(crate) updated_at: String,
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigValidationReport {
+    /// False when any issue is an error; warnings alone keep the config valid.
+    pub(crate) valid: bool,
+    pub(crate) issues: Vec<ConfigValidationIssue>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct GitHubIssuesResponse {
-    pub(crate) total: usize,
-    pub(crate) issues: Vec<GitHubIssue>,
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ConfigDryRunResult {
+    pub(crate) ok: bool,
+    pub(crate) validation: ConfigValidationReport,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+    #[serde(default)]
+    pub(crate) stderr: Option<String>,
+    pub(crate) duration_ms: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct GitHubPullRequestAuthor {
-    pub(crate) login: String,
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScheduledRun {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) workspace_id: String,
+    pub(crate) prompt: String,
+    /// Five-field cron expression (`minute hour day month weekday`) in local time,
+    /// or one of `@hourly`, `@daily`, `@weekly`, `@monthly`.
+    pub(crate) cron: String,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+    #[serde(default)]
+    pub(crate) access_mode: Option<String>,
+    #[serde(default = "default_scheduled_run_enabled")]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) max_duration_minutes: Option<u32>,
+    pub(crate) created_at_ms: i64,
+    #[serde(default)]
+    pub(crate) next_run_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) last_run_at_ms: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct GitHubPullRequest {
-    pub(crate) number: u64,
-    pub(crate) title: String,
-    pub(crate) url: String,
-    #[serde(rename = "updatedAt")]
-    pub(crate) updated_at: String,
-    #[serde(rename = "createdAt")]
-    pub(crate) created_at: String,
-    pub(crate) body: String,
-    #[serde(rename = "headRefName")]
-    pub(crate) head_ref_name: String,
-    #[serde(rename = "baseRefName")]
-    pub(crate) base_ref_name: String,
-    #[serde(rename = "isDraft")]
-    pub(crate) is_draft: bool,
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScheduledRunInput {
+    pub(crate) name: String,
+    pub(crate) workspace_id: String,
+    pub(crate) prompt: String,
+    pub(crate) cron: String,
     #[serde(default)]
-    pub(crate) author: Option<GitHubPullRequestAuthor>,
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+    #[serde(default)]
+    pub(crate) access_mode: Option<String>,
+    #[serde(default)]
+    pub(crate) enabled: Option<bool>,
+    #[serde(default)]
+    pub(crate) max_duration_minutes: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ScheduledRunTrigger {
+    Schedule,
+    Manual,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum ScheduledRunStatus {
+    Running,
+    Completed,
+    Failed,
+    Interrupted,
+    TimedOut,
+    Skipped,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct GitHubPullRequestsResponse {
-    pub(crate) total: usize,
-    #[serde(rename = "pullRequests")]
-    pub(crate) pull_requests: Vec<GitHubPullRequest>,
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScheduledRunRecord {
+    pub(crate) run_id: String,
+    pub(crate) schedule_id: String,
+    pub(crate) schedule_name: String,
+    pub(crate) workspace_id: String,
+    pub(crate) trigger: ScheduledRunTrigger,
+    pub(crate) status: ScheduledRunStatus,
+    pub(crate) started_at_ms: i64,
+    #[serde(default)]
+    pub(crate) finished_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) thread_id: Option<String>,
+    #[serde(default)]
+    pub(crate) turn_id: Option<String>,
+    #[serde(default)]
+    pub(crate) approval_requests: u32,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+    /// Process that drives the run; used to tell orphaned runs from live ones.
+    #[serde(default)]
+    pub(crate) owner_pid: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchRunVariant {
+    #[serde(default)]
+    pub(crate) label: Option<String>,
+    #[serde(default)]
+    pub(crate) model: Option<String>,
+    #[serde(default)]
+    pub(crate) effort: Option<String>,
+    /// Extra instructions appended to the shared prompt for this variant.
+    #[serde(default)]
+    pub(crate) instructions: Option<String>,
+    #[serde(default)]
+    pub(crate) access_mode: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct GitHubPullRequestDiff {
-    pub(crate) path: String,
-    pub(crate) status: String,
-    pub(crate) diff: String,
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchRunInput {
+    pub(crate) workspace_id: String,
+    pub(crate) prompt: String,
+    pub(crate) variants: Vec<BatchRunVariant>,
+    #[serde(default)]
+    pub(crate) copy_agents_md: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum BatchRunEntryStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+    Interrupted,
+    /// Still running as far as the store knows, but its thread could not be
+    /// read (e.g. the worktree is not connected). Never persisted.
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum BatchRunStatus {
+    Running,
+    Completed,
+    Partial,
+    Failed,
+    Unknown,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub(crate) struct GitHubPullRequestComment {
-    pub(crate) id: u64,
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchRunEntry {
+    pub(crate) index: usize,
+    pub(crate) label: String,
+    pub(crate) variant: BatchRunVariant,
+    pub(crate) branch: String,
     #[serde(default)]
-    pub(crate) body: String,
-    #[serde(rename = "createdAt")]
-    pub(crate) created_at: String,
+    pub(crate) workspace_id: Option<String>,
     #[serde(default)]
-    pub(crate) url: String,
+    pub(crate) thread_id: Option<String>,
     #[serde(default)]
-    pub(crate) author: Option<GitHubPullRequestAuthor>,
+    pub(crate) turn_id: Option<String>,
+    pub(crate) status: BatchRunEntryStatus,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+    #[serde(default)]
+    pub(crate) finished_at_ms: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct LocalUsageDay {
-    pub(crate) day: String,
-    pub(crate) input_tokens: i64,
-    pub(crate) cached_input_tokens: i64,
-    pub(crate) output_tokens: i64,
-    pub(crate) total_tokens: i64,
+pub(crate) struct BatchRun {
+    pub(crate) id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) title: String,
+    pub(crate) prompt: String,
+    pub(crate) created_at_ms: i64,
+    /// Parent HEAD the variant worktrees branched from.
     #[serde(default)]
-    pub(crate) agent_time_ms: i64,
+    pub(crate) base_commit: Option<String>,
+    pub(crate) status: BatchRunStatus,
+    pub(crate) entries: Vec<BatchRunEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchRunDiffEntry {
+    pub(crate) index: usize,
+    pub(crate) label: String,
+    pub(crate) branch: String,
     #[serde(default)]
-    pub(crate) agent_runs: i64,
+    pub(crate) workspace_id: Option<String>,
+    pub(crate) status: BatchRunEntryStatus,
+    pub(crate) files_changed: usize,
+    pub(crate) additions: i64,
+    pub(crate) deletions: i64,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct LocalUsageTotals {
-    pub(crate) last7_days_tokens: i64,
-    pub(crate) last30_days_tokens: i64,
-    pub(crate) average_daily_tokens: i64,
-    pub(crate) cache_hit_rate_percent: f64,
-    pub(crate) peak_day: Option<String>,
-    pub(crate) peak_day_tokens: i64,
+pub(crate) struct BatchRunFileComparison {
+    pub(crate) path: String,
+    /// One slot per batch entry, in entry order; `None` when that variant left
+    /// the file untouched.
+    pub(crate) changes: Vec<Option<GitFileStatus>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct LocalUsageModel {
-    pub(crate) model: String,
-    pub(crate) tokens: i64,
-    pub(crate) share_percent: f64,
+pub(crate) struct BatchRunDiffSummary {
+    pub(crate) batch_id: String,
+    pub(crate) entries: Vec<BatchRunDiffEntry>,
+    pub(crate) files: Vec<BatchRunFileComparison>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PromptLibrarySettings {
+    pub(crate) id: String,
+    #[serde(default)]
+    pub(crate) name: String,
+    /// Git remote URL, or a local folder read in place when no ref is pinned.
+    pub(crate) source: String,
+    #[serde(default, rename = "ref")]
+    pub(crate) git_ref: Option<String>,
+    #[serde(default)]
+    pub(crate) subdir: Option<String>,
+    #[serde(default = "default_prompt_library_enabled")]
+    pub(crate) enabled: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct LocalUsageSnapshot {
-    pub(crate) updated_at: i64,
-    pub(crate) days: Vec<LocalUsageDay>,
-    pub(crate) totals: LocalUsageTotals,
+pub(crate) struct PromptLibraryStatus {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) source: String,
+    #[serde(default, rename = "ref")]
+    pub(crate) git_ref: Option<String>,
     #[serde(default)]
-    pub(crate) top_models: Vec<LocalUsageModel>,
+    pub(crate) prompts_dir: Option<String>,
+    #[serde(default)]
+    pub(crate) revision: Option<String>,
+    #[serde(default)]
+    pub(crate) last_synced_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -331,6 +1331,11 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) worktree_setup_script: Option<String>,
     #[serde(default, rename = "worktreesFolder")]
     pub(crate) worktrees_folder: Option<String>,
+    #[serde(default, rename = "sandboxProfiles")]
+    pub(crate) sandbox_profiles: Vec<SandboxProfile>,
+    /// Profile used when a turn is sent with the "current" access mode.
+    #[serde(default, rename = "defaultSandboxProfile")]
+    pub(crate) default_sandbox_profile: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -342,11 +1347,157 @@ pub(crate) struct LaunchScriptEntry {
     pub(crate) label: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum SandboxMode {
+    ReadOnly,
+    WorkspaceWrite,
+    DangerFullAccess,
+}
+
+impl Default for SandboxMode {
+    fn default() -> Self {
+        SandboxMode::WorkspaceWrite
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ApprovalPolicy {
+    Untrusted,
+    OnFailure,
+    OnRequest,
+    Never,
+}
+
+impl Default for ApprovalPolicy {
+    fn default() -> Self {
+        ApprovalPolicy::OnRequest
+    }
+}
+
+impl ApprovalPolicy {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ApprovalPolicy::Untrusted => "untrusted",
+            ApprovalPolicy::OnFailure => "on-failure",
+            ApprovalPolicy::OnRequest => "on-request",
+            ApprovalPolicy::Never => "never",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SandboxProfile {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) sandbox_mode: SandboxMode,
+    /// Writable in addition to the workspace itself; only used with
+    /// `workspace-write`. Relative paths resolve against the workspace.
+    #[serde(default)]
+    pub(crate) writable_roots: Vec<String>,
+    #[serde(default = "default_sandbox_network_access")]
+    pub(crate) network_access: bool,
+    #[serde(default)]
+    pub(crate) approval_policy: ApprovalPolicy,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct WorktreeSetupStatus {
     #[serde(rename = "shouldRun")]
     pub(crate) should_run: bool,
     pub(crate) script: Option<String>,
+    #[serde(default, rename = "lastRun")]
+    pub(crate) last_run: Option<WorktreeSetupRun>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WorktreeSetupRunState {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl WorktreeSetupRunState {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            WorktreeSetupRunState::Running => "running",
+            WorktreeSetupRunState::Succeeded => "succeeded",
+            WorktreeSetupRunState::Failed => "failed",
+            WorktreeSetupRunState::Cancelled => "cancelled",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorktreeSetupRun {
+    pub(crate) run_id: String,
+    pub(crate) workspace_id: String,
+    pub(crate) state: WorktreeSetupRunState,
+    pub(crate) attempt: u32,
+    pub(crate) script: String,
+    pub(crate) started_at_ms: i64,
+    #[serde(default)]
+    pub(crate) finished_at_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) duration_ms: Option<i64>,
+    #[serde(default)]
+    pub(crate) exit_code: Option<i32>,
+    #[serde(default)]
+    pub(crate) error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorktreeSetupLog {
+    pub(crate) content: String,
+    pub(crate) truncated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WorkspaceDoctorSeverity {
+    Ok,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum WorkspaceDoctorFix {
+    ClearGitRoot,
+    PruneWorktrees,
+    WriteSetupMarker,
+    RemoveSetupMarker,
+    RecordInterruptedSetupRun,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceDoctorFinding {
+    pub(crate) check: String,
+    pub(crate) severity: WorkspaceDoctorSeverity,
+    pub(crate) message: String,
+    #[serde(default)]
+    pub(crate) suggested_fix: Option<String>,
+    #[serde(default)]
+    pub(crate) fix: Option<WorkspaceDoctorFix>,
+    #[serde(default)]
+    pub(crate) fixed: bool,
+    #[serde(default)]
+    pub(crate) fix_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WorkspaceDoctorReport {
+    pub(crate) workspace_id: String,
+    pub(crate) workspace_name: String,
+    pub(crate) healthy: bool,
+    pub(crate) findings: Vec<WorkspaceDoctorFinding>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -648,6 +1799,12 @@ pub(crate) struct AppSettings {
     pub(crate) open_app_targets: Vec<OpenAppTarget>,
     #[serde(default = "default_selected_open_app_id", rename = "selectedOpenAppId")]
     pub(crate) selected_open_app_id: String,
+    #[serde(default = "default_usage_pricing", rename = "usagePricing")]
+    pub(crate) usage_pricing: Vec<UsageModelPricing>,
+    #[serde(default, rename = "usageBudgets")]
+    pub(crate) usage_budgets: UsageBudgets,
+    #[serde(default, rename = "promptLibraries")]
+    pub(crate) prompt_libraries: Vec<PromptLibrarySettings>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -711,6 +1868,26 @@ fn default_usage_show_remaining() -> bool {
     false
 }
 
+fn default_scheduled_run_enabled() -> bool {
+    true
+}
+
+fn default_auto_approval_rule_enabled() -> bool {
+    true
+}
+
+fn default_webhook_enabled() -> bool {
+    true
+}
+
+fn default_prompt_library_enabled() -> bool {
+    true
+}
+
+fn default_sandbox_network_access() -> bool {
+    true
+}
+
 fn default_show_message_file_path() -> bool {
     true
 }
@@ -1120,6 +2297,31 @@ fn default_selected_open_app_id() -> String {
     }
 }
 
+fn default_usage_pricing() -> Vec<UsageModelPricing> {
+    [
+        ("gpt-5.1-codex-mini", 0.25, 0.025, 2.0),
+        ("gpt-5.1-codex", 1.25, 0.125, 10.0),
+        ("gpt-5.1", 1.25, 0.125, 10.0),
+        ("gpt-5-codex", 1.25, 0.125, 10.0),
+        ("gpt-5-mini", 0.25, 0.025, 2.0),
+        ("gpt-5-nano", 0.05, 0.005, 0.4),
+        ("gpt-5", 1.25, 0.125, 10.0),
+        ("o3", 2.0, 0.5, 8.0),
+        ("o4-mini", 1.1, 0.275, 4.4),
+        ("gpt-4.1-mini", 0.4, 0.1, 1.6),
+        ("gpt-4.1-nano", 0.1, 0.025, 0.4),
+        ("gpt-4.1", 2.0, 0.5, 8.0),
+    ]
+    .into_iter()
+    .map(|(model, input, cached, output)| UsageModelPricing {
+        model: model.to_string(),
+        input_per_million: input,
+        cached_input_per_million: cached,
+        output_per_million: output,
+    })
+    .collect()
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -1199,6 +2401,9 @@ impl Default for AppSettings {
             global_worktrees_folder: None,
             open_app_targets: default_open_app_targets(),
             selected_open_app_id: default_selected_open_app_id(),
+            usage_pricing: default_usage_pricing(),
+            usage_budgets: UsageBudgets::default(),
+            prompt_libraries: Vec::new(),
         }
     }
 }
@@ -0,0 +1,118 @@
+use serde_json::json;
+use tauri::{AppHandle, Manager, State};
+
+use crate::remote_backend;
+use crate::shared::webhooks_core;
+use crate::state::AppState;
+use crate::types::{WebhookDelivery, WebhookInfo, WebhookInput};
+
+fn app_data_dir(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))
+}
+
+#[tauri::command]
+pub(crate) async fn webhooks_list(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WebhookInfo>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "webhooks_list", json!({})).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    webhooks_core::webhooks_list_core(&app_data_dir(&app)?)
+}
+
+#[tauri::command]
+pub(crate) async fn webhooks_create(
+    input: WebhookInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WebhookInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "webhooks_create", json!({ "input": input }))
+                .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    webhooks_core::webhooks_create_core(&app_data_dir(&app)?, input)
+}
+
+#[tauri::command]
+pub(crate) async fn webhooks_update(
+    id: String,
+    input: WebhookInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WebhookInfo, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "webhooks_update",
+            json!({ "id": id, "input": input }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    webhooks_core::webhooks_update_core(&app_data_dir(&app)?, id, input)
+}
+
+#[tauri::command]
+pub(crate) async fn webhooks_delete(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(&*state, app, "webhooks_delete", json!({ "id": id })).await?;
+        return Ok(());
+    }
+
+    webhooks_core::webhooks_delete_core(&app_data_dir(&app)?, id)
+}
+
+#[tauri::command]
+pub(crate) async fn webhook_deliveries(
+    webhook_id: Option<String>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WebhookDelivery>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "webhook_deliveries",
+            json!({ "webhookId": webhook_id, "limit": limit }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    webhooks_core::webhook_deliveries_core(
+        &app_data_dir(&app)?,
+        webhook_id,
+        limit.map(|value| value as usize),
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn webhooks_test(
+    id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WebhookDelivery, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response =
+            remote_backend::call_remote(&*state, app, "webhooks_test", json!({ "id": id })).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    webhooks_core::webhooks_test_core(&app_data_dir(&app)?, id).await
+}
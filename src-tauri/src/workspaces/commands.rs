@@ -2,7 +2,8 @@ use std::path::PathBuf;
 
 use std::sync::Arc;
 
-use tauri::{AppHandle, Manager, State};
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager, State};
 
 use super::files::{list_workspace_files_inner, read_workspace_file_inner, WorkspaceFileResponse};
 use super::git::{
@@ -17,12 +18,20 @@ use super::worktree::{
 };
 
 use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::AppServerEvent;
 use crate::codex::spawn_workspace_session;
+use crate::event_sink::TauriEventSink;
 use crate::git_utils::resolve_git_root;
 use crate::remote_backend;
-use crate::shared::{workspace_rpc, workspaces_core};
+use crate::shared::workspace_bundle_core::{
+    self, WorkspaceBundle, WorkspaceBundleImportOptions, WorkspaceBundleImportSummary,
+};
+use crate::shared::{batch_runs_core, workspace_rpc, workspaces_core};
 use crate::state::AppState;
-use crate::types::{WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus};
+use crate::types::{
+    BatchRun, BatchRunDiffSummary, BatchRunInput, WorkspaceDoctorReport, WorkspaceEntry,
+    WorkspaceInfo, WorkspaceSettings, WorktreeSetupLog, WorktreeSetupRun, WorktreeSetupStatus,
+};
 
 fn spawn_with_app(
     app: &AppHandle,
@@ -321,7 +330,13 @@ pub(crate) async fn worktree_setup_status(
         .path()
         .app_data_dir()
         .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
-    workspaces_core::worktree_setup_status_core(&state.workspaces, &workspace_id, &data_dir).await
+    workspaces_core::worktree_setup_status_core(
+        &state.workspaces,
+        &state.worktree_setup_jobs,
+        &workspace_id,
+        &data_dir,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -349,6 +364,365 @@ pub(crate) async fn worktree_setup_mark_ran(
     workspaces_core::worktree_setup_mark_ran_core(&state.workspaces, &workspace_id, &data_dir).await
 }
 
+#[tauri::command]
+pub(crate) async fn worktree_setup_run(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorktreeSetupRun, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_setup_run",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    workspaces_core::worktree_setup_run_core(
+        &state.workspaces,
+        &state.worktree_setup_jobs,
+        &workspace_id,
+        &data_dir,
+        TauriEventSink::new(app.clone()),
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn worktree_setup_retry(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorktreeSetupRun, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_setup_retry",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    workspaces_core::worktree_setup_retry_core(
+        &state.workspaces,
+        &state.worktree_setup_jobs,
+        &workspace_id,
+        &data_dir,
+        TauriEventSink::new(app.clone()),
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn worktree_setup_cancel(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_setup_cancel",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    workspaces_core::worktree_setup_cancel_core(&state.worktree_setup_jobs, &workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn worktree_setup_log(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorktreeSetupLog, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceIdRequest { workspace_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_setup_log",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    workspaces_core::worktree_setup_log_core(&state.workspaces, &workspace_id, &data_dir).await
+}
+
+#[tauri::command]
+pub(crate) async fn batch_runs_start(
+    input: BatchRunInput,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BatchRun, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::BatchRunStartRequest { input };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "batch_runs_start",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let copy_agents_md = input.copy_agents_md;
+    batch_runs_core::batch_runs_start_core(
+        &data_dir,
+        &state.sessions,
+        &state.workspaces,
+        input,
+        |parent_id, branch, name| {
+            add_worktree(
+                parent_id,
+                branch,
+                name,
+                copy_agents_md,
+                state.clone(),
+                app.clone(),
+            )
+        },
+        |workspace_id, thread_id| {
+            let _ = app.emit(
+                "app-server-event",
+                AppServerEvent {
+                    workspace_id: workspace_id.to_string(),
+                    message: json!({
+                        "method": "codex/backgroundThread",
+                        "params": {
+                            "threadId": thread_id,
+                            "action": "hide"
+                        }
+                    }),
+                },
+            );
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn batch_runs_list(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<BatchRun>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "batch_runs_list",
+            workspace_remote_empty_params(),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    batch_runs_core::batch_runs_list_core(&data_dir)
+}
+
+#[tauri::command]
+pub(crate) async fn batch_runs_status(
+    batch_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BatchRun, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::BatchRunIdRequest { batch_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "batch_runs_status",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    batch_runs_core::batch_runs_status_core(&data_dir, &state.sessions, batch_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn batch_runs_diff_summary(
+    batch_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<BatchRunDiffSummary, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::BatchRunIdRequest { batch_id };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "batch_runs_diff_summary",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    batch_runs_core::batch_runs_diff_summary_core(&data_dir, &state.workspaces, batch_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn batch_runs_delete(
+    batch_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::BatchRunIdRequest { batch_id };
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "batch_runs_delete",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    batch_runs_core::batch_runs_delete_core(&data_dir, batch_id)
+}
+
+#[tauri::command]
+pub(crate) async fn workspace_doctor(
+    workspace_id: Option<String>,
+    apply_fixes: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceDoctorReport>, String> {
+    let apply_fixes = apply_fixes.unwrap_or(false);
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::WorkspaceDoctorRequest {
+            workspace_id,
+            apply_fixes,
+        };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "workspace_doctor",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    workspaces_core::workspace_doctor_core(
+        &state.workspaces,
+        &state.sessions,
+        &state.worktree_setup_jobs,
+        &state.storage_path,
+        &data_dir,
+        workspace_id,
+        apply_fixes,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn export_workspace_bundle(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceBundle, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "export_workspace_bundle",
+            workspace_remote_empty_params(),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspace_bundle_core::export_workspace_bundle_core(
+        &state.workspaces,
+        &state.app_settings,
+        &state.settings_path,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn import_workspace_bundle(
+    bundle: WorkspaceBundle,
+    options: Option<WorkspaceBundleImportOptions>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceBundleImportSummary, String> {
+    let options = options.unwrap_or_default();
+    if remote_backend::is_remote_mode(&*state).await {
+        let request = workspace_rpc::ImportWorkspaceBundleRequest { bundle, options };
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "import_workspace_bundle",
+            workspace_remote_params(&request)?,
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    workspace_bundle_core::import_workspace_bundle_core(
+        bundle,
+        options,
+        &state.workspaces,
+        &state.app_settings,
+        &state.storage_path,
+        &state.settings_path,
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn remove_workspace(
     id: String,
@@ -62,7 +62,7 @@ const MAX_WORKSPACE_FILE_BYTES: u64 = 400_000;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub(crate) struct WorkspaceFileResponse {
-    content: String,
+    pub(crate) content: String,
     truncated: bool,
 }
 
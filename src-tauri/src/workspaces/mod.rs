@@ -6,6 +6,7 @@ mod settings;
 mod worktree;
 
 pub(crate) use commands::*;
+pub(crate) use files::read_workspace_file_inner;
 
 #[cfg(test)]
 mod tests;
@@ -59,6 +59,8 @@ fn workspace_with_id_and_kind(
             launch_scripts: None,
             worktree_setup_script: None,
             worktrees_folder: None,
+            sandbox_profiles: Vec::new(),
+            default_sandbox_profile: None,
         },
     }
 }
@@ -17,6 +17,7 @@ import "./styles/approval-toasts.css";
 import "./styles/error-toasts.css";
 import "./styles/request-user-input.css";
 import "./styles/update-toasts.css";
+import "./styles/worktree-setup-toasts.css";
 import "./styles/composer.css";
 import "./styles/review-inline.css";
 import "./styles/diff.css";
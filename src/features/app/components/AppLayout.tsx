@@ -21,6 +21,7 @@ type AppLayoutProps = {
   approvalToastsNode: ReactNode;
   updateToastNode: ReactNode;
   errorToastsNode: ReactNode;
+  worktreeSetupToastsNode: ReactNode;
   homeNode: ReactNode;
   mainHeaderNode: ReactNode;
   desktopTopbarLeftNode: ReactNode;
@@ -60,6 +61,7 @@ export const AppLayout = memo(function AppLayout({
   approvalToastsNode,
   updateToastNode,
   errorToastsNode,
+  worktreeSetupToastsNode,
   homeNode,
   mainHeaderNode,
   desktopTopbarLeftNode,
@@ -86,6 +88,7 @@ export const AppLayout = memo(function AppLayout({
         approvalToastsNode={approvalToastsNode}
         updateToastNode={updateToastNode}
         errorToastsNode={errorToastsNode}
+        worktreeSetupToastsNode={worktreeSetupToastsNode}
         tabBarNode={tabBarNode}
         homeNode={homeNode}
         sidebarNode={sidebarNode}
@@ -113,6 +116,7 @@ export const AppLayout = memo(function AppLayout({
         approvalToastsNode={approvalToastsNode}
         updateToastNode={updateToastNode}
         errorToastsNode={errorToastsNode}
+        worktreeSetupToastsNode={worktreeSetupToastsNode}
         homeNode={homeNode}
         showHome={showHome}
         showWorkspace={activeWorkspace && !showHome}
@@ -136,6 +140,7 @@ export const AppLayout = memo(function AppLayout({
       updateToastNode={updateToastNode}
       approvalToastsNode={approvalToastsNode}
       errorToastsNode={errorToastsNode}
+      worktreeSetupToastsNode={worktreeSetupToastsNode}
       homeNode={homeNode}
       showHome={showHome}
       showWorkspace={activeWorkspace && !showHome}
@@ -866,9 +866,6 @@ export default function MainApp() {
   });
 
   const worktreeSetupScriptState = useWorktreeSetupScript({
-    ensureTerminalWithTitle,
-    restartTerminalSession,
-    openTerminal,
     onDebug: addDebugEntry,
   });
 
@@ -1777,6 +1774,10 @@ export default function MainApp() {
     dismissPostUpdateNotice,
     errorToasts,
     dismissErrorToast,
+    worktreeSetupJobs: worktreeSetupScriptState.setupJobs,
+    retryWorktreeSetup: worktreeSetupScriptState.retryWorktreeSetup,
+    cancelWorktreeSetup: worktreeSetupScriptState.cancelWorktreeSetup,
+    dismissWorktreeSetup: worktreeSetupScriptState.dismissWorktreeSetup,
     showDebugButton,
     handleDebugClick,
   });
@@ -1788,6 +1789,7 @@ export default function MainApp() {
     approvalToastsNode,
     updateToastNode,
     errorToastsNode,
+    worktreeSetupToastsNode,
     homeNode,
     mainHeaderNode,
     desktopTopbarLeftNode,
@@ -1850,6 +1852,7 @@ export default function MainApp() {
       approvalToastsNode,
       updateToastNode,
       errorToastsNode,
+      worktreeSetupToastsNode,
       homeNode,
       mainHeaderNode,
       tabletNavNode,
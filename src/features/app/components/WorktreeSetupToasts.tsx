@@ -0,0 +1,134 @@
+import { useMemo } from "react";
+import type { WorkspaceInfo } from "../../../types";
+import type { WorktreeSetupJob } from "../hooks/useWorktreeSetupScript";
+import {
+  ToastActions,
+  ToastBody,
+  ToastCard,
+  ToastError,
+  ToastHeader,
+  ToastTitle,
+  ToastViewport,
+} from "../../design-system/components/toast/ToastPrimitives";
+
+type WorktreeSetupToastsProps = {
+  jobs: WorktreeSetupJob[];
+  workspaces: WorkspaceInfo[];
+  onRetry: (workspaceId: string) => void;
+  onCancel: (workspaceId: string) => void;
+  onDismiss: (workspaceId: string) => void;
+};
+
+const STATE_LABELS: Record<WorktreeSetupJob["run"]["state"], string> = {
+  running: "Running",
+  succeeded: "Succeeded",
+  failed: "Failed",
+  cancelled: "Cancelled",
+};
+
+function formatDuration(durationMs: number | null) {
+  if (durationMs === null) {
+    return null;
+  }
+  return durationMs < 1000
+    ? `${durationMs} ms`
+    : `${(durationMs / 1000).toFixed(1)} s`;
+}
+
+export function WorktreeSetupToasts({
+  jobs,
+  workspaces,
+  onRetry,
+  onCancel,
+  onDismiss,
+}: WorktreeSetupToastsProps) {
+  const workspaceLabels = useMemo(
+    () => new Map(workspaces.map((workspace) => [workspace.id, workspace.name])),
+    [workspaces],
+  );
+
+  if (!jobs.length) {
+    return null;
+  }
+
+  return (
+    <ToastViewport
+      className="worktree-setup-toasts"
+      role="region"
+      ariaLive="polite"
+    >
+      {jobs.map((job) => {
+        const { run } = job;
+        const running = run.state === "running";
+        const duration = formatDuration(run.durationMs);
+        const meta = [
+          STATE_LABELS[run.state],
+          run.attempt > 1 ? `attempt ${run.attempt}` : null,
+          run.exitCode !== null ? `exit ${run.exitCode}` : null,
+          duration,
+        ]
+          .filter(Boolean)
+          .join(" · ");
+        return (
+          <ToastCard
+            key={job.workspaceId}
+            className={`worktree-setup-toast is-${run.state}`}
+            role="status"
+          >
+            <ToastHeader className="worktree-setup-toast-header">
+              <ToastTitle className="worktree-setup-toast-title">
+                Setup · {workspaceLabels.get(job.workspaceId) ?? job.workspaceId}
+              </ToastTitle>
+              <div className="worktree-setup-toast-state">{meta}</div>
+            </ToastHeader>
+            <ToastBody className="worktree-setup-toast-body">
+              {job.logTruncated ? (
+                <div className="worktree-setup-toast-note">
+                  Showing the end of the setup log.
+                </div>
+              ) : null}
+              <pre className="worktree-setup-toast-output">
+                {job.output || (running ? "Waiting for output…" : "No output.")}
+              </pre>
+            </ToastBody>
+            {job.error ? (
+              <ToastError className="worktree-setup-toast-error">
+                {job.error}
+              </ToastError>
+            ) : null}
+            <ToastActions className="worktree-setup-toast-actions">
+              {running ? (
+                <button
+                  type="button"
+                  className="secondary"
+                  onClick={() => onCancel(job.workspaceId)}
+                >
+                  Cancel
+                </button>
+              ) : (
+                <>
+                  <button
+                    type="button"
+                    className="secondary"
+                    onClick={() => onDismiss(job.workspaceId)}
+                  >
+                    Dismiss
+                  </button>
+                  {run.state !== "succeeded" ? (
+                    <button
+                      type="button"
+                      className="primary"
+                      onClick={() => onRetry(job.workspaceId)}
+                    >
+                      Retry
+                    </button>
+                  ) : null}
+                </>
+              )}
+            </ToastActions>
+          </ToastCard>
+        );
+      })}
+    </ToastViewport>
+  );
+}
@@ -223,6 +223,10 @@ type UseMainAppLayoutSurfacesArgs = {
   dismissPostUpdateNotice: LayoutNodesOptions["primary"]["updateToastProps"]["onDismissPostUpdateNotice"];
   errorToasts: LayoutNodesOptions["primary"]["errorToastsProps"]["toasts"];
   dismissErrorToast: LayoutNodesOptions["primary"]["errorToastsProps"]["onDismiss"];
+  worktreeSetupJobs: LayoutNodesOptions["primary"]["worktreeSetupToastsProps"]["jobs"];
+  retryWorktreeSetup: LayoutNodesOptions["primary"]["worktreeSetupToastsProps"]["onRetry"];
+  cancelWorktreeSetup: LayoutNodesOptions["primary"]["worktreeSetupToastsProps"]["onCancel"];
+  dismissWorktreeSetup: LayoutNodesOptions["primary"]["worktreeSetupToastsProps"]["onDismiss"];
   showDebugButton: boolean;
   handleDebugClick: () => void;
 };
@@ -372,6 +376,10 @@ function buildPrimarySurface({
   dismissPostUpdateNotice,
   errorToasts,
   dismissErrorToast,
+  worktreeSetupJobs,
+  retryWorktreeSetup,
+  cancelWorktreeSetup,
+  dismissWorktreeSetup,
   showDebugButton,
   handleDebugClick,
 }: MainAppLayoutSurfacesContext): LayoutNodesOptions["primary"] {
@@ -580,6 +588,13 @@ function buildPrimarySurface({
       toasts: errorToasts,
       onDismiss: dismissErrorToast,
     },
+    worktreeSetupToastsProps: {
+      jobs: worktreeSetupJobs,
+      workspaces,
+      onRetry: retryWorktreeSetup,
+      onCancel: cancelWorktreeSetup,
+      onDismiss: dismissWorktreeSetup,
+    },
     homeProps: {
       onAddWorkspace: handleAddWorkspace,
       onAddWorkspaceFromUrl: openWorkspaceFromUrlPrompt,
@@ -1096,6 +1111,10 @@ export function useMainAppLayoutSurfaces({
   dismissPostUpdateNotice,
   errorToasts,
   dismissErrorToast,
+  worktreeSetupJobs,
+  retryWorktreeSetup,
+  cancelWorktreeSetup,
+  dismissWorktreeSetup,
   showDebugButton,
   handleDebugClick,
 }: UseMainAppLayoutSurfacesArgs): LayoutNodesOptions {
@@ -1258,6 +1277,10 @@ export function useMainAppLayoutSurfaces({
     dismissPostUpdateNotice,
     errorToasts,
     dismissErrorToast,
+    worktreeSetupJobs,
+    retryWorktreeSetup,
+    cancelWorktreeSetup,
+    dismissWorktreeSetup,
     showDebugButton,
     handleDebugClick,
     sidebarRateLimits,
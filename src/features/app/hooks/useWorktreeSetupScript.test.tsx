@@ -0,0 +1,178 @@
+// @vitest-environment jsdom
+import { act, renderHook, waitFor } from "@testing-library/react";
+import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
+import type { AppServerEvent, WorkspaceInfo } from "../../../types";
+import {
+  cancelWorktreeSetup,
+  getWorktreeSetupLog,
+  getWorktreeSetupStatus,
+  retryWorktreeSetup,
+  runWorktreeSetup,
+  type WorktreeSetupRun,
+} from "../../../services/tauri";
+import { subscribeAppServerEvents } from "../../../services/events";
+import { useWorktreeSetupScript } from "./useWorktreeSetupScript";
+
+vi.mock("../../../services/tauri", () => ({
+  cancelWorktreeSetup: vi.fn(),
+  getWorktreeSetupLog: vi.fn(),
+  getWorktreeSetupStatus: vi.fn(),
+  retryWorktreeSetup: vi.fn(),
+  runWorktreeSetup: vi.fn(),
+}));
+
+vi.mock("../../../services/events", () => ({
+  subscribeAppServerEvents: vi.fn(),
+}));
+
+const worktree: WorkspaceInfo = {
+  id: "wt-1",
+  name: "feature",
+  path: "/tmp/wt-1",
+  connected: true,
+  kind: "worktree",
+  settings: { sidebarCollapsed: false },
+};
+
+const runningRun: WorktreeSetupRun = {
+  runId: "run-1",
+  workspaceId: "wt-1",
+  state: "running",
+  attempt: 1,
+  script: "npm install",
+  startedAtMs: 1,
+  finishedAtMs: null,
+  durationMs: null,
+  exitCode: null,
+  error: null,
+};
+
+let listener: ((event: AppServerEvent) => void) | null = null;
+
+beforeEach(() => {
+  listener = null;
+  vi.mocked(subscribeAppServerEvents).mockImplementation((cb) => {
+    listener = cb;
+    return vi.fn();
+  });
+  vi.mocked(getWorktreeSetupStatus).mockResolvedValue({
+    shouldRun: true,
+    script: "npm install",
+  });
+  vi.mocked(runWorktreeSetup).mockResolvedValue(runningRun);
+});
+
+afterEach(() => {
+  vi.clearAllMocks();
+});
+
+function emit(method: string, params: Record<string, unknown>) {
+  act(() => {
+    listener?.({ workspace_id: "wt-1", message: { method, params } });
+  });
+}
+
+describe("useWorktreeSetupScript", () => {
+  it("skips main workspaces and worktrees that already ran", async () => {
+    const { result } = renderHook(() => useWorktreeSetupScript({}));
+
+    await act(async () => {
+      await result.current.maybeRunWorktreeSetupScript({
+        ...worktree,
+        kind: "main",
+      });
+    });
+    vi.mocked(getWorktreeSetupStatus).mockResolvedValueOnce({
+      shouldRun: false,
+      script: "npm install",
+    });
+    await act(async () => {
+      await result.current.maybeRunWorktreeSetupScript(worktree);
+    });
+
+    expect(runWorktreeSetup).not.toHaveBeenCalled();
+    expect(result.current.setupJobs).toEqual([]);
+  });
+
+  it("starts a backend job and streams its output", async () => {
+    const { result } = renderHook(() => useWorktreeSetupScript({}));
+
+    await act(async () => {
+      await result.current.maybeRunWorktreeSetupScript(worktree);
+    });
+    expect(runWorktreeSetup).toHaveBeenCalledWith("wt-1");
+    expect(result.current.setupJobs).toHaveLength(1);
+
+    emit("worktree/setupOutput", {
+      workspaceId: "wt-1",
+      runId: "run-1",
+      stream: "stdout",
+      data: "added 12 packages\n",
+    });
+    emit("worktree/setupOutput", {
+      workspaceId: "wt-1",
+      runId: "stale-run",
+      stream: "stdout",
+      data: "ignored\n",
+    });
+
+    expect(result.current.setupJobs[0].output).toBe("added 12 packages\n");
+  });
+
+  it("loads the persisted log when the run finishes", async () => {
+    vi.mocked(getWorktreeSetupLog).mockResolvedValue({
+      content: "$ npm install\nboom\n",
+      truncated: false,
+    });
+    const { result } = renderHook(() => useWorktreeSetupScript({}));
+
+    await act(async () => {
+      await result.current.maybeRunWorktreeSetupScript(worktree);
+    });
+    emit("worktree/setupFinished", {
+      workspaceId: "wt-1",
+      run: { ...runningRun, state: "failed", exitCode: 1 },
+    });
+
+    await waitFor(() => {
+      expect(result.current.setupJobs[0].output).toBe("$ npm install\nboom\n");
+    });
+    expect(result.current.setupJobs[0].run.state).toBe("failed");
+    expect(getWorktreeSetupLog).toHaveBeenCalledWith("wt-1");
+  });
+
+  it("retries, cancels and dismisses through the job api", async () => {
+    vi.mocked(retryWorktreeSetup).mockResolvedValue({
+      ...runningRun,
+      runId: "run-2",
+      attempt: 2,
+    });
+    vi.mocked(cancelWorktreeSetup).mockResolvedValue(undefined);
+    const { result } = renderHook(() => useWorktreeSetupScript({}));
+
+    emit("worktree/setupStarted", { workspaceId: "wt-1", run: runningRun });
+    await act(async () => {
+      await result.current.cancelWorktreeSetup("wt-1");
+    });
+    expect(cancelWorktreeSetup).toHaveBeenCalledWith("wt-1");
+
+    act(() => {
+      result.current.dismissWorktreeSetup("wt-1");
+    });
+    expect(result.current.setupJobs).toHaveLength(1);
+
+    await act(async () => {
+      await result.current.retryWorktreeSetup("wt-1");
+    });
+    expect(result.current.setupJobs[0].run.attempt).toBe(2);
+
+    emit("worktree/setupFinished", {
+      workspaceId: "wt-1",
+      run: { ...runningRun, runId: "run-2", state: "cancelled" },
+    });
+    act(() => {
+      result.current.dismissWorktreeSetup("wt-1");
+    });
+    expect(result.current.setupJobs).toEqual([]);
+  });
+});
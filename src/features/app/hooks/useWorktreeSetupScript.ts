@@ -1,84 +1,222 @@
-import { useCallback, useRef } from "react";
+import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import type { DebugEntry, WorkspaceInfo } from "../../../types";
 import { buildErrorDebugEntry } from "../../../utils/debugEntries";
+import { subscribeAppServerEvents } from "../../../services/events";
 import {
+  cancelWorktreeSetup as cancelWorktreeSetupService,
+  getWorktreeSetupLog,
   getWorktreeSetupStatus,
-  markWorktreeSetupRan,
-  openTerminalSession,
-  writeTerminalSession,
+  retryWorktreeSetup as retryWorktreeSetupService,
+  runWorktreeSetup,
+  type WorktreeSetupRun,
 } from "../../../services/tauri";
 
-const WORKTREE_SETUP_TERMINAL_ID = "worktree-setup";
-const WORKTREE_SETUP_TERMINAL_TITLE = "Setup";
-const DEFAULT_TERMINAL_COLS = 120;
-const DEFAULT_TERMINAL_ROWS = 32;
+const MAX_SETUP_OUTPUT_CHARS = 64 * 1024;
+
+export type WorktreeSetupJob = {
+  workspaceId: string;
+  run: WorktreeSetupRun;
+  output: string;
+  logTruncated: boolean;
+  error: string | null;
+};
 
 type UseWorktreeSetupScriptOptions = {
-  ensureTerminalWithTitle: (workspaceId: string, terminalId: string, title: string) => string;
-  restartTerminalSession: (workspaceId: string, terminalId: string) => Promise<void>;
-  openTerminal: () => void;
   onDebug?: (entry: DebugEntry) => void;
 };
 
 export type WorktreeSetupScriptState = {
+  setupJobs: WorktreeSetupJob[];
   maybeRunWorktreeSetupScript: (worktree: WorkspaceInfo) => Promise<void>;
+  retryWorktreeSetup: (workspaceId: string) => Promise<void>;
+  cancelWorktreeSetup: (workspaceId: string) => Promise<void>;
+  dismissWorktreeSetup: (workspaceId: string) => void;
 };
 
+function appendOutput(output: string, chunk: string) {
+  const next = output + chunk;
+  return next.length > MAX_SETUP_OUTPUT_CHARS
+    ? next.slice(next.length - MAX_SETUP_OUTPUT_CHARS)
+    : next;
+}
+
+function asSetupRun(value: unknown): WorktreeSetupRun | null {
+  if (!value || typeof value !== "object") {
+    return null;
+  }
+  const run = value as WorktreeSetupRun;
+  return typeof run.runId === "string" ? run : null;
+}
+
 export function useWorktreeSetupScript({
-  ensureTerminalWithTitle,
-  restartTerminalSession,
-  openTerminal,
   onDebug,
 }: UseWorktreeSetupScriptOptions): WorktreeSetupScriptState {
-  const runningRef = useRef<Set<string>>(new Set());
+  const [jobs, setJobs] = useState<Record<string, WorktreeSetupJob>>({});
+  const startingRef = useRef<Set<string>>(new Set());
+
+  const upsertRun = useCallback((run: WorktreeSetupRun) => {
+    setJobs((prev) => {
+      const existing = prev[run.workspaceId];
+      const sameRun = existing?.run.runId === run.runId;
+      return {
+        ...prev,
+        [run.workspaceId]: {
+          workspaceId: run.workspaceId,
+          run,
+          output: sameRun ? existing.output : "",
+          logTruncated: sameRun ? existing.logTruncated : false,
+          error: run.error,
+        },
+      };
+    });
+  }, []);
+
+  const loadLog = useCallback(
+    async (workspaceId: string, runId: string) => {
+      try {
+        const log = await getWorktreeSetupLog(workspaceId);
+        setJobs((prev) => {
+          const existing = prev[workspaceId];
+          if (!existing || existing.run.runId !== runId) {
+            return prev;
+          }
+          return {
+            ...prev,
+            [workspaceId]: {
+              ...existing,
+              output: log.content,
+              logTruncated: log.truncated,
+            },
+          };
+        });
+      } catch (error) {
+        onDebug?.(buildErrorDebugEntry("worktree setup log error", error));
+      }
+    },
+    [onDebug],
+  );
+
+  useEffect(() => {
+    return subscribeAppServerEvents((event) => {
+      const method = event.message.method;
+      const params = event.message.params as
+        | Record<string, unknown>
+        | undefined;
+      if (typeof method !== "string" || !params) {
+        return;
+      }
+      if (method === "worktree/setupStarted") {
+        const run = asSetupRun(params.run);
+        if (run) {
+          upsertRun(run);
+        }
+        return;
+      }
+      if (method === "worktree/setupOutput") {
+        const runId = params.runId;
+        const data = params.data;
+        if (typeof runId !== "string" || typeof data !== "string") {
+          return;
+        }
+        setJobs((prev) => {
+          const existing = prev[event.workspace_id];
+          if (!existing || existing.run.runId !== runId) {
+            return prev;
+          }
+          return {
+            ...prev,
+            [event.workspace_id]: {
+              ...existing,
+              output: appendOutput(existing.output, data),
+            },
+          };
+        });
+        return;
+      }
+      if (method === "worktree/setupFinished") {
+        const run = asSetupRun(params.run);
+        if (run) {
+          upsertRun(run);
+          // The persisted log is authoritative; it also covers output emitted
+          // before this window subscribed.
+          void loadLog(run.workspaceId, run.runId);
+        }
+      }
+    });
+  }, [loadLog, upsertRun]);
 
   const maybeRunWorktreeSetupScript = useCallback(
     async (worktree: WorkspaceInfo) => {
       if ((worktree.kind ?? "main") !== "worktree") {
         return;
       }
-      if (runningRef.current.has(worktree.id)) {
+      if (startingRef.current.has(worktree.id)) {
         return;
       }
-      runningRef.current.add(worktree.id);
+      startingRef.current.add(worktree.id);
       try {
         const status = await getWorktreeSetupStatus(worktree.id);
         const script = status.script?.trim() ? status.script : null;
         if (!status.shouldRun || !script) {
           return;
         }
-
-        openTerminal();
-        const terminalId = ensureTerminalWithTitle(
-          worktree.id,
-          WORKTREE_SETUP_TERMINAL_ID,
-          WORKTREE_SETUP_TERMINAL_TITLE,
-        );
-
-        try {
-          await restartTerminalSession(worktree.id, terminalId);
-        } catch (error) {
-          onDebug?.(buildErrorDebugEntry("worktree setup restart error", error));
-        }
-
-        await openTerminalSession(
-          worktree.id,
-          terminalId,
-          DEFAULT_TERMINAL_COLS,
-          DEFAULT_TERMINAL_ROWS,
-        );
-        await writeTerminalSession(worktree.id, terminalId, `${script}\n`);
-        await markWorktreeSetupRan(worktree.id);
+        upsertRun(await runWorktreeSetup(worktree.id));
       } catch (error) {
         onDebug?.(buildErrorDebugEntry("worktree setup script error", error));
       } finally {
-        runningRef.current.delete(worktree.id);
+        startingRef.current.delete(worktree.id);
       }
     },
-    [ensureTerminalWithTitle, onDebug, openTerminal, restartTerminalSession],
+    [onDebug, upsertRun],
   );
 
+  const retryWorktreeSetup = useCallback(
+    async (workspaceId: string) => {
+      try {
+        upsertRun(await retryWorktreeSetupService(workspaceId));
+      } catch (error) {
+        const message = error instanceof Error ? error.message : String(error);
+        setJobs((prev) => {
+          const existing = prev[workspaceId];
+          return existing
+            ? { ...prev, [workspaceId]: { ...existing, error: message } }
+            : prev;
+        });
+        onDebug?.(buildErrorDebugEntry("worktree setup retry error", error));
+      }
+    },
+    [onDebug, upsertRun],
+  );
+
+  const cancelWorktreeSetup = useCallback(
+    async (workspaceId: string) => {
+      try {
+        await cancelWorktreeSetupService(workspaceId);
+      } catch (error) {
+        onDebug?.(buildErrorDebugEntry("worktree setup cancel error", error));
+      }
+    },
+    [onDebug],
+  );
+
+  const dismissWorktreeSetup = useCallback((workspaceId: string) => {
+    setJobs((prev) => {
+      if (!prev[workspaceId] || prev[workspaceId].run.state === "running") {
+        return prev;
+      }
+      const next = { ...prev };
+      delete next[workspaceId];
+      return next;
+    });
+  }, []);
+
+  const setupJobs = useMemo(() => Object.values(jobs), [jobs]);
+
   return {
+    setupJobs,
     maybeRunWorktreeSetupScript,
+    retryWorktreeSetup,
+    cancelWorktreeSetup,
+    dismissWorktreeSetup,
   };
 }
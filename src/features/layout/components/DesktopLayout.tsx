@@ -59,6 +59,7 @@ type DesktopLayoutProps = {
   updateToastNode: ReactNode;
   approvalToastsNode: ReactNode;
   errorToastsNode: ReactNode;
+  worktreeSetupToastsNode: ReactNode;
   homeNode: ReactNode;
   showHome: boolean;
   showWorkspace: boolean;
@@ -86,6 +87,7 @@ export function DesktopLayout({
   updateToastNode,
   approvalToastsNode,
   errorToastsNode,
+  worktreeSetupToastsNode,
   homeNode,
   showHome,
   showWorkspace,
@@ -153,6 +155,7 @@ export function DesktopLayout({
       <section className="main">
         {updateToastNode}
         {errorToastsNode}
+        {worktreeSetupToastsNode}
         {showHome && homeNode}
 
         {showWorkspace && (
@@ -6,6 +6,7 @@ type PhoneLayoutProps = {
   approvalToastsNode: ReactNode;
   updateToastNode: ReactNode;
   errorToastsNode: ReactNode;
+  worktreeSetupToastsNode: ReactNode;
   tabBarNode: ReactNode;
   homeNode: ReactNode;
   sidebarNode: ReactNode;
@@ -28,6 +29,7 @@ export function PhoneLayout({
   approvalToastsNode,
   updateToastNode,
   errorToastsNode,
+  worktreeSetupToastsNode,
   tabBarNode,
   homeNode,
   sidebarNode,
@@ -50,6 +52,7 @@ export function PhoneLayout({
       {approvalToastsNode}
       {updateToastNode}
       {errorToastsNode}
+      {worktreeSetupToastsNode}
       {activeTab === "home" && <div className="compact-panel">{homeNode}</div>}
       {activeTab === "projects" && <div className="compact-panel">{sidebarNode}</div>}
       {activeTab === "codex" && (
@@ -7,6 +7,7 @@ type TabletLayoutProps = {
   approvalToastsNode: ReactNode;
   updateToastNode: ReactNode;
   errorToastsNode: ReactNode;
+  worktreeSetupToastsNode: ReactNode;
   homeNode: ReactNode;
   showHome: boolean;
   showWorkspace: boolean;
@@ -27,6 +28,7 @@ export function TabletLayout({
   approvalToastsNode,
   updateToastNode,
   errorToastsNode,
+  worktreeSetupToastsNode,
   homeNode,
   showHome,
   showWorkspace,
@@ -56,6 +58,7 @@ export function TabletLayout({
         {approvalToastsNode}
         {updateToastNode}
         {errorToastsNode}
+        {worktreeSetupToastsNode}
         {showHome && homeNode}
         {showWorkspace && (
           <>
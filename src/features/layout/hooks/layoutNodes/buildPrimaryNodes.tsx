@@ -6,6 +6,7 @@ import { Messages } from "../../../messages/components/Messages";
 import { ApprovalToasts } from "../../../app/components/ApprovalToasts";
 import { UpdateToast } from "../../../update/components/UpdateToast";
 import { ErrorToasts } from "../../../notifications/components/ErrorToasts";
+import { WorktreeSetupToasts } from "../../../app/components/WorktreeSetupToasts";
 import { Composer } from "../../../composer/components/Composer";
 import { TabBar } from "../../../app/components/TabBar";
 import { TabletNav } from "../../../app/components/TabletNav";
@@ -24,6 +25,7 @@ type PrimaryLayoutNodes = Pick<
   | "approvalToastsNode"
   | "updateToastNode"
   | "errorToastsNode"
+  | "worktreeSetupToastsNode"
   | "homeNode"
   | "mainHeaderNode"
   | "desktopTopbarLeftNode"
@@ -44,6 +46,10 @@ export function buildPrimaryNodes(options: PrimaryLayoutNodesOptions): PrimaryLa
 
   const errorToastsNode = <ErrorToasts {...options.errorToastsProps} />;
 
+  const worktreeSetupToastsNode = (
+    <WorktreeSetupToasts {...options.worktreeSetupToastsProps} />
+  );
+
   const homeNode = <Home {...options.homeProps} />;
 
   const mainHeaderNode = options.mainHeaderProps ? (
@@ -78,6 +84,7 @@ export function buildPrimaryNodes(options: PrimaryLayoutNodesOptions): PrimaryLa
     approvalToastsNode,
     updateToastNode,
     errorToastsNode,
+    worktreeSetupToastsNode,
     homeNode,
     mainHeaderNode,
     desktopTopbarLeftNode,
@@ -4,6 +4,7 @@ import { MainHeader } from "../../../app/components/MainHeader";
 import { Sidebar } from "../../../app/components/Sidebar";
 import { TabBar } from "../../../app/components/TabBar";
 import { TabletNav } from "../../../app/components/TabletNav";
+import { WorktreeSetupToasts } from "../../../app/components/WorktreeSetupToasts";
 import { Composer } from "../../../composer/components/Composer";
 import { DebugPanel } from "../../../debug/components/DebugPanel";
 import { FileTreePanel } from "../../../files/components/FileTreePanel";
@@ -44,6 +45,7 @@ export type LayoutPrimarySurface = {
   approvalToastsProps: ComponentProps<typeof ApprovalToasts>;
   updateToastProps: ComponentProps<typeof UpdateToast>;
   errorToastsProps: ComponentProps<typeof ErrorToasts>;
+  worktreeSetupToastsProps: ComponentProps<typeof WorktreeSetupToasts>;
   homeProps: ComponentProps<typeof Home>;
   mainHeaderProps: ComponentProps<typeof MainHeader> | null;
   desktopTopbarProps: {
@@ -96,6 +98,7 @@ export type LayoutNodesResult = {
   approvalToastsNode: ReactNode;
   updateToastNode: ReactNode;
   errorToastsNode: ReactNode;
+  worktreeSetupToastsNode: ReactNode;
   homeNode: ReactNode;
   mainHeaderNode: ReactNode;
   desktopTopbarLeftNode: ReactNode;
@@ -16,7 +16,7 @@ import Wrench from "lucide-react/dist/esm/icons/wrench";
 import X from "lucide-react/dist/esm/icons/x";
 import { exportMarkdownFile } from "@services/tauri";
 import { pushErrorToast } from "@services/toasts";
-import type { ConversationItem } from "../../../types";
+import type { ConversationItem, MessageContextAttachment } from "../../../types";
 import type { ParsedFileLocation } from "../../../utils/fileLinks";
 import { PierreDiffBlock } from "../../git/components/PierreDiffBlock";
 import {
@@ -128,6 +128,55 @@ const MessageImageGrid = memo(function MessageImageGrid({
   );
 });
 
+function attachmentIcon(kind: MessageContextAttachment["kind"]) {
+  switch (kind) {
+    case "diff":
+    case "commit":
+      return Diff;
+    case "pullRequest":
+      return FileDiffIcon;
+    case "terminal":
+      return Terminal;
+    default:
+      return FileText;
+  }
+}
+
+const MessageAttachmentList = memo(function MessageAttachmentList({
+  attachments,
+  hasText,
+}: {
+  attachments: MessageContextAttachment[];
+  hasText: boolean;
+}) {
+  return (
+    <div
+      className={`message-attachments${hasText ? " message-attachments--with-text" : ""}`}
+      role="list"
+    >
+      {attachments.map((attachment, index) => {
+        const Icon = attachmentIcon(attachment.kind);
+        return (
+          <details
+            key={`${attachment.kind}-${attachment.label}-${index}`}
+            className="message-attachment"
+            role="listitem"
+          >
+            <summary className="message-attachment-summary">
+              <Icon size={12} aria-hidden />
+              <span className="message-attachment-label">{attachment.label}</span>
+              {attachment.truncated && (
+                <span className="message-attachment-badge">truncated</span>
+              )}
+            </summary>
+            <pre className="message-attachment-content">{attachment.content}</pre>
+          </details>
+        );
+      })}
+    </div>
+  );
+});
+
 const ImageLightbox = memo(function ImageLightbox({
   images,
   activeIndex,
@@ -453,6 +502,12 @@ export const MessageRow = memo(function MessageRow({
             hasText={hasText}
           />
         )}
+        {item.attachments && item.attachments.length > 0 && (
+          <MessageAttachmentList
+            attachments={item.attachments}
+            hasText={hasText}
+          />
+        )}
         {hasText && (
           <Markdown
             value={item.text}
@@ -81,7 +81,11 @@ export function useCustomPrompts({ activeWorkspace, onDebug }: UseCustomPromptsO
         }
 
         let scope: CustomPromptOption["scope"];
-        if (item.scope === "workspace" || item.scope === "global") {
+        if (
+          item.scope === "workspace" ||
+          item.scope === "global" ||
+          item.scope === "library"
+        ) {
           scope = item.scope;
         }
 
@@ -90,8 +94,11 @@ export function useCustomPrompts({ activeWorkspace, onDebug }: UseCustomPromptsO
           path: String(item.path ?? ""),
           description: item.description ? String(item.description) : undefined,
           argumentHint,
+          arguments: Array.isArray(item.arguments) ? item.arguments : undefined,
           content: String(item.content ?? ""),
           scope,
+          libraryId: item.libraryId ? String(item.libraryId) : undefined,
+          overridden: item.overridden === true ? true : undefined,
         };
       });
       setPrompts(data);
@@ -346,6 +346,7 @@ describe("useThreadMessaging telemetry", () => {
       "thread-1",
       { type: "uncommittedChanges" },
       "inline",
+      "current",
     );
   });
 
@@ -459,6 +460,8 @@ describe("useThreadMessaging telemetry", () => {
       "turn-1",
       "steer this",
       [],
+      [],
+      "current",
     );
     expect(sendUserMessageService).not.toHaveBeenCalled();
     expect(ensureWorkspaceRuntimeCodexArgs).not.toHaveBeenCalled();
@@ -726,6 +729,7 @@ describe("useThreadMessaging telemetry", () => {
       "thread-override",
       { type: "uncommittedChanges" },
       "detached",
+      "current",
     );
   });
 
@@ -231,8 +231,10 @@ export function useThreadMessaging({
         ) {
           await ensureWorkspaceRuntimeCodexArgs(workspace.id, threadId);
         }
-        const response: Record<string, unknown> = shouldSteer
-          ? (await (appMentions.length > 0
+        // Pass the thread's access mode so the steer resolves the same
+        // sandbox policy as the turn it joins.
+        const steer = () =>
+          resolvedAccessMode
             ? steerTurnService(
               workspace.id,
               threadId,
@@ -240,14 +242,26 @@ export function useThreadMessaging({
               finalText,
               images,
               appMentions,
+              resolvedAccessMode,
             )
-            : steerTurnService(
-              workspace.id,
-              threadId,
-              activeTurnId ?? "",
-              finalText,
-              images,
-            ))) as Record<string, unknown>
+            : appMentions.length > 0
+              ? steerTurnService(
+                workspace.id,
+                threadId,
+                activeTurnId ?? "",
+                finalText,
+                images,
+                appMentions,
+              )
+              : steerTurnService(
+                workspace.id,
+                threadId,
+                activeTurnId ?? "",
+                finalText,
+                images,
+              );
+        const response: Record<string, unknown> = shouldSteer
+          ? ((await steer()) as Record<string, unknown>)
           : (await sendUserMessageService(
             workspace.id,
             threadId,
@@ -520,12 +534,20 @@ export function useThreadMessaging({
         },
       });
       try {
-        const response = await startReviewService(
-          workspaceId,
-          threadId,
-          target,
-          reviewDeliveryMode,
-        );
+        const response = accessMode
+          ? await startReviewService(
+            workspaceId,
+            threadId,
+            target,
+            reviewDeliveryMode,
+            accessMode,
+          )
+          : await startReviewService(
+            workspaceId,
+            threadId,
+            target,
+            reviewDeliveryMode,
+          );
         onDebug?.({
           id: `${Date.now()}-server-review-start`,
           timestamp: Date.now(),
@@ -577,6 +599,7 @@ export function useThreadMessaging({
       }
     },
     [
+      accessMode,
       activeWorkspace,
       ensureThreadForActiveWorkspace,
       ensureThreadForWorkspace,
@@ -2,23 +2,61 @@ import { invoke } from "@tauri-apps/api/core";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import type { Options as NotificationOptions } from "@tauri-apps/plugin-notification";
 import type {
+  AgentAuditEntry,
+  AgentAuditExport,
+  AgentAuditQuery,
   AppSettings,
+  ApprovalRuleSpec,
+  ApprovalRuleTestResult,
+  ApprovalRulesFile,
+  AutoApprovalEvaluation,
+  AutoApprovalPolicy,
+  AutoApprovalRequest,
+  BatchRun,
+  BatchRunDiffSummary,
+  BatchRunInput,
   CodexUpdateResult,
   CodexDoctorResult,
+  ConfigDryRunResult,
+  ConfigValidationReport,
   DictationModelStatus,
+  CustomPromptOption,
   DictationSessionState,
+  LocalUsageBreakdown,
+  LocalUsageExport,
   LocalUsageSnapshot,
+  PromptLibraryStatus,
+  PromptRenderResult,
+  PromptRevision,
+  PromptRevisionDiff,
+  ScheduledRun,
+  ScheduledRunInput,
+  ScheduledRunRecord,
   TcpDaemonStatus,
+  ThreadExport,
+  ThreadExportFormat,
+  ThreadSearchQuery,
+  ThreadSearchResponse,
   TailscaleDaemonCommandPreview,
   TailscaleStatus,
   TrayRecentThreadEntry,
   TraySessionUsage,
+  TurnAccessMode,
+  TurnContextAttachment,
+  UsageBreakdownGroup,
+  UsageExportFormat,
+  WebhookDelivery,
+  WebhookInfo,
+  WebhookInput,
   WorkspaceInfo,
   AppMention,
   WorkspaceSettings,
+  WorkspaceGroup,
+  OpenAppTarget,
 } from "../types";
 import type {
   GitFileDiff,
+  GitStatusPage,
   GitFileStatus,
   GitCommitDiff,
   GitHubIssuesResponse,
@@ -174,6 +212,58 @@ export type DeleteAgentInput = {
   deleteManagedFile?: boolean;
 };
 
+export type AgentBundleFormat = "toml" | "markdown";
+
+export type AgentImportConflict = "fail" | "skip" | "rename" | "overwrite";
+
+export type AgentImportAction = "created" | "renamed" | "overwritten" | "skipped";
+
+export type AgentRoleBundle = {
+  name: string;
+  description: string | null;
+  developerInstructions: string | null;
+  model: string | null;
+  reasoningEffort: string | null;
+};
+
+export type AgentExport = {
+  name: string;
+  format: AgentBundleFormat;
+  fileName: string;
+  content: string;
+};
+
+export type ImportAgentInput = {
+  content: string;
+  format?: AgentBundleFormat | null;
+  name?: string | null;
+  conflict?: AgentImportConflict | null;
+};
+
+export type AgentImportResult = {
+  name: string;
+  action: AgentImportAction;
+  settings: AgentsSettings;
+};
+
+export type AgentTemplate = {
+  id: string;
+  bundle: AgentRoleBundle;
+  installed: boolean;
+};
+
+export type AgentTemplateInstall = {
+  id: string;
+  name: string | null;
+  action: AgentImportAction | null;
+  error: string | null;
+};
+
+export type InstallAgentTemplatesResult = {
+  results: AgentTemplateInstall[];
+  settings: AgentsSettings;
+};
+
 type FileScope = "workspace" | "global";
 type FileKind = "agents" | "config";
 
@@ -214,6 +304,16 @@ export async function writeGlobalCodexConfigToml(content: string): Promise<void>
   return fileWrite("global", "config", content);
 }
 
+export async function validateCodexConfigToml(
+  content?: string,
+): Promise<ConfigValidationReport> {
+  return invoke<ConfigValidationReport>("config_toml_validate", { content });
+}
+
+export async function dryRunCodexConfigToml(content?: string): Promise<ConfigDryRunResult> {
+  return invoke<ConfigDryRunResult>("config_toml_dry_run", { content });
+}
+
 export async function getAgentsSettings(): Promise<AgentsSettings> {
   return invoke<AgentsSettings>("get_agents_settings");
 }
@@ -247,6 +347,30 @@ export async function writeAgentConfigToml(
   return invoke("write_agent_config_toml", { agentName, content });
 }
 
+export async function exportAgent(
+  name: string,
+  format?: AgentBundleFormat,
+): Promise<AgentExport> {
+  return invoke<AgentExport>("export_agent", { input: { name, format } });
+}
+
+export async function importAgent(input: ImportAgentInput): Promise<AgentImportResult> {
+  return invoke<AgentImportResult>("import_agent", { input });
+}
+
+export async function listAgentTemplates(): Promise<AgentTemplate[]> {
+  return invoke<AgentTemplate[]>("list_agent_templates");
+}
+
+export async function installAgentTemplates(
+  ids: string[] = [],
+  conflict?: AgentImportConflict,
+): Promise<InstallAgentTemplatesResult> {
+  return invoke<InstallAgentTemplatesResult>("install_agent_templates", {
+    input: { ids, conflict },
+  });
+}
+
 export async function getConfigModel(workspaceId: string): Promise<string | null> {
   const response = await invoke<{ model?: string | null }>("get_config_model", {
     workspaceId,
@@ -300,9 +424,30 @@ export async function addWorktree(
   return invoke<WorkspaceInfo>("add_worktree", { parentId, branch, name, copyAgentsMd });
 }
 
+export type WorktreeSetupRunState = "running" | "succeeded" | "failed" | "cancelled";
+
+export type WorktreeSetupRun = {
+  runId: string;
+  workspaceId: string;
+  state: WorktreeSetupRunState;
+  attempt: number;
+  script: string;
+  startedAtMs: number;
+  finishedAtMs: number | null;
+  durationMs: number | null;
+  exitCode: number | null;
+  error: string | null;
+};
+
 export type WorktreeSetupStatus = {
   shouldRun: boolean;
   script: string | null;
+  lastRun?: WorktreeSetupRun | null;
+};
+
+export type WorktreeSetupLog = {
+  content: string;
+  truncated: boolean;
 };
 
 export async function getWorktreeSetupStatus(
@@ -311,8 +456,115 @@ export async function getWorktreeSetupStatus(
   return invoke<WorktreeSetupStatus>("worktree_setup_status", { workspaceId });
 }
 
-export async function markWorktreeSetupRan(workspaceId: string): Promise<void> {
-  return invoke("worktree_setup_mark_ran", { workspaceId });
+export async function runWorktreeSetup(workspaceId: string): Promise<WorktreeSetupRun> {
+  return invoke<WorktreeSetupRun>("worktree_setup_run", { workspaceId });
+}
+
+export async function retryWorktreeSetup(workspaceId: string): Promise<WorktreeSetupRun> {
+  return invoke<WorktreeSetupRun>("worktree_setup_retry", { workspaceId });
+}
+
+export async function cancelWorktreeSetup(workspaceId: string): Promise<void> {
+  return invoke("worktree_setup_cancel", { workspaceId });
+}
+
+export async function getWorktreeSetupLog(workspaceId: string): Promise<WorktreeSetupLog> {
+  return invoke<WorktreeSetupLog>("worktree_setup_log", { workspaceId });
+}
+
+export type WorkspaceDoctorFinding = {
+  check: string;
+  severity: "ok" | "warning" | "error";
+  message: string;
+  suggestedFix: string | null;
+  fix:
+    | "clearGitRoot"
+    | "pruneWorktrees"
+    | "writeSetupMarker"
+    | "removeSetupMarker"
+    | "recordInterruptedSetupRun"
+    | null;
+  fixed: boolean;
+  fixError: string | null;
+};
+
+export type WorkspaceDoctorReport = {
+  workspaceId: string;
+  workspaceName: string;
+  healthy: boolean;
+  findings: WorkspaceDoctorFinding[];
+};
+
+export async function runWorkspaceDoctor(
+  workspaceId: string | null,
+  applyFixes = false,
+): Promise<WorkspaceDoctorReport[]> {
+  return invoke<WorkspaceDoctorReport[]>("workspace_doctor", {
+    workspaceId,
+    applyFixes,
+  });
+}
+
+export type WorkspaceBundle = {
+  version: number;
+  exportedAtMs: number;
+  workspaceGroups: WorkspaceGroup[];
+  workspaces: {
+    id: string;
+    name: string;
+    path: string;
+    settings: WorkspaceSettings;
+  }[];
+  openAppTargets: OpenAppTarget[];
+  selectedOpenAppId: string | null;
+  globalWorktreesFolder: string | null;
+  prompts: {
+    scope: "workspace" | "global";
+    workspaceId: string | null;
+    name: string;
+    content: string;
+  }[];
+  agentsSettings: {
+    multiAgentEnabled: boolean;
+    maxThreads: number;
+    maxDepth: number;
+  } | null;
+  agents: {
+    name: string;
+    description: string | null;
+    configToml: string | null;
+  }[];
+};
+
+export type WorkspaceBundleImportOptions = {
+  pathRemaps?: { from: string; to: string }[];
+  overwriteExisting?: boolean;
+  skipPrompts?: boolean;
+  skipAgents?: boolean;
+};
+
+export type WorkspaceBundleImportSummary = {
+  workspacesAdded: number;
+  workspacesUpdated: number;
+  groupsImported: number;
+  openAppTargetsImported: number;
+  promptsImported: number;
+  agentsImported: number;
+  warnings: string[];
+};
+
+export async function exportWorkspaceBundle(): Promise<WorkspaceBundle> {
+  return invoke<WorkspaceBundle>("export_workspace_bundle");
+}
+
+export async function importWorkspaceBundle(
+  bundle: WorkspaceBundle,
+  options?: WorkspaceBundleImportOptions,
+): Promise<WorkspaceBundleImportSummary> {
+  return invoke<WorkspaceBundleImportSummary>("import_workspace_bundle", {
+    bundle,
+    options: options ?? null,
+  });
 }
 
 export async function updateWorkspaceSettings(
@@ -453,10 +705,11 @@ export async function sendUserMessage(
     model?: string | null;
     effort?: string | null;
     serviceTier?: "fast" | "flex" | null | undefined;
-    accessMode?: "read-only" | "current" | "full-access";
+    accessMode?: TurnAccessMode;
     images?: string[];
     collaborationMode?: Record<string, unknown> | null;
     appMentions?: AppMention[];
+    attachments?: TurnContextAttachment[];
   },
 ) {
   const images = await normalizeImagesForRpc(options?.images);
@@ -478,6 +731,9 @@ export async function sendUserMessage(
   if (options?.appMentions && options.appMentions.length > 0) {
     payload.appMentions = options.appMentions;
   }
+  if (options?.attachments && options.attachments.length > 0) {
+    payload.attachments = options.attachments;
+  }
   return invoke("send_user_message", payload);
 }
 
@@ -496,6 +752,8 @@ export async function steerTurn(
   text: string,
   images?: string[],
   appMentions?: AppMention[],
+  accessMode?: TurnAccessMode,
+  attachments?: TurnContextAttachment[],
 ) {
   const normalizedImages = await normalizeImagesForRpc(images);
   const payload: Record<string, unknown> = {
@@ -508,6 +766,12 @@ export async function steerTurn(
   if (appMentions && appMentions.length > 0) {
     payload.appMentions = appMentions;
   }
+  if (accessMode) {
+    payload.accessMode = accessMode;
+  }
+  if (attachments && attachments.length > 0) {
+    payload.attachments = attachments;
+  }
   return invoke("turn_steer", payload);
 }
 
@@ -516,11 +780,15 @@ export async function startReview(
   threadId: string,
   target: ReviewTarget,
   delivery?: "inline" | "detached",
+  accessMode?: TurnAccessMode,
 ) {
   const payload: Record<string, unknown> = { workspaceId, threadId, target };
   if (delivery) {
     payload.delivery = delivery;
   }
+  if (accessMode) {
+    payload.accessMode = accessMode;
+  }
   return invoke("start_review", payload);
 }
 
@@ -555,15 +823,123 @@ export async function rememberApprovalRule(
   return invoke("remember_approval_rule", { workspaceId, command });
 }
 
-export async function getGitStatus(workspace_id: string): Promise<{
+export async function listApprovalRules(workspaceId: string) {
+  return invoke<ApprovalRulesFile[]>("approval_rules_list", { workspaceId });
+}
+
+export async function validateApprovalRule(rule: ApprovalRuleSpec) {
+  return invoke<string[]>("approval_rule_validate", { rule });
+}
+
+export async function addApprovalRule(
+  workspaceId: string,
+  data: { rule: ApprovalRuleSpec; file?: string | null; index?: number | null },
+) {
+  return invoke<ApprovalRulesFile>("approval_rule_add", {
+    workspaceId,
+    file: data.file ?? null,
+    rule: data.rule,
+    index: data.index ?? null,
+  });
+}
+
+export async function updateApprovalRule(
+  workspaceId: string,
+  data: { index: number; rule: ApprovalRuleSpec; file?: string | null },
+) {
+  return invoke<ApprovalRulesFile>("approval_rule_update", {
+    workspaceId,
+    file: data.file ?? null,
+    index: data.index,
+    rule: data.rule,
+  });
+}
+
+export async function deleteApprovalRule(
+  workspaceId: string,
+  data: { index: number; file?: string | null },
+) {
+  return invoke<ApprovalRulesFile>("approval_rule_delete", {
+    workspaceId,
+    file: data.file ?? null,
+    index: data.index,
+  });
+}
+
+export async function moveApprovalRule(
+  workspaceId: string,
+  data: { from: number; to: number; file?: string | null },
+) {
+  return invoke<ApprovalRulesFile>("approval_rule_move", {
+    workspaceId,
+    file: data.file ?? null,
+    from: data.from,
+    to: data.to,
+  });
+}
+
+export async function testApprovalRules(workspaceId: string, command: string[]) {
+  return invoke<ApprovalRuleTestResult>("approval_rules_test", {
+    workspaceId,
+    command,
+  });
+}
+
+export async function getAutoApprovalPolicy() {
+  return invoke<AutoApprovalPolicy>("auto_approval_policy_get");
+}
+
+export async function setAutoApprovalPolicy(policy: AutoApprovalPolicy) {
+  return invoke<AutoApprovalPolicy>("auto_approval_policy_set", { policy });
+}
+
+export async function testAutoApproval(request: AutoApprovalRequest) {
+  return invoke<AutoApprovalEvaluation>("auto_approval_test", { request });
+}
+
+export async function queryAgentAudit(query: AgentAuditQuery) {
+  return invoke<AgentAuditEntry[]>("agent_audit_query", { query });
+}
+
+export async function exportAgentAudit(
+  query: AgentAuditQuery,
+  format: UsageExportFormat = "csv",
+) {
+  return invoke<AgentAuditExport>("agent_audit_export", { query, format });
+}
+
+export type GitStatusResponse = {
   branchName: string;
   files: GitFileStatus[];
   stagedFiles: GitFileStatus[];
   unstagedFiles: GitFileStatus[];
   totalAdditions: number;
   totalDeletions: number;
-}> {
-  return invoke("get_git_status", { workspaceId: workspace_id });
+};
+
+export async function getGitStatus(
+  workspace_id: string,
+  root?: string,
+): Promise<GitStatusResponse> {
+  return invoke("get_git_status", { workspaceId: workspace_id, root });
+}
+
+export type GitRootStatus =
+  | (GitStatusResponse & { root: string; error?: undefined })
+  | { root: string; error: string };
+
+export type GitStatusAggregateResponse = {
+  roots: GitRootStatus[];
+  changedFiles: number;
+  totalAdditions: number;
+  totalDeletions: number;
+};
+
+export async function getGitStatusAggregate(
+  workspace_id: string,
+  depth?: number,
+): Promise<GitStatusAggregateResponse> {
+  return invoke("get_git_status_aggregate", { workspaceId: workspace_id, depth });
 }
 
 export type InitGitRepoResponse =
@@ -612,15 +988,41 @@ export async function listGitRoots(
 
 export async function getGitDiffs(
   workspace_id: string,
+  root?: string,
 ): Promise<GitFileDiff[]> {
-  return invoke("get_git_diffs", { workspaceId: workspace_id });
+  return invoke("get_git_diffs", { workspaceId: workspace_id, root });
+}
+
+export async function getGitStatusPage(
+  workspace_id: string,
+  options: {
+    root?: string;
+    offset?: number;
+    limit?: number;
+    includeStats?: boolean;
+  } = {},
+): Promise<GitStatusPage> {
+  return invoke("get_git_status_page", { workspaceId: workspace_id, ...options });
+}
+
+export async function getGitFileDiff(
+  workspace_id: string,
+  path: string,
+  options: { root?: string; maxLines?: number } = {},
+): Promise<GitFileDiff> {
+  return invoke("get_git_file_diff", {
+    workspaceId: workspace_id,
+    path,
+    ...options,
+  });
 }
 
 export async function getGitLog(
   workspace_id: string,
   limit = 40,
+  root?: string,
 ): Promise<GitLogResponse> {
-  return invoke("get_git_log", { workspaceId: workspace_id, limit });
+  return invoke("get_git_log", { workspaceId: workspace_id, limit, root });
 }
 
 export async function getGitCommitDiff(
@@ -634,31 +1036,47 @@ export async function getGitRemote(workspace_id: string): Promise<string | null>
   return invoke("get_git_remote", { workspaceId: workspace_id });
 }
 
-export async function stageGitFile(workspaceId: string, path: string) {
-  return invoke("stage_git_file", { workspaceId, path });
+export async function stageGitFile(
+  workspaceId: string,
+  path: string,
+  root?: string,
+) {
+  return invoke("stage_git_file", { workspaceId, path, root });
 }
 
-export async function stageGitAll(workspaceId: string): Promise<void> {
-  return invoke("stage_git_all", { workspaceId });
+export async function stageGitAll(
+  workspaceId: string,
+  root?: string,
+): Promise<void> {
+  return invoke("stage_git_all", { workspaceId, root });
 }
 
-export async function unstageGitFile(workspaceId: string, path: string) {
-  return invoke("unstage_git_file", { workspaceId, path });
+export async function unstageGitFile(
+  workspaceId: string,
+  path: string,
+  root?: string,
+) {
+  return invoke("unstage_git_file", { workspaceId, path, root });
 }
 
-export async function revertGitFile(workspaceId: string, path: string) {
-  return invoke("revert_git_file", { workspaceId, path });
+export async function revertGitFile(
+  workspaceId: string,
+  path: string,
+  root?: string,
+) {
+  return invoke("revert_git_file", { workspaceId, path, root });
 }
 
-export async function revertGitAll(workspaceId: string) {
-  return invoke("revert_git_all", { workspaceId });
+export async function revertGitAll(workspaceId: string, root?: string) {
+  return invoke("revert_git_all", { workspaceId, root });
 }
 
 export async function commitGit(
   workspaceId: string,
   message: string,
+  root?: string,
 ): Promise<void> {
-  return invoke("commit_git", { workspaceId, message });
+  return invoke("commit_git", { workspaceId, message, root });
 }
 
 export async function pushGit(workspaceId: string): Promise<void> {
@@ -730,6 +1148,128 @@ export async function localUsageSnapshot(
   return invoke("local_usage_snapshot", payload);
 }
 
+export async function localUsageBreakdown(options?: {
+  startDate?: string | null;
+  endDate?: string | null;
+  groupBy?: UsageBreakdownGroup;
+  workspacePath?: string | null;
+  limit?: number;
+}): Promise<LocalUsageBreakdown> {
+  return invoke("local_usage_breakdown", {
+    startDate: options?.startDate ?? null,
+    endDate: options?.endDate ?? null,
+    groupBy: options?.groupBy ?? null,
+    workspacePath: options?.workspacePath ?? null,
+    limit: options?.limit ?? null,
+  });
+}
+
+export async function localUsageExport(options?: {
+  startDate?: string | null;
+  endDate?: string | null;
+  workspacePath?: string | null;
+  format?: UsageExportFormat;
+}): Promise<LocalUsageExport> {
+  return invoke("local_usage_export", {
+    startDate: options?.startDate ?? null,
+    endDate: options?.endDate ?? null,
+    workspacePath: options?.workspacePath ?? null,
+    format: options?.format ?? null,
+  });
+}
+
+export async function listSchedules(): Promise<ScheduledRun[]> {
+  return invoke("schedules_list");
+}
+
+export async function createSchedule(
+  input: ScheduledRunInput,
+): Promise<ScheduledRun> {
+  return invoke("schedules_create", { input });
+}
+
+export async function updateSchedule(
+  id: string,
+  input: ScheduledRunInput,
+): Promise<ScheduledRun> {
+  return invoke("schedules_update", { id, input });
+}
+
+export async function deleteSchedule(id: string): Promise<void> {
+  return invoke("schedules_delete", { id });
+}
+
+export async function getScheduleHistory(options?: {
+  scheduleId?: string | null;
+  limit?: number | null;
+}): Promise<ScheduledRunRecord[]> {
+  return invoke("schedules_history", {
+    scheduleId: options?.scheduleId ?? null,
+    limit: options?.limit ?? null,
+  });
+}
+
+export async function runScheduleNow(id: string): Promise<ScheduledRunRecord> {
+  return invoke("schedules_run_now", { id });
+}
+
+export async function listWebhooks(): Promise<WebhookInfo[]> {
+  return invoke("webhooks_list");
+}
+
+export async function createWebhook(
+  input: WebhookInput,
+): Promise<WebhookInfo> {
+  return invoke("webhooks_create", { input });
+}
+
+export async function updateWebhook(
+  id: string,
+  input: WebhookInput,
+): Promise<WebhookInfo> {
+  return invoke("webhooks_update", { id, input });
+}
+
+export async function deleteWebhook(id: string): Promise<void> {
+  return invoke("webhooks_delete", { id });
+}
+
+export async function getWebhookDeliveries(options?: {
+  webhookId?: string | null;
+  limit?: number | null;
+}): Promise<WebhookDelivery[]> {
+  return invoke("webhook_deliveries", {
+    webhookId: options?.webhookId ?? null,
+    limit: options?.limit ?? null,
+  });
+}
+
+export async function testWebhook(id: string): Promise<WebhookDelivery> {
+  return invoke("webhooks_test", { id });
+}
+
+export async function startBatchRun(input: BatchRunInput): Promise<BatchRun> {
+  return invoke("batch_runs_start", { input });
+}
+
+export async function listBatchRuns(): Promise<BatchRun[]> {
+  return invoke("batch_runs_list");
+}
+
+export async function getBatchRunStatus(batchId: string): Promise<BatchRun> {
+  return invoke("batch_runs_status", { batchId });
+}
+
+export async function getBatchRunDiffSummary(
+  batchId: string,
+): Promise<BatchRunDiffSummary> {
+  return invoke("batch_runs_diff_summary", { batchId });
+}
+
+export async function deleteBatchRun(batchId: string): Promise<void> {
+  return invoke("batch_runs_delete", { batchId });
+}
+
 export async function getModelList(workspaceId: string) {
   return invoke<any>("model_list", { workspaceId });
 }
@@ -861,6 +1401,64 @@ export async function movePrompt(
   });
 }
 
+export async function renderPrompt(
+  workspaceId: string,
+  data: {
+    path: string;
+    args?: Record<string, string | number | boolean>;
+    selection?: string | null;
+  },
+) {
+  return invoke<PromptRenderResult>("prompts_render", {
+    workspaceId,
+    path: data.path,
+    args: data.args ?? {},
+    selection: data.selection ?? null,
+  });
+}
+
+export async function upstreamPromptOverride(libraryId: string, name: string) {
+  return invoke<CustomPromptOption>("prompts_upstream_override", {
+    libraryId,
+    name,
+  });
+}
+
+export async function getPromptHistory(workspaceId: string, path: string) {
+  return invoke<PromptRevision[]>("prompts_history_list", { workspaceId, path });
+}
+
+export async function diffPromptRevisions(
+  workspaceId: string,
+  data: { path: string; fromRevision: string; toRevision?: string | null },
+) {
+  return invoke<PromptRevisionDiff>("prompts_history_diff", {
+    workspaceId,
+    path: data.path,
+    fromRevision: data.fromRevision,
+    toRevision: data.toRevision ?? null,
+  });
+}
+
+export async function restorePromptRevision(
+  workspaceId: string,
+  data: { path: string; revisionId: string },
+) {
+  return invoke<CustomPromptOption>("prompts_history_restore", {
+    workspaceId,
+    path: data.path,
+    revisionId: data.revisionId,
+  });
+}
+
+export async function syncPromptLibraries() {
+  return invoke<PromptLibraryStatus[]>("prompt_libraries_sync");
+}
+
+export async function getPromptLibrariesStatus() {
+  return invoke<PromptLibraryStatus[]>("prompt_libraries_status");
+}
+
 export async function getAppSettings(): Promise<AppSettings> {
   return invoke<AppSettings>("get_app_settings");
 }
@@ -1068,6 +1666,20 @@ export async function readThread(workspaceId: string, threadId: string) {
   return invoke<any>("read_thread", { workspaceId, threadId });
 }
 
+export async function exportThread(
+  workspaceId: string,
+  threadId: string,
+  format: ThreadExportFormat = "markdown",
+): Promise<ThreadExport> {
+  return invoke("export_thread", { workspaceId, threadId, format });
+}
+
+export async function searchThreads(
+  query: ThreadSearchQuery,
+): Promise<ThreadSearchResponse> {
+  return invoke("search_threads", { query });
+}
+
 export async function threadLiveSubscribe(workspaceId: string, threadId: string) {
   return invoke<any>("thread_live_subscribe", { workspaceId, threadId });
 }
@@ -8,6 +8,20 @@ export type WorkspaceSettings = {
   launchScripts?: LaunchScriptEntry[] | null;
   worktreeSetupScript?: string | null;
   worktreesFolder?: string | null;
+  sandboxProfiles?: SandboxProfile[];
+  defaultSandboxProfile?: string | null;
+};
+
+export type SandboxMode = "read-only" | "workspace-write" | "danger-full-access";
+
+export type ApprovalPolicy = "untrusted" | "on-failure" | "on-request" | "never";
+
+export type SandboxProfile = {
+  name: string;
+  sandboxMode: SandboxMode;
+  writableRoots: string[];
+  networkAccess: boolean;
+  approvalPolicy: ApprovalPolicy;
 };
 
 export type LaunchScriptIconId =
@@ -104,6 +118,7 @@ export type ConversationItem =
       role: "user" | "assistant";
       text: string;
       images?: string[];
+      attachments?: MessageContextAttachment[];
     }
   | {
       id: string;
@@ -194,6 +209,9 @@ export type PullRequestSelectionRange = {
 };
 
 export type AccessMode = "read-only" | "current" | "full-access";
+
+export type TurnAccessMode = AccessMode | `profile:${string}`;
+
 export type ServiceTier = "fast" | "flex";
 export type BackendMode = "local" | "remote";
 export type RemoteBackendProvider = "tcp";
@@ -310,6 +328,9 @@ export type AppSettings = {
   globalWorktreesFolder: string | null;
   openAppTargets: OpenAppTarget[];
   selectedOpenAppId: string;
+  usagePricing?: UsageModelPricing[];
+  usageBudgets?: UsageBudgets;
+  promptLibraries?: PromptLibrarySettings[];
 };
 
 export type CodexFeatureStage =
@@ -443,6 +464,25 @@ export type GitFileDiff = {
   newImageData?: string | null;
   oldImageMime?: string | null;
   newImageMime?: string | null;
+  truncated?: boolean;
+};
+
+export type GitStatusPageEntry = {
+  path: string;
+  status: string;
+  staged: boolean;
+  unstaged: boolean;
+  additions?: number;
+  deletions?: number;
+};
+
+export type GitStatusPage = {
+  branchName: string;
+  cacheKey: string;
+  total: number;
+  offset: number;
+  files: GitStatusPageEntry[];
+  hasMore: boolean;
 };
 
 export type GitCommitDiff = {
@@ -546,6 +586,7 @@ export type LocalUsageDay = {
   totalTokens: number;
   agentTimeMs: number;
   agentRuns: number;
+  estimatedCostUsd?: number;
 };
 
 export type LocalUsageTotals = {
@@ -555,12 +596,42 @@ export type LocalUsageTotals = {
   cacheHitRatePercent: number;
   peakDay: string | null;
   peakDayTokens: number;
+  last7DaysCostUsd?: number;
+  totalCostUsd?: number;
 };
 
 export type LocalUsageModel = {
   model: string;
   tokens: number;
   sharePercent: number;
+  estimatedCostUsd?: number;
+};
+
+export type LocalUsageModelCost = {
+  model: string;
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+  estimatedCostUsd: number;
+  priced: boolean;
+};
+
+export type LocalUsageWorkspaceCost = {
+  workspaceId: string | null;
+  workspaceName: string | null;
+  path: string;
+  tokens: number;
+  estimatedCostUsd: number;
+};
+
+export type UsageBudgetPeriod = "daily" | "weekly" | "monthly";
+
+export type LocalUsageBudgetStatus = {
+  period: UsageBudgetPeriod;
+  periodKey: string;
+  limitUsd: number;
+  spentUsd: number;
+  exceeded: boolean;
 };
 
 export type LocalUsageSnapshot = {
@@ -568,6 +639,477 @@ export type LocalUsageSnapshot = {
   days: LocalUsageDay[];
   totals: LocalUsageTotals;
   topModels: LocalUsageModel[];
+  modelCosts?: LocalUsageModelCost[];
+  workspaceCosts?: LocalUsageWorkspaceCost[];
+  budgets?: LocalUsageBudgetStatus[];
+};
+
+export type UsageBreakdownGroup = "workspace" | "session" | "cwd";
+
+export type LocalUsageBreakdownEntry = {
+  key: string;
+  label: string;
+  workspaceId: string | null;
+  cwd: string | null;
+  sessionPath: string | null;
+  threadId: string | null;
+  sessions: number;
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+  totalTokens: number;
+  agentTimeMs: number;
+  agentRuns: number;
+  estimatedCostUsd: number;
+  sharePercent: number;
+  firstDay: string | null;
+  lastDay: string | null;
+};
+
+export type LocalUsageBreakdown = {
+  updatedAt: number;
+  startDate: string;
+  endDate: string;
+  groupBy: UsageBreakdownGroup;
+  totalTokens: number;
+  totalCostUsd: number;
+  totalEntries: number;
+  entries: LocalUsageBreakdownEntry[];
+};
+
+export type UsageExportFormat = "csv" | "json";
+
+export type LocalUsageExportRow = {
+  day: string;
+  model: string;
+  workspaceId: string | null;
+  workspaceName: string | null;
+  path: string;
+  inputTokens: number;
+  cachedInputTokens: number;
+  outputTokens: number;
+  totalTokens: number;
+  estimatedCostUsd: number;
+};
+
+export type LocalUsageExport = {
+  format: UsageExportFormat;
+  startDate: string;
+  endDate: string;
+  rowCount: number;
+  content: string;
+};
+
+export type ThreadExportFormat = "markdown" | "html" | "json";
+
+export type ThreadExport = {
+  format: ThreadExportFormat;
+  title: string;
+  fileName: string;
+  content: string;
+};
+
+export type ThreadSearchQuery = {
+  query: string;
+  workspaceId?: string | null;
+  startDate?: string | null;
+  endDate?: string | null;
+  model?: string | null;
+  limit?: number | null;
+};
+
+export type ThreadSearchSnippet = {
+  role: "user" | "assistant";
+  text: string;
+  timestampMs: number | null;
+};
+
+export type ThreadSearchResult = {
+  threadId: string;
+  title: string;
+  workspaceId: string | null;
+  workspaceName: string | null;
+  cwd: string | null;
+  models: string[];
+  startedAtMs: number | null;
+  updatedAtMs: number | null;
+  matchCount: number;
+  snippets: ThreadSearchSnippet[];
+};
+
+export type ThreadSearchResponse = {
+  results: ThreadSearchResult[];
+  totalMatches: number;
+  indexedThreads: number;
+};
+
+export type UsageModelPricing = {
+  model: string;
+  inputPerMillion: number;
+  cachedInputPerMillion: number;
+  outputPerMillion: number;
+};
+
+export type UsageBudgets = {
+  dailyUsd: number | null;
+  weeklyUsd: number | null;
+  monthlyUsd: number | null;
+};
+
+export type RulePatternToken = string | string[];
+
+export type RuleDecision = "allow" | "prompt" | "forbidden";
+
+export type ApprovalRuleSpec = {
+  pattern: RulePatternToken[];
+  decision: RuleDecision;
+  justification?: string | null;
+  match?: (string | string[])[];
+  notMatch?: (string | string[])[];
+};
+
+export type ApprovalRuleEntry = ApprovalRuleSpec & {
+  index: number;
+  line: number;
+};
+
+export type ApprovalRulesFile = {
+  name: string;
+  path: string;
+  rules: ApprovalRuleEntry[];
+  error: string | null;
+};
+
+export type ApprovalRuleMatch = {
+  file: string;
+  index: number;
+  line: number;
+  decision: RuleDecision;
+  pattern: RulePatternToken[];
+};
+
+export type ApprovalRuleTestResult = {
+  command: string[];
+  decision: RuleDecision | null;
+  matches: ApprovalRuleMatch[];
+};
+
+export type AutoApprovalRequestKind = "exec" | "applyPatch";
+
+export type AutoApprovalDecision = "accept" | "decline";
+
+export type AutoApprovalRule = {
+  id: string;
+  name: string;
+  enabled: boolean;
+  decision: AutoApprovalDecision;
+  kinds: AutoApprovalRequestKind[];
+  workspaceIds: string[];
+  threadIds: string[];
+  commands: string[];
+  paths: string[];
+};
+
+export type AutoApprovalPolicy = {
+  enabled: boolean;
+  rules: AutoApprovalRule[];
+};
+
+export type AutoApprovalOutcome = "accept" | "decline" | "prompt";
+
+export type AutoApprovalRequest = {
+  workspaceId: string;
+  threadId?: string | null;
+  method: string;
+  params: Record<string, unknown>;
+};
+
+export type AutoApprovalEvaluation = {
+  outcome: AutoApprovalOutcome;
+  kind: AutoApprovalRequestKind | null;
+  command: string | null;
+  paths: string[];
+  ruleId: string | null;
+  ruleName: string | null;
+};
+
+export type AutoApprovalEvent = AutoApprovalEvaluation & {
+  timestampMs: number;
+  workspaceId: string;
+  threadId: string | null;
+  requestId: string | number;
+  method: string;
+};
+
+export type AgentAuditKind =
+  | "turnStarted"
+  | "turnCompleted"
+  | "commandExecuted"
+  | "fileChanged"
+  | "approvalRequested"
+  | "approvalDecided";
+
+export type AgentAuditFileChange = {
+  path: string;
+  kind: "add" | "delete" | "update";
+};
+
+export type AgentAuditEntry = {
+  id: string;
+  timestampMs: number;
+  workspaceId: string;
+  kind: AgentAuditKind;
+  threadId: string | null;
+  turnId: string | null;
+  itemId: string | null;
+  status: string | null;
+  command: string | null;
+  cwd: string | null;
+  exitCode: number | null;
+  durationMs: number | null;
+  files: AgentAuditFileChange[];
+  method: string | null;
+  requestId: string | number | null;
+  decision: string | null;
+  decidedBy: "policy" | "user" | null;
+  ruleName: string | null;
+  error: string | null;
+};
+
+export type AgentAuditQuery = {
+  workspaceId: string;
+  threadId?: string | null;
+  kinds?: AgentAuditKind[];
+  startMs?: number | null;
+  endMs?: number | null;
+  text?: string | null;
+  limit?: number | null;
+};
+
+export type AgentAuditExport = {
+  format: UsageExportFormat;
+  workspaceId: string;
+  entryCount: number;
+  fileName: string;
+  content: string;
+};
+
+export type ConfigIssueSeverity = "error" | "warning";
+
+export type ConfigValidationIssue = {
+  severity: ConfigIssueSeverity;
+  message: string;
+  keyPath?: string | null;
+  line?: number | null;
+  column?: number | null;
+};
+
+export type ConfigValidationReport = {
+  valid: boolean;
+  issues: ConfigValidationIssue[];
+};
+
+export type ConfigDryRunResult = {
+  ok: boolean;
+  validation: ConfigValidationReport;
+  error?: string | null;
+  stderr?: string | null;
+  durationMs: number;
+};
+
+export type ScheduledRun = {
+  id: string;
+  name: string;
+  workspaceId: string;
+  prompt: string;
+  cron: string;
+  model?: string | null;
+  effort?: string | null;
+  accessMode?: TurnAccessMode | null;
+  enabled: boolean;
+  maxDurationMinutes?: number | null;
+  createdAtMs: number;
+  nextRunAtMs?: number | null;
+  lastRunAtMs?: number | null;
+};
+
+export type ScheduledRunInput = {
+  name: string;
+  workspaceId: string;
+  prompt: string;
+  cron: string;
+  model?: string | null;
+  effort?: string | null;
+  accessMode?: TurnAccessMode | null;
+  enabled?: boolean | null;
+  maxDurationMinutes?: number | null;
+};
+
+export type ScheduledRunStatus =
+  | "running"
+  | "completed"
+  | "failed"
+  | "interrupted"
+  | "timedOut"
+  | "skipped";
+
+export type ScheduledRunRecord = {
+  runId: string;
+  scheduleId: string;
+  scheduleName: string;
+  workspaceId: string;
+  trigger: "schedule" | "manual";
+  status: ScheduledRunStatus;
+  startedAtMs: number;
+  finishedAtMs?: number | null;
+  threadId?: string | null;
+  turnId?: string | null;
+  approvalRequests: number;
+  error?: string | null;
+  ownerPid?: number | null;
+};
+
+export type TurnContextAttachment =
+  | {
+      type: "file";
+      path: string;
+      startLine?: number | null;
+      endLine?: number | null;
+    }
+  | { type: "diff" }
+  | { type: "commit"; sha: string }
+  | { type: "issue"; number: number }
+  | { type: "pullRequest"; number: number }
+  | { type: "terminal"; terminalId: string };
+
+export type MessageContextAttachment = {
+  kind: TurnContextAttachment["type"];
+  label: string;
+  truncated: boolean;
+  content: string;
+};
+
+export type WebhookInfo = {
+  id: string;
+  name: string;
+  url: string;
+  enabled: boolean;
+  events: string[];
+  workspaceIds: string[];
+  hasSecret: boolean;
+  createdAtMs: number;
+};
+
+export type WebhookInput = {
+  name: string;
+  url: string;
+  enabled?: boolean | null;
+  events: string[];
+  workspaceIds?: string[];
+  secret?: string | null;
+  clearSecret?: boolean;
+};
+
+export type WebhookDeliveryStatus = "pending" | "delivered" | "failed";
+
+export type WebhookDelivery = {
+  id: string;
+  webhookId: string;
+  webhookName: string;
+  event: string;
+  workspaceId: string;
+  threadId?: string | null;
+  status: WebhookDeliveryStatus;
+  attempts: number;
+  responseStatus?: number | null;
+  error?: string | null;
+  createdAtMs: number;
+  finishedAtMs?: number | null;
+};
+
+export type BatchRunVariant = {
+  label?: string | null;
+  model?: string | null;
+  effort?: string | null;
+  instructions?: string | null;
+  accessMode?: TurnAccessMode | null;
+};
+
+export type BatchRunInput = {
+  workspaceId: string;
+  prompt: string;
+  variants: BatchRunVariant[];
+  copyAgentsMd?: boolean | null;
+};
+
+export type BatchRunEntryStatus =
+  | "pending"
+  | "running"
+  | "completed"
+  | "failed"
+  | "interrupted"
+  | "unknown";
+
+export type BatchRunEntry = {
+  index: number;
+  label: string;
+  variant: BatchRunVariant;
+  branch: string;
+  workspaceId?: string | null;
+  threadId?: string | null;
+  turnId?: string | null;
+  status: BatchRunEntryStatus;
+  error?: string | null;
+  finishedAtMs?: number | null;
+};
+
+export type BatchRun = {
+  id: string;
+  workspaceId: string;
+  title: string;
+  prompt: string;
+  createdAtMs: number;
+  baseCommit?: string | null;
+  status: "running" | "completed" | "partial" | "failed" | "unknown";
+  entries: BatchRunEntry[];
+};
+
+export type BatchRunDiffEntry = {
+  index: number;
+  label: string;
+  branch: string;
+  workspaceId?: string | null;
+  status: BatchRunEntryStatus;
+  filesChanged: number;
+  additions: number;
+  deletions: number;
+  error?: string | null;
+};
+
+export type BatchRunDiffSummary = {
+  batchId: string;
+  entries: BatchRunDiffEntry[];
+  files: { path: string; changes: (GitFileStatus | null)[] }[];
+};
+
+export type PromptLibrarySettings = {
+  id: string;
+  name: string;
+  source: string;
+  ref?: string | null;
+  subdir?: string | null;
+  enabled: boolean;
+};
+
+export type PromptLibraryStatus = {
+  id: string;
+  name: string;
+  source: string;
+  ref: string | null;
+  promptsDir: string | null;
+  revision: string | null;
+  lastSyncedAtMs: number | null;
+  error: string | null;
 };
 
 export type TurnPlanStepStatus = "pending" | "inProgress" | "completed";
@@ -657,13 +1199,47 @@ export type AppOption = {
   distributionChannel?: string | null;
 };
 
+export type PromptArgumentType = "string" | "number" | "boolean" | "choice";
+
+export type PromptArgument = {
+  name: string;
+  type: PromptArgumentType;
+  description?: string | null;
+  default?: string | null;
+  choices?: string[];
+  required: boolean;
+};
+
 export type CustomPromptOption = {
   name: string;
   path: string;
   description?: string;
   argumentHint?: string;
+  arguments?: PromptArgument[];
+  content: string;
+  scope?: "workspace" | "global" | "library";
+  libraryId?: string;
+  overridden?: boolean;
+};
+
+export type PromptRevision = {
+  id: string;
+  createdAtMs: number;
+  action: "initial" | "create" | "update" | "delete" | "restore" | "import";
+  size: number;
+};
+
+export type PromptRevisionDiff = {
+  fromRevision: string;
+  toRevision: string | null;
+  diff: string;
+};
+
+export type PromptRenderResult = {
   content: string;
-  scope?: "workspace" | "global";
+  arguments: Record<string, string>;
+  unresolved: string[];
+  truncated: boolean;
 };
 
 export type BranchInfo = {
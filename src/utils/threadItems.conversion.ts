@@ -1,4 +1,4 @@
-import type { ConversationItem } from "../types";
+import type { ConversationItem, MessageContextAttachment } from "../types";
 import { parseCollabToolCallItem } from "./threadItems.collab";
 import { asNumber, asString } from "./threadItems.shared";
 
@@ -12,13 +12,46 @@ function extractImageInputValue(input: Record<string, unknown>) {
   return value.trim();
 }
 
+const CONTEXT_ATTACHMENT_PATTERN =
+  /^<context_attachment kind="([^"]*)" label="([^"]*)" truncated="(true|false)">\n([\s\S]*)\n<\/context_attachment>$/;
+
+function unescapeAttribute(value: string) {
+  return value
+    .replace(/&quot;/g, '"')
+    .replace(/&lt;/g, "<")
+    .replace(/&gt;/g, ">")
+    .replace(/&amp;/g, "&");
+}
+
+// The backend adds a backslash to every closing tag inside the content.
+const ESCAPED_CLOSING_TAG_PATTERN = /<\\(\\*\/context_attachment)/g;
+
+function parseContextAttachment(text: string): MessageContextAttachment | null {
+  const match = CONTEXT_ATTACHMENT_PATTERN.exec(text);
+  if (!match) {
+    return null;
+  }
+  return {
+    kind: match[1] as MessageContextAttachment["kind"],
+    label: unescapeAttribute(match[2]),
+    truncated: match[3] === "true",
+    content: match[4].replace(ESCAPED_CLOSING_TAG_PATTERN, "<$1"),
+  };
+}
+
 function parseUserInputs(inputs: Array<Record<string, unknown>>) {
   const textParts: string[] = [];
   const images: string[] = [];
+  const attachments: MessageContextAttachment[] = [];
   inputs.forEach((input) => {
     const type = asString(input.type);
     if (type === "text") {
       const text = asString(input.text);
+      const attachment = text ? parseContextAttachment(text) : null;
+      if (attachment) {
+        attachments.push(attachment);
+        return;
+      }
       if (text) {
         textParts.push(text);
       }
@@ -38,7 +71,7 @@ function parseUserInputs(inputs: Array<Record<string, unknown>>) {
       }
     }
   });
-  return { text: textParts.join(" ").trim(), images };
+  return { text: textParts.join(" ").trim(), images, attachments };
 }
 
 export function buildConversationItem(
@@ -54,13 +87,16 @@ export function buildConversationItem(
   }
   if (type === "userMessage") {
     const content = Array.isArray(item.content) ? item.content : [];
-    const { text, images } = parseUserInputs(content as Array<Record<string, unknown>>);
+    const { text, images, attachments } = parseUserInputs(
+      content as Array<Record<string, unknown>>,
+    );
     return {
       id,
       kind: "message",
       role: "user",
       text,
       images: images.length > 0 ? images : undefined,
+      attachments: attachments.length > 0 ? attachments : undefined,
     };
   }
   if (type === "reasoning") {
@@ -216,13 +252,16 @@ export function buildConversationItemFromThreadItem(
   }
   if (type === "userMessage") {
     const content = Array.isArray(item.content) ? item.content : [];
-    const { text, images } = parseUserInputs(content as Array<Record<string, unknown>>);
+    const { text, images, attachments } = parseUserInputs(
+      content as Array<Record<string, unknown>>,
+    );
     return {
       id,
       kind: "message",
       role: "user",
       text,
       images: images.length > 0 ? images : undefined,
+      attachments: attachments.length > 0 ? attachments : undefined,
     };
   }
   if (type === "agentMessage") {
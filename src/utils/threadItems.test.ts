@@ -784,6 +784,52 @@ describe("threadItems", () => {
     }
   });
 
+  it("parses context attachments out of user message inputs", () => {
+    const item = buildConversationItemFromThreadItem({
+      type: "userMessage",
+      id: "msg-3",
+      content: [
+        { type: "text", text: "Review this" },
+        {
+          type: "text",
+          text:
+            '<context_attachment kind="file" ' +
+            'label="a &quot;b&quot; &lt;c&gt;.md:2-3" truncated="true">\n' +
+            "line 2\nend <\\/context_attachment> " +
+            "and <\\\\/context_attachment>\n" +
+            "</context_attachment>",
+        },
+      ],
+    });
+    expect(item).not.toBeNull();
+    if (item && item.kind === "message") {
+      expect(item.text).toBe("Review this");
+      expect(item.attachments).toEqual([
+        {
+          kind: "file",
+          label: 'a "b" <c>.md:2-3',
+          truncated: true,
+          content:
+            "line 2\nend </context_attachment> and <\\/context_attachment>",
+        },
+      ]);
+    }
+  });
+
+  it("keeps text that only resembles a context attachment", () => {
+    const text = '<context_attachment kind="file" label="x">no body';
+    const item = buildConversationItemFromThreadItem({
+      type: "userMessage",
+      id: "msg-4",
+      content: [{ type: "text", text }],
+    });
+    expect(item).not.toBeNull();
+    if (item && item.kind === "message") {
+      expect(item.text).toBe(text);
+      expect(item.attachments).toBeUndefined();
+    }
+  });
+
   it("keeps image-only user messages without placeholder text", () => {
     const item = buildConversationItemFromThreadItem({
       type: "userMessage",